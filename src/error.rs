@@ -152,6 +152,88 @@ pub enum PdfiumError {
     /// The setting cannot be returned because this `PdfPageGroupObject` is empty.
     EmptyPageObjectGroup,
 
+    /// The document is not a tagged PDF, so no `PdfStructTree` is available for its pages.
+    DocumentNotTagged,
+
+    /// The given index does not refer to a structure element in a `PdfStructTree`.
+    StructElementIndexOutOfBounds,
+
+    /// The given index does not refer to an attribute object in a `PdfStructElementAttributes`
+    /// collection.
+    StructElementAttributeIndexOutOfBounds,
+
+    /// The given index does not refer to a content mark in a `PdfPageObjectMarks` collection.
+    PageObjectMarkIndexOutOfBounds,
+
+    /// Pdfium's progressive rendering functions do not support rendering with a custom
+    /// transformation matrix or clipping rectangle.
+    ProgressiveRenderTransformationNotSupported,
+
+    /// The given index does not refer to a web link in a `PdfPageWebLinks` collection.
+    PageWebLinkIndexOutOfBounds,
+
+    /// Not enough of the file has yet arrived to open the document, or the document has
+    /// not yet been opened from its `PdfDocumentAvailability`.
+    DocumentNotYetAvailable,
+
+    /// The given index does not refer to a destination in a `PdfNamedDestinations` collection.
+    NamedDestinationIndexOutOfBounds,
+
+    /// The given index does not refer to a script in a `PdfJavaScriptActions` collection.
+    JavaScriptActionIndexOutOfBounds,
+
+    /// A call to `FPDF_VIEWERREF_GetDuplex()` returned a value that is not a valid
+    /// `FPDF_DUPLEXTYPE` constant.
+    UnknownPdfDuplexType,
+
+    /// The page indices given to a `PdfPages` reordering function contain the same index
+    /// more than once, or do not include every page in the document when they must.
+    InvalidPageOrder,
+
+    /// Interactive form functionality was requested for a `PdfPage` in a `PdfDocument`
+    /// that was loaded without an initialized form fill environment.
+    FormNotAvailable,
+
+    /// The `/ByteRange` of a `PdfSignature` is malformed, or does not lie within
+    /// the document bytes it was checked against.
+    InvalidSignatureByteRange,
+
+    /// The saved bytes of a `PdfDocument` could not be updated directly, because they are
+    /// malformed or use a part of the PDF file format that pdfium-render cannot edit,
+    /// such as a stream filter other than `/FlateDecode`.
+    UnsupportedPdfFileStructure,
+
+    /// The signature data given to a `PdfSignaturePlaceholder` is larger than the space
+    /// reserved for it in the saved file.
+    SignatureTooLarge,
+
+    /// The `PdfSigner` used to sign a `PdfSignaturePlaceholder` returned an error.
+    /// The wrapped error contains more information.
+    SignerError(Box<dyn Error + Send + Sync>),
+
+    /// The page label ranges given to `PdfPages::set_page_labels()` do not begin at the
+    /// first page, are not in ascending order of first page, refer to a page outside
+    /// the document, or start numbering at zero.
+    InvalidPageLabelRanges,
+
+    /// The form data given to `PdfForm::import_fdf()` or `PdfForm::import_xfdf()` is not
+    /// well-formed FDF or XFDF.
+    InvalidFormData,
+
+    /// The changes made to an encrypted `PdfDocument` that Pdfium cannot save by itself,
+    /// such as edited bookmarks or page labels, cannot be written to the encrypted file.
+    /// Save the document using `PdfSaveMode::RemoveSecurity` to write these changes
+    /// to an unencrypted copy of the document.
+    EncryptedDocumentUpdateNotSupported,
+
+    /// A form field widget with changes waiting to be written when its `PdfDocument` is saved,
+    /// such as a widget in a new radio button group, could not be found in the saved document.
+    FormFieldWidgetNotFound,
+
+    /// Text drawn in a generated form field appearance contains characters that cannot be
+    /// represented in the WinAnsiEncoding used by the standard Helvetica font.
+    AppearanceTextNotEncodable,
+
     /// A call to a internal Pdfium `FPDF_*` function returned a value indicating failure.
     ///
     /// For Pdfium functions that return enumerations, this means the function returned
@@ -122,7 +122,9 @@ pub mod prelude {
         pdf::document::bookmarks::*,
         pdf::document::fonts::*,
         pdf::document::form::*,
+        pdf::document::javascript_actions::*,
         pdf::document::metadata::*,
+        pdf::document::named_destinations::*,
         pdf::document::page::annotation::attachment_points::*,
         pdf::document::page::annotation::caret::*,
         pdf::document::page::annotation::circle::*,
@@ -155,19 +157,26 @@ pub mod prelude {
         pdf::document::page::annotations::*,
         pdf::document::page::boundaries::*,
         pdf::document::page::field::button::*,
+        pdf::document::page::field::button_appearance::*,
         pdf::document::page::field::checkbox::*,
+        pdf::document::page::field::choice_appearance::*,
         pdf::document::page::field::combo::*,
         pdf::document::page::field::list::*,
         pdf::document::page::field::option::*,
         pdf::document::page::field::options::*,
         pdf::document::page::field::radio::*,
+        pdf::document::page::field::radio_group::*,
         pdf::document::page::field::signature::*,
         pdf::document::page::field::text::*,
         pdf::document::page::field::unknown::*,
-        pdf::document::page::field::{PdfFormField, PdfFormFieldCommon, PdfFormFieldType},
+        pdf::document::page::field::{
+            PdfFormField, PdfFormFieldActionEvent, PdfFormFieldCommon, PdfFormFieldType,
+        },
+        pdf::document::page::form_session::*,
         pdf::document::page::links::*,
         pdf::document::page::object::group::*,
         pdf::document::page::object::image::*,
+        pdf::document::page::object::marks::*,
         pdf::document::page::object::path::*,
         pdf::document::page::object::shading::*,
         pdf::document::page::object::text::*,
@@ -179,23 +188,32 @@ pub mod prelude {
         },
         pdf::document::page::objects::common::*,
         pdf::document::page::objects::*,
+        pdf::document::page::progressive_render::*,
         pdf::document::page::render_config::*,
         pdf::document::page::size::*,
+        pdf::document::page::struct_tree::element::*,
+        pdf::document::page::struct_tree::*,
         pdf::document::page::text::char::*,
         pdf::document::page::text::chars::*,
         pdf::document::page::text::search::*,
         pdf::document::page::text::segment::*,
         pdf::document::page::text::segments::*,
+        pdf::document::page::text::web_links::*,
         pdf::document::page::text::*,
         pdf::document::page::{
             PdfBitmapRotation, PdfPage, PdfPageContentRegenerationStrategy, PdfPageOrientation,
             PdfPageRenderRotation,
         },
+        pdf::document::page_labels::*,
         pdf::document::pages::*,
         pdf::document::permissions::*,
+        pdf::document::save_options::*,
         pdf::document::signature::*,
+        pdf::document::signature_placeholder::*,
         pdf::document::signatures::*,
-        pdf::document::{PdfDocument, PdfDocumentVersion},
+        pdf::document::struct_trees::*,
+        pdf::document::viewer_preferences::*,
+        pdf::document::{PdfDocument, PdfDocumentFileIdentifierType, PdfDocumentVersion},
         pdf::font::glyph::*,
         pdf::font::glyphs::*,
         pdf::font::*,
@@ -209,6 +227,25 @@ pub mod prelude {
         pdf::rect::*,
         pdfium::*,
     };
+
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+    ))]
+    pub use crate::pdf::document::page::struct_tree::attributes::*;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub use crate::pdf::document::availability::*;
 }
 
 #[cfg(test)]
@@ -2,6 +2,7 @@
 //! associated with a clickable link or document bookmark.
 
 pub mod embedded_destination;
+pub mod javascript;
 pub mod launch;
 pub mod local_destination;
 pub(crate) mod private; // Keep private so that the PdfActionPrivate trait is not exposed.
@@ -10,12 +11,13 @@ pub mod unsupported;
 pub mod uri;
 
 use crate::bindgen::{
-    FPDF_ACTION, FPDF_DOCUMENT, PDFACTION_EMBEDDEDGOTO, PDFACTION_GOTO, PDFACTION_LAUNCH,
-    PDFACTION_REMOTEGOTO, PDFACTION_UNSUPPORTED, PDFACTION_URI,
+    FPDF_ACTION, FPDF_BOOKMARK, FPDF_DOCUMENT, FPDF_LINK, PDFACTION_EMBEDDEDGOTO, PDFACTION_GOTO,
+    PDFACTION_LAUNCH, PDFACTION_REMOTEGOTO, PDFACTION_UNSUPPORTED, PDFACTION_URI,
 };
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
 use crate::pdf::action::embedded_destination::PdfActionEmbeddedDestination;
+use crate::pdf::action::javascript::PdfActionJavaScript;
 use crate::pdf::action::launch::PdfActionLaunch;
 use crate::pdf::action::local_destination::PdfActionLocalDestination;
 use crate::pdf::action::private::internal::PdfActionPrivate;
@@ -31,6 +33,10 @@ pub enum PdfActionType {
     GoToDestinationInEmbeddedDocument = PDFACTION_EMBEDDEDGOTO as isize,
     Launch = PDFACTION_LAUNCH as isize,
     Uri = PDFACTION_URI as isize,
+
+    // Pdfium does not define a PDFACTION_* constant for JavaScript actions.
+    JavaScript = PDFACTION_EMBEDDEDGOTO as isize + 1,
+
     Unsupported = PDFACTION_UNSUPPORTED as isize,
 }
 
@@ -48,6 +54,14 @@ impl PdfActionType {
     }
 }
 
+/// The Pdfium object carrying an action, used to find the action's dictionary when Pdfium
+/// does not recognize the action's type.
+#[derive(Copy, Clone)]
+pub(crate) enum PdfActionOwner {
+    Link(FPDF_LINK),
+    Bookmark(FPDF_BOOKMARK),
+}
+
 /// The action associated with a clickable link or document bookmark.
 pub enum PdfAction<'a> {
     LocalDestination(PdfActionLocalDestination<'a>),
@@ -55,6 +69,7 @@ pub enum PdfAction<'a> {
     EmbeddedDestination(PdfActionEmbeddedDestination<'a>),
     Launch(PdfActionLaunch<'a>),
     Uri(PdfActionUri<'a>),
+    JavaScript(PdfActionJavaScript<'a>),
     Unsupported(PdfActionUnsupported<'a>),
 }
 
@@ -62,13 +77,23 @@ impl<'a> PdfAction<'a> {
     pub(crate) fn from_pdfium(
         handle: FPDF_ACTION,
         document: FPDF_DOCUMENT,
+        owner: PdfActionOwner,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         match PdfActionType::from_pdfium(bindings.FPDFAction_GetType(handle) as u32)
             .unwrap_or(PdfActionType::Unsupported)
         {
-            PdfActionType::Unsupported => {
-                PdfAction::Unsupported(PdfActionUnsupported::from_pdfium(handle, bindings))
+            PdfActionType::Unsupported | PdfActionType::JavaScript => {
+                // Pdfium never reports an FPDF_ACTION as a JavaScript action. Checking the
+                // action's dictionary for a script requires saving the document, so it is
+                // left to PdfActionUnsupported::to_javascript_action().
+
+                PdfAction::Unsupported(PdfActionUnsupported::from_pdfium(
+                    handle,
+                    document,
+                    Some(owner),
+                    bindings,
+                ))
             }
             PdfActionType::GoToDestinationInSameDocument => PdfAction::LocalDestination(
                 PdfActionLocalDestination::from_pdfium(handle, document, bindings),
@@ -96,6 +121,7 @@ impl<'a> PdfAction<'a> {
             PdfAction::EmbeddedDestination(action) => action,
             PdfAction::Launch(action) => action,
             PdfAction::Uri(action) => action,
+            PdfAction::JavaScript(action) => action,
             PdfAction::Unsupported(action) => action,
         }
     }
@@ -109,16 +135,18 @@ impl<'a> PdfAction<'a> {
             PdfAction::EmbeddedDestination(action) => action,
             PdfAction::Launch(action) => action,
             PdfAction::Uri(action) => action,
+            PdfAction::JavaScript(action) => action,
             PdfAction::Unsupported(action) => action,
         }
     }
 
     /// Returns the [PdfActionType] for this [PdfAction].
     ///
-    /// Note that Pdfium does not support or recognize all PDF action types. For instance,
-    /// Pdfium does not currently support or recognize the interactive Javascript action type
-    /// supported by Adobe Acrobat or Foxit's commercial PDF SDK. In these cases,
-    /// Pdfium will return [PdfActionType::Unsupported].
+    /// Note that Pdfium does not support or recognize all PDF action types; in these cases,
+    /// Pdfium will return [PdfActionType::Unsupported]. Pdfium does not recognize JavaScript
+    /// actions attached to links or bookmarks either; use the
+    /// [PdfActionUnsupported::to_javascript_action()] function to check whether an unsupported
+    /// action is a JavaScript action.
     #[inline]
     pub fn action_type(&self) -> PdfActionType {
         match self {
@@ -127,6 +155,7 @@ impl<'a> PdfAction<'a> {
             PdfAction::EmbeddedDestination(_) => PdfActionType::GoToDestinationInEmbeddedDocument,
             PdfAction::Launch(_) => PdfActionType::Launch,
             PdfAction::Uri(_) => PdfActionType::Uri,
+            PdfAction::JavaScript(_) => PdfActionType::JavaScript,
             PdfAction::Unsupported(_) => PdfActionType::Unsupported,
         }
     }
@@ -135,7 +164,7 @@ impl<'a> PdfAction<'a> {
     ///
     /// The [PdfAction::as_local_destination_action()], [PdfAction::as_remote_destination_action()],
     /// [PdfAction::as_embedded_destination_action()], [PdfAction::as_launch_action()],
-    /// and [PdfAction::as_uri_action()] functions can be used to access properties and functions
+    /// [PdfAction::as_uri_action()], and [PdfAction::as_javascript_action()] functions can be used to access properties and functions
     /// pertaining to a specific action type.
     #[inline]
     pub fn is_supported(&self) -> bool {
@@ -256,6 +285,26 @@ impl<'a> PdfAction<'a> {
             _ => None,
         }
     }
+
+    /// Returns an immutable reference to the underlying [PdfActionJavaScript] for this [PdfAction],
+    /// if this action has an action type of [PdfActionType::JavaScript].
+    #[inline]
+    pub fn as_javascript_action(&self) -> Option<&PdfActionJavaScript<'_>> {
+        match self {
+            PdfAction::JavaScript(action) => Some(action),
+            _ => None,
+        }
+    }
+
+    /// Returns a mutable reference to the underlying [PdfActionJavaScript] for this [PdfAction],
+    /// if this action has an action type of [PdfActionType::JavaScript].
+    #[inline]
+    pub fn as_javascript_action_mut(&mut self) -> Option<&mut PdfActionJavaScript<'a>> {
+        match self {
+            PdfAction::JavaScript(action) => Some(action),
+            _ => None,
+        }
+    }
 }
 
 /// Functionality common to all [PdfAction] objects, regardless of their [PdfActionType].
@@ -314,6 +363,13 @@ impl<'a> From<PdfActionUri<'a>> for PdfAction<'a> {
     }
 }
 
+impl<'a> From<PdfActionJavaScript<'a>> for PdfAction<'a> {
+    #[inline]
+    fn from(action: PdfActionJavaScript<'a>) -> Self {
+        Self::JavaScript(action)
+    }
+}
+
 impl<'a> From<PdfActionUnsupported<'a>> for PdfAction<'a> {
     #[inline]
     fn from(action: PdfActionUnsupported<'a>) -> Self {
@@ -0,0 +1,228 @@
+//! Defines the [PdfActionJavaScript] struct, exposing functionality related to a single
+//! action of type `PdfActionType::JavaScript`.
+
+use crate::bindgen::{FPDF_ACTION, FPDF_BOOKMARK, FPDF_DOCUMENT, FPDF_LINK};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::pdf::action::private::internal::PdfActionPrivate;
+use crate::pdf::action::PdfActionOwner;
+use crate::pdf::document::incremental_update::{
+    parse_array, PdfIncrementalUpdate, PdfRawDictionary,
+};
+use crate::pdf::document::javascript_actions::{
+    action_script, page_dictionaries, resolve_dictionary,
+};
+use crate::pdf::document::page::field::choice_appearance::resolve;
+use std::collections::HashSet;
+
+#[cfg(doc)]
+use crate::pdf::{
+    action::unsupported::PdfActionUnsupported, document::javascript_actions::PdfJavaScriptActions,
+};
+
+/// A single JavaScript action, carrying a script to be executed by a PDF viewer.
+///
+/// Pdfium does not recognize JavaScript actions, reporting the actions of links and bookmarks
+/// as unsupported. Use the [PdfActionUnsupported::to_javascript_action()] function to check
+/// whether an unsupported action is a JavaScript action. To list every script in a document,
+/// including document-level scripts and the additional actions of pages and form fields,
+/// use the [PdfJavaScriptActions] collection instead.
+pub struct PdfActionJavaScript<'a> {
+    handle: FPDF_ACTION,
+    script: String,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfActionJavaScript<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        handle: FPDF_ACTION,
+        script: String,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfActionJavaScript {
+            handle,
+            script,
+            bindings,
+        }
+    }
+
+    /// Returns the action with the given handle, carried by the given owner in the document
+    /// with the given handle, if that action is a JavaScript action.
+    pub(crate) fn find(
+        handle: FPDF_ACTION,
+        document: FPDF_DOCUMENT,
+        owner: PdfActionOwner,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Option<Self> {
+        let update = PdfIncrementalUpdate::from_document(document, bindings).ok()?;
+
+        let dictionary = match owner {
+            PdfActionOwner::Link(link) => find_link(&update, document, link, bindings),
+            PdfActionOwner::Bookmark(bookmark) => {
+                find_bookmark(&update, document, bookmark, bindings)
+            }
+        }?;
+
+        let action = resolve_dictionary(&update, dictionary.get("A")?)?;
+
+        let script = action_script(&update, &action).ok().flatten()?;
+
+        Some(Self::from_pdfium(handle, script, bindings))
+    }
+
+    /// Returns the JavaScript source code carried by this [PdfActionJavaScript].
+    #[inline]
+    pub fn script(&self) -> &str {
+        self.script.as_str()
+    }
+}
+
+impl<'a> PdfActionPrivate<'a> for PdfActionJavaScript<'a> {
+    #[inline]
+    fn handle(&self) -> &FPDF_ACTION {
+        &self.handle
+    }
+
+    #[inline]
+    fn bindings(&self) -> &dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+}
+
+/// Returns the dictionary of the link annotation with the given handle from the given saved
+/// copy of the document with the given handle.
+fn find_link(
+    update: &PdfIncrementalUpdate,
+    document: FPDF_DOCUMENT,
+    link: FPDF_LINK,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Option<PdfRawDictionary> {
+    // A link handle does not record the page it belongs to, so we search every page for
+    // the annotation holding the link. The link's position in the page's /Annots array
+    // is the same in the saved copy.
+
+    for page_index in 0..bindings.FPDF_GetPageCount(document) {
+        let page = bindings.FPDF_LoadPage(document, page_index);
+
+        if page.is_null() {
+            continue;
+        }
+
+        let mut position = None;
+
+        for annotation_index in 0..bindings.FPDFPage_GetAnnotCount(page) {
+            let annotation = bindings.FPDFPage_GetAnnot(page, annotation_index);
+
+            if annotation.is_null() {
+                continue;
+            }
+
+            let is_link = bindings.FPDFAnnot_GetLink(annotation) == link;
+
+            bindings.FPDFPage_CloseAnnot(annotation);
+
+            if is_link {
+                position = Some(annotation_index as usize);
+
+                break;
+            }
+        }
+
+        bindings.FPDF_ClosePage(page);
+
+        if let Some(position) = position {
+            let pages = page_dictionaries(update).ok()?;
+
+            let annotations = resolve(update, pages.get(page_index as usize)?.get("Annots")?);
+
+            let annotation = *parse_array(&annotations).ok()?.get(position)?;
+
+            return resolve_dictionary(update, annotation);
+        }
+    }
+
+    None
+}
+
+/// Returns the dictionary of the outline item of the bookmark with the given handle from
+/// the given saved copy of the document with the given handle.
+fn find_bookmark(
+    update: &PdfIncrementalUpdate,
+    document: FPDF_DOCUMENT,
+    bookmark: FPDF_BOOKMARK,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Option<PdfRawDictionary> {
+    // Record the position of the bookmark among its siblings at each level of the bookmark
+    // tree, then follow the same positions through the outline items of the saved copy.
+
+    let mut path = Vec::new();
+
+    if !find_bookmark_path(
+        document,
+        std::ptr::null_mut(),
+        bookmark,
+        bindings,
+        &mut path,
+        &mut HashSet::new(),
+    ) {
+        return None;
+    }
+
+    let catalog = update.get_dictionary(update.root().ok()?).ok()?;
+
+    let mut item = resolve_dictionary(update, catalog.get("Outlines")?)?;
+
+    for position in path {
+        let mut child = resolve_dictionary(update, item.get("First")?)?;
+
+        for _ in 0..position {
+            child = resolve_dictionary(update, child.get("Next")?)?;
+        }
+
+        item = child;
+    }
+
+    Some(item)
+}
+
+/// Searches the descendants of the given parent bookmark for the given bookmark, pushing
+/// the position of each bookmark on the way to it onto the given path. Returns `true`
+/// if the bookmark was found.
+fn find_bookmark_path(
+    document: FPDF_DOCUMENT,
+    parent: FPDF_BOOKMARK,
+    bookmark: FPDF_BOOKMARK,
+    bindings: &dyn PdfiumLibraryBindings,
+    path: &mut Vec<usize>,
+    visited: &mut HashSet<FPDF_BOOKMARK>,
+) -> bool {
+    // Limit the depth of the search, in case the bookmark tree is unreasonably deep.
+
+    const MAX_DEPTH: usize = 32;
+
+    if path.len() > MAX_DEPTH {
+        return false;
+    }
+
+    let mut child = bindings.FPDFBookmark_GetFirstChild(document, parent);
+
+    let mut position = 0;
+
+    while !child.is_null() && visited.insert(child) {
+        path.push(position);
+
+        if child == bookmark
+            || find_bookmark_path(document, child, bookmark, bindings, path, visited)
+        {
+            return true;
+        }
+
+        path.pop();
+
+        child = bindings.FPDFBookmark_GetNextSibling(document, child);
+
+        position += 1;
+    }
+
+    false
+}
@@ -1,13 +1,17 @@
 //! Defines the [PdfActionUnsupported] struct, exposing functionality related to a single
 //! action of type `PdfActionType::Unsupported`.
 
-use crate::bindgen::FPDF_ACTION;
+use crate::bindgen::{FPDF_ACTION, FPDF_DOCUMENT};
 use crate::bindings::PdfiumLibraryBindings;
+use crate::pdf::action::javascript::PdfActionJavaScript;
 use crate::pdf::action::private::internal::PdfActionPrivate;
+use crate::pdf::action::PdfActionOwner;
 
 pub struct PdfActionUnsupported<'a> {
     #[allow(dead_code)]
     handle: FPDF_ACTION,
+    document: FPDF_DOCUMENT,
+    owner: Option<PdfActionOwner>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -15,9 +19,28 @@ impl<'a> PdfActionUnsupported<'a> {
     #[inline]
     pub(crate) fn from_pdfium(
         handle: FPDF_ACTION,
+        document: FPDF_DOCUMENT,
+        owner: Option<PdfActionOwner>,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
-        PdfActionUnsupported { handle, bindings }
+        PdfActionUnsupported {
+            handle,
+            document,
+            owner,
+            bindings,
+        }
+    }
+
+    /// Returns this action as a [PdfActionJavaScript], if it is a JavaScript action.
+    ///
+    /// Pdfium reports JavaScript actions attached to links and bookmarks as unsupported, so
+    /// the action's dictionary is read from a copy of the document saved to memory. This is
+    /// comparatively expensive for large documents, so the result should be kept rather than
+    /// retrieved repeatedly.
+    pub fn to_javascript_action(&self) -> Option<PdfActionJavaScript<'a>> {
+        let owner = self.owner?;
+
+        PdfActionJavaScript::find(self.handle, self.document, owner, self.bindings)
     }
 }
 
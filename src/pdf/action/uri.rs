@@ -11,6 +11,9 @@ use std::ffi::{c_void, CString};
 pub struct PdfActionUri<'a> {
     handle: FPDF_ACTION,
     document: FPDF_DOCUMENT,
+    // The URI of an action set since the document was loaded, which Pdfium does not know
+    // about. The action handle is null in this case.
+    unsaved_uri: Option<String>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -24,12 +27,33 @@ impl<'a> PdfActionUri<'a> {
         PdfActionUri {
             handle,
             document,
+            unsaved_uri: None,
+            bindings,
+        }
+    }
+
+    /// Creates a [PdfActionUri] for an action set since the containing document was loaded,
+    /// opening the given URI.
+    #[inline]
+    pub(crate) fn from_unsaved(
+        uri: String,
+        document: FPDF_DOCUMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfActionUri {
+            handle: std::ptr::null_mut(),
+            document,
+            unsaved_uri: Some(uri),
             bindings,
         }
     }
 
     /// Returns the URI path associated with this [PdfActionUri], if any.
     pub fn uri(&self) -> Result<String, PdfiumError> {
+        if let Some(uri) = self.unsaved_uri.as_ref() {
+            return Ok(uri.clone());
+        }
+
         // Retrieving the URI path from Pdfium is a two-step operation. First, we call
         // FPDFAction_GetURIPath() with a null buffer; this will retrieve the length of
         // the path in bytes. If the length is zero, then there is no path associated
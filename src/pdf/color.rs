@@ -348,6 +348,15 @@ impl PdfColor {
         ((alpha << 24) | (b << 16) | (g << 8) | r) as FPDF_DWORD
     }
 
+    /// Returns this color encoded as a 32-bit hexadecimal 0xAARRGGBB value with the red
+    /// component in the high-order color byte, as expected by Pdfium's `FPDF_COLORSCHEME` struct.
+    #[inline]
+    pub(crate) fn as_pdfium_argb_color(&self) -> FPDF_DWORD {
+        let (alpha, r, g, b) = self.color_components();
+
+        ((alpha << 24) | (r << 16) | (g << 8) | b) as FPDF_DWORD
+    }
+
     /// Returns a tuple comprising this color encoded as a 32-bit hexadecimal 0xFFRRGGBB value
     /// and this alpha encoded as an 8-bit value, suitable for passing to Pdfium.
     #[inline]
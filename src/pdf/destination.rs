@@ -238,6 +238,9 @@ impl PdfDestinationViewSettings {
 pub struct PdfDestination<'a> {
     document_handle: FPDF_DOCUMENT,
     destination_handle: FPDF_DEST,
+    // The page index and view settings of a destination set since the document was loaded,
+    // which Pdfium does not know about. The destination handle is null in this case.
+    unsaved: Option<(PdfPageIndex, PdfDestinationViewSettings)>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -253,6 +256,23 @@ impl<'a> PdfDestination<'a> {
         PdfDestination {
             document_handle,
             destination_handle,
+            unsaved: None,
+            bindings,
+        }
+    }
+
+    /// Creates a [PdfDestination] for a destination set since the containing document
+    /// was loaded, targeting the page at the given index with the given view settings.
+    pub(crate) fn from_unsaved(
+        document_handle: FPDF_DOCUMENT,
+        page_index: PdfPageIndex,
+        view: PdfDestinationViewSettings,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfDestination {
+            document_handle,
+            destination_handle: std::ptr::null_mut(),
+            unsaved: Some((page_index, view)),
             bindings,
         }
     }
@@ -274,6 +294,10 @@ impl<'a> PdfDestination<'a> {
     /// Returns the zero-based index of the `PdfPage` containing this [PdfDestination].
     #[inline]
     pub fn page_index(&self) -> Result<PdfPageIndex, PdfiumError> {
+        if let Some((page_index, _)) = self.unsaved {
+            return Ok(page_index);
+        }
+
         match self
             .bindings
             .FPDFDest_GetDestPageIndex(self.document_handle, self.destination_handle)
@@ -287,6 +311,10 @@ impl<'a> PdfDestination<'a> {
     ///`PdfPage` containing this [PdfDestination].
     #[inline]
     pub fn view_settings(&self) -> Result<PdfDestinationViewSettings, PdfiumError> {
+        if let Some((_, view)) = self.unsaved {
+            return Ok(view);
+        }
+
         PdfDestinationViewSettings::from_pdfium(self)
     }
 
@@ -3,20 +3,36 @@
 
 pub mod attachment;
 pub mod attachments;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod availability;
 pub mod bookmark;
+pub(crate) mod bookmark_tree;
 pub mod bookmarks;
 pub mod fonts;
 pub mod form;
+pub(crate) mod form_data;
+pub(crate) mod incremental_update;
+pub mod javascript_actions;
 pub mod metadata;
+pub mod named_destinations;
 pub mod page;
+pub mod page_labels;
 pub mod pages;
 pub mod permissions;
+pub mod save_options;
 pub mod signature;
+pub mod signature_placeholder;
 pub mod signatures;
+pub mod struct_trees;
+pub mod viewer_preferences;
+pub(crate) mod xmp;
 
 use crate::bindgen::FPDF_DOCUMENT;
 #[cfg(feature = "pdfium_future")]
 use crate::bindgen::FPDF_FORMHANDLE;
+use crate::bindgen::{
+    FPDF_FILEIDTYPE, FPDF_FILEIDTYPE_FILEIDTYPE_CHANGING, FPDF_FILEIDTYPE_FILEIDTYPE_PERMANENT,
+};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
 use crate::error::PdfiumInternalError;
@@ -24,16 +40,30 @@ use crate::pdf::document::attachments::PdfAttachments;
 use crate::pdf::document::bookmarks::PdfBookmarks;
 use crate::pdf::document::fonts::PdfFonts;
 use crate::pdf::document::form::PdfForm;
+use crate::pdf::document::incremental_update::PdfIncrementalUpdate;
+use crate::pdf::document::javascript_actions::PdfJavaScriptActions;
 use crate::pdf::document::metadata::PdfMetadata;
+use crate::pdf::document::named_destinations::PdfNamedDestinations;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
+use crate::pdf::document::page::field::signature::PdfFormSignatureField;
+use crate::pdf::document::page_labels::write_page_labels;
 use crate::pdf::document::pages::PdfPages;
 use crate::pdf::document::permissions::PdfPermissions;
+use crate::pdf::document::save_options::{PdfSaveMode, PdfSaveOptions};
+use crate::pdf::document::signature_placeholder::{PdfSignatureOptions, PdfSignaturePlaceholder};
 use crate::pdf::document::signatures::PdfSignatures;
+use crate::pdf::document::struct_trees::PdfStructTrees;
+use crate::pdf::document::viewer_preferences::PdfViewerPreferences;
+use crate::pdf::document::xmp::read_xmp_metadata;
 use crate::utils::files::get_pdfium_file_writer_from_writer;
 use crate::utils::files::FpdfFileAccessExt;
+use crate::utils::mem::create_byte_buffer;
 use once_cell::sync::OnceCell;
 use std::fmt::{Debug, Formatter};
 use std::io::Cursor;
 use std::io::Write;
+use std::os::raw::{c_uint, c_void};
+use std::ptr::null_mut;
 
 static DEBUG_ONCE: OnceCell<()> = OnceCell::new();
 
@@ -143,20 +173,52 @@ impl PdfDocumentVersion {
     }
 }
 
+/// The two identifiers in the `/ID` array of a [PdfDocument]'s trailer.
+///
+/// For more information on file identifiers, refer to Section 10.3 of
+/// The PDF Reference (Sixth Edition, PDF Format 1.7), starting on page 847.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PdfDocumentFileIdentifierType {
+    /// The permanent identifier, created when the file is first written and left
+    /// unchanged when the file is subsequently updated.
+    Permanent,
+
+    /// The changing identifier, updated each time the file is updated.
+    Changing,
+}
+
+impl PdfDocumentFileIdentifierType {
+    #[inline]
+    pub(crate) fn as_pdfium(&self) -> FPDF_FILEIDTYPE {
+        match self {
+            PdfDocumentFileIdentifierType::Permanent => FPDF_FILEIDTYPE_FILEIDTYPE_PERMANENT,
+            PdfDocumentFileIdentifierType::Changing => FPDF_FILEIDTYPE_FILEIDTYPE_CHANGING,
+        }
+    }
+}
+
 /// An entry point to all the various object collections contained in a single PDF file.
 /// These collections include:
 /// * [PdfDocument::attachments()], an immutable collection of all the [PdfAttachments] in the document.
 /// * [PdfDocument::attachments_mut()], a mutable collection of all the [PdfAttachments] in the document.
 /// * [PdfDocument::bookmarks()], an immutable collection of all the [PdfBookmarks] in the document.
+/// * [PdfDocument::bookmarks_mut()], a mutable collection of all the [PdfBookmarks] in the document.
 /// * [PdfDocument::fonts()], an immutable collection of all the [PdfFonts] in the document.
 /// * [PdfDocument::fonts_mut()], a mutable collection of all the [PdfFonts] in the document.
 /// * [PdfDocument::form()], an immutable reference to the [PdfForm] embedded in the document, if any.
+/// * [PdfDocument::javascript_actions()], an immutable collection of the scripts of all the
+///   [PdfJavaScriptActions] in the document.
 /// * [PdfDocument::metadata()], an immutable collection of all the [PdfMetadata] tags in the document.
+/// * [PdfDocument::metadata_mut()], a mutable collection of all the [PdfMetadata] tags in the document.
+/// * [PdfDocument::named_destinations()], an immutable collection of all the [PdfNamedDestinations]
+///   in the document.
 /// * [PdfDocument::pages()], an immutable collection of all the [PdfPages] in the document.
 /// * [PdfDocument::pages_mut()], a mutable collection of all the [PdfPages] in the document.
 /// * [PdfDocument::permissions()], settings relating to security handlers and document permissions
 ///   for the document.
 /// * [PdfDocument::signatures()], an immutable collection of all the [PdfSignatures] in the document.
+/// * [PdfDocument::viewer_preferences()], the [PdfViewerPreferences] requested by the document's
+///   author for displaying and printing the document.
 pub struct PdfDocument<'a> {
     handle: FPDF_DOCUMENT,
     output_version: Option<PdfDocumentVersion>,
@@ -168,6 +230,7 @@ pub struct PdfDocument<'a> {
     pages: PdfPages<'a>,
     permissions: PdfPermissions<'a>,
     signatures: PdfSignatures<'a>,
+    pending_field_changes: PdfPendingFieldChanges,
     bindings: &'a dyn PdfiumLibraryBindings,
     source_byte_buffer: Option<Vec<u8>>,
 
@@ -184,8 +247,14 @@ impl<'a> PdfDocument<'a> {
     ) -> Self {
         let form = PdfForm::from_pdfium(handle, bindings);
 
-        let pages =
-            PdfPages::from_pdfium(handle, form.as_ref().map(|form| form.handle()), bindings);
+        let pending_field_changes = PdfPendingFieldChanges::new();
+
+        let pages = PdfPages::from_pdfium(
+            handle,
+            form.as_ref().map(|form| form.handle()),
+            pending_field_changes.clone(),
+            bindings,
+        );
 
         // Debug all annotations only when the first document is loaded
         DEBUG_ONCE.get_or_init(|| {
@@ -241,6 +310,7 @@ impl<'a> PdfDocument<'a> {
             pages,
             permissions: PdfPermissions::from_pdfium(handle, bindings),
             signatures: PdfSignatures::from_pdfium(handle, bindings),
+            pending_field_changes,
             bindings,
             source_byte_buffer: None,
             file_access_reader: None,
@@ -370,6 +440,9 @@ impl<'a> PdfDocument<'a> {
             // Store it in the document
             self.form = Some(form);
 
+            // Pages retrieved from now on can create form fields
+            self.pages.set_form_handle(form_handle);
+
             Ok(form_handle)
         }
     }
@@ -406,6 +479,89 @@ impl<'a> PdfDocument<'a> {
         self.output_version = Some(version);
     }
 
+    /// Returns the file identifier of the given type from the `/ID` array in this
+    /// [PdfDocument]'s trailer, if any. File identifiers are byte strings, usually
+    /// 16 bytes long, and are not guaranteed to be valid text.
+    ///
+    /// The permanent identifier can be used to recognize different versions of the same file;
+    /// a file with the same permanent identifier but a different changing identifier
+    /// is a modified version of the same document.
+    pub fn file_identifier(&self, id_type: PdfDocumentFileIdentifierType) -> Option<Vec<u8>> {
+        // Retrieving the identifier from Pdfium is a two-step operation. First, we call
+        // FPDF_GetFileIdentifier() with a null buffer; this will retrieve the length of
+        // the identifier in bytes, including a trailing null terminator. If the length is zero,
+        // then the document has no identifier of the given type.
+
+        // If the length is non-zero, then we reserve a byte buffer of the given
+        // length and call FPDF_GetFileIdentifier() again with a pointer to the buffer;
+        // this will write the identifier to the buffer, followed by a null terminator.
+
+        let buffer_length =
+            self.bindings
+                .FPDF_GetFileIdentifier(self.handle, id_type.as_pdfium(), null_mut(), 0);
+
+        if buffer_length <= 1 {
+            // The document has no identifier of the given type, or the identifier is empty.
+
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        let result = self.bindings.FPDF_GetFileIdentifier(
+            self.handle,
+            id_type.as_pdfium(),
+            buffer.as_mut_ptr() as *mut c_void,
+            buffer_length,
+        );
+
+        debug_assert_eq!(result, buffer_length);
+
+        // Remove the trailing null terminator.
+
+        buffer.truncate(buffer_length as usize - 1);
+
+        Some(buffer)
+    }
+
+    /// Returns the byte offset of the end of each trailer in the file from which this
+    /// [PdfDocument] was loaded, in the order in which the trailers appear in the file.
+    ///
+    /// Each trailer ends one revision of the document: the original revision, followed by
+    /// one revision for each incremental update appended to the file since. The number of
+    /// offsets returned is therefore the number of revisions in the file. For a signed
+    /// document, comparing these offsets with the byte ranges of its signatures shows
+    /// which revisions were added after each signature was applied.
+    ///
+    /// Documents created in memory rather than loaded from a file have no trailers.
+    pub fn revisions(&self) -> Vec<usize> {
+        // Retrieving the trailer ends from Pdfium is a two-step operation. First, we call
+        // FPDF_GetTrailerEnds() with a null buffer; this will retrieve the number of
+        // trailer ends. If the number is zero, then there are no trailers.
+
+        // If the number is non-zero, then we reserve an integer buffer of the given
+        // length and call FPDF_GetTrailerEnds() again with a pointer to the buffer;
+        // this will write the byte offsets of the trailer ends to the buffer.
+
+        let buffer_length = self
+            .bindings
+            .FPDF_GetTrailerEnds(self.handle, null_mut(), 0);
+
+        if buffer_length == 0 {
+            return Vec::new();
+        }
+
+        let mut buffer: Vec<c_uint> = vec![0; buffer_length as usize];
+
+        let result =
+            self.bindings
+                .FPDF_GetTrailerEnds(self.handle, buffer.as_mut_ptr(), buffer_length);
+
+        debug_assert_eq!(result, buffer_length);
+
+        buffer.into_iter().map(|offset| offset as usize).collect()
+    }
+
     /// Returns an immutable collection of all the [PdfAttachments] embedded in this [PdfDocument].
     #[inline]
     pub fn attachments(&self) -> &PdfAttachments<'_> {
@@ -424,6 +580,12 @@ impl<'a> PdfDocument<'a> {
         &self.bookmarks
     }
 
+    /// Returns a mutable collection of all the [PdfBookmarks] in this [PdfDocument].
+    #[inline]
+    pub fn bookmarks_mut(&mut self) -> &mut PdfBookmarks<'a> {
+        &mut self.bookmarks
+    }
+
     /// Returns an immutable reference to the [PdfForm] embedded in this [PdfDocument], if any.
     #[inline]
     pub fn form(&self) -> Option<&PdfForm<'_>> {
@@ -448,6 +610,59 @@ impl<'a> PdfDocument<'a> {
         &self.metadata
     }
 
+    /// Returns a mutable collection of all the [PdfMetadata] tags in this [PdfDocument].
+    #[inline]
+    pub fn metadata_mut(&mut self) -> &mut PdfMetadata<'a> {
+        &mut self.metadata
+    }
+
+    /// Returns the raw XML of the XMP metadata stream referenced by the `/Metadata` entry
+    /// of this [PdfDocument]'s catalog, if any.
+    ///
+    /// Pdfium does not provide access to the metadata stream, so the stream is read from the
+    /// bytes of the file from which this [PdfDocument] was loaded, if they are held in memory
+    /// and are not encrypted. Otherwise, the document is saved to memory and the stream is
+    /// read from the saved bytes, which is comparatively expensive for large documents.
+    /// Unfiltered and `/FlateDecode` metadata streams can be read.
+    ///
+    /// Tags changed using this document's [PdfMetadata] collection are only written to
+    /// the XMP metadata when the document is saved, so they are not reflected in the
+    /// returned XML until then.
+    pub fn xmp_metadata(&self) -> Result<Option<String>, PdfiumError> {
+        if let Some(xmp) = self.metadata.xmp() {
+            return Ok(Some(xmp.to_string()));
+        }
+
+        if let Some(bytes) = self.source_byte_buffer.as_ref() {
+            if let Ok(update) = PdfIncrementalUpdate::new(bytes.clone()) {
+                return read_xmp_metadata(&update);
+            }
+        }
+
+        let mut cursor = Cursor::new(Vec::new());
+
+        self.save_to_writer_with_pdfium(
+            &mut cursor,
+            &PdfSaveOptions::new().set_mode(PdfSaveMode::RemoveSecurity),
+        )?;
+
+        read_xmp_metadata(&PdfIncrementalUpdate::new(cursor.into_inner())?)
+    }
+
+    /// Sets the raw XML of the XMP metadata stream of this [PdfDocument], writing a new
+    /// metadata stream or replacing the existing one when the document is saved.
+    ///
+    /// To keep the document information dictionary consistent with the XMP metadata,
+    /// as required by PDF/A, the tags in this document's [PdfMetadata] collection are replaced
+    /// with the values of the corresponding Dublin Core and other basic XMP properties in the
+    /// given packet, such as `dc:title` and `xmp:CreateDate`. Tags without a corresponding
+    /// property in the packet are removed. Tags changed after calling this function are
+    /// written back to the corresponding properties of the packet when the document is saved.
+    #[inline]
+    pub fn set_xmp_metadata(&mut self, xml: &str) {
+        self.metadata.set_xmp(xml);
+    }
+
     /// Returns an immutable collection of all the [PdfPages] in this [PdfDocument].
     #[inline]
     pub fn pages(&self) -> &PdfPages<'a> {
@@ -466,27 +681,123 @@ impl<'a> PdfDocument<'a> {
         &self.permissions
     }
 
+    /// Returns an immutable collection of the scripts of all the JavaScript actions in this
+    /// [PdfDocument], exposed as a [PdfJavaScriptActions] object.
+    ///
+    /// Pdfium only exposes document-level scripts, so the document is saved to memory and
+    /// the remaining scripts are read from the saved bytes, which is comparatively expensive
+    /// for large documents. Bookmarks changed using this document's [PdfBookmarks] collection are
+    /// included. An error is returned if the saved bytes cannot be read, or if a script is
+    /// held in a stream that cannot be decoded; only unfiltered and `/FlateDecode` streams
+    /// can be decoded.
+    pub fn javascript_actions(&self) -> Result<PdfJavaScriptActions, PdfiumError> {
+        let mut update = PdfIncrementalUpdate::from_document(self.handle, self.bindings)?;
+
+        if self.bookmarks.is_modified() {
+            self.bookmarks.apply_to(&mut update)?;
+        }
+
+        PdfJavaScriptActions::from_pdfium(self.handle, &update, self.bindings)
+    }
+
+    /// Returns an immutable collection of all the named destinations in this [PdfDocument],
+    /// exposed as a [PdfNamedDestinations] object.
+    #[inline]
+    pub fn named_destinations(&self) -> PdfNamedDestinations<'_> {
+        PdfNamedDestinations::from_pdfium(self.handle, self.bindings)
+    }
+
     /// Returns an immutable collection of all the [PdfSignatures] attached to this [PdfDocument].
     #[inline]
     pub fn signatures(&self) -> &PdfSignatures<'_> {
         &self.signatures
     }
 
-    /// Writes this [PdfDocument] to the given writer.
+    /// Returns the tagged logical structure of this [PdfDocument], exposed as a
+    /// [PdfStructTrees] object containing one structure tree for each page.
+    #[inline]
+    pub fn structure_tree(&self) -> PdfStructTrees<'_> {
+        PdfStructTrees::from_pdfium(self.handle, &self.pages, self.bindings)
+    }
+
+    /// Returns the [PdfViewerPreferences] requested by the author of this [PdfDocument]
+    /// for displaying and printing the document.
+    #[inline]
+    pub fn viewer_preferences(&self) -> PdfViewerPreferences<'_> {
+        PdfViewerPreferences::from_pdfium(self.handle, self.bindings)
+    }
+
+    /// Writes this [PdfDocument] to the given writer, rewriting the entire document.
+    ///
+    /// To save the document incrementally, use the [PdfDocument::save_to_writer_with_options()]
+    /// function.
+    #[inline]
     pub fn save_to_writer<W: Write + 'static>(&self, writer: &mut W) -> Result<(), PdfiumError> {
-        // FPDF_NO_INCREMENTAL (2) forces a complete rewrite of the document,
-        // which is needed to persist annotation appearance stream changes.
-        // Without this flag, modifications to annotation AP dictionaries may not be saved.
-        // See: https://forum.patagames.com/posts/t155-PDF-SaveFlags
-        const FPDF_NO_INCREMENTAL: u32 = 2;
+        self.save_to_writer_with_options(writer, &PdfSaveOptions::default())
+    }
+
+    /// Writes this [PdfDocument] to the given writer using the given [PdfSaveOptions].
+    ///
+    /// Changes that Pdfium cannot save by itself, such as edited metadata, page labels,
+    /// or bookmarks, are appended to the bytes written by Pdfium as an incremental update.
+    /// These changes cannot be written to an encrypted file; if the document is encrypted
+    /// and has such changes, [PdfiumError::EncryptedDocumentUpdateNotSupported] is returned
+    /// unless the document is saved using [PdfSaveMode::RemoveSecurity].
+    pub fn save_to_writer_with_options<W: Write + 'static>(
+        &self,
+        writer: &mut W,
+        options: &PdfSaveOptions,
+    ) -> Result<(), PdfiumError> {
+        if !self.metadata.is_modified()
+            && self.pages.pending_page_labels().is_none()
+            && !self.bookmarks.is_modified()
+            && self.pending_field_changes.is_empty()
+        {
+            return self.save_to_writer_with_pdfium(writer, options);
+        }
+
+        // Some changes cannot be made through Pdfium. We save the document using Pdfium,
+        // then append those changes to the saved bytes as an incremental update.
+
+        let mut cursor = Cursor::new(Vec::new());
+
+        self.save_to_writer_with_pdfium(&mut cursor, options)?;
 
-        // Use platform-appropriate type via bindgen's FPDF_DWORD (c_ulong)
-        // The value is small enough to fit in any integer type
-        let flags = FPDF_NO_INCREMENTAL as std::os::raw::c_ulong;
+        let mut update = PdfIncrementalUpdate::new(cursor.into_inner())?;
+
+        if self.metadata.is_modified() {
+            self.metadata.apply_to(&mut update)?;
+        }
+
+        if let Some(ranges) = self.pages.pending_page_labels() {
+            write_page_labels(&mut update, &ranges)?;
+        }
+
+        if self.bookmarks.is_modified() {
+            self.bookmarks.apply_to(&mut update)?;
+        }
+
+        self.pending_field_changes.apply_to(&mut update)?;
+
+        let (bytes, _) = update.write()?;
+
+        writer
+            .write_all(&bytes)
+            .and_then(|_| writer.flush())
+            .map_err(PdfiumError::IoError)
+    }
+
+    /// Writes this [PdfDocument] to the given writer using Pdfium alone.
+    fn save_to_writer_with_pdfium<W: Write + 'static>(
+        &self,
+        writer: &mut W,
+        options: &PdfSaveOptions,
+    ) -> Result<(), PdfiumError> {
+        let flags = options.mode().as_pdfium();
 
         let mut pdfium_file_writer = get_pdfium_file_writer_from_writer(writer);
 
-        let result = match self.output_version {
+        let result = match options.version().or(self.output_version) {
             Some(version) => self.bindings.FPDF_SaveWithVersion(
                 self.handle,
                 pdfium_file_writer.as_fpdf_file_write_mut_ptr(),
@@ -518,7 +829,7 @@ impl<'a> PdfDocument<'a> {
         }
     }
 
-    /// Writes this [PdfDocument] to the file at the given path.
+    /// Writes this [PdfDocument] to the file at the given path, rewriting the entire document.
     ///
     /// This function is not available when compiling to WASM. You have several options for
     /// saving your PDF document data in WASM:
@@ -527,25 +838,94 @@ impl<'a> PdfDocument<'a> {
     /// * Use the [PdfDocument::save_to_blob()] function to save document data directly into a new
     ///   Javascript `Blob` object. This function is only available when compiling to WASM.
     #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
     pub fn save_to_file(&self, path: &(impl AsRef<Path> + ?Sized)) -> Result<(), PdfiumError> {
-        self.save_to_writer(&mut File::create(path).map_err(PdfiumError::IoError)?)
+        self.save_to_file_with_options(path, &PdfSaveOptions::default())
+    }
+
+    /// Writes this [PdfDocument] to the file at the given path using the given [PdfSaveOptions].
+    ///
+    /// This function is not available when compiling to WASM.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_to_file_with_options(
+        &self,
+        path: &(impl AsRef<Path> + ?Sized),
+        options: &PdfSaveOptions,
+    ) -> Result<(), PdfiumError> {
+        self.save_to_writer_with_options(
+            &mut File::create(path).map_err(PdfiumError::IoError)?,
+            options,
+        )
     }
 
-    /// Writes this [PdfDocument] to a new byte buffer, returning the byte buffer.
+    /// Writes this [PdfDocument] to a new byte buffer, rewriting the entire document,
+    /// and returns the byte buffer.
+    #[inline]
     pub fn save_to_bytes(&self) -> Result<Vec<u8>, PdfiumError> {
+        self.save_to_bytes_with_options(&PdfSaveOptions::default())
+    }
+
+    /// Writes this [PdfDocument] to a new byte buffer using the given [PdfSaveOptions],
+    /// returning the byte buffer.
+    pub fn save_to_bytes_with_options(
+        &self,
+        options: &PdfSaveOptions,
+    ) -> Result<Vec<u8>, PdfiumError> {
         let mut cursor = Cursor::new(Vec::new());
 
-        self.save_to_writer(&mut cursor)?;
+        self.save_to_writer_with_options(&mut cursor, options)?;
 
         Ok(cursor.into_inner())
     }
 
-    /// Writes this [PdfDocument] to a new `Blob`, returning the `Blob`.
+    /// Writes this [PdfDocument] to a new byte buffer, ready to be digitally signed using
+    /// the given signature field.
+    ///
+    /// The document is saved incrementally, so the bytes of the file from which it was loaded
+    /// are preserved and any existing signatures remain valid. An incremental update is then
+    /// appended that sets the value of the given field to a new signature dictionary
+    /// configured by the given options.
+    /// The signature dictionary's `/ByteRange` covers the entire file except for its `/Contents`
+    /// entry, a zero-filled placeholder for the signature data. Use the returned
+    /// [PdfSignaturePlaceholder] to compute the signature data with your own signing
+    /// infrastructure and write it into the placeholder. Once the signature data is written,
+    /// the bytes of the file must not be changed, or the signature will be invalidated.
+    ///
+    /// Returns [PdfiumError::EncryptedDocumentUpdateNotSupported] if the saved document
+    /// is encrypted.
+    pub fn save_with_signature_placeholder(
+        &self,
+        field: &PdfFormSignatureField,
+        options: &PdfSignatureOptions,
+    ) -> Result<PdfSignaturePlaceholder, PdfiumError> {
+        PdfSignaturePlaceholder::new(
+            || {
+                self.save_to_bytes_with_options(
+                    &PdfSaveOptions::new().set_mode(PdfSaveMode::Incremental),
+                )
+            },
+            field,
+            options,
+        )
+    }
+
+    /// Writes this [PdfDocument] to a new `Blob`, rewriting the entire document,
+    /// and returns the `Blob`.
     ///
     /// This function is only available when compiling to WASM.
     #[cfg(any(doc, target_arch = "wasm32"))]
+    #[inline]
     pub fn save_to_blob(&self) -> Result<Blob, PdfiumError> {
-        let bytes = self.save_to_bytes()?;
+        self.save_to_blob_with_options(&PdfSaveOptions::default())
+    }
+
+    /// Writes this [PdfDocument] to a new `Blob` using the given [PdfSaveOptions],
+    /// returning the `Blob`.
+    ///
+    /// This function is only available when compiling to WASM.
+    #[cfg(any(doc, target_arch = "wasm32"))]
+    pub fn save_to_blob_with_options(&self, options: &PdfSaveOptions) -> Result<Blob, PdfiumError> {
+        let bytes = self.save_to_bytes_with_options(options)?;
 
         let array = Uint8Array::new_with_length(bytes.len() as u32);
 
@@ -587,3 +967,144 @@ unsafe impl<'a> Sync for PdfDocument<'a> {}
 
 #[cfg(feature = "sync")]
 unsafe impl<'a> Send for PdfDocument<'a> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_file_identifiers_and_revisions() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let bytes = std::fs::read("./test/signatures-test.pdf").unwrap();
+
+        let document = pdfium.load_pdf_from_byte_slice(&bytes, None)?;
+
+        let revisions = document.revisions();
+
+        assert!(!revisions.is_empty());
+        assert!(revisions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(revisions.iter().all(|offset| *offset <= bytes.len()));
+
+        // Pdfium reads the /ID array from the trailer of the latest revision.
+
+        assert_eq!(
+            document.file_identifier(PdfDocumentFileIdentifierType::Permanent),
+            Some(vec![
+                0x9d, 0x2b, 0x0c, 0x41, 0xfe, 0x39, 0x9b, 0x32, 0x1a, 0x1f, 0x2e, 0x4d, 0xf7, 0xe0,
+                0x17, 0x91,
+            ])
+        );
+
+        let document = pdfium.create_new_pdf()?;
+
+        assert!(document.revisions().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_xmp_metadata() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        document
+            .pages_mut()
+            .create_page_at_end(PdfPagePaperSize::a4())?;
+
+        assert_eq!(document.xmp_metadata()?, None);
+
+        document
+            .metadata_mut()
+            .set(PdfDocumentMetadataTagType::Title, "Annual Report");
+
+        let xmp = document.metadata().to_xmp();
+
+        document.set_xmp_metadata(&xmp);
+
+        let bytes = document.save_to_bytes()?;
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        assert_eq!(document.xmp_metadata()?, Some(xmp));
+        assert_eq!(
+            document
+                .metadata()
+                .get(PdfDocumentMetadataTagType::Title)
+                .map(|tag| tag.value().to_string()),
+            Some("Annual Report".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_pending_changes() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        // test-toc.pdf uses a cross-reference stream and object streams, so the pending
+        // changes are written on top of Pdfium's own incremental update of the file.
+
+        let original = std::fs::read("./test/test-toc.pdf").unwrap();
+
+        let mut document = pdfium.load_pdf_from_byte_slice(&original, None)?;
+
+        document
+            .metadata_mut()
+            .set(PdfDocumentMetadataTagType::Title, "Table of Contents");
+
+        let bytes = document.save_to_bytes_with_options(
+            &PdfSaveOptions::new().set_mode(PdfSaveMode::Incremental),
+        )?;
+
+        assert!(bytes.starts_with(&original));
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        assert_eq!(
+            document
+                .metadata()
+                .get(PdfDocumentMetadataTagType::Title)
+                .map(|tag| tag.value().to_string()),
+            Some("Table of Contents".to_string())
+        );
+
+        // form-test.pdf is encrypted, so its pending changes can only be saved to
+        // an unencrypted copy.
+
+        let mut document = pdfium.load_pdf_from_file("./test/form-test.pdf", None)?;
+
+        document
+            .metadata_mut()
+            .set(PdfDocumentMetadataTagType::Title, "Form");
+
+        assert!(matches!(
+            document.save_to_bytes(),
+            Err(PdfiumError::EncryptedDocumentUpdateNotSupported)
+        ));
+        assert!(matches!(
+            document.save_to_bytes_with_options(
+                &PdfSaveOptions::new().set_mode(PdfSaveMode::Incremental)
+            ),
+            Err(PdfiumError::EncryptedDocumentUpdateNotSupported)
+        ));
+
+        let bytes = document.save_to_bytes_with_options(
+            &PdfSaveOptions::new().set_mode(PdfSaveMode::RemoveSecurity),
+        )?;
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        assert_eq!(
+            document
+                .metadata()
+                .get(PdfDocumentMetadataTagType::Title)
+                .map(|tag| tag.value().to_string()),
+            Some("Form".to_string())
+        );
+
+        Ok(())
+    }
+}
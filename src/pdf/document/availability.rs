@@ -0,0 +1,575 @@
+//! Defines the [PdfDocumentAvailability] struct, exposing functionality related to loading
+//! a `PdfDocument` incrementally as its data arrives over a slow or streaming connection.
+
+use crate::bindgen::{
+    FPDF_AVAIL, FX_DOWNLOADHINTS, PDF_DATA_AVAIL, PDF_DATA_NOTAVAIL, PDF_FORM_AVAIL,
+    PDF_FORM_NOTAVAIL, PDF_FORM_NOTEXIST, PDF_LINEARIZED, PDF_NOT_LINEARIZED,
+};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::page::PdfPage;
+use crate::pdf::document::pages::PdfPageIndex;
+use crate::pdf::document::PdfDocument;
+use crate::pdfium::Pdfium;
+use crate::utils::files::{
+    get_pdfium_download_hints_from_callback, get_pdfium_file_accessor_from_reader,
+    get_pdfium_file_avail_from_callback, FpdfFileAccessExt, FxDownloadHintsExt, FxFileAvailExt,
+};
+use std::io::{Read, Seek};
+use std::os::raw::c_int;
+
+/// Tracks the availability of the data in a PDF file that is still being downloaded,
+/// allowing the [PdfDocument] and its pages to be opened as soon as the data they need
+/// has arrived rather than only once the entire file has been received.
+///
+/// Create a [PdfDocumentAvailability] by calling the [Pdfium::load_pdf_availability_from_reader()]
+/// function. Two callbacks connect Pdfium to the download:
+/// * a "data is available" callback that Pdfium uses to ask whether a given range of bytes
+///   in the file has arrived, and
+/// * a "download hints" callback that Pdfium uses to ask for a given range of bytes to be
+///   downloaded as a matter of priority.
+///
+/// Whenever new data arrives, call [PdfDocumentAvailability::is_doc_avail()] until it returns
+/// `true`, then call [PdfDocumentAvailability::load_document()] to open the document.
+/// Individual pages can then be opened by calling [PdfDocumentAvailability::page_if_available()].
+///
+/// Pdfium can open the first page of a linearized ("fast web view") PDF file after receiving
+/// only the beginning of the file. For files that are not linearized, most or all of the file
+/// must be received before the document can be opened.
+pub struct PdfDocumentAvailability<'a> {
+    handle: FPDF_AVAIL,
+    document: Option<PdfDocument<'a>>,
+    download_hints_ptr: *mut FX_DOWNLOADHINTS,
+    bindings: &'a dyn PdfiumLibraryBindings,
+
+    // Pdfium reads from the following structs for as long as the availability provider
+    // and any document loaded from it are open. They are boxed so that their memory
+    // locations remain stable, and must not be dropped until after the availability
+    // provider has been destroyed.
+    #[allow(dead_code)]
+    reader: Box<FpdfFileAccessExt<'a>>,
+    #[allow(dead_code)]
+    file_avail: Box<FxFileAvailExt<'a>>,
+    #[allow(dead_code)]
+    download_hints: Box<FxDownloadHintsExt<'a>>,
+}
+
+impl<'a> PdfDocumentAvailability<'a> {
+    pub(crate) fn from_reader<R: Read + Seek + 'a>(
+        reader: R,
+        is_data_available: impl FnMut(u64, u64) -> bool + 'a,
+        add_download_hint: impl FnMut(u64, u64) + 'a,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Result<Self, PdfiumError> {
+        let mut reader = get_pdfium_file_accessor_from_reader(reader);
+
+        let mut file_avail = get_pdfium_file_avail_from_callback(is_data_available);
+
+        let mut download_hints = get_pdfium_download_hints_from_callback(add_download_hint);
+
+        let handle = bindings.FPDFAvail_Create(
+            file_avail.as_fx_file_avail_mut_ptr(),
+            reader.as_fpdf_file_access_mut_ptr(),
+        );
+
+        if handle.is_null() {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        // Moving the boxes into the returned struct does not change the memory location
+        // of their contents, so this pointer remains valid.
+
+        let download_hints_ptr = download_hints.as_fx_download_hints_mut_ptr();
+
+        Ok(PdfDocumentAvailability {
+            handle,
+            document: None,
+            download_hints_ptr,
+            bindings,
+            reader,
+            file_avail,
+            download_hints,
+        })
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfDocumentAvailability].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns `Some(true)` if the file is a linearized PDF, `Some(false)` if it is not,
+    /// or `None` if not enough data has yet arrived to tell. Pdfium can usually tell
+    /// once the first kilobyte of the file has arrived.
+    pub fn is_linearized(&self) -> Option<bool> {
+        match self.bindings.FPDFAvail_IsLinearized(self.handle) {
+            result if result == PDF_LINEARIZED as c_int => Some(true),
+            result if result == PDF_NOT_LINEARIZED as c_int => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if enough data has arrived to open the document by calling
+    /// [PdfDocumentAvailability::load_document()]. If not, the byte ranges still needed
+    /// are passed to the download hints callback.
+    ///
+    /// Call this function whenever new data arrives until it returns `true`.
+    #[inline]
+    pub fn is_doc_avail(&self) -> Result<bool, PdfiumError> {
+        Self::data_avail_to_result(
+            self.bindings
+                .FPDFAvail_IsDocAvail(self.handle, self.download_hints_ptr),
+        )
+    }
+
+    /// Returns `true` if enough data has arrived to open the page at the given index.
+    /// If not, the byte ranges still needed are passed to the download hints callback.
+    ///
+    /// An error will be returned if the document has not yet been loaded by calling
+    /// [PdfDocumentAvailability::load_document()].
+    pub fn is_page_avail(&self, index: PdfPageIndex) -> Result<bool, PdfiumError> {
+        let document = self
+            .document
+            .as_ref()
+            .ok_or(PdfiumError::DocumentNotYetAvailable)?;
+
+        if index >= document.pages().len() {
+            return Err(PdfiumError::PageIndexOutOfBounds);
+        }
+
+        Self::data_avail_to_result(self.bindings.FPDFAvail_IsPageAvail(
+            self.handle,
+            index as c_int,
+            self.download_hints_ptr,
+        ))
+    }
+
+    /// Returns `true` if enough data has arrived to fully initialize the document's
+    /// interactive form, or if the document does not contain a form. If not, the byte ranges
+    /// still needed are passed to the download hints callback.
+    ///
+    /// An error will be returned if the document has not yet been loaded by calling
+    /// [PdfDocumentAvailability::load_document()].
+    pub fn is_form_avail(&self) -> Result<bool, PdfiumError> {
+        if self.document.is_none() {
+            return Err(PdfiumError::DocumentNotYetAvailable);
+        }
+
+        match self
+            .bindings
+            .FPDFAvail_IsFormAvail(self.handle, self.download_hints_ptr)
+        {
+            result if result == PDF_FORM_AVAIL as c_int || result == PDF_FORM_NOTEXIST as c_int => {
+                Ok(true)
+            }
+            result if result == PDF_FORM_NOTAVAIL as c_int => Ok(false),
+            _ => Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure),
+        }
+    }
+
+    /// Opens the [PdfDocument], returning a reference to it. If the document is password
+    /// protected, the given password will be used to unlock it. Calling this function again
+    /// once the document is open simply returns the open document.
+    ///
+    /// An error will be returned if not enough data has yet arrived to open the document.
+    pub fn load_document(
+        &mut self,
+        password: Option<&str>,
+    ) -> Result<&PdfDocument<'a>, PdfiumError> {
+        if self.document.is_none() {
+            if !self.is_doc_avail()? {
+                return Err(PdfiumError::DocumentNotYetAvailable);
+            }
+
+            let document = Pdfium::pdfium_document_handle_to_result(
+                self.bindings.FPDFAvail_GetDocument(self.handle, password),
+                self.bindings,
+            )?;
+
+            self.document = Some(document);
+        }
+
+        self.document
+            .as_ref()
+            .ok_or(PdfiumError::DocumentNotYetAvailable)
+    }
+
+    /// Returns the [PdfDocument] opened by calling [PdfDocumentAvailability::load_document()],
+    /// if any.
+    #[inline]
+    pub fn document(&self) -> Option<&PdfDocument<'a>> {
+        self.document.as_ref()
+    }
+
+    /// Returns the index of the first page that becomes available in a linearized document.
+    /// This is usually, but not always, the first page in the document. For documents that
+    /// are not linearized, this is always zero.
+    ///
+    /// An error will be returned if the document has not yet been loaded by calling
+    /// [PdfDocumentAvailability::load_document()].
+    pub fn first_available_page_index(&self) -> Result<PdfPageIndex, PdfiumError> {
+        let document = self
+            .document
+            .as_ref()
+            .ok_or(PdfiumError::DocumentNotYetAvailable)?;
+
+        Ok(self
+            .bindings
+            .FPDFAvail_GetFirstPageNum(document.handle())
+            .max(0) as PdfPageIndex)
+    }
+
+    /// Opens the page at the given index, if enough data has arrived to do so.
+    /// Returns `None` if the page's data has not yet arrived; the byte ranges still needed
+    /// are passed to the download hints callback.
+    ///
+    /// An error will be returned if the document has not yet been loaded by calling
+    /// [PdfDocumentAvailability::load_document()].
+    pub fn page_if_available(
+        &self,
+        index: PdfPageIndex,
+    ) -> Result<Option<PdfPage<'_>>, PdfiumError> {
+        if self.is_page_avail(index)? {
+            self.document
+                .as_ref()
+                .ok_or(PdfiumError::DocumentNotYetAvailable)?
+                .pages()
+                .get(index)
+                .map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    #[inline]
+    fn data_avail_to_result(result: c_int) -> Result<bool, PdfiumError> {
+        match result {
+            result if result == PDF_DATA_AVAIL as c_int => Ok(true),
+            result if result == PDF_DATA_NOTAVAIL as c_int => Ok(false),
+            _ => Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure),
+        }
+    }
+}
+
+impl<'a> Drop for PdfDocumentAvailability<'a> {
+    /// Closes the [PdfDocument] loaded from this [PdfDocumentAvailability], if any,
+    /// then destroys the availability provider, releasing held memory.
+    #[inline]
+    fn drop(&mut self) {
+        // The document must be closed before the availability provider it was loaded from.
+
+        self.document = None;
+        self.bindings.FPDFAvail_Destroy(self.handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+    use std::cell::{Cell, RefCell};
+    use std::fs;
+    use std::io::Cursor;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_document_availability_from_chunks() -> Result<(), PdfiumError> {
+        // Simulate a download by releasing the file's bytes to Pdfium in fixed-size chunks.
+
+        const CHUNK_SIZE: u64 = 4096;
+
+        let pdfium = test_bind_to_pdfium();
+
+        let bytes = fs::read("./test/form-test.pdf").unwrap();
+
+        let total = bytes.len() as u64;
+
+        let received = Rc::new(Cell::new(0u64));
+
+        let hints = Rc::new(RefCell::new(Vec::new()));
+
+        let mut availability = pdfium.load_pdf_availability_from_reader(
+            Cursor::new(bytes),
+            {
+                let received = received.clone();
+
+                move |offset, size| offset + size <= received.get()
+            },
+            {
+                let hints = hints.clone();
+
+                move |offset, size| hints.borrow_mut().push((offset, size))
+            },
+        )?;
+
+        assert!(availability.document().is_none());
+        assert!(matches!(
+            availability.is_page_avail(0),
+            Err(PdfiumError::DocumentNotYetAvailable)
+        ));
+
+        while !availability.is_doc_avail()? {
+            assert!(received.get() < total);
+
+            received.set((received.get() + CHUNK_SIZE).min(total));
+        }
+
+        assert!(availability.is_linearized().is_some());
+
+        let page_count = availability.load_document(None)?.pages().len();
+
+        assert!(page_count > 0);
+
+        let page = loop {
+            if let Some(page) = availability.page_if_available(0)? {
+                break page;
+            }
+
+            assert!(received.get() < total);
+
+            received.set((received.get() + CHUNK_SIZE).min(total));
+        };
+
+        assert!(page.width().value > 0.0);
+
+        while !availability.is_form_avail()? {
+            assert!(received.get() < total);
+
+            received.set((received.get() + CHUNK_SIZE).min(total));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_linearized_first_page_available_early() -> Result<(), PdfiumError> {
+        const CHUNK_SIZE: u64 = 1024;
+
+        let pdfium = test_bind_to_pdfium();
+
+        let bytes = linearized_test_file();
+
+        let total = bytes.len() as u64;
+
+        let received = Rc::new(Cell::new(0u64));
+
+        let mut availability = pdfium.load_pdf_availability_from_reader(
+            Cursor::new(bytes),
+            {
+                let received = received.clone();
+
+                move |offset, size| offset + size <= received.get()
+            },
+            |_, _| {},
+        )?;
+
+        while !availability.is_doc_avail()? {
+            assert!(received.get() < total);
+
+            received.set((received.get() + CHUNK_SIZE).min(total));
+        }
+
+        assert_eq!(availability.is_linearized(), Some(true));
+        assert_eq!(availability.load_document(None)?.pages().len(), 3);
+        assert_eq!(availability.first_available_page_index()?, 0);
+
+        let page = loop {
+            if let Some(page) = availability.page_if_available(0)? {
+                break page;
+            }
+
+            assert!(received.get() < total);
+
+            received.set((received.get() + CHUNK_SIZE).min(total));
+        };
+
+        // The first page must become available long before the padded content streams
+        // of the remaining pages have arrived.
+
+        assert!(received.get() < total / 2);
+        assert_eq!(page.width().value, 200.0);
+
+        drop(page);
+
+        assert!(!availability.is_page_avail(2)?);
+
+        received.set(total);
+
+        assert!(availability.is_page_avail(2)?);
+
+        Ok(())
+    }
+
+    /// Returns the bytes of a linearized file with three pages. The first page section
+    /// occupies the first kilobyte or so of the file; the remaining two pages each have a
+    /// content stream padded to several tens of kilobytes.
+    ///
+    /// The primary hint stream is left empty. Pdfium does not need the hint tables to
+    /// tell when the first page is available, and falls back to waiting for the whole
+    /// file before making any other page available.
+    fn linearized_test_file() -> Vec<u8> {
+        use std::io::Write;
+
+        fn object(number: u32, body: &str) -> Vec<u8> {
+            format!("{} 0 obj\n{}\nendobj\n", number, body).into_bytes()
+        }
+
+        fn stream_object(number: u32, content: &str) -> Vec<u8> {
+            object(
+                number,
+                &format!(
+                    "<< /Length {} >>\nstream\n{}\nendstream",
+                    content.len(),
+                    content
+                ),
+            )
+        }
+
+        fn page_object(number: u32, contents: u32) -> Vec<u8> {
+            object(
+                number,
+                &format!(
+                    "<< /Type /Page /Parent 1 0 R /MediaBox [0 0 200 200] /Resources << >> \
+                    /Contents {} 0 R >>",
+                    contents
+                ),
+            )
+        }
+
+        fn linearization_dictionary(
+            length: usize,
+            hint_offset: usize,
+            hint_length: usize,
+            first_page_end: usize,
+            main_xref_first_entry: usize,
+        ) -> Vec<u8> {
+            // Numbers are written with a fixed width, so that the dictionary is the same
+            // length whatever the offsets are.
+
+            object(
+                6,
+                &format!(
+                    "<< /Linearized 1 /L {:010} /H [{:010} {:010}] /O 9 /E {:010} /N 3 \
+                    /T {:010} >>",
+                    length, hint_offset, hint_length, first_page_end, main_xref_first_entry
+                ),
+            )
+        }
+
+        let padding = "0 0 m 200 200 l S\n".repeat(2000);
+
+        // Objects 6 to 10 make up the first page section; objects 1 to 5 the rest of
+        // the document.
+
+        let first_page_objects = [
+            (7, object(7, "<< /Type /Catalog /Pages 1 0 R >>")),
+            (8, object(8, "<< /S 0 /Length 0 >>\nstream\n\nendstream")),
+            (9, page_object(9, 10)),
+            (10, stream_object(10, "0 0 m 200 200 l S")),
+        ];
+
+        let remaining_objects = [
+            (
+                1,
+                object(1, "<< /Type /Pages /Kids [9 0 R 2 0 R 4 0 R] /Count 3 >>"),
+            ),
+            (2, page_object(2, 3)),
+            (3, stream_object(3, &padding)),
+            (4, page_object(4, 5)),
+            (5, stream_object(5, &padding)),
+        ];
+
+        let header = b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n".to_vec();
+
+        let linearization_offset = header.len();
+
+        let first_xref_offset =
+            linearization_offset + linearization_dictionary(0, 0, 0, 0, 0).len();
+
+        // The first page cross-reference table has five entries, each 20 bytes long.
+
+        let first_xref_length = format!(
+            "xref\n6 5\n{}trailer\n<< /Size 11 /Prev {:010} /Root 7 0 R >>\n\
+            startxref\n0\n%%EOF\n",
+            " ".repeat(5 * 20),
+            0
+        )
+        .len();
+
+        let mut offsets = [0; 11];
+
+        offsets[6] = linearization_offset;
+
+        let mut position = first_xref_offset + first_xref_length;
+
+        for (number, bytes) in first_page_objects.iter() {
+            offsets[*number] = position;
+            position += bytes.len();
+        }
+
+        let first_page_end = position;
+
+        for (number, bytes) in remaining_objects.iter() {
+            offsets[*number] = position;
+            position += bytes.len();
+        }
+
+        let main_xref_offset = position;
+
+        let mut bytes = header;
+
+        let mut first_xref = b"xref\n6 5\n".to_vec();
+
+        for offset in offsets[6..].iter() {
+            write!(first_xref, "{:010} 00000 n\r\n", offset).unwrap();
+        }
+
+        write!(
+            first_xref,
+            "trailer\n<< /Size 11 /Prev {:010} /Root 7 0 R >>\nstartxref\n0\n%%EOF\n",
+            main_xref_offset
+        )
+        .unwrap();
+
+        assert_eq!(first_xref.len(), first_xref_length);
+
+        let mut main_xref = b"xref\n0 6\n0000000000 65535 f\r\n".to_vec();
+
+        for offset in offsets[1..6].iter() {
+            write!(main_xref, "{:010} 00000 n\r\n", offset).unwrap();
+        }
+
+        write!(
+            main_xref,
+            "trailer\n<< /Size 6 >>\nstartxref\n{}\n%%EOF\n",
+            first_xref_offset
+        )
+        .unwrap();
+
+        let length = main_xref_offset + main_xref.len();
+
+        // The main cross-reference table's first entry follows the end-of-line marker
+        // after its subsection header.
+
+        let main_xref_first_entry = main_xref_offset + "xref\n0 6".len();
+
+        bytes.extend(linearization_dictionary(
+            length,
+            offsets[8],
+            first_page_objects[1].1.len(),
+            first_page_end,
+            main_xref_first_entry,
+        ));
+        bytes.extend(first_xref);
+
+        for (_, object) in first_page_objects.iter().chain(remaining_objects.iter()) {
+            bytes.extend(object);
+        }
+
+        bytes.extend(main_xref);
+
+        assert_eq!(bytes.len(), length);
+
+        bytes
+    }
+}
@@ -3,13 +3,24 @@
 
 use crate::bindgen::{FPDF_BOOKMARK, FPDF_DOCUMENT};
 use crate::bindings::PdfiumLibraryBindings;
-use crate::pdf::action::PdfAction;
-use crate::pdf::destination::PdfDestination;
+use crate::error::PdfiumError;
+use crate::pdf::action::javascript::PdfActionJavaScript;
+use crate::pdf::action::launch::PdfActionLaunch;
+use crate::pdf::action::remote_destination::PdfActionRemoteDestination;
+use crate::pdf::action::unsupported::PdfActionUnsupported;
+use crate::pdf::action::uri::PdfActionUri;
+use crate::pdf::action::{PdfAction, PdfActionOwner};
+use crate::pdf::color::PdfColor;
+use crate::pdf::destination::{PdfDestination, PdfDestinationViewSettings};
+use crate::pdf::document::bookmark_tree::{
+    get_title, PdfBookmarkNodeId, PdfBookmarkTarget, PdfBookmarkTree,
+};
 use crate::pdf::document::bookmarks::PdfBookmarksIterator;
-use crate::utils::mem::create_byte_buffer;
-use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use crate::pdf::document::incremental_update::{encode_name, encode_text_string};
+use crate::pdf::document::pages::PdfPageIndex;
+use bitflags::bitflags;
 use std::hash::{Hash, Hasher};
-use std::os::raw::c_void;
+use std::ptr::null_mut;
 
 #[cfg(doc)]
 use {
@@ -17,12 +28,121 @@ use {
     crate::pdf::document::PdfDocument,
 };
 
+bitflags! {
+    /// The style used to display the title of a [PdfBookmark].
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct PdfBookmarkStyle: u32 {
+        const ITALIC = 1;
+        const BOLD = 2;
+    }
+}
+
+/// An action that can be assigned to a [PdfBookmark] using the [PdfBookmarkMut::set_action()]
+/// function. To navigate to a page in the same document, use [PdfBookmarkMut::set_destination()]
+/// instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfBookmarkAction {
+    /// Opens the given URI.
+    Uri(String),
+
+    /// Runs the given JavaScript.
+    JavaScript(String),
+
+    /// Launches the application or opens the document at the given file path.
+    Launch(String),
+
+    /// Navigates to the page with the given zero-based index in the PDF file at the given path.
+    RemoteDestination(String, PdfPageIndex),
+
+    /// Performs the named action with the given name, such as `NextPage` or `PrevPage`,
+    /// defined by the PDF viewer.
+    Named(String),
+}
+
+impl PdfBookmarkAction {
+    /// Returns the action dictionary for this [PdfBookmarkAction].
+    pub(crate) fn to_dictionary(&self) -> Vec<u8> {
+        let mut result = b"<</S ".to_vec();
+
+        match self {
+            PdfBookmarkAction::Uri(uri) => {
+                result.extend_from_slice(b"/URI /URI ");
+                result.extend_from_slice(&encode_text_string(uri));
+            }
+            PdfBookmarkAction::JavaScript(script) => {
+                result.extend_from_slice(b"/JavaScript /JS ");
+                result.extend_from_slice(&encode_text_string(script));
+            }
+            PdfBookmarkAction::Launch(path) => {
+                result.extend_from_slice(b"/Launch /F ");
+                result.extend_from_slice(&encode_text_string(path));
+            }
+            PdfBookmarkAction::RemoteDestination(path, page) => {
+                result.extend_from_slice(b"/GoToR /F ");
+                result.extend_from_slice(&encode_text_string(path));
+                result.extend_from_slice(format!(" /D [{page} /Fit]").as_bytes());
+            }
+            PdfBookmarkAction::Named(name) => {
+                result.extend_from_slice(b"/Named /N ");
+                result.extend_from_slice(&encode_name(name));
+            }
+        }
+
+        result.extend_from_slice(b">>");
+
+        result
+    }
+
+    /// Returns the [PdfAction] that Pdfium will report for this [PdfBookmarkAction] once
+    /// the document containing it is saved and reloaded. The returned action has no
+    /// Pdfium handle.
+    pub(crate) fn to_action<'a>(
+        &self,
+        document_handle: FPDF_DOCUMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> PdfAction<'a> {
+        match self {
+            PdfBookmarkAction::Uri(uri) => PdfAction::Uri(PdfActionUri::from_unsaved(
+                uri.clone(),
+                document_handle,
+                bindings,
+            )),
+            PdfBookmarkAction::JavaScript(script) => PdfAction::JavaScript(
+                PdfActionJavaScript::from_pdfium(null_mut(), script.clone(), bindings),
+            ),
+            PdfBookmarkAction::Launch(_) => {
+                PdfAction::Launch(PdfActionLaunch::from_pdfium(null_mut(), bindings))
+            }
+            PdfBookmarkAction::RemoteDestination(..) => PdfAction::RemoteDestination(
+                PdfActionRemoteDestination::from_pdfium(null_mut(), bindings),
+            ),
+            PdfBookmarkAction::Named(_) => {
+                // Pdfium does not recognize named actions.
+
+                PdfAction::Unsupported(PdfActionUnsupported::from_pdfium(
+                    null_mut(),
+                    document_handle,
+                    None,
+                    bindings,
+                ))
+            }
+        }
+    }
+}
+
 /// A single bookmark in a [PdfBookmarks] collection.
+///
+/// A [PdfBookmark] is a read-only view of a bookmark. To edit a bookmark, retrieve a
+/// [PdfBookmarkMut] for it from the mutable [PdfBookmarks] collection returned by
+/// [PdfDocument::bookmarks_mut()]. Edits are immediately visible to all the bookmark
+/// functions of the containing [PdfDocument], and are written to the document when it is saved.
 #[derive(Clone)]
 pub struct PdfBookmark<'a> {
     bookmark_handle: FPDF_BOOKMARK,
     parent: Option<FPDF_BOOKMARK>,
+    node: Option<PdfBookmarkNodeId>,
     document_handle: FPDF_DOCUMENT,
+    tree: Option<&'a PdfBookmarkTree>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -44,7 +164,13 @@ impl<'a> PartialEq for PdfBookmark<'a> {
         // - The structure is allocated and retained by Pdfium for as long as the document is open,
         //   so the same bookmark will always give the same handle.
 
+        // Bookmarks created since the document was loaded have no handle; they are instead
+        // identified by their node in the document's bookmark tree.
+
         self.bookmark_handle == other.bookmark_handle
+            && self.node == other.node
+            && (self.node.is_none()
+                || matches!((self.tree, other.tree), (Some(a), Some(b)) if std::ptr::eq(a, b)))
     }
 }
 
@@ -56,6 +182,7 @@ impl<'a> Hash for PdfBookmark<'a> {
         H: Hasher,
     {
         self.bookmark_handle.hash(state);
+        self.node.hash(state);
     }
 }
 
@@ -69,17 +196,56 @@ impl<'a> PdfBookmark<'a> {
         PdfBookmark {
             bookmark_handle,
             parent,
+            node: None,
+            document_handle,
+            tree: None,
+            bindings,
+        }
+    }
+
+    /// Creates a [PdfBookmark] for the node with the given identifier in the given
+    /// bookmark tree.
+    pub(crate) fn from_node(
+        tree: &'a PdfBookmarkTree,
+        id: PdfBookmarkNodeId,
+        document_handle: FPDF_DOCUMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        let node = tree.node(id);
+
+        let parent = node
+            .parent
+            .map(|parent| tree.node(parent).handle)
+            .filter(|parent| !parent.is_null());
+
+        PdfBookmark {
+            bookmark_handle: node.handle,
+            parent,
+            node: if node.handle.is_null() {
+                Some(id)
+            } else {
+                None
+            },
             document_handle,
+            tree: Some(tree),
             bindings,
         }
     }
 
-    /// Returns the internal `FPDF_BOOKMARK` handle for this [PdfBookmark].
+    /// Returns the internal `FPDF_BOOKMARK` handle for this [PdfBookmark]. The handle is null
+    /// if this bookmark was created since the containing [PdfDocument] was loaded.
     #[inline]
     pub(crate) fn bookmark_handle(&self) -> FPDF_BOOKMARK {
         self.bookmark_handle
     }
 
+    /// Returns the identifier of this bookmark's node in the document's bookmark tree,
+    /// if this bookmark was created since the containing [PdfDocument] was loaded.
+    #[inline]
+    pub(crate) fn node(&self) -> Option<PdfBookmarkNodeId> {
+        self.node
+    }
+
     /// Returns the internal `FPDF_DOCUMENT` handle of the [PdfDocument] containing this [PdfBookmark].
     #[inline]
     pub(crate) fn document_handle(&self) -> FPDF_DOCUMENT {
@@ -94,35 +260,10 @@ impl<'a> PdfBookmark<'a> {
 
     /// Returns the title of this [PdfBookmark], if any.
     pub fn title(&self) -> Option<String> {
-        // Retrieving the bookmark title from Pdfium is a two-step operation. First, we call
-        // FPDFBookmark_GetTitle() with a null buffer; this will retrieve the length of
-        // the bookmark title in bytes. If the length is zero, then there is no title.
-
-        // If the length is non-zero, then we reserve a byte buffer of the given
-        // length and call FPDFBookmark_GetTitle() again with a pointer to the buffer;
-        // this will write the bookmark title to the buffer in UTF16-LE format.
-
-        let buffer_length =
-            self.bindings
-                .FPDFBookmark_GetTitle(self.bookmark_handle, std::ptr::null_mut(), 0);
-
-        if buffer_length == 0 {
-            // No title is defined.
-
-            return None;
-        }
-
-        let mut buffer = create_byte_buffer(buffer_length as usize);
-
-        let result = self.bindings.FPDFBookmark_GetTitle(
-            self.bookmark_handle,
-            buffer.as_mut_ptr() as *mut c_void,
-            buffer_length,
-        );
-
-        assert_eq!(result, buffer_length);
-
-        get_string_from_pdfium_utf16le_bytes(buffer)
+        self.query(
+            |tree, id| id.and_then(|id| tree.node(id).title.clone()),
+            || get_title(self.bindings, self.bookmark_handle),
+        )
     }
 
     /// Returns the [PdfAction] associated with this [PdfBookmark], if any.
@@ -131,7 +272,23 @@ impl<'a> PdfBookmark<'a> {
     /// bookmark in a PDF viewer. For most bookmarks, this will be a local navigation action
     /// of type [PdfActionType::GoToDestinationInSameDocument], but the PDF file format supports
     /// a variety of other actions.
+    ///
+    /// For an action set using [PdfBookmarkMut::set_action()], the returned [PdfAction] has
+    /// the type Pdfium will report once the document is saved and reloaded.
     pub fn action(&self) -> Option<PdfAction<'a>> {
+        self.query(
+            |tree, id| match id.map(|id| tree.node(id).target.as_ref()) {
+                Some(Some(PdfBookmarkTarget::Action(action))) => {
+                    Some(action.to_action(self.document_handle, self.bindings))
+                }
+                Some(None) if !self.bookmark_handle.is_null() => self.get_action_from_pdfium(),
+                _ => None,
+            },
+            || self.get_action_from_pdfium(),
+        )
+    }
+
+    fn get_action_from_pdfium(&self) -> Option<PdfAction<'a>> {
         let handle = self.bindings.FPDFBookmark_GetAction(self.bookmark_handle);
 
         if handle.is_null() {
@@ -140,6 +297,7 @@ impl<'a> PdfBookmark<'a> {
             Some(PdfAction::from_pdfium(
                 handle,
                 self.document_handle,
+                PdfActionOwner::Bookmark(self.bookmark_handle),
                 self.bindings,
             ))
         }
@@ -150,6 +308,19 @@ impl<'a> PdfBookmark<'a> {
     /// The destination specifies the page and region, if any, that will be the target
     /// of the action behaviour specified by [PdfBookmark::action()].
     pub fn destination(&self) -> Option<PdfDestination<'a>> {
+        self.query(
+            |tree, id| match id.map(|id| tree.node(id).target.as_ref()) {
+                Some(Some(PdfBookmarkTarget::Destination(page, view))) => Some(
+                    PdfDestination::from_unsaved(self.document_handle, *page, *view, self.bindings),
+                ),
+                Some(None) if !self.bookmark_handle.is_null() => self.get_destination_from_pdfium(),
+                _ => None,
+            },
+            || self.get_destination_from_pdfium(),
+        )
+    }
+
+    fn get_destination_from_pdfium(&self) -> Option<PdfDestination<'a>> {
         let handle = self
             .bindings
             .FPDFBookmark_GetDest(self.document_handle, self.bookmark_handle);
@@ -166,101 +337,140 @@ impl<'a> PdfBookmark<'a> {
     }
 
     /// Returns this [PdfBookmark] object's direct parent, if available.
-    #[inline]
     pub fn parent(&self) -> Option<PdfBookmark<'a>> {
-        self.parent.map(|parent_handle| {
-            PdfBookmark::from_pdfium(parent_handle, None, self.document_handle, self.bindings)
-        })
+        self.query(
+            |tree, id| {
+                id.and_then(|id| tree.node(id).parent)
+                    .map(|parent| self.bookmark_for_node(tree, parent))
+            },
+            || {
+                self.parent.map(|parent_handle| {
+                    PdfBookmark::from_pdfium(
+                        parent_handle,
+                        None,
+                        self.document_handle,
+                        self.bindings,
+                    )
+                })
+            },
+        )
     }
 
     /// Returns the number of direct children of this [PdfBookmark].
-    #[inline]
     pub fn children_len(&self) -> usize {
-        // If there are N child bookmarks, then FPDFBookmark_GetCount returns a
-        // N if the bookmark tree should be displayed open by default, and -N if
-        // the child tree should be displayed closed by deafult.
-        self.bindings
-            .FPDFBookmark_GetCount(self.bookmark_handle)
-            .unsigned_abs() as usize
+        self.query(
+            |tree, id| id.map(|id| tree.node(id).children.len()).unwrap_or(0),
+            || {
+                // If there are N child bookmarks, then FPDFBookmark_GetCount returns a
+                // N if the bookmark tree should be displayed open by default, and -N if
+                // the child tree should be displayed closed by deafult.
+                self.bindings
+                    .FPDFBookmark_GetCount(self.bookmark_handle)
+                    .unsigned_abs() as usize
+            },
+        )
     }
 
     /// Returns the first child [PdfBookmark] of this [PdfBookmark], if any.
     pub fn first_child(&self) -> Option<PdfBookmark<'a>> {
-        let handle = self
-            .bindings
-            .FPDFBookmark_GetFirstChild(self.document_handle, self.bookmark_handle);
-
-        if handle.is_null() {
-            None
-        } else {
-            Some(PdfBookmark::from_pdfium(
-                handle,
-                Some(self.bookmark_handle),
-                self.document_handle,
-                self.bindings,
-            ))
-        }
+        self.query(
+            |tree, id| {
+                id.and_then(|id| tree.node(id).children.first().copied())
+                    .map(|child| self.bookmark_for_node(tree, child))
+            },
+            || {
+                let handle = self
+                    .bindings
+                    .FPDFBookmark_GetFirstChild(self.document_handle(), self.bookmark_handle());
+
+                if handle.is_null() {
+                    None
+                } else {
+                    Some(PdfBookmark::from_pdfium(
+                        handle,
+                        Some(self.bookmark_handle),
+                        self.document_handle,
+                        self.bindings,
+                    ))
+                }
+            },
+        )
     }
 
     /// Returns the next [PdfBookmark] at the same tree level as this [PdfBookmark], if any.
     pub fn next_sibling(&self) -> Option<PdfBookmark<'a>> {
-        let handle = self
-            .bindings
-            .FPDFBookmark_GetNextSibling(self.document_handle, self.bookmark_handle);
-
-        if handle.is_null() {
-            None
-        } else {
-            Some(PdfBookmark::from_pdfium(
-                handle,
-                self.parent,
-                self.document_handle,
-                self.bindings,
-            ))
-        }
+        self.query(
+            |tree, id| {
+                let id = id?;
+
+                let siblings = tree.siblings(id);
+
+                let position = siblings.iter().position(|sibling| *sibling == id)?;
+
+                siblings
+                    .get(position + 1)
+                    .map(|sibling| self.bookmark_for_node(tree, *sibling))
+            },
+            || {
+                let handle = self
+                    .bindings
+                    .FPDFBookmark_GetNextSibling(self.document_handle(), self.bookmark_handle());
+
+                if handle.is_null() {
+                    None
+                } else {
+                    Some(PdfBookmark::from_pdfium(
+                        handle,
+                        self.parent,
+                        self.document_handle,
+                        self.bindings,
+                    ))
+                }
+            },
+        )
     }
 
     /// Returns an iterator over all [PdfBookmark] sibling nodes of this [PdfBookmark].
-    #[inline]
     pub fn iter_siblings(&self) -> PdfBookmarksIterator<'a> {
-        match self.parent {
-            Some(parent_handle) => {
-                // Siblings by definition all share the same parent. We can achieve a more
-                // consistent result, irrespective of whether we are the parent's first direct
-                // child or not, by iterating over all the parent's children.
+        let first_sibling = self.query(
+            |tree, id| {
+                // Once the bookmark tree has been edited, the parent of every bookmark is known,
+                // so we can always iterate over all the parent's children.
+
+                id.and_then(|id| tree.siblings(id).first().copied())
+                    .map(|first| self.bookmark_for_node(tree, first))
+            },
+            || match self.parent {
+                Some(parent_handle) => {
+                    // Siblings by definition all share the same parent. We can achieve a more
+                    // consistent result, irrespective of whether we are the parent's first direct
+                    // child or not, by iterating over all the parent's children.
 
-                PdfBookmarksIterator::new(
                     PdfBookmark::from_pdfium(
                         parent_handle,
                         None,
                         self.document_handle,
                         self.bindings,
                     )
-                    .first_child(),
-                    false,
-                    // Signal that the iterator should skip over this bookmark when iterating
-                    // the parent's direct children.
-                    Some(self.clone()),
-                    self.document_handle(),
-                    self.bindings(),
-                )
-            }
-            None => {
-                // Since no handle to the parent is available, the best we can do is create an iterator
-                // that repeatedly calls Self::next_sibling(). If we are not the first direct child
-                // of a parent node, then this approach may not include all the parent's children.
-
-                PdfBookmarksIterator::new(
-                    Some(self.clone()),
-                    false,
-                    // Signal that the iterator should skip over this bookmark when iterating
-                    // the parent's direct children.
-                    Some(self.clone()),
-                    self.document_handle(),
-                    self.bindings(),
-                )
-            }
-        }
+                    .first_child()
+                }
+                None => {
+                    // Since no handle to the parent is available, the best we can do is create an iterator
+                    // that repeatedly calls Self::next_sibling(). If we are not the first direct child
+                    // of a parent node, then this approach may not include all the parent's children.
+
+                    Some(self.clone())
+                }
+            },
+        );
+
+        PdfBookmarksIterator::new(
+            first_sibling,
+            false,
+            // Signal that the iterator should skip over this bookmark when iterating
+            // the parent's direct children.
+            Some(self.clone()),
+        )
     }
 
     /// Returns an iterator over all [PdfBookmark] child nodes of this [PdfBookmark].
@@ -269,13 +479,7 @@ impl<'a> PdfBookmark<'a> {
     /// To visit all child nodes, including children of children, use [PdfBookmark::iter_all_descendants()].
     #[inline]
     pub fn iter_direct_children(&self) -> PdfBookmarksIterator<'a> {
-        PdfBookmarksIterator::new(
-            self.first_child(),
-            false,
-            None,
-            self.document_handle(),
-            self.bindings(),
-        )
+        PdfBookmarksIterator::new(self.first_child(), false, None)
     }
 
     /// Returns an iterator over all [PdfBookmark] descendant nodes of this [PdfBookmark],
@@ -283,13 +487,164 @@ impl<'a> PdfBookmark<'a> {
     /// use [PdfBookmark::iter_direct_children()].
     #[inline]
     pub fn iter_all_descendants(&self) -> PdfBookmarksIterator<'a> {
-        PdfBookmarksIterator::new(
-            self.first_child(),
-            true,
-            None,
-            self.document_handle(),
-            self.bindings(),
-        )
+        PdfBookmarksIterator::new(self.first_child(), true, None)
+    }
+
+    /// Returns the identifier of this bookmark's node in the given bookmark tree,
+    /// or `None` if this bookmark has been deleted.
+    fn node_id(&self, tree: &PdfBookmarkTree) -> Option<PdfBookmarkNodeId> {
+        self.node
+            .or_else(|| tree.node_for_handle(self.bookmark_handle))
+            .filter(|id| !tree.node(*id).is_deleted)
+    }
+
+    /// Creates a [PdfBookmark] for the node with the given identifier in the given
+    /// bookmark tree, sharing this bookmark's document.
+    #[inline]
+    fn bookmark_for_node(
+        &self,
+        tree: &'a PdfBookmarkTree,
+        id: PdfBookmarkNodeId,
+    ) -> PdfBookmark<'a> {
+        PdfBookmark::from_node(tree, id, self.document_handle, self.bindings)
+    }
+
+    /// Answers a query about this bookmark using the document's bookmark tree, if the document's
+    /// bookmarks have been edited, or using Pdfium otherwise.
+    fn query<T>(
+        &self,
+        from_tree: impl FnOnce(&'a PdfBookmarkTree, Option<PdfBookmarkNodeId>) -> T,
+        from_pdfium: impl FnOnce() -> T,
+    ) -> T {
+        match self.tree {
+            Some(tree) => from_tree(tree, self.node_id(tree)),
+            None => from_pdfium(),
+        }
+    }
+}
+
+/// A single bookmark in a [PdfBookmarks] collection, borrowed for editing from the mutable
+/// collection returned by [PdfDocument::bookmarks_mut()].
+///
+/// Retrieve a [PdfBookmarkMut] using the [PdfBookmarks::create_root()],
+/// [PdfBookmarks::root_mut()], [PdfBookmarks::find_first_by_title_mut()], or
+/// [PdfBookmarks::find_first_mut()] functions. Bookmarks created using the
+/// [PdfBookmarkMut::add_child()] and [PdfBookmarkMut::insert_sibling_after()] functions
+/// are returned as a [PdfBookmarkMut] in turn.
+pub struct PdfBookmarkMut<'a> {
+    tree: &'a mut PdfBookmarkTree,
+    node: PdfBookmarkNodeId,
+    document_handle: FPDF_DOCUMENT,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfBookmarkMut<'a> {
+    #[inline]
+    pub(crate) fn from_node(
+        tree: &'a mut PdfBookmarkTree,
+        node: PdfBookmarkNodeId,
+        document_handle: FPDF_DOCUMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfBookmarkMut {
+            tree,
+            node,
+            document_handle,
+            bindings,
+        }
+    }
+
+    /// Returns a read-only [PdfBookmark] for this bookmark.
+    #[inline]
+    pub fn as_bookmark(&self) -> PdfBookmark<'_> {
+        PdfBookmark::from_node(self.tree, self.node, self.document_handle, self.bindings)
+    }
+
+    /// Creates a new bookmark with the given title as the last direct child of this
+    /// bookmark, returning the new bookmark.
+    pub fn add_child(&mut self, title: &str) -> PdfBookmarkMut<'_> {
+        let position = self.tree.node(self.node).children.len();
+
+        let child = self.tree.insert(Some(self.node), position, title);
+
+        PdfBookmarkMut::from_node(self.tree, child, self.document_handle, self.bindings)
+    }
+
+    /// Creates a new bookmark with the given title at the same tree level as this
+    /// bookmark, immediately after it, returning the new bookmark.
+    pub fn insert_sibling_after(&mut self, title: &str) -> PdfBookmarkMut<'_> {
+        let parent = self.tree.node(self.node).parent;
+
+        let position = self
+            .tree
+            .siblings(self.node)
+            .iter()
+            .position(|sibling| *sibling == self.node)
+            .map(|position| position + 1)
+            .unwrap_or(0);
+
+        let sibling = self.tree.insert(parent, position, title);
+
+        PdfBookmarkMut::from_node(self.tree, sibling, self.document_handle, self.bindings)
+    }
+
+    /// Sets the title of this bookmark.
+    #[inline]
+    pub fn set_title(&mut self, title: &str) {
+        self.tree.node_mut(self.node).title = Some(title.to_string());
+    }
+
+    /// Sets the target of this bookmark to the page at the given index in the containing
+    /// [PdfDocument], displayed using the given view settings. Any action previously
+    /// associated with this bookmark is replaced.
+    ///
+    /// The page index is resolved when the document is saved.
+    pub fn set_destination(
+        &mut self,
+        page: PdfPageIndex,
+        view: PdfDestinationViewSettings,
+    ) -> Result<(), PdfiumError> {
+        if page as i32 >= self.bindings.FPDF_GetPageCount(self.document_handle) {
+            return Err(PdfiumError::PageIndexOutOfBounds);
+        }
+
+        self.tree.node_mut(self.node).target = Some(PdfBookmarkTarget::Destination(page, view));
+
+        Ok(())
+    }
+
+    /// Sets the action performed when the user activates this bookmark. Any destination
+    /// previously associated with this bookmark is replaced.
+    #[inline]
+    pub fn set_action(&mut self, action: PdfBookmarkAction) {
+        self.tree.node_mut(self.node).target = Some(PdfBookmarkTarget::Action(action));
+    }
+
+    /// Sets whether the children of this bookmark should be displayed when the
+    /// containing [PdfDocument] is opened in a PDF viewer.
+    #[inline]
+    pub fn set_open(&mut self, is_open: bool) {
+        self.tree.node_mut(self.node).is_open = is_open;
+    }
+
+    /// Sets the color used to display the title of this bookmark. The alpha channel
+    /// of the given color is ignored.
+    #[inline]
+    pub fn set_color(&mut self, color: PdfColor) {
+        self.tree.node_mut(self.node).color = Some(color);
+    }
+
+    /// Sets the style used to display the title of this bookmark.
+    #[inline]
+    pub fn set_style(&mut self, style: PdfBookmarkStyle) {
+        self.tree.node_mut(self.node).style = Some(style);
+    }
+
+    /// Deletes this bookmark, along with all its descendants, from the containing
+    /// [PdfDocument].
+    #[inline]
+    pub fn delete(self) {
+        self.tree.delete(self.node);
     }
 }
 
@@ -567,4 +922,85 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_edit_bookmarks() -> Result<(), PdfiumError> {
+        fn titles(document: &PdfDocument) -> Vec<String> {
+            document
+                .bookmarks()
+                .iter()
+                .map(|bookmark| bookmark.title().expect("Bookmark Title"))
+                .collect()
+        }
+
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        for _ in 0..3 {
+            document
+                .pages_mut()
+                .create_page_at_end(PdfPagePaperSize::a4())?;
+        }
+
+        let mut chapter1 = document.bookmarks_mut().create_root("Chapter 1");
+
+        chapter1.set_destination(0, PdfDestinationViewSettings::FitPageToWindow)?;
+
+        let mut section1 = chapter1.add_child("Section 1.1");
+
+        section1.set_destination(1, PdfDestinationViewSettings::FitPageToWindow)?;
+        section1.insert_sibling_after("Section 1.2").delete();
+        section1.set_title("Section 1.1 (revised)");
+
+        let mut chapter2 = document.bookmarks_mut().create_root("Chapter 2");
+
+        chapter2.set_action(PdfBookmarkAction::Uri("https://example.com".to_string()));
+
+        assert!(chapter2
+            .set_destination(3, PdfDestinationViewSettings::FitPageToWindow)
+            .is_err());
+
+        let expected = ["Chapter 1", "Section 1.1 (revised)", "Chapter 2"];
+
+        assert_eq!(titles(&document), expected);
+
+        // The new targets are reported before the document is saved.
+
+        let section1 = document
+            .bookmarks()
+            .find_first_by_title("Section 1.1 (revised)")?;
+
+        assert_eq!(section1.destination().unwrap().page_index()?, 1);
+        assert!(section1.action().is_none());
+
+        let chapter2 = document.bookmarks().find_first_by_title("Chapter 2")?;
+
+        assert!(chapter2.destination().is_none());
+        assert_eq!(
+            chapter2.action().unwrap().as_uri_action().unwrap().uri()?,
+            "https://example.com"
+        );
+
+        document
+            .bookmarks_mut()
+            .find_first_by_title_mut("Chapter 2")?
+            .set_title("Chapter 2 (revised)");
+
+        let expected = ["Chapter 1", "Section 1.1 (revised)", "Chapter 2 (revised)"];
+
+        assert_eq!(titles(&document), expected);
+
+        let document = pdfium.load_pdf_from_byte_vec(document.save_to_bytes()?, None)?;
+
+        assert_eq!(titles(&document), expected);
+
+        let section1 = document
+            .bookmarks()
+            .find_first_by_title("Section 1.1 (revised)")?;
+
+        assert_eq!(section1.destination().unwrap().page_index()?, 1);
+
+        Ok(())
+    }
 }
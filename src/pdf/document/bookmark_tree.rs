@@ -0,0 +1,623 @@
+//! Defines the [PdfBookmarkTree] struct, an editable copy of the bookmark tree of a
+//! `PdfDocument`.
+//!
+//! Pdfium cannot edit bookmarks, so the first change made to a document's bookmarks takes
+//! a copy of the bookmark tree. From then on, the copy is used to answer all queries about
+//! the document's bookmarks, and is written to the document's `/Outlines` tree as part of
+//! an incremental update each time the document is saved.
+
+use crate::bindgen::{FPDF_BOOKMARK, FPDF_DOCUMENT};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::color::PdfColor;
+use crate::pdf::destination::PdfDestinationViewSettings;
+use crate::pdf::document::bookmark::{PdfBookmarkAction, PdfBookmarkStyle};
+use crate::pdf::document::incremental_update::{
+    encode_text_string, PdfIncrementalUpdate, PdfObjectId, PdfRawDictionary,
+};
+use crate::pdf::document::pages::PdfPageIndex;
+use crate::utils::mem::create_byte_buffer;
+use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use std::collections::{HashMap, HashSet};
+use std::os::raw::c_void;
+use std::ptr::null_mut;
+
+/// The identifier of a single node in a [PdfBookmarkTree].
+pub(crate) type PdfBookmarkNodeId = usize;
+
+/// The target of a bookmark set since the document was loaded.
+#[derive(Debug, Clone)]
+pub(crate) enum PdfBookmarkTarget {
+    Destination(PdfPageIndex, PdfDestinationViewSettings),
+    Action(PdfBookmarkAction),
+}
+
+/// A single bookmark in a [PdfBookmarkTree].
+pub(crate) struct PdfBookmarkNode {
+    /// The Pdfium handle of the bookmark, or null if the bookmark was created since
+    /// the document was loaded.
+    pub(crate) handle: FPDF_BOOKMARK,
+    pub(crate) parent: Option<PdfBookmarkNodeId>,
+    pub(crate) children: Vec<PdfBookmarkNodeId>,
+    pub(crate) title: Option<String>,
+    /// The bookmark's target, if it has been set since the document was loaded.
+    pub(crate) target: Option<PdfBookmarkTarget>,
+    pub(crate) is_open: bool,
+    pub(crate) color: Option<PdfColor>,
+    pub(crate) style: Option<PdfBookmarkStyle>,
+    pub(crate) is_deleted: bool,
+}
+
+impl PdfBookmarkNode {
+    fn new(
+        handle: FPDF_BOOKMARK,
+        parent: Option<PdfBookmarkNodeId>,
+        title: Option<String>,
+    ) -> Self {
+        PdfBookmarkNode {
+            handle,
+            parent,
+            children: Vec::new(),
+            title,
+            target: None,
+            is_open: false,
+            color: None,
+            style: None,
+            is_deleted: false,
+        }
+    }
+}
+
+/// An editable copy of the bookmark tree of a `PdfDocument`.
+///
+/// Bookmarks that existed when the document was loaded are held in the order in which
+/// a depth-first prefix-order traversal of the document's `/Outlines` tree visits them,
+/// followed by any bookmarks created since.
+pub(crate) struct PdfBookmarkTree {
+    nodes: Vec<PdfBookmarkNode>,
+    top_level: Vec<PdfBookmarkNodeId>,
+    original_len: usize,
+    nodes_by_handle: HashMap<FPDF_BOOKMARK, PdfBookmarkNodeId>,
+}
+
+impl PdfBookmarkTree {
+    /// Copies the bookmark tree of the given document.
+    pub(crate) fn from_pdfium(
+        document_handle: FPDF_DOCUMENT,
+        bindings: &dyn PdfiumLibraryBindings,
+    ) -> Self {
+        let mut result = PdfBookmarkTree {
+            nodes: Vec::new(),
+            top_level: Vec::new(),
+            original_len: 0,
+            nodes_by_handle: HashMap::new(),
+        };
+
+        result.top_level = result.copy_children(
+            document_handle,
+            bindings,
+            null_mut(),
+            None,
+            &mut HashSet::new(),
+        );
+
+        result.original_len = result.nodes.len();
+
+        result
+    }
+
+    /// Copies the children of the given bookmark, or the top-level bookmarks if the given
+    /// handle is null, returning the identifiers of the copied children.
+    fn copy_children(
+        &mut self,
+        document_handle: FPDF_DOCUMENT,
+        bindings: &dyn PdfiumLibraryBindings,
+        parent_handle: FPDF_BOOKMARK,
+        parent: Option<PdfBookmarkNodeId>,
+        visited: &mut HashSet<FPDF_BOOKMARK>,
+    ) -> Vec<PdfBookmarkNodeId> {
+        let mut children = Vec::new();
+
+        let mut handle = bindings.FPDFBookmark_GetFirstChild(document_handle, parent_handle);
+
+        // Guard against cyclic bookmark graphs in the same way as PdfBookmarksIterator.
+
+        while !handle.is_null() && visited.insert(handle) {
+            let id = self.nodes.len();
+
+            let mut node = PdfBookmarkNode::new(handle, parent, get_title(bindings, handle));
+
+            node.is_open = bindings.FPDFBookmark_GetCount(handle) > 0;
+
+            self.nodes.push(node);
+            self.nodes_by_handle.insert(handle, id);
+
+            children.push(id);
+
+            self.nodes[id].children =
+                self.copy_children(document_handle, bindings, handle, Some(id), visited);
+
+            handle = bindings.FPDFBookmark_GetNextSibling(document_handle, handle);
+        }
+
+        children
+    }
+
+    /// Returns the identifier of the node for the bookmark with the given Pdfium handle, if any.
+    #[inline]
+    pub(crate) fn node_for_handle(&self, handle: FPDF_BOOKMARK) -> Option<PdfBookmarkNodeId> {
+        self.nodes_by_handle.get(&handle).copied()
+    }
+
+    /// Returns the node with the given identifier.
+    #[inline]
+    pub(crate) fn node(&self, id: PdfBookmarkNodeId) -> &PdfBookmarkNode {
+        &self.nodes[id]
+    }
+
+    /// Returns the node with the given identifier.
+    #[inline]
+    pub(crate) fn node_mut(&mut self, id: PdfBookmarkNodeId) -> &mut PdfBookmarkNode {
+        &mut self.nodes[id]
+    }
+
+    /// Returns the identifiers of the top-level bookmarks in this tree.
+    #[inline]
+    pub(crate) fn top_level(&self) -> &[PdfBookmarkNodeId] {
+        self.top_level.as_slice()
+    }
+
+    /// Returns the identifiers of the given node and all its siblings, in order.
+    pub(crate) fn siblings(&self, id: PdfBookmarkNodeId) -> &[PdfBookmarkNodeId] {
+        match self.nodes[id].parent {
+            Some(parent) => self.nodes[parent].children.as_slice(),
+            None => self.top_level.as_slice(),
+        }
+    }
+
+    /// Creates a new bookmark with the given title as a child of the given parent node,
+    /// or as a top-level bookmark if no parent is given, at the given position among
+    /// its siblings. Returns the identifier of the new node.
+    pub(crate) fn insert(
+        &mut self,
+        parent: Option<PdfBookmarkNodeId>,
+        position: usize,
+        title: &str,
+    ) -> PdfBookmarkNodeId {
+        let id = self.nodes.len();
+
+        self.nodes.push(PdfBookmarkNode::new(
+            null_mut(),
+            parent,
+            Some(title.to_string()),
+        ));
+
+        match parent {
+            Some(parent) => self.nodes[parent].children.insert(position, id),
+            None => self.top_level.insert(position, id),
+        }
+
+        id
+    }
+
+    /// Removes the given node and all its descendants from this tree.
+    pub(crate) fn delete(&mut self, id: PdfBookmarkNodeId) {
+        match self.nodes[id].parent {
+            Some(parent) => self.nodes[parent].children.retain(|child| *child != id),
+            None => self.top_level.retain(|child| *child != id),
+        }
+
+        let mut pending = vec![id];
+
+        while let Some(id) = pending.pop() {
+            self.nodes[id].is_deleted = true;
+
+            pending.extend_from_slice(&self.nodes[id].children);
+        }
+    }
+
+    /// Returns the number of descendants of the given node that are visible when the node
+    /// is open, or the number of visible top-level bookmarks if no node is given.
+    fn visible_descendants(&self, id: Option<PdfBookmarkNodeId>) -> usize {
+        let children = match id {
+            Some(id) => &self.nodes[id].children,
+            None => &self.top_level,
+        };
+
+        children
+            .iter()
+            .map(|child| {
+                1 + if self.nodes[*child].is_open {
+                    self.visible_descendants(Some(*child))
+                } else {
+                    0
+                }
+            })
+            .sum()
+    }
+
+    /// Writes this tree to the `/Outlines` tree of the given saved document. Existing outline
+    /// items are updated in place, keeping any entries that this tree does not track;
+    /// outline items for deleted bookmarks are no longer referenced.
+    pub(crate) fn apply_to(&self, update: &mut PdfIncrementalUpdate) -> Result<(), PdfiumError> {
+        let root = update.root()?;
+
+        let mut catalog = update.get_dictionary(root)?;
+
+        let original_items = match catalog.get_reference("Outlines") {
+            Some(outlines) => get_outline_items(update, outlines)?,
+            None => Vec::new(),
+        };
+
+        if original_items.len() != self.original_len {
+            // The saved outline does not match the bookmarks reported by Pdfium.
+
+            return Err(PdfiumError::UnsupportedPdfFileStructure);
+        }
+
+        let pages = get_page_objects(
+            update,
+            catalog
+                .get_reference("Pages")
+                .ok_or(PdfiumError::UnsupportedPdfFileStructure)?,
+        )?;
+
+        let outlines = match catalog.get_reference("Outlines") {
+            Some(outlines) => outlines,
+            None => {
+                let outlines = update.add_object(b"<</Type /Outlines>>".to_vec());
+
+                catalog.set("Outlines", outlines.to_reference());
+
+                update.set_object(root, catalog.to_bytes());
+
+                outlines
+            }
+        };
+
+        // Allocate an object for every bookmark in the tree before writing any of them,
+        // so that every bookmark can refer to its parent, siblings, and children.
+
+        let mut objects: HashMap<PdfBookmarkNodeId, PdfObjectId> = HashMap::new();
+
+        let mut pending = self.top_level.clone();
+
+        while let Some(id) = pending.pop() {
+            let object = if id < self.original_len {
+                original_items[id]
+            } else {
+                update.add_object(Vec::new())
+            };
+
+            objects.insert(id, object);
+
+            pending.extend_from_slice(&self.nodes[id].children);
+        }
+
+        for (id, object) in objects.iter() {
+            let node = &self.nodes[*id];
+
+            let mut item = if *id < self.original_len {
+                update.get_dictionary(*object)?
+            } else {
+                PdfRawDictionary::new()
+            };
+
+            match node.title.as_ref() {
+                Some(title) => item.set("Title", encode_text_string(title)),
+                None => item.remove("Title"),
+            }
+
+            match node.target.as_ref() {
+                Some(PdfBookmarkTarget::Destination(page, view)) => {
+                    let page = pages
+                        .get(*page as usize)
+                        .ok_or(PdfiumError::PageIndexOutOfBounds)?;
+
+                    item.remove("A");
+                    item.set("Dest", destination_to_bytes(*page, view));
+                }
+                Some(PdfBookmarkTarget::Action(action)) => {
+                    item.remove("Dest");
+                    item.set("A", action.to_dictionary());
+                }
+                None => {}
+            }
+
+            if let Some(color) = node.color.as_ref() {
+                item.set(
+                    "C",
+                    format!(
+                        "[{} {} {}]",
+                        format_number(color.red() as f32 / 255.0),
+                        format_number(color.green() as f32 / 255.0),
+                        format_number(color.blue() as f32 / 255.0)
+                    ),
+                );
+            }
+
+            if let Some(style) = node.style.as_ref() {
+                item.set("F", style.bits().to_string());
+            }
+
+            let siblings = self.siblings(*id);
+
+            let position = siblings
+                .iter()
+                .position(|sibling| sibling == id)
+                .unwrap_or(0);
+
+            item.set(
+                "Parent",
+                node.parent
+                    .map(|parent| objects[&parent])
+                    .unwrap_or(outlines)
+                    .to_reference(),
+            );
+
+            set_reference(
+                &mut item,
+                "Prev",
+                position
+                    .checked_sub(1)
+                    .map(|previous| objects[&siblings[previous]]),
+            );
+            set_reference(
+                &mut item,
+                "Next",
+                siblings.get(position + 1).map(|next| objects[next]),
+            );
+
+            self.set_children(&mut item, Some(*id), &objects);
+
+            update.set_object(*object, item.to_bytes());
+        }
+
+        let mut outlines_dictionary = update.get_dictionary(outlines)?;
+
+        self.set_children(&mut outlines_dictionary, None, &objects);
+
+        update.set_object(outlines, outlines_dictionary.to_bytes());
+
+        Ok(())
+    }
+
+    /// Sets the `/First`, `/Last`, and `/Count` entries of the given outline item dictionary
+    /// for the given node, or of the outline dictionary if no node is given.
+    fn set_children(
+        &self,
+        dictionary: &mut PdfRawDictionary,
+        id: Option<PdfBookmarkNodeId>,
+        objects: &HashMap<PdfBookmarkNodeId, PdfObjectId>,
+    ) {
+        let children = match id {
+            Some(id) => &self.nodes[id].children,
+            None => &self.top_level,
+        };
+
+        set_reference(
+            dictionary,
+            "First",
+            children.first().map(|child| objects[child]),
+        );
+        set_reference(
+            dictionary,
+            "Last",
+            children.last().map(|child| objects[child]),
+        );
+
+        let count = self.visible_descendants(id) as i64;
+
+        // The count of a closed outline item is negated. The count of the outline dictionary
+        // itself is always positive.
+
+        match id {
+            _ if children.is_empty() => dictionary.remove("Count"),
+            Some(id) if !self.nodes[id].is_open => dictionary.set("Count", (-count).to_string()),
+            _ => dictionary.set("Count", count.to_string()),
+        }
+    }
+}
+
+/// Retrieves the title of the given bookmark from Pdfium, if any.
+pub(crate) fn get_title(
+    bindings: &dyn PdfiumLibraryBindings,
+    bookmark_handle: FPDF_BOOKMARK,
+) -> Option<String> {
+    // Retrieving the bookmark title from Pdfium is a two-step operation. First, we call
+    // FPDFBookmark_GetTitle() with a null buffer; this will retrieve the length of
+    // the bookmark title in bytes. If the length is zero, then there is no title.
+
+    // If the length is non-zero, then we reserve a byte buffer of the given
+    // length and call FPDFBookmark_GetTitle() again with a pointer to the buffer;
+    // this will write the bookmark title to the buffer in UTF16-LE format.
+
+    let buffer_length = bindings.FPDFBookmark_GetTitle(bookmark_handle, null_mut(), 0);
+
+    if buffer_length == 0 {
+        // No title is defined.
+
+        return None;
+    }
+
+    let mut buffer = create_byte_buffer(buffer_length as usize);
+
+    let result = bindings.FPDFBookmark_GetTitle(
+        bookmark_handle,
+        buffer.as_mut_ptr() as *mut c_void,
+        buffer_length,
+    );
+
+    assert_eq!(result, buffer_length);
+
+    get_string_from_pdfium_utf16le_bytes(buffer)
+}
+
+/// Returns the outline items in the given outline dictionary, in depth-first prefix order.
+fn get_outline_items(
+    update: &PdfIncrementalUpdate,
+    outlines: PdfObjectId,
+) -> Result<Vec<PdfObjectId>, PdfiumError> {
+    fn visit(
+        update: &PdfIncrementalUpdate,
+        first: Option<PdfObjectId>,
+        items: &mut Vec<PdfObjectId>,
+        visited: &mut HashSet<PdfObjectId>,
+    ) -> Result<(), PdfiumError> {
+        let mut next = first;
+
+        while let Some(item) = next {
+            if !visited.insert(item) {
+                break;
+            }
+
+            items.push(item);
+
+            let dictionary = update.get_dictionary(item)?;
+
+            visit(update, dictionary.get_reference("First"), items, visited)?;
+
+            next = dictionary.get_reference("Next");
+        }
+
+        Ok(())
+    }
+
+    let mut items = Vec::new();
+
+    visit(
+        update,
+        update.get_dictionary(outlines)?.get_reference("First"),
+        &mut items,
+        &mut HashSet::new(),
+    )?;
+
+    Ok(items)
+}
+
+/// Returns the page objects in the given page tree, in page order.
+fn get_page_objects(
+    update: &PdfIncrementalUpdate,
+    pages: PdfObjectId,
+) -> Result<Vec<PdfObjectId>, PdfiumError> {
+    let mut result = Vec::new();
+
+    let mut pending = vec![pages];
+
+    let mut visited = HashSet::new();
+
+    while let Some(node) = pending.pop() {
+        if !visited.insert(node) {
+            continue;
+        }
+
+        let dictionary = update.get_dictionary(node)?;
+
+        match dictionary.get("Kids") {
+            Some(kids) => {
+                let kids = PdfObjectId::from_reference_array(kids)
+                    .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+                // Push the kids in reverse order so they are visited in page order.
+
+                pending.extend(kids.into_iter().rev());
+            }
+            None => result.push(node),
+        }
+    }
+
+    Ok(result)
+}
+
+/// Sets the given entry in the given dictionary to a reference to the given object,
+/// or removes the entry if no object is given.
+fn set_reference(dictionary: &mut PdfRawDictionary, key: &str, object: Option<PdfObjectId>) {
+    match object {
+        Some(object) => dictionary.set(key, object.to_reference()),
+        None => dictionary.remove(key),
+    }
+}
+
+/// Returns an explicit destination array targeting the given page object with the given
+/// view settings.
+fn destination_to_bytes(page: PdfObjectId, view: &PdfDestinationViewSettings) -> Vec<u8> {
+    let optional = |value: Option<f32>| value.map(format_number).unwrap_or_else(|| "null".into());
+
+    let view = match view {
+        PdfDestinationViewSettings::Unknown => "/XYZ null null null".to_string(),
+        PdfDestinationViewSettings::SpecificCoordinatesAndZoom(x, y, zoom) => format!(
+            "/XYZ {} {} {}",
+            optional(x.map(|x| x.value)),
+            optional(y.map(|y| y.value)),
+            optional(*zoom)
+        ),
+        PdfDestinationViewSettings::FitPageToWindow => "/Fit".to_string(),
+        PdfDestinationViewSettings::FitPageHorizontallyToWindow(y) => {
+            format!("/FitH {}", optional(y.map(|y| y.value)))
+        }
+        PdfDestinationViewSettings::FitPageVerticallyToWindow(x) => {
+            format!("/FitV {}", optional(x.map(|x| x.value)))
+        }
+        PdfDestinationViewSettings::FitPageToRectangle(rect) => format!(
+            "/FitR {} {} {} {}",
+            format_number(rect.left().value),
+            format_number(rect.bottom().value),
+            format_number(rect.right().value),
+            format_number(rect.top().value)
+        ),
+        PdfDestinationViewSettings::FitBoundsToWindow => "/FitB".to_string(),
+        PdfDestinationViewSettings::FitBoundsHorizontallyToWindow(y) => {
+            format!("/FitBH {}", optional(y.map(|y| y.value)))
+        }
+        PdfDestinationViewSettings::FitBoundsVerticallyToWindow(x) => {
+            format!("/FitBV {}", optional(x.map(|x| x.value)))
+        }
+    };
+
+    format!(
+        "[{} {}]",
+        String::from_utf8_lossy(&page.to_reference()),
+        view
+    )
+    .into_bytes()
+}
+
+/// Formats the given number as a PDF real number.
+#[inline]
+pub(crate) fn format_number(value: f32) -> String {
+    if value.is_finite() {
+        format!("{value}")
+    } else {
+        "0".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pdf::points::PdfPoints;
+
+    #[test]
+    fn test_destination_to_bytes() {
+        let page = PdfObjectId {
+            number: 7,
+            generation: 0,
+        };
+
+        assert_eq!(
+            destination_to_bytes(
+                page,
+                &PdfDestinationViewSettings::SpecificCoordinatesAndZoom(
+                    Some(PdfPoints::new(72.0)),
+                    None,
+                    Some(1.5)
+                )
+            ),
+            b"[7 0 R /XYZ 72 null 1.5]".to_vec()
+        );
+        assert_eq!(
+            destination_to_bytes(page, &PdfDestinationViewSettings::FitPageToWindow),
+            b"[7 0 R /Fit]".to_vec()
+        );
+    }
+}
@@ -1,10 +1,12 @@
 //! Defines the [PdfBookmarks] struct, exposing functionality related to the
 //! bookmarks contained within a single `PdfDocument`.
 
-use crate::bindgen::{FPDF_BOOKMARK, FPDF_DOCUMENT};
+use crate::bindgen::FPDF_DOCUMENT;
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::{PdfiumError, PdfiumInternalError};
-use crate::pdf::document::bookmark::PdfBookmark;
+use crate::pdf::document::bookmark::{PdfBookmark, PdfBookmarkMut};
+use crate::pdf::document::bookmark_tree::PdfBookmarkTree;
+use crate::pdf::document::incremental_update::PdfIncrementalUpdate;
 use std::collections::HashSet;
 use std::ptr::null_mut;
 
@@ -18,8 +20,21 @@ use std::ptr::null_mut;
 /// To search the tree for a bookmark with a specific title, use the [PdfBookmarks::find_first_by_title()]
 /// and [PdfBookmarks::find_all_by_title()] functions. To traverse the tree breadth-first, visiting
 /// every bookmark in the tree, create an iterator using the [PdfBookmarks::iter()] function.
+///
+/// Bookmarks are edited through the mutable collection returned by `PdfDocument::bookmarks_mut()`.
+/// To add a new top-level bookmark, use the [PdfBookmarks::create_root()] function; to edit
+/// an existing bookmark, retrieve a [PdfBookmarkMut] for it using functions such as
+/// [PdfBookmarks::root_mut()] and [PdfBookmarks::find_first_by_title_mut()], then use functions
+/// such as [PdfBookmarkMut::add_child()] and [PdfBookmarkMut::set_title()].
+/// Pdfium cannot edit bookmarks, so the edited tree is written to the `/Outlines` tree of the
+/// containing `PdfDocument` as an incremental update when the document is saved. Since the
+/// update is appended to the bytes written by Pdfium, a document with edited bookmarks cannot
+/// be saved in encrypted form; save it using `PdfSaveMode::RemoveSecurity` instead.
 pub struct PdfBookmarks<'a> {
     document_handle: FPDF_DOCUMENT,
+    // An editable copy of the document's bookmark tree, taken when the bookmarks are
+    // first edited. Until then, all queries are answered by Pdfium.
+    tree: Option<PdfBookmarkTree>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -31,6 +46,7 @@ impl<'a> PdfBookmarks<'a> {
     ) -> Self {
         Self {
             document_handle,
+            tree: None,
             bindings,
         }
     }
@@ -50,9 +66,15 @@ impl<'a> PdfBookmarks<'a> {
 
     /// Returns the root [PdfBookmark] in the containing `PdfDocument`, if any.
     pub fn root(&self) -> Option<PdfBookmark<'_>> {
+        if let Some(tree) = self.tree.as_ref() {
+            return tree.top_level().first().map(|root| {
+                PdfBookmark::from_node(tree, *root, self.document_handle, self.bindings)
+            });
+        }
+
         let bookmark_handle = self
             .bindings
-            .FPDFBookmark_GetFirstChild(self.document_handle, null_mut());
+            .FPDFBookmark_GetFirstChild(self.document_handle(), null_mut());
 
         if bookmark_handle.is_null() {
             None
@@ -66,6 +88,13 @@ impl<'a> PdfBookmarks<'a> {
         }
     }
 
+    /// Returns a [PdfBookmarkMut] for editing the root [PdfBookmark] in the containing
+    /// `PdfDocument`, if any.
+    #[inline]
+    pub fn root_mut(&mut self) -> Option<PdfBookmarkMut<'_>> {
+        self.find_first_mut(|_| true)
+    }
+
     /// Returns the first [PdfBookmark] in the containing `PdfDocument` that has a title matching
     /// the given string.
     ///
@@ -73,14 +102,24 @@ impl<'a> PdfBookmarks<'a> {
     /// bookmarks could match a given title. This function only ever returns the first. To return
     /// all matches, use [PdfBookmarks::find_all_by_title()].
     pub fn find_first_by_title(&self, title: &str) -> Result<PdfBookmark<'_>, PdfiumError> {
+        let error = PdfiumError::PdfiumLibraryInternalError(PdfiumInternalError::Unknown);
+
+        if self.is_modified() {
+            // Pdfium is not aware of any changes to the bookmark tree, so we search the
+            // tree ourselves.
+
+            return self
+                .iter()
+                .find(|bookmark| bookmark.title().as_deref() == Some(title))
+                .ok_or(error);
+        }
+
         let handle = self
             .bindings
-            .FPDFBookmark_Find_str(self.document_handle, title);
+            .FPDFBookmark_Find_str(self.document_handle(), title);
 
         if handle.is_null() {
-            Err(PdfiumError::PdfiumLibraryInternalError(
-                PdfiumInternalError::Unknown,
-            ))
+            Err(error)
         } else {
             Ok(PdfBookmark::from_pdfium(
                 handle,
@@ -91,6 +130,44 @@ impl<'a> PdfBookmarks<'a> {
         }
     }
 
+    /// Returns a [PdfBookmarkMut] for editing the first [PdfBookmark] in the containing
+    /// `PdfDocument` that has a title matching the given string.
+    pub fn find_first_by_title_mut(
+        &mut self,
+        title: &str,
+    ) -> Result<PdfBookmarkMut<'_>, PdfiumError> {
+        self.find_first_mut(|bookmark| bookmark.title().as_deref() == Some(title))
+            .ok_or(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ))
+    }
+
+    /// Returns a [PdfBookmarkMut] for editing the first [PdfBookmark], in the depth-first
+    /// prefix order used by [PdfBookmarks::iter()], for which the given predicate
+    /// returns `true`.
+    pub fn find_first_mut(
+        &mut self,
+        predicate: impl FnMut(&PdfBookmark) -> bool,
+    ) -> Option<PdfBookmarkMut<'_>> {
+        let (handle, node) = self
+            .iter()
+            .find(predicate)
+            .map(|bookmark| (bookmark.bookmark_handle(), bookmark.node()))?;
+
+        let (document_handle, bindings) = (self.document_handle, self.bindings);
+
+        let tree = self.tree_mut();
+
+        let id = node.or_else(|| tree.node_for_handle(handle))?;
+
+        Some(PdfBookmarkMut::from_node(
+            tree,
+            id,
+            document_handle,
+            bindings,
+        ))
+    }
+
     /// Returns all [PdfBookmark] objects in the containing `PdfDocument` that have a title
     /// matching the given string.
     ///
@@ -107,18 +184,51 @@ impl<'a> PdfBookmarks<'a> {
             .collect()
     }
 
+    /// Creates a new top-level [PdfBookmark] with the given title after all existing top-level
+    /// bookmarks in the containing `PdfDocument`, returning the new bookmark. If the document
+    /// has no bookmarks, the new bookmark becomes the root bookmark.
+    pub fn create_root(&mut self, title: &str) -> PdfBookmarkMut<'_> {
+        let (document_handle, bindings) = (self.document_handle, self.bindings);
+
+        let tree = self.tree_mut();
+
+        let position = tree.top_level().len();
+
+        let id = tree.insert(None, position, title);
+
+        PdfBookmarkMut::from_node(tree, id, document_handle, bindings)
+    }
+
     /// Returns a depth-first prefix-order iterator over all the [PdfBookmark]
     /// objects in the containing `PdfDocument`, starting from the top-level
     /// root bookmark.
     #[inline]
     pub fn iter(&self) -> PdfBookmarksIterator<'_> {
-        PdfBookmarksIterator::new(
-            self.root(),
-            true,
-            None,
-            self.document_handle(),
-            self.bindings(),
-        )
+        PdfBookmarksIterator::new(self.root(), true, None)
+    }
+
+    /// Returns `true` if the bookmarks in the containing `PdfDocument` have been edited
+    /// since the document was loaded.
+    #[inline]
+    pub(crate) fn is_modified(&self) -> bool {
+        self.tree.is_some()
+    }
+
+    /// Returns the editable copy of the bookmark tree of the containing `PdfDocument`,
+    /// taking the copy first if necessary.
+    fn tree_mut(&mut self) -> &mut PdfBookmarkTree {
+        let (document_handle, bindings) = (self.document_handle, self.bindings);
+
+        self.tree
+            .get_or_insert_with(|| PdfBookmarkTree::from_pdfium(document_handle, bindings))
+    }
+
+    /// Writes the edited bookmark tree, if any, to the `/Outlines` tree of the given update.
+    pub(crate) fn apply_to(&self, update: &mut PdfIncrementalUpdate) -> Result<(), PdfiumError> {
+        match self.tree.as_ref() {
+            Some(tree) => tree.apply_to(update),
+            None => Ok(()),
+        }
     }
 }
 
@@ -126,17 +236,14 @@ impl<'a> PdfBookmarks<'a> {
 pub struct PdfBookmarksIterator<'a> {
     // If true, recurse into descendants.
     include_descendants: bool,
-    // Stack of pairs of (Bookmark Node, Node's Parent). The parent may be NULL
-    // if its a root node or the parent is unknown.
-    pending_stack: Vec<(FPDF_BOOKMARK, FPDF_BOOKMARK)>,
+    // Stack of bookmark nodes still to be visited. Each node knows its parent, if available.
+    pending_stack: Vec<PdfBookmark<'a>>,
     // Set of nodes already visitied. This ensures we terminate if the PDF's
     // bookmark graph is cyclic.
-    visited: HashSet<FPDF_BOOKMARK>,
+    visited: HashSet<PdfBookmark<'a>>,
     // This bookmark will not be returned by the iterator (but its siblings and
-    // descendants will be explored). May be NULL.
-    skip_sibling: FPDF_BOOKMARK,
-    document_handle: FPDF_DOCUMENT,
-    bindings: &'a dyn PdfiumLibraryBindings,
+    // descendants will be explored).
+    skip_sibling: Option<PdfBookmark<'a>>,
 }
 
 impl<'a> PdfBookmarksIterator<'a> {
@@ -144,32 +251,17 @@ impl<'a> PdfBookmarksIterator<'a> {
         start_node: Option<PdfBookmark<'a>>,
         include_descendants: bool,
         skip_sibling: Option<PdfBookmark<'a>>,
-        document_handle: FPDF_DOCUMENT,
-        bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         let mut result = PdfBookmarksIterator {
-            document_handle,
             include_descendants,
             pending_stack: Vec::with_capacity(20),
             visited: HashSet::new(),
-            skip_sibling: null_mut(),
-            bindings,
+            skip_sibling,
         };
 
-        // If we have a skip-sibling, record its handle.
-        if let Some(skip_sibling) = skip_sibling {
-            result.skip_sibling = skip_sibling.bookmark_handle();
-        }
-
         // Push the start node onto the stack to initiate graph traversal.
         if let Some(start_node) = start_node {
-            result.pending_stack.push((
-                start_node.bookmark_handle(),
-                start_node
-                    .parent()
-                    .map(|parent| parent.bookmark_handle())
-                    .unwrap_or(null_mut()),
-            ));
+            result.pending_stack.push(start_node);
         }
 
         result
@@ -197,49 +289,33 @@ impl<'a> Iterator for PdfBookmarksIterator<'a> {
         // - Iterators, of course, can't take advantage of recursion. So the
         //   call stack which is implicit in the recursive version becomes an
         //   explicit stack retained in PdfIterator::pending_stack.
-        // - For efficiency, the iterator internally operates with FPDF_BOOKMARK
-        //   handles, and only constructs PdfBookmark objects right before
-        //   they're returned.
         // - PdfIterator::visited keeps a HashSet of visited nodes, to ensure
         //   termination even if the PDF's bookmark graph is cyclic.
-        // - PdfIterator::skip_sibling keeps a FPDF_BOOKMARK that will not be
+        // - PdfIterator::skip_sibling keeps a PdfBookmark that will not be
         //   returned by the iterator (but, importantly, it's siblings will
         //   still be explored).
 
-        while let Some((node, parent)) = self.pending_stack.pop() {
-            if node.is_null() || self.visited.contains(&node) {
+        while let Some(node) = self.pending_stack.pop() {
+            if !self.visited.insert(node.clone()) {
                 continue;
             }
-            self.visited.insert(node);
 
             // Add our next sibling to the stack first, so we'll come back to it
-            // after having addressed our descendants. It's okay if it's NULL,
-            // we'll handle that when it comes off the stack.
-            self.pending_stack.push((
-                self.bindings
-                    .FPDFBookmark_GetNextSibling(self.document_handle, node),
-                parent,
-            ));
+            // after having addressed our descendants.
+            if let Some(next_sibling) = node.next_sibling() {
+                self.pending_stack.push(next_sibling);
+            }
 
             // Add our first descendant to the stack if we should include them.
-            // Again, its okay if it's NULL.
             if self.include_descendants {
-                self.pending_stack.push((
-                    self.bindings
-                        .FPDFBookmark_GetFirstChild(self.document_handle, node),
-                    node,
-                ));
+                if let Some(first_child) = node.first_child() {
+                    self.pending_stack.push(first_child);
+                }
             }
 
             // If the present node isn't the one we're meant to skip, return it.
-            if node != self.skip_sibling {
-                let parent = if parent.is_null() { None } else { Some(parent) };
-                return Some(PdfBookmark::from_pdfium(
-                    node,
-                    parent,
-                    self.document_handle,
-                    self.bindings,
-                ));
+            if self.skip_sibling.as_ref() != Some(&node) {
+                return Some(node);
             }
         }
 
@@ -7,16 +7,25 @@ use crate::bindgen::{
 };
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
+use crate::pdf::document::form_data::{
+    read_fdf, read_xfdf, write_fdf, write_xfdf, PdfFormFieldData,
+};
+use crate::pdf::document::page::field::options::{read_option_pairs, PdfFormFieldOptionPairs};
+use crate::pdf::document::page::field::private::internal::PdfFormFieldPrivate;
+use crate::pdf::document::page::field::radio::PdfFormRadioButtonField;
+use crate::pdf::document::page::field::PdfFormField;
 use crate::pdf::document::page::field::PdfFormFieldCommon;
 use crate::pdf::document::page::field::PdfFormFieldType;
+use crate::pdf::document::page::PdfPage;
 use crate::pdf::document::pages::PdfPages;
 use std::collections::HashMap;
 use std::ops::DerefMut;
+use std::os::raw::c_int;
 use std::pin::Pin;
 use std::ptr::null_mut;
 
 #[cfg(doc)]
-use crate::pdf::document::PdfDocument;
+use {crate::pdf::document::save_options::PdfSaveMode, crate::pdf::document::PdfDocument};
 
 /// The internal definition type of a [PdfForm] embedded in a [PdfDocument].
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -74,6 +83,9 @@ impl PdfFormType {
 ///
 /// Alternatively, use the [PdfForm::field_values()] function to eagerly retrieve the values of all
 /// fields in the document as a map of (field name, field value) pairs.
+///
+/// Field values can be exchanged with other tools in bulk using the [PdfForm::export_fdf()],
+/// [PdfForm::import_fdf()], [PdfForm::export_xfdf()], and [PdfForm::import_xfdf()] functions.
 pub struct PdfForm<'a> {
     form_handle: FPDF_FORMHANDLE,
     document_handle: FPDF_DOCUMENT,
@@ -286,6 +298,388 @@ impl<'a> PdfForm<'a> {
 
         result
     }
+
+    /// Exports the value of every form field on every page of the given [PdfPages] collection
+    /// as a Forms Data Format (FDF) file.
+    ///
+    /// Fields are identified by their fully qualified names. Text fields export their text,
+    /// checkbox and radio button groups export the appearance state of their selected control
+    /// (or `Off`), and combo boxes and list boxes export the export values of their selected
+    /// options, as given by the `/Opt` arrays of the fields. The text of an editable combo box
+    /// that matches none of its options is exported as is. Push buttons and signature fields
+    /// have no value and are not exported.
+    #[inline]
+    pub fn export_fdf(&self, pages: &'a PdfPages<'a>) -> Vec<u8> {
+        write_fdf(&self.field_data(pages))
+    }
+
+    /// Exports the value of every form field on every page of the given [PdfPages] collection
+    /// as an XML Forms Data Format (XFDF) document.
+    ///
+    /// Field values are mapped in the same way as [PdfForm::export_fdf()].
+    #[inline]
+    pub fn export_xfdf(&self, pages: &'a PdfPages<'a>) -> String {
+        write_xfdf(&self.field_data(pages))
+    }
+
+    /// Sets the values of form fields on every page of the given [PdfPages] collection from
+    /// the given Forms Data Format (FDF) file.
+    ///
+    /// Fields are matched by their fully qualified names; fields not mentioned in the file
+    /// are left unchanged. Values are mapped in the same way as [PdfForm::export_fdf()], so
+    /// the values of choice fields are matched against the export values of their options.
+    /// Values are set through Pdfium's form fill API where possible, so Pdfium regenerates
+    /// the appearances of the updated fields. Widgets that have no appearance stream are
+    /// left without one; use [PdfForm::import_fdf_and_generate_appearances()] to generate them.
+    ///
+    /// Importing `Off` for a radio button group deselects each of its radio buttons, but
+    /// leaves the value of the group's parent field unchanged, since Pdfium offers no way
+    /// of clearing it.
+    ///
+    /// Returns [PdfiumError::InvalidFormData] if the bytes are not a well-formed FDF file.
+    #[inline]
+    pub fn import_fdf(&self, pages: &'a PdfPages<'a>, bytes: &[u8]) -> Result<(), PdfiumError> {
+        self.set_field_data(pages, read_fdf(bytes)?, false)
+    }
+
+    /// Sets the values of form fields on every page of the given [PdfPages] collection from
+    /// the given Forms Data Format (FDF) file, as [PdfForm::import_fdf()] does, and gives
+    /// every updated widget that has no appearance stream an appearance using the field's
+    /// `set_appearance()` builder.
+    ///
+    /// The generated appearances of checkboxes, radio buttons, combo boxes, and list boxes
+    /// are written as an incremental update when the document is saved. Saving an encrypted
+    /// document then returns [PdfiumError::EncryptedDocumentUpdateNotSupported] unless the
+    /// document is saved using [PdfSaveMode::RemoveSecurity].
+    ///
+    /// Returns [PdfiumError::InvalidFormData] if the bytes are not a well-formed FDF file.
+    #[inline]
+    pub fn import_fdf_and_generate_appearances(
+        &self,
+        pages: &'a PdfPages<'a>,
+        bytes: &[u8],
+    ) -> Result<(), PdfiumError> {
+        self.set_field_data(pages, read_fdf(bytes)?, true)
+    }
+
+    /// Sets the values of form fields on every page of the given [PdfPages] collection from
+    /// the given XML Forms Data Format (XFDF) document.
+    ///
+    /// Fields are matched and updated in the same way as [PdfForm::import_fdf()].
+    ///
+    /// Returns [PdfiumError::InvalidFormData] if the text is not a well-formed XFDF document.
+    #[inline]
+    pub fn import_xfdf(&self, pages: &'a PdfPages<'a>, xml: &str) -> Result<(), PdfiumError> {
+        self.set_field_data(pages, read_xfdf(xml)?, false)
+    }
+
+    /// Sets the values of form fields on every page of the given [PdfPages] collection from
+    /// the given XML Forms Data Format (XFDF) document, generating appearances for widgets
+    /// that have none in the same way as [PdfForm::import_fdf_and_generate_appearances()].
+    ///
+    /// Returns [PdfiumError::InvalidFormData] if the text is not a well-formed XFDF document.
+    #[inline]
+    pub fn import_xfdf_and_generate_appearances(
+        &self,
+        pages: &'a PdfPages<'a>,
+        xml: &str,
+    ) -> Result<(), PdfiumError> {
+        self.set_field_data(pages, read_xfdf(xml)?, true)
+    }
+
+    /// Captures the value of every form field on every page of the given [PdfPages] collection
+    /// for export, combining the controls of checkbox and radio button groups into a single
+    /// value for the group.
+    fn field_data(&self, pages: &'a PdfPages<'a>) -> Vec<PdfFormFieldData> {
+        let mut result: Vec<PdfFormFieldData> = Vec::new();
+
+        let mut indices = HashMap::new();
+
+        // The export values of choice field options are only read if the form has choice fields.
+
+        let mut option_pairs: Option<PdfFormFieldOptionPairs> = None;
+
+        for page in pages.iter() {
+            for annotation in page.annotations().iter() {
+                let field = match annotation.as_form_field() {
+                    Some(field) => field,
+                    None => continue,
+                };
+
+                let name = match field.name() {
+                    Some(name) if !name.is_empty() => name,
+                    _ => continue,
+                };
+
+                let (values, is_state) = match field {
+                    PdfFormField::Text(field) => (vec![field.value().unwrap_or_default()], false),
+                    PdfFormField::Checkbox(field) => {
+                        let state = if field.is_checked().unwrap_or(false) {
+                            on_state(field.export_value_impl())
+                        } else {
+                            OFF_STATE.to_string()
+                        };
+
+                        (vec![state], true)
+                    }
+                    PdfFormField::RadioButton(field) => {
+                        let state = if field.is_checked().unwrap_or(false) {
+                            on_state(field.export_value_impl().or_else(|| field.group_value()))
+                        } else {
+                            OFF_STATE.to_string()
+                        };
+
+                        (vec![state], true)
+                    }
+                    PdfFormField::ComboBox(field) => {
+                        let label = field.value().unwrap_or_default();
+
+                        let pairs = option_pairs.get_or_insert_with(|| self.option_pairs());
+
+                        let value = pairs
+                            .get(&name)
+                            .and_then(|pairs| {
+                                pairs
+                                    .iter()
+                                    .find(|(_, option_label)| *option_label == label)
+                                    .map(|(export_value, _)| export_value.clone())
+                            })
+                            .unwrap_or(label);
+
+                        (vec![value], false)
+                    }
+                    PdfFormField::ListBox(field) => {
+                        let pairs = option_pairs.get_or_insert_with(|| self.option_pairs());
+
+                        let pairs = pairs.get(&name);
+
+                        let selected = field
+                            .options()
+                            .iter()
+                            .filter(|option| option.is_set())
+                            .filter_map(|option| {
+                                match pairs.and_then(|pairs| pairs.get(option.index())) {
+                                    Some((export_value, _)) => Some(export_value.clone()),
+                                    None => option.label().cloned(),
+                                }
+                            })
+                            .collect();
+
+                        (selected, false)
+                    }
+                    PdfFormField::PushButton(_)
+                    | PdfFormField::Signature(_)
+                    | PdfFormField::Unknown(_) => continue,
+                };
+
+                match indices.get(&name) {
+                    Some(index) => {
+                        // The controls of a checkbox or radio button group share the same name.
+                        // Prefer the state of a selected control to that of an unselected one.
+                        // Other fields with several widgets share a single value, so the first
+                        // widget's value is kept.
+
+                        if is_state && values.iter().any(|value| value != OFF_STATE) {
+                            let field: &mut PdfFormFieldData = &mut result[*index];
+
+                            field.values = values;
+                        }
+                    }
+                    None => {
+                        indices.insert(name.clone(), result.len());
+
+                        result.push(PdfFormFieldData {
+                            name,
+                            values,
+                            is_state,
+                        });
+                    }
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Sets the values of all form fields on every page of the given [PdfPages] collection
+    /// that are named in the given imported field data. If `generate_appearances` is `true`,
+    /// updated widgets without an appearance stream are given one.
+    fn set_field_data(
+        &self,
+        pages: &'a PdfPages<'a>,
+        fields: Vec<PdfFormFieldData>,
+        generate_appearances: bool,
+    ) -> Result<(), PdfiumError> {
+        let values = fields
+            .into_iter()
+            .map(|field| (field.name, field.values))
+            .collect::<HashMap<_, _>>();
+
+        let mut option_pairs: Option<PdfFormFieldOptionPairs> = None;
+
+        for page in pages.iter() {
+            for mut annotation in page.annotations().iter() {
+                let field = match annotation.as_form_field_mut() {
+                    Some(field) => field,
+                    None => continue,
+                };
+
+                let name = match field.name() {
+                    Some(name) => name,
+                    None => continue,
+                };
+
+                let values = match values.get(&name) {
+                    Some(values) => values,
+                    None => continue,
+                };
+
+                let generate_appearance = generate_appearances && !has_appearance_stream(field);
+
+                let value = values
+                    .first()
+                    .map(|value| value.as_str())
+                    .unwrap_or_default();
+
+                match field {
+                    PdfFormField::Text(field) => {
+                        field.set_value(value)?;
+
+                        if generate_appearance {
+                            field.set_appearance().apply()?;
+                        }
+                    }
+                    PdfFormField::Checkbox(field) => {
+                        let state = on_state(field.export_value_impl());
+
+                        field.set_checked(value.trim_start_matches('/') == state)?;
+
+                        if generate_appearance {
+                            field.set_appearance().apply()?;
+                        }
+                    }
+                    PdfFormField::RadioButton(field) => {
+                        let state = field.export_value_impl();
+
+                        if state.as_deref().map(|state| state.trim_start_matches('/'))
+                            == Some(value.trim_start_matches('/'))
+                        {
+                            self.select_radio_button(&page, field)?;
+                        } else {
+                            field.set_unchecked()?;
+                        }
+
+                        if generate_appearance {
+                            field.set_appearance().apply()?;
+                        }
+                    }
+                    PdfFormField::ComboBox(field) => {
+                        let pairs = option_pairs.get_or_insert_with(|| self.option_pairs());
+
+                        field.set_value(option_label(pairs.get(&name), value))?;
+
+                        if generate_appearance {
+                            field.set_appearance().apply()?;
+                        }
+                    }
+                    PdfFormField::ListBox(field) => {
+                        let pairs = option_pairs.get_or_insert_with(|| self.option_pairs());
+
+                        let values = values
+                            .iter()
+                            .map(|value| option_label(pairs.get(&name), value))
+                            .collect::<Vec<_>>();
+
+                        field.set_values(&values)?;
+
+                        if generate_appearance {
+                            field.set_appearance().apply()?;
+                        }
+                    }
+                    PdfFormField::PushButton(_)
+                    | PdfFormField::Signature(_)
+                    | PdfFormField::Unknown(_) => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Selects the given radio button on the given page through Pdfium's form fill API,
+    /// as if the user had pressed the space bar on it. Pdfium sets the value of the control
+    /// group's parent field, updates the appearance state of every radio button in the group,
+    /// and regenerates their appearances.
+    fn select_radio_button(
+        &self,
+        page: &PdfPage,
+        field: &PdfFormRadioButtonField,
+    ) -> Result<(), PdfiumError> {
+        if !self.bindings.is_true(
+            self.bindings
+                .FORM_SetFocusedAnnot(self.form_handle, field.annotation_handle()),
+        ) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        let is_selected = self.bindings.is_true(self.bindings.FORM_OnChar(
+            self.form_handle,
+            page.page_handle(),
+            ' ' as c_int,
+            0,
+        ));
+
+        self.bindings.FORM_ForceToKillFocus(self.form_handle);
+
+        if is_selected {
+            Ok(())
+        } else {
+            Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure)
+        }
+    }
+
+    /// Returns the export value and label of every option of every choice field in the
+    /// document containing this [PdfForm]. If the options cannot be read, choice fields
+    /// fall back to using their option labels as their values.
+    #[inline]
+    fn option_pairs(&self) -> PdfFormFieldOptionPairs {
+        read_option_pairs(self.document_handle, self.bindings).unwrap_or_default()
+    }
+}
+
+/// Returns the label of the option with the given export value from the given
+/// `(export value, label)` pairs of a choice field, or the value itself if no option has
+/// that export value.
+fn option_label<'b>(pairs: Option<&'b Vec<(String, String)>>, value: &'b str) -> &'b str {
+    pairs
+        .and_then(|pairs| {
+            pairs
+                .iter()
+                .find(|(export_value, _)| export_value == value)
+                .map(|(_, label)| label.as_str())
+        })
+        .unwrap_or(value)
+}
+
+/// The appearance state of an unselected checkbox or radio button.
+const OFF_STATE: &str = "Off";
+
+/// Returns the appearance state of a selected checkbox or radio button with the given
+/// export value, following the PDF convention of `Yes` when no export value is defined.
+fn on_state(export_value: Option<String>) -> String {
+    export_value
+        .map(|value| value.trim_start_matches('/').to_string())
+        .unwrap_or_else(|| "Yes".to_string())
+}
+
+/// Returns `true` if the widget annotation wrapping the given form field has an `/AP`
+/// appearance dictionary.
+fn has_appearance_stream<'a>(field: &impl PdfFormFieldPrivate<'a>) -> bool {
+    field.bindings().is_true(
+        field
+            .bindings()
+            .FPDFAnnot_HasKey(field.annotation_handle(), "AP"),
+    )
 }
 
 impl<'a> Drop for PdfForm<'a> {
@@ -296,3 +690,107 @@ impl<'a> Drop for PdfForm<'a> {
             .FPDFDOC_ExitFormFillEnvironment(self.form_handle);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_export_and_import_field_values() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        // Change the values of the first editable text field, unchecked checkbox, and
+        // combo box in the form, then export the changed values in both formats.
+
+        let document = pdfium.load_pdf_from_file("test/form-test.pdf", None)?;
+
+        let mut text_name = None;
+        let mut checkbox_name = None;
+        let mut combo_box = None;
+
+        for page in document.pages().iter() {
+            for mut annotation in page.annotations().iter() {
+                let field = match annotation.as_form_field_mut() {
+                    Some(field) if !field.is_read_only() => field,
+                    _ => continue,
+                };
+
+                let name = field.name().unwrap_or_default();
+
+                match field {
+                    PdfFormField::Text(field) if text_name.is_none() => {
+                        field.set_value("Round trip")?;
+                        text_name = Some(name);
+                    }
+                    PdfFormField::Checkbox(field)
+                        if checkbox_name.is_none() && !field.is_checked()? =>
+                    {
+                        field.set_checked(true)?;
+                        checkbox_name = Some(name);
+                    }
+                    PdfFormField::ComboBox(field) if combo_box.is_none() => {
+                        let value = field.value();
+
+                        let label = field
+                            .options()
+                            .iter()
+                            .filter_map(|option| option.label().cloned())
+                            .find(|label| Some(label) != value.as_ref())
+                            .unwrap();
+
+                        field.set_value(&label)?;
+                        combo_box = Some((name, label));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let text_name = text_name.unwrap();
+        let checkbox_name = checkbox_name.unwrap();
+        let (combo_box_name, combo_box_label) = combo_box.unwrap();
+
+        let form = document.form().unwrap();
+        let fdf = form.export_fdf(document.pages());
+        let xfdf = form.export_xfdf(document.pages());
+
+        // Import the exported values into fresh copies of the form, and check that every
+        // changed value survives saving and reloading the document.
+
+        for is_xfdf in [false, true] {
+            let document = pdfium.load_pdf_from_file("test/form-test.pdf", None)?;
+
+            let form = document.form().unwrap();
+
+            if is_xfdf {
+                form.import_xfdf(document.pages(), &xfdf)?;
+            } else {
+                form.import_fdf(document.pages(), &fdf)?;
+            }
+
+            // form-test.pdf is encrypted, so the imported values can only be saved to
+            // an unencrypted copy.
+
+            let bytes = document.save_to_bytes_with_options(
+                &PdfSaveOptions::new().set_mode(PdfSaveMode::RemoveSecurity),
+            )?;
+
+            let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+            let values = document.form().unwrap().field_values(document.pages());
+
+            assert_eq!(
+                values.get(&text_name),
+                Some(&Some("Round trip".to_string()))
+            );
+            assert_eq!(values.get(&checkbox_name), Some(&Some("true".to_string())));
+            assert_eq!(
+                values.get(&combo_box_name),
+                Some(&Some(combo_box_label.clone()))
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,664 @@
+//! Reads and writes the values of form fields in the Forms Data Format (FDF) and the
+//! XML Forms Data Format (XFDF), for use by `PdfForm::export_fdf()`, `PdfForm::import_fdf()`,
+//! `PdfForm::export_xfdf()`, and `PdfForm::import_xfdf()`.
+//!
+//! Only field values are supported. Other content that FDF and XFDF files can carry,
+//! such as annotations, JavaScript actions, and embedded files, is never written and is
+//! ignored when reading.
+
+use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::{
+    decode_name, decode_text_string, encode_name, encode_text_string, find_indirect_object,
+    parse_array, PdfObjectId, PdfRawDictionary,
+};
+use crate::pdf::document::xmp::{escape, unescape};
+
+/// The maximum depth of the field hierarchy read from an FDF file. Guards against
+/// reference cycles between fields.
+const MAX_FIELD_DEPTH: usize = 64;
+
+/// The value of a single terminal form field, identified by its fully qualified name.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PdfFormFieldData {
+    /// The fully qualified name of the field, with partial names separated by periods.
+    pub(crate) name: String,
+
+    /// The values of the field. Text, checkbox, radio button, and combo box fields have
+    /// a single value; list boxes have one value for each selected option. A field with
+    /// no values is cleared when imported.
+    pub(crate) values: Vec<String>,
+
+    /// `true` if the values are the names of appearance states, as for checkboxes and
+    /// radio buttons, rather than text strings. Only affects how values are written to FDF.
+    pub(crate) is_state: bool,
+}
+
+/// A single node in the hierarchy of fields built from fully qualified field names.
+struct PdfFormFieldNode<'a> {
+    partial_name: &'a str,
+    field: Option<&'a PdfFormFieldData>,
+    kids: Vec<PdfFormFieldNode<'a>>,
+}
+
+/// Builds the hierarchy of fields described by the fully qualified names of the given
+/// fields, keeping fields in the order in which they were given.
+fn build_field_tree(fields: &[PdfFormFieldData]) -> Vec<PdfFormFieldNode<'_>> {
+    let mut roots = Vec::new();
+
+    for field in fields {
+        let mut level = &mut roots;
+
+        let mut partial_names = field.name.split('.').peekable();
+
+        while let Some(partial_name) = partial_names.next() {
+            let index = match level
+                .iter()
+                .position(|node: &PdfFormFieldNode| node.partial_name == partial_name)
+            {
+                Some(index) => index,
+                None => {
+                    level.push(PdfFormFieldNode {
+                        partial_name,
+                        field: None,
+                        kids: Vec::new(),
+                    });
+
+                    level.len() - 1
+                }
+            };
+
+            if partial_names.peek().is_none() {
+                level[index].field = Some(field);
+            }
+
+            level = &mut level[index].kids;
+        }
+    }
+
+    roots
+}
+
+/// Writes the given field values as an FDF file.
+pub(crate) fn write_fdf(fields: &[PdfFormFieldData]) -> Vec<u8> {
+    let mut result = b"%FDF-1.2\n%\xE2\xE3\xCF\xD3\n1 0 obj\n<< /FDF << /Fields [".to_vec();
+
+    for node in build_field_tree(fields) {
+        write_fdf_field(&mut result, &node);
+    }
+
+    result.extend_from_slice(b"\n] >> >>\nendobj\ntrailer\n<< /Root 1 0 R >>\n%%EOF\n");
+
+    result
+}
+
+/// Writes a single field dictionary, along with the dictionaries of all its kids.
+fn write_fdf_field(result: &mut Vec<u8>, node: &PdfFormFieldNode) {
+    result.extend_from_slice(b"\n<< /T ");
+    result.extend_from_slice(&encode_text_string(node.partial_name));
+
+    if let Some(field) = node.field {
+        let encode = |value: &str| {
+            if field.is_state {
+                encode_name(value)
+            } else {
+                encode_text_string(value)
+            }
+        };
+
+        match field.values.as_slice() {
+            [] => {}
+            [value] => {
+                result.extend_from_slice(b" /V ");
+                result.extend_from_slice(&encode(value));
+            }
+            values => {
+                result.extend_from_slice(b" /V [");
+
+                for value in values {
+                    result.push(b' ');
+                    result.extend_from_slice(&encode(value));
+                }
+
+                result.extend_from_slice(b" ]");
+            }
+        }
+    }
+
+    if !node.kids.is_empty() {
+        result.extend_from_slice(b" /Kids [");
+
+        for kid in node.kids.iter() {
+            write_fdf_field(result, kid);
+        }
+
+        result.extend_from_slice(b" ]");
+    }
+
+    result.extend_from_slice(b" >>");
+}
+
+/// Reads the field values from the given FDF file.
+pub(crate) fn read_fdf(bytes: &[u8]) -> Result<Vec<PdfFormFieldData>, PdfiumError> {
+    if !bytes.windows(5).any(|window| window == b"%FDF-") {
+        return Err(PdfiumError::InvalidFormData);
+    }
+
+    let trailer_start = bytes
+        .windows(7)
+        .rposition(|window| window == b"trailer")
+        .ok_or(PdfiumError::InvalidFormData)?;
+
+    let (trailer, _) = PdfRawDictionary::parse_at(bytes, trailer_start + 7)
+        .map_err(|_| PdfiumError::InvalidFormData)?;
+
+    let catalog = resolve_dictionary(bytes, trailer.get("Root"))?;
+
+    let fdf = resolve_dictionary(bytes, catalog.get("FDF"))?;
+
+    let mut result = Vec::new();
+
+    if let Some(fields) = fdf.get("Fields") {
+        for field in read_array(bytes, fields)? {
+            let field = resolve_dictionary(bytes, Some(field))?;
+
+            read_fdf_field(bytes, &field, "", 0, &mut result)?;
+        }
+    }
+
+    Ok(result)
+}
+
+/// Reads the value of a single field dictionary, along with the values of all its kids.
+fn read_fdf_field(
+    bytes: &[u8],
+    field: &PdfRawDictionary,
+    parent_name: &str,
+    depth: usize,
+    result: &mut Vec<PdfFormFieldData>,
+) -> Result<(), PdfiumError> {
+    if depth > MAX_FIELD_DEPTH {
+        return Err(PdfiumError::InvalidFormData);
+    }
+
+    let name = match field.get("T").and_then(decode_text_string) {
+        Some(partial_name) if parent_name.is_empty() => partial_name,
+        Some(partial_name) => format!("{parent_name}.{partial_name}"),
+        None => parent_name.to_string(),
+    };
+
+    match field.get("V") {
+        Some(value) => {
+            let (values, is_state) = read_fdf_value(resolve(bytes, value)?)?;
+
+            result.push(PdfFormFieldData {
+                name: name.clone(),
+                values,
+                is_state,
+            });
+        }
+        None if !field.contains_key("Kids") => result.push(PdfFormFieldData {
+            name: name.clone(),
+            values: Vec::new(),
+            is_state: false,
+        }),
+        None => {}
+    }
+
+    if let Some(kids) = field.get("Kids") {
+        for kid in read_array(bytes, kids)? {
+            let kid = resolve_dictionary(bytes, Some(kid))?;
+
+            read_fdf_field(bytes, &kid, &name, depth + 1, result)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Reads the given `/V` value, returning the field values along with whether they
+/// are the names of appearance states.
+fn read_fdf_value(value: &[u8]) -> Result<(Vec<String>, bool), PdfiumError> {
+    if let Some(name) = decode_name(value) {
+        Ok((vec![name], true))
+    } else if let Some(text) = decode_text_string(value) {
+        Ok((vec![text], false))
+    } else if let Ok(elements) = parse_array(value) {
+        let mut values = Vec::with_capacity(elements.len());
+
+        let mut is_state = false;
+
+        for element in elements {
+            if let Some(name) = decode_name(element) {
+                values.push(name);
+
+                is_state = true;
+            } else {
+                values.push(decode_text_string(element).ok_or(PdfiumError::InvalidFormData)?);
+            }
+        }
+
+        Ok((values, is_state))
+    } else {
+        // Numbers and other simple values are imported as text.
+
+        std::str::from_utf8(value)
+            .map(|value| (vec![value.trim().to_string()], false))
+            .map_err(|_| PdfiumError::InvalidFormData)
+    }
+}
+
+/// Returns the body of the indirect object referred to by the given value, if the value
+/// is an indirect reference, or the value itself otherwise.
+fn resolve<'a>(bytes: &'a [u8], value: &'a [u8]) -> Result<&'a [u8], PdfiumError> {
+    match PdfObjectId::from_reference(value) {
+        Some(id) => find_indirect_object(bytes, id).ok_or(PdfiumError::InvalidFormData),
+        None => Ok(value),
+    }
+}
+
+/// Resolves the given value and parses it as a dictionary.
+fn resolve_dictionary(bytes: &[u8], value: Option<&[u8]>) -> Result<PdfRawDictionary, PdfiumError> {
+    let value = resolve(bytes, value.ok_or(PdfiumError::InvalidFormData)?)?;
+
+    PdfRawDictionary::parse(value).map_err(|_| PdfiumError::InvalidFormData)
+}
+
+/// Resolves the given value and splits it into the raw bytes of its array elements.
+fn read_array<'a>(bytes: &'a [u8], value: &'a [u8]) -> Result<Vec<&'a [u8]>, PdfiumError> {
+    parse_array(resolve(bytes, value)?).map_err(|_| PdfiumError::InvalidFormData)
+}
+
+/// Writes the given field values as an XFDF document.
+pub(crate) fn write_xfdf(fields: &[PdfFormFieldData]) -> String {
+    let mut result = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+        <xfdf xmlns=\"http://ns.adobe.com/xfdf/\" xml:space=\"preserve\">\n  <fields>\n",
+    );
+
+    for node in build_field_tree(fields) {
+        write_xfdf_field(&mut result, &node, 2);
+    }
+
+    result.push_str("  </fields>\n</xfdf>\n");
+
+    result
+}
+
+/// Writes a single `<field>` element, along with the elements of all its kids.
+fn write_xfdf_field(result: &mut String, node: &PdfFormFieldNode, depth: usize) {
+    let indent = "  ".repeat(depth);
+
+    result.push_str(&format!(
+        "{indent}<field name=\"{}\">\n",
+        escape(node.partial_name)
+    ));
+
+    if let Some(field) = node.field {
+        for value in field.values.iter() {
+            result.push_str(&format!("{indent}  <value>{}</value>\n", escape(value)));
+        }
+    }
+
+    for kid in node.kids.iter() {
+        write_xfdf_field(result, kid, depth + 1);
+    }
+
+    result.push_str(&format!("{indent}</field>\n"));
+}
+
+/// Reads the field values from the given XFDF document.
+///
+/// The document is scanned as text rather than parsed as XML. Only `<field>` and `<value>`
+/// elements are interpreted; all other elements are skipped.
+pub(crate) fn read_xfdf(xml: &str) -> Result<Vec<PdfFormFieldData>, PdfiumError> {
+    struct OpenField {
+        name: String,
+        values: Vec<String>,
+        has_kids: bool,
+    }
+
+    let mut result = Vec::new();
+
+    let mut open_fields: Vec<OpenField> = Vec::new();
+
+    let mut has_root = false;
+
+    let mut rest = xml;
+
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+
+        // Skip comments, processing instructions, declarations, and character data
+        // outside of field values.
+
+        let skipped = [
+            ("<!--", "-->"),
+            ("<?", "?>"),
+            ("<![CDATA[", "]]>"),
+            ("<!", ">"),
+        ]
+        .iter()
+        .find(|(open, _)| rest.starts_with(open));
+
+        if let Some((open, close)) = skipped {
+            let end = rest[open.len()..]
+                .find(close)
+                .ok_or(PdfiumError::InvalidFormData)?;
+
+            rest = &rest[open.len() + end + close.len()..];
+
+            continue;
+        }
+
+        let end = rest.find('>').ok_or(PdfiumError::InvalidFormData)?;
+
+        let tag = &rest[1..end];
+
+        rest = &rest[end + 1..];
+
+        let (is_end_tag, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+
+        let (is_empty_element, tag) = match tag.strip_suffix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+
+        let element = tag.split_whitespace().next().unwrap_or_default();
+
+        // Ignore any namespace prefix.
+
+        let local_name = element.rsplit(':').next().unwrap_or_default();
+
+        match (local_name, is_end_tag) {
+            ("xfdf", false) => has_root = true,
+            ("field", false) => {
+                let partial_name = attribute(tag, "name").ok_or(PdfiumError::InvalidFormData)?;
+
+                let name = match open_fields.last_mut() {
+                    Some(parent) => {
+                        parent.has_kids = true;
+
+                        format!("{}.{}", parent.name, partial_name)
+                    }
+                    None => partial_name,
+                };
+
+                if is_empty_element {
+                    result.push(PdfFormFieldData {
+                        name,
+                        values: Vec::new(),
+                        is_state: false,
+                    });
+                } else {
+                    open_fields.push(OpenField {
+                        name,
+                        values: Vec::new(),
+                        has_kids: false,
+                    });
+                }
+            }
+            ("field", true) => {
+                let field = open_fields.pop().ok_or(PdfiumError::InvalidFormData)?;
+
+                if !field.has_kids || !field.values.is_empty() {
+                    result.push(PdfFormFieldData {
+                        name: field.name,
+                        values: field.values,
+                        is_state: false,
+                    });
+                }
+            }
+            ("value", false) => {
+                let value = if is_empty_element {
+                    String::new()
+                } else {
+                    let closing_tag = format!("</{element}>");
+
+                    let end = rest
+                        .find(&closing_tag)
+                        .ok_or(PdfiumError::InvalidFormData)?;
+
+                    let value = character_data(&rest[..end]);
+
+                    rest = &rest[end + closing_tag.len()..];
+
+                    value
+                };
+
+                if let Some(field) = open_fields.last_mut() {
+                    field.values.push(value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !has_root || !open_fields.is_empty() {
+        return Err(PdfiumError::InvalidFormData);
+    }
+
+    Ok(result)
+}
+
+/// Returns the unescaped value of the attribute with the given name in the given start tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let mut rest = tag;
+
+    while let Some(index) = rest.find(name) {
+        let is_attribute_start = rest[..index].ends_with(char::is_whitespace);
+
+        rest = &rest[index + name.len()..];
+
+        if !is_attribute_start {
+            continue;
+        }
+
+        let value = match rest.trim_start().strip_prefix('=') {
+            Some(value) => value.trim_start(),
+            None => continue,
+        };
+
+        let quote = value
+            .chars()
+            .next()
+            .filter(|quote| *quote == '"' || *quote == '\'')?;
+
+        let value = &value[1..];
+
+        return value.find(quote).map(|end| unescape(&value[..end]));
+    }
+
+    None
+}
+
+/// Returns the text content of the given element content, unescaping character references
+/// outside of CDATA sections.
+fn character_data(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<![CDATA[") {
+        result.push_str(&unescape(&rest[..start]));
+
+        rest = &rest[start + 9..];
+
+        let end = rest.find("]]>").unwrap_or(rest.len());
+
+        result.push_str(&rest[..end]);
+
+        rest = &rest[(end + 3).min(rest.len())..];
+    }
+
+    result.push_str(&unescape(rest));
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_fields() -> Vec<PdfFormFieldData> {
+        vec![
+            PdfFormFieldData {
+                name: "customer.name".to_string(),
+                values: vec!["Ann (\"A&B\") <Ltd>".to_string()],
+                is_state: false,
+            },
+            PdfFormFieldData {
+                name: "customer.address.city".to_string(),
+                values: vec!["Zürich".to_string()],
+                is_state: false,
+            },
+            PdfFormFieldData {
+                name: "subscribe".to_string(),
+                values: vec!["Yes".to_string()],
+                is_state: true,
+            },
+            PdfFormFieldData {
+                name: "customer.colors".to_string(),
+                values: vec!["Red".to_string(), "Blue".to_string()],
+                is_state: false,
+            },
+            PdfFormFieldData {
+                name: "notes".to_string(),
+                values: Vec::new(),
+                is_state: false,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_fdf_round_trip() -> Result<(), PdfiumError> {
+        let fields = test_fields();
+
+        let fdf = write_fdf(&fields);
+
+        assert!(fdf.starts_with(b"%FDF-1.2\n"));
+
+        let text = String::from_utf8_lossy(&fdf);
+
+        assert!(text.contains("<< /T (subscribe) /V /Yes >>"));
+        assert!(text.contains("<< /T (colors) /V [ (Red) (Blue) ] >>"));
+        assert!(text.contains("<< /T (address) /Kids [\n<< /T (city) /V <FEFF005A00FC"));
+
+        let mut read = read_fdf(&fdf)?;
+
+        // Fields are read back in hierarchy order, so the fields sharing the "customer"
+        // parent are grouped together.
+
+        read.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut expected = fields;
+
+        expected.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(read, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_fdf_with_indirect_objects() -> Result<(), PdfiumError> {
+        let fdf = b"%FDF-1.2\r\n1 0 obj\r<</FDF<</F(form.pdf)/Fields 2 0 R>>>>\rendobj\r\
+            2 0 obj\r[3 0 R<</T(choice)/V[/A(B\\351)]>>]\rendobj\r\
+            3 0 obj\r<</T(parent)/Kids[<</T(child)/V(line\\\r\nbreak\\051)>>]>>\rendobj\r\
+            trailer\r<</Root 1 0 R>>\r%%EOF\r\n";
+
+        assert_eq!(
+            read_fdf(fdf)?,
+            vec![
+                PdfFormFieldData {
+                    name: "parent.child".to_string(),
+                    values: vec!["linebreak)".to_string()],
+                    is_state: false,
+                },
+                PdfFormFieldData {
+                    name: "choice".to_string(),
+                    values: vec!["A".to_string(), "Bé".to_string()],
+                    is_state: true,
+                },
+            ]
+        );
+
+        assert!(matches!(
+            read_fdf(b"%PDF-1.7\ntrailer\n<< /Root 1 0 R >>"),
+            Err(PdfiumError::InvalidFormData)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_xfdf_round_trip() -> Result<(), PdfiumError> {
+        let fields = test_fields();
+
+        let xfdf = write_xfdf(&fields);
+
+        assert!(xfdf.contains(
+            "    <field name=\"customer\">\n      \
+            <field name=\"name\">\n        \
+            <value>Ann (&quot;A&amp;B&quot;) &lt;Ltd&gt;</value>\n      \
+            </field>\n"
+        ));
+
+        let mut read = read_xfdf(&xfdf)?;
+
+        read.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut expected = fields
+            .into_iter()
+            .map(|field| PdfFormFieldData {
+                is_state: false,
+                ..field
+            })
+            .collect::<Vec<_>>();
+
+        expected.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(read, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_xfdf() -> Result<(), PdfiumError> {
+        let xfdf = "<?xml version='1.0'?>\n<!-- exported -->\n\
+            <xfdf xmlns='http://ns.adobe.com/xfdf/'><f href='form.pdf'/>\
+            <fields><field name='a'><field name = 'b'><value><![CDATA[<x> & y]]></value>\
+            </field><field name='c'/></field><field name='d'><value/><value>&#x41;&#66;</value>\
+            </field></fields><ids original='1' modified='2'/></xfdf>";
+
+        assert_eq!(
+            read_xfdf(xfdf)?,
+            vec![
+                PdfFormFieldData {
+                    name: "a.b".to_string(),
+                    values: vec!["<x> & y".to_string()],
+                    is_state: false,
+                },
+                PdfFormFieldData {
+                    name: "a.c".to_string(),
+                    values: Vec::new(),
+                    is_state: false,
+                },
+                PdfFormFieldData {
+                    name: "d".to_string(),
+                    values: vec![String::new(), "AB".to_string()],
+                    is_state: false,
+                },
+            ]
+        );
+
+        assert!(matches!(
+            read_xfdf("<xfdf><fields><field name='a'></fields></xfdf>"),
+            Err(PdfiumError::InvalidFormData)
+        ));
+
+        Ok(())
+    }
+}
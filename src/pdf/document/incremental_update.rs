@@ -0,0 +1,1801 @@
+//! Defines the [PdfIncrementalUpdate] struct, used to append an incremental update to the
+//! saved bytes of a `PdfDocument` for changes that cannot be made through Pdfium's API,
+//! such as adding dictionary-valued entries to the document catalog.
+//!
+//! Both classic cross-reference tables and cross-reference streams are supported, along with
+//! objects stored in object streams; the only stream filter that can be decoded is
+//! `/FlateDecode`. Encrypted files cannot be updated.
+
+use crate::bindgen::FPDF_DOCUMENT;
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::{PdfiumError, PdfiumInternalError};
+use crate::pdf::document::save_options::PdfSaveMode;
+use crate::utils::files::get_pdfium_file_writer_from_writer;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::{Cursor, Write};
+use std::ops::Range;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The object number and generation number of a single indirect object in a PDF file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct PdfObjectId {
+    pub(crate) number: u32,
+    pub(crate) generation: u16,
+}
+
+impl PdfObjectId {
+    /// Returns an indirect reference to this object, suitable for use as a dictionary value.
+    pub(crate) fn to_reference(self) -> Vec<u8> {
+        format!("{} {} R", self.number, self.generation).into_bytes()
+    }
+
+    /// Parses an indirect reference of the form `n g R`.
+    pub(crate) fn from_reference(bytes: &[u8]) -> Option<Self> {
+        let text = std::str::from_utf8(bytes).ok()?;
+
+        let mut tokens = text.split_ascii_whitespace();
+
+        let number = tokens.next()?.parse().ok()?;
+        let generation = tokens.next()?.parse().ok()?;
+
+        match (tokens.next(), tokens.next()) {
+            (Some("R"), None) => Some(PdfObjectId { number, generation }),
+            _ => None,
+        }
+    }
+
+    /// Parses an array of indirect references of the form `[n g R n g R ...]`.
+    pub(crate) fn from_reference_array(bytes: &[u8]) -> Option<Vec<Self>> {
+        let text = std::str::from_utf8(bytes).ok()?.trim();
+
+        let tokens = text
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .split_ascii_whitespace()
+            .collect::<Vec<_>>();
+
+        tokens
+            .chunks(3)
+            .map(|chunk| match chunk {
+                [number, generation, "R"] => Some(PdfObjectId {
+                    number: number.parse().ok()?,
+                    generation: generation.parse().ok()?,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A PDF dictionary whose keys are parsed but whose values are kept as raw, unparsed bytes.
+///
+/// Entries keep the order in which they were parsed or added, so a dictionary that is parsed
+/// and then written back out without changes is reproduced entry for entry.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PdfRawDictionary {
+    entries: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl PdfRawDictionary {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        PdfRawDictionary {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Parses the dictionary starting at the given position in the given bytes, returning
+    /// the dictionary along with the position immediately following it.
+    pub(crate) fn parse_at(bytes: &[u8], position: usize) -> Result<(Self, usize), PdfiumError> {
+        let mut position = skip_whitespace(bytes, position);
+
+        if !bytes[position..].starts_with(b"<<") {
+            return Err(PdfiumError::UnsupportedPdfFileStructure);
+        }
+
+        position += 2;
+
+        let mut entries = Vec::new();
+
+        loop {
+            position = skip_whitespace(bytes, position);
+
+            if bytes[position..].starts_with(b">>") {
+                return Ok((PdfRawDictionary { entries }, position + 2));
+            }
+
+            if bytes.get(position) != Some(&b'/') {
+                return Err(PdfiumError::UnsupportedPdfFileStructure);
+            }
+
+            let key_end = skip_object(bytes, position)?;
+
+            let key = bytes[position + 1..key_end].to_vec();
+
+            let value_start = skip_whitespace(bytes, key_end);
+
+            let value_end = skip_value(bytes, value_start)?;
+
+            entries.push((key, bytes[value_start..value_end].to_vec()));
+
+            position = value_end;
+        }
+    }
+
+    /// Parses the dictionary at the start of the given bytes.
+    #[inline]
+    pub(crate) fn parse(bytes: &[u8]) -> Result<Self, PdfiumError> {
+        Self::parse_at(bytes, 0).map(|(dictionary, _)| dictionary)
+    }
+
+    /// Returns the raw value of the entry with the given key, if any.
+    pub(crate) fn get(&self, key: &str) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key.as_bytes())
+            .map(|(_, value)| value.as_slice())
+    }
+
+    /// Returns the value of the entry with the given key as an indirect reference, if
+    /// the entry exists and is an indirect reference.
+    #[inline]
+    pub(crate) fn get_reference(&self, key: &str) -> Option<PdfObjectId> {
+        self.get(key).and_then(PdfObjectId::from_reference)
+    }
+
+    /// Returns the keys of the entries in this dictionary, without their leading `/`.
+    #[inline]
+    pub(crate) fn keys(&self) -> impl Iterator<Item = &[u8]> {
+        self.entries.iter().map(|(key, _)| key.as_slice())
+    }
+
+    /// Returns `true` if this dictionary contains an entry with the given key.
+    #[inline]
+    pub(crate) fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Sets the raw value of the entry with the given key, replacing any existing value.
+    pub(crate) fn set(&mut self, key: &str, value: impl Into<Vec<u8>>) {
+        let value = value.into();
+
+        match self.entries.iter_mut().find(|(k, _)| k == key.as_bytes()) {
+            Some((_, existing)) => *existing = value,
+            None => self.entries.push((key.as_bytes().to_vec(), value)),
+        }
+    }
+
+    /// Removes the entry with the given key, if any.
+    pub(crate) fn remove(&mut self, key: &str) {
+        self.entries.retain(|(k, _)| k != key.as_bytes());
+    }
+
+    /// Serializes this dictionary.
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut result = b"<<".to_vec();
+
+        for (key, value) in self.entries.iter() {
+            result.push(b'/');
+            result.extend_from_slice(key);
+            result.push(b' ');
+            result.extend_from_slice(value);
+            result.push(b'\n');
+        }
+
+        result.extend_from_slice(b">>");
+
+        result
+    }
+}
+
+/// The location of a single in-use object, as recorded in a cross-reference section.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum PdfXrefEntry {
+    /// An object stored directly in the file at the given byte offset.
+    Uncompressed { offset: usize, generation: u16 },
+
+    /// An object stored at the given index in the object stream with the given object number.
+    Compressed { stream: u32, index: usize },
+}
+
+/// Collects changes to the indirect objects of a saved PDF file, then appends them
+/// to the file as an incremental update.
+pub(crate) struct PdfIncrementalUpdate {
+    bytes: Vec<u8>,
+    trailer: PdfRawDictionary,
+    startxref: usize,
+    is_xref_stream: bool,
+    offsets: HashMap<u32, (usize, u16)>,
+    compressed: HashMap<u32, Vec<u8>>,
+    size: u32,
+    objects: BTreeMap<u32, (u16, Vec<u8>)>,
+}
+
+impl PdfIncrementalUpdate {
+    /// Prepares an incremental update to the given saved PDF file.
+    pub(crate) fn new(bytes: Vec<u8>) -> Result<Self, PdfiumError> {
+        let startxref = Self::find_startxref(&bytes)?;
+
+        let is_xref_stream = !bytes[skip_whitespace(&bytes, startxref)..].starts_with(b"xref");
+
+        let mut entries = HashMap::new();
+
+        let mut trailer = None;
+
+        let mut visited = HashSet::new();
+
+        let mut next_section = Some(startxref);
+
+        // Walk the chain of cross-reference sections from newest to oldest. Entries in newer
+        // sections take precedence over entries for the same object in older sections.
+
+        while let Some(section) = next_section {
+            if !visited.insert(section) {
+                break;
+            }
+
+            let section_trailer = Self::read_xref_section(&bytes, section, &mut entries)?;
+
+            // A hybrid-reference file supplements a classic section with a cross-reference
+            // stream listing the objects stored in object streams.
+
+            if let Some(stream) = integer_entry(&section_trailer, "XRefStm") {
+                if visited.insert(stream) {
+                    Self::read_xref_stream(&bytes, stream, &mut entries)?;
+                }
+            }
+
+            next_section = integer_entry(&section_trailer, "Prev");
+
+            trailer.get_or_insert(section_trailer);
+        }
+
+        let mut trailer = trailer.ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        if trailer.contains_key("Encrypt") {
+            return Err(PdfiumError::EncryptedDocumentUpdateNotSupported);
+        }
+
+        // The entries describing a cross-reference stream itself do not belong in
+        // the trailer of the update.
+
+        for key in ["Type", "W", "Index", "Filter", "DecodeParms", "Length"] {
+            trailer.remove(key);
+        }
+
+        let size = integer_entry(&trailer, "Size")
+            .and_then(|size| u32::try_from(size).ok())
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let mut offsets = HashMap::new();
+
+        let mut object_streams = BTreeMap::new();
+
+        for (number, entry) in entries {
+            match entry {
+                PdfXrefEntry::Uncompressed { offset, generation } => {
+                    offsets.insert(number, (offset, generation));
+                }
+                PdfXrefEntry::Compressed { stream, index } => {
+                    object_streams
+                        .entry(stream)
+                        .or_insert_with(Vec::new)
+                        .push((number, index));
+                }
+            }
+        }
+
+        let mut update = PdfIncrementalUpdate {
+            bytes,
+            trailer,
+            startxref,
+            is_xref_stream,
+            offsets,
+            compressed: HashMap::new(),
+            size,
+            objects: BTreeMap::new(),
+        };
+
+        for (stream, objects) in object_streams {
+            update.read_object_stream(stream, &objects)?;
+        }
+
+        Ok(update)
+    }
+
+    /// Saves an unencrypted copy of the document with the given handle to memory, then
+    /// prepares an incremental update to the saved copy.
+    ///
+    /// This is the only way to read parts of a document that Pdfium does not expose, such
+    /// as the dictionaries of appearance streams. It is comparatively expensive for large
+    /// documents.
+    pub(crate) fn from_document(
+        document_handle: FPDF_DOCUMENT,
+        bindings: &dyn PdfiumLibraryBindings,
+    ) -> Result<Self, PdfiumError> {
+        let mut cursor = Cursor::new(Vec::new());
+
+        {
+            let mut writer = get_pdfium_file_writer_from_writer(&mut cursor);
+
+            if !bindings.is_true(bindings.FPDF_SaveAsCopy(
+                document_handle,
+                writer.as_fpdf_file_write_mut_ptr(),
+                PdfSaveMode::RemoveSecurity.as_pdfium(),
+            )) {
+                return Err(PdfiumError::PdfiumLibraryInternalError(
+                    PdfiumInternalError::Unknown,
+                ));
+            }
+
+            writer.flush().map_err(PdfiumError::IoError)?;
+        }
+
+        Self::new(cursor.into_inner())
+    }
+
+    /// Returns the byte offset of the newest cross-reference section in the given file.
+    fn find_startxref(bytes: &[u8]) -> Result<usize, PdfiumError> {
+        let position =
+            rfind(bytes, b"startxref").ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let start = skip_whitespace(bytes, position + b"startxref".len());
+
+        let end = skip_object(bytes, start)?;
+
+        std::str::from_utf8(&bytes[start..end])
+            .ok()
+            .and_then(|offset| offset.parse().ok())
+            .filter(|offset| *offset < bytes.len())
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)
+    }
+
+    /// Reads the cross-reference section at the given byte offset, adding any in-use
+    /// objects not already present to the given entries, and returns the section's trailer.
+    fn read_xref_section(
+        bytes: &[u8],
+        offset: usize,
+        entries: &mut HashMap<u32, PdfXrefEntry>,
+    ) -> Result<PdfRawDictionary, PdfiumError> {
+        let mut position = skip_whitespace(bytes, offset);
+
+        // A cross-reference stream begins with an object header rather than the xref keyword.
+
+        if !bytes[position..].starts_with(b"xref") {
+            return Self::read_xref_stream(bytes, offset, entries);
+        }
+
+        position += b"xref".len();
+
+        loop {
+            position = skip_whitespace(bytes, position);
+
+            if bytes[position..].starts_with(b"trailer") {
+                return PdfRawDictionary::parse_at(bytes, position + b"trailer".len())
+                    .map(|(trailer, _)| trailer);
+            }
+
+            let first = read_integer(bytes, &mut position)? as u32;
+            let count = read_integer(bytes, &mut position)? as u32;
+
+            for number in first..first + count {
+                let object_offset = read_integer(bytes, &mut position)?;
+                let generation = read_integer(bytes, &mut position)? as u16;
+
+                position = skip_whitespace(bytes, position);
+
+                let kind = bytes.get(position).copied();
+
+                position += 1;
+
+                if kind == Some(b'n') {
+                    entries.entry(number).or_insert(PdfXrefEntry::Uncompressed {
+                        offset: object_offset,
+                        generation,
+                    });
+                } else if kind != Some(b'f') {
+                    return Err(PdfiumError::UnsupportedPdfFileStructure);
+                }
+            }
+        }
+    }
+
+    /// Reads the cross-reference stream at the given byte offset, adding any in-use objects
+    /// not already present to the given entries, and returns the stream's dictionary, which
+    /// doubles as the section's trailer.
+    fn read_xref_stream(
+        bytes: &[u8],
+        offset: usize,
+        entries: &mut HashMap<u32, PdfXrefEntry>,
+    ) -> Result<PdfRawDictionary, PdfiumError> {
+        let mut position = offset;
+
+        read_integer(bytes, &mut position)?;
+        read_integer(bytes, &mut position)?;
+
+        position = skip_whitespace(bytes, position);
+
+        if !bytes[position..].starts_with(b"obj") {
+            return Err(PdfiumError::UnsupportedPdfFileStructure);
+        }
+
+        let (dictionary, position) = PdfRawDictionary::parse_at(bytes, position + b"obj".len())?;
+
+        if dictionary.get("Type") != Some(b"/XRef".as_slice()) {
+            return Err(PdfiumError::UnsupportedPdfFileStructure);
+        }
+
+        // The entries of a cross-reference stream dictionary are always direct objects.
+
+        let length =
+            integer_entry(&dictionary, "Length").ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let data = decode_stream(&dictionary, stream_data(bytes, position, length)?)?;
+
+        let widths = integer_array_entry(&dictionary, "W")?;
+
+        if widths.len() != 3 {
+            return Err(PdfiumError::UnsupportedPdfFileStructure);
+        }
+
+        let index = if dictionary.contains_key("Index") {
+            integer_array_entry(&dictionary, "Index")?
+        } else {
+            vec![
+                0,
+                integer_entry(&dictionary, "Size")
+                    .ok_or(PdfiumError::UnsupportedPdfFileStructure)?,
+            ]
+        };
+
+        let mut fields = data.chunks_exact(widths.iter().sum::<usize>().max(1));
+
+        for subsection in index.chunks_exact(2) {
+            for number in subsection[0]..subsection[0] + subsection[1] {
+                let row = fields
+                    .next()
+                    .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+                let (kind, row) = row.split_at(widths[0]);
+                let (second, third) = row.split_at(widths[1]);
+
+                // The type field defaults to 1 when it is omitted.
+
+                let kind = if widths[0] == 0 {
+                    1
+                } else {
+                    big_endian_integer(kind)
+                };
+
+                let entry = match kind {
+                    1 => PdfXrefEntry::Uncompressed {
+                        offset: big_endian_integer(second),
+                        generation: big_endian_integer(third) as u16,
+                    },
+                    2 => PdfXrefEntry::Compressed {
+                        stream: big_endian_integer(second) as u32,
+                        index: big_endian_integer(third),
+                    },
+                    _ => continue,
+                };
+
+                entries.entry(number as u32).or_insert(entry);
+            }
+        }
+
+        Ok(dictionary)
+    }
+
+    /// Reads the given objects, each identified by its object number and its index within
+    /// the stream, from the object stream with the given object number.
+    fn read_object_stream(
+        &mut self,
+        stream: u32,
+        objects: &[(u32, usize)],
+    ) -> Result<(), PdfiumError> {
+        let id = PdfObjectId {
+            number: stream,
+            generation: self
+                .offsets
+                .get(&stream)
+                .map(|(_, generation)| *generation)
+                .ok_or(PdfiumError::UnsupportedPdfFileStructure)?,
+        };
+
+        let dictionary = self.get_dictionary(id)?;
+
+        let data = self.get_stream_data(id)?;
+
+        let first =
+            integer_entry(&dictionary, "First").ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let count =
+            integer_entry(&dictionary, "N").ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        // The stream begins with pairs of integers giving the object number of each object
+        // and its offset relative to the first object.
+
+        let mut position = 0;
+
+        let mut headers = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let number = read_integer(&data, &mut position)?;
+            let offset = read_integer(&data, &mut position)?;
+
+            headers.push((number, first + offset));
+        }
+
+        for (number, index) in objects.iter() {
+            let (header_number, start) = headers
+                .get(*index)
+                .copied()
+                .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+            if header_number != *number as usize || start > data.len() {
+                return Err(PdfiumError::UnsupportedPdfFileStructure);
+            }
+
+            let start = skip_whitespace(&data, start);
+
+            let end = skip_value(&data, start)?;
+
+            self.compressed.insert(*number, data[start..end].to_vec());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the trailer dictionary of the newest revision of the file.
+    #[inline]
+    pub(crate) fn trailer(&self) -> &PdfRawDictionary {
+        &self.trailer
+    }
+
+    /// Sets the raw value of the entry with the given key in the trailer written
+    /// as part of this update.
+    #[inline]
+    pub(crate) fn set_trailer_entry(&mut self, key: &str, value: impl Into<Vec<u8>>) {
+        self.trailer.set(key, value);
+    }
+
+    /// Returns the identifier of the document catalog.
+    #[inline]
+    pub(crate) fn root(&self) -> Result<PdfObjectId, PdfiumError> {
+        self.trailer
+            .get_reference("Root")
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)
+    }
+
+    /// Returns the raw body of the indirect object with the given identifier, taking into
+    /// account any changes already made as part of this update.
+    pub(crate) fn get_object(&self, id: PdfObjectId) -> Result<&[u8], PdfiumError> {
+        if let Some((generation, body)) = self.objects.get(&id.number) {
+            if *generation == id.generation {
+                return Ok(body.as_slice());
+            }
+        }
+
+        // Objects in object streams always have a generation number of zero.
+
+        if let Some(body) = self.compressed.get(&id.number) {
+            if id.generation == 0 {
+                return Ok(body.as_slice());
+            }
+        }
+
+        let range = self.object_body_range(id)?;
+
+        Ok(&self.bytes[range])
+    }
+
+    /// Returns the dictionary held by the indirect object with the given identifier.
+    /// If the object is a stream, the stream's dictionary is returned.
+    #[inline]
+    pub(crate) fn get_dictionary(&self, id: PdfObjectId) -> Result<PdfRawDictionary, PdfiumError> {
+        PdfRawDictionary::parse(self.get_object(id)?)
+    }
+
+    /// Returns the decoded data of the stream held by the indirect object with the given
+    /// identifier. Only unfiltered and `/FlateDecode` streams can be decoded.
+    pub(crate) fn get_stream_data(&self, id: PdfObjectId) -> Result<Vec<u8>, PdfiumError> {
+        let body = self.get_object(id)?;
+
+        let (dictionary, position) = PdfRawDictionary::parse_at(body, 0)?;
+
+        let length = match dictionary.get_reference("Length") {
+            Some(length_id) => read_integer(self.get_object(length_id)?, &mut 0)?,
+            None => integer_entry(&dictionary, "Length")
+                .ok_or(PdfiumError::UnsupportedPdfFileStructure)?,
+        };
+
+        decode_stream(&dictionary, stream_data(body, position, length)?)
+    }
+
+    /// Returns the byte range of the body of the indirect object with the given identifier
+    /// in the original file, excluding the surrounding `obj` and `endobj` keywords.
+    fn object_body_range(&self, id: PdfObjectId) -> Result<Range<usize>, PdfiumError> {
+        let (offset, generation) = self
+            .offsets
+            .get(&id.number)
+            .copied()
+            .filter(|(_, generation)| *generation == id.generation)
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let mut position = offset;
+
+        let number = read_integer(&self.bytes, &mut position)?;
+        let header_generation = read_integer(&self.bytes, &mut position)?;
+
+        position = skip_whitespace(&self.bytes, position);
+
+        if number != id.number as usize
+            || header_generation != generation as usize
+            || !self.bytes[position..].starts_with(b"obj")
+        {
+            return Err(PdfiumError::UnsupportedPdfFileStructure);
+        }
+
+        let start = position + b"obj".len();
+
+        let end = find(&self.bytes[start..], b"endobj")
+            .map(|end| start + end)
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        Ok(start..end)
+    }
+
+    /// Returns the identifier of the indirect object in the original file whose body contains
+    /// the last occurrence of the given bytes. Objects stored directly in the file are searched
+    /// before objects in object streams.
+    pub(crate) fn find_object_containing(&self, needle: &[u8]) -> Result<PdfObjectId, PdfiumError> {
+        if let Some(id) = self.find_uncompressed_object_containing(needle) {
+            return Ok(id);
+        }
+
+        self.compressed
+            .iter()
+            .filter(|(_, body)| find(body, needle).is_some())
+            .map(|(number, _)| *number)
+            .max()
+            .map(|number| PdfObjectId {
+                number,
+                generation: 0,
+            })
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)
+    }
+
+    /// Returns the identifier of the indirect object stored directly in the original file
+    /// whose body contains the last occurrence of the given bytes, if any. Occurrences inside
+    /// object streams are ignored, since their data may not be compressed.
+    fn find_uncompressed_object_containing(&self, needle: &[u8]) -> Option<PdfObjectId> {
+        let mut search_end = self.bytes.len();
+
+        while let Some(position) = rfind(&self.bytes[..search_end], needle) {
+            search_end = position;
+
+            let (number, (_, generation)) = match self
+                .offsets
+                .iter()
+                .filter(|(_, (offset, _))| *offset <= position)
+                .max_by_key(|(_, (offset, _))| *offset)
+            {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            let id = PdfObjectId {
+                number: *number,
+                generation: *generation,
+            };
+
+            let is_match = self
+                .object_body_range(id)
+                .map(|range| range.contains(&position))
+                .unwrap_or(false);
+
+            let is_object_stream = self
+                .get_dictionary(id)
+                .map(|dictionary| dictionary.get("Type") == Some(b"/ObjStm".as_slice()))
+                .unwrap_or(false);
+
+            if is_match && !is_object_stream {
+                return Some(id);
+            }
+        }
+
+        None
+    }
+
+    /// Adds a new indirect object with the given body, returning its identifier.
+    pub(crate) fn add_object(&mut self, body: impl Into<Vec<u8>>) -> PdfObjectId {
+        let id = PdfObjectId {
+            number: self.size,
+            generation: 0,
+        };
+
+        self.size += 1;
+
+        self.objects.insert(id.number, (id.generation, body.into()));
+
+        id
+    }
+
+    /// Replaces the body of the indirect object with the given identifier.
+    #[inline]
+    pub(crate) fn set_object(&mut self, id: PdfObjectId, body: impl Into<Vec<u8>>) {
+        self.objects.insert(id.number, (id.generation, body.into()));
+    }
+
+    /// Appends all changed and added objects to the original file, followed by a new
+    /// cross-reference section and trailer. Returns the updated file, along with the byte
+    /// offset of the body of each changed or added object, keyed by object number.
+    pub(crate) fn write(self) -> Result<(Vec<u8>, HashMap<u32, usize>), PdfiumError> {
+        let mut bytes = self.bytes;
+
+        if !bytes.ends_with(b"\n") {
+            bytes.push(b'\n');
+        }
+
+        let mut object_offsets = HashMap::new();
+
+        let mut xref_entries = Vec::new();
+
+        for (number, (generation, body)) in self.objects.iter() {
+            xref_entries.push((*number, *generation, bytes.len()));
+
+            writeln!(bytes, "{number} {generation} obj").map_err(PdfiumError::IoError)?;
+
+            object_offsets.insert(*number, bytes.len());
+
+            bytes.extend_from_slice(body);
+            bytes.extend_from_slice(b"\nendobj\n");
+        }
+
+        let mut trailer = self.trailer;
+
+        trailer.set("Prev", self.startxref.to_string());
+        trailer.remove("XRefStm");
+
+        let xref_offset = bytes.len();
+
+        // An update to a file whose newest cross-reference section is a stream is given
+        // a cross-reference stream of its own, so that readers that do not support
+        // hybrid-reference files can still read the updated file.
+
+        if self.is_xref_stream {
+            let number = self.size;
+
+            xref_entries.push((number, 0, xref_offset));
+
+            trailer.set("Size", (number + 1).to_string());
+
+            write_xref_stream(&mut bytes, number, &xref_entries, trailer)?;
+        } else {
+            trailer.set("Size", self.size.to_string());
+
+            write_xref_table(&mut bytes, &xref_entries, trailer)?;
+        }
+
+        write!(bytes, "\nstartxref\n{xref_offset}\n%%EOF\n").map_err(PdfiumError::IoError)?;
+
+        Ok((bytes, object_offsets))
+    }
+}
+
+/// Groups the given cross-reference entries, sorted by object number, into subsections
+/// of consecutive object numbers, returning the first object number and the entries of
+/// each subsection.
+fn xref_subsections(
+    entries: &[(u32, u16, usize)],
+) -> impl Iterator<Item = (u32, &[(u32, u16, usize)])> {
+    let mut index = 0;
+
+    std::iter::from_fn(move || {
+        let first = entries.get(index)?.0;
+
+        let count = entries[index..]
+            .iter()
+            .enumerate()
+            .take_while(|(offset, (number, _, _))| *number == first + *offset as u32)
+            .count();
+
+        index += count;
+
+        Some((first, &entries[index - count..index]))
+    })
+}
+
+/// Writes a classic cross-reference table for the given entries, followed by
+/// the given trailer.
+fn write_xref_table(
+    bytes: &mut Vec<u8>,
+    entries: &[(u32, u16, usize)],
+    trailer: PdfRawDictionary,
+) -> Result<(), PdfiumError> {
+    bytes.extend_from_slice(b"xref\n");
+
+    for (first, subsection) in xref_subsections(entries) {
+        writeln!(bytes, "{first} {}", subsection.len()).map_err(PdfiumError::IoError)?;
+
+        for (_, generation, offset) in subsection.iter() {
+            write!(bytes, "{offset:010} {generation:05} n\r\n").map_err(PdfiumError::IoError)?;
+        }
+    }
+
+    bytes.extend_from_slice(b"trailer\n");
+    bytes.extend_from_slice(&trailer.to_bytes());
+
+    Ok(())
+}
+
+/// Writes an unfiltered cross-reference stream with the given object number for the given
+/// entries, which must include the stream itself. The entries of the given trailer are
+/// added to the stream's dictionary.
+fn write_xref_stream(
+    bytes: &mut Vec<u8>,
+    number: u32,
+    entries: &[(u32, u16, usize)],
+    mut dictionary: PdfRawDictionary,
+) -> Result<(), PdfiumError> {
+    let max_offset = entries
+        .iter()
+        .map(|(_, _, offset)| *offset)
+        .max()
+        .unwrap_or(0);
+
+    let offset_width = (1..8)
+        .find(|width| max_offset >> (8 * width) == 0)
+        .unwrap_or(8);
+
+    let mut index = Vec::new();
+
+    let mut data = Vec::new();
+
+    for (first, subsection) in xref_subsections(entries) {
+        index.push(format!("{first} {}", subsection.len()));
+
+        for (_, generation, offset) in subsection.iter() {
+            data.push(1);
+            data.extend_from_slice(&offset.to_be_bytes()[8 - offset_width..]);
+            data.extend_from_slice(&generation.to_be_bytes());
+        }
+    }
+
+    dictionary.set("Type", "/XRef");
+    dictionary.set("W", format!("[1 {offset_width} 2]"));
+    dictionary.set("Index", format!("[{}]", index.join(" ")));
+    dictionary.set("Length", data.len().to_string());
+
+    writeln!(bytes, "{number} 0 obj").map_err(PdfiumError::IoError)?;
+
+    bytes.extend_from_slice(&dictionary.to_bytes());
+    bytes.extend_from_slice(b"\nstream\n");
+    bytes.extend_from_slice(&data);
+    bytes.extend_from_slice(b"\nendstream\nendobj");
+
+    Ok(())
+}
+
+/// Returns the data of the stream whose dictionary ends at the given position in the given
+/// bytes, given the stream's length.
+fn stream_data(bytes: &[u8], position: usize, length: usize) -> Result<&[u8], PdfiumError> {
+    let mut position = skip_whitespace(bytes, position);
+
+    if !bytes[position..].starts_with(b"stream") {
+        return Err(PdfiumError::UnsupportedPdfFileStructure);
+    }
+
+    // The stream keyword is followed by either a carriage return and line feed,
+    // or by a line feed alone.
+
+    position += b"stream".len();
+
+    if bytes[position..].starts_with(b"\r\n") {
+        position += 2;
+    } else if bytes[position..].starts_with(b"\n") {
+        position += 1;
+    }
+
+    bytes
+        .get(position..position + length)
+        .ok_or(PdfiumError::UnsupportedPdfFileStructure)
+}
+
+/// Decodes the given data of the stream with the given dictionary. Only unfiltered and
+/// `/FlateDecode` streams can be decoded, optionally using a PNG predictor.
+fn decode_stream(dictionary: &PdfRawDictionary, data: &[u8]) -> Result<Vec<u8>, PdfiumError> {
+    let filter = match dictionary.get("Filter") {
+        Some(filter) if trim(filter).starts_with(b"[") => {
+            let filters = parse_array(filter)?;
+
+            match filters.as_slice() {
+                [] => None,
+                [filter] => Some(*filter),
+                _ => return Err(PdfiumError::UnsupportedPdfFileStructure),
+            }
+        }
+        filter => filter,
+    };
+
+    let filter = match filter.map(trim) {
+        None => return Ok(data.to_vec()),
+        Some(filter) => filter,
+    };
+
+    if filter != b"/FlateDecode" && filter != b"/Fl" {
+        return Err(PdfiumError::UnsupportedPdfFileStructure);
+    }
+
+    let inflated = miniz_oxide::inflate::decompress_to_vec_zlib(data)
+        .map_err(|_| PdfiumError::UnsupportedPdfFileStructure)?;
+
+    let parameters = match dictionary.get("DecodeParms").map(trim) {
+        Some(parameters) if parameters.starts_with(b"<<") => PdfRawDictionary::parse(parameters)?,
+        Some(parameters) if parameters.starts_with(b"[") => {
+            match parse_array(parameters)?.first() {
+                Some(parameters) if parameters.starts_with(b"<<") => {
+                    PdfRawDictionary::parse(parameters)?
+                }
+                _ => return Ok(inflated),
+            }
+        }
+        _ => return Ok(inflated),
+    };
+
+    match integer_entry(&parameters, "Predictor").unwrap_or(1) {
+        1 => Ok(inflated),
+        predictor if predictor >= 10 => {
+            let colors = integer_entry(&parameters, "Colors").unwrap_or(1);
+            let bits = integer_entry(&parameters, "BitsPerComponent").unwrap_or(8);
+            let columns = integer_entry(&parameters, "Columns").unwrap_or(1);
+
+            decode_png_predictor(
+                &inflated,
+                (colors * bits * columns + 7) / 8,
+                ((colors * bits + 7) / 8).max(1),
+            )
+        }
+        _ => Err(PdfiumError::UnsupportedPdfFileStructure),
+    }
+}
+
+/// Reverses the PNG predictor applied to the given data, made up of rows of the given
+/// length, each preceded by a byte identifying the PNG filter type used for that row.
+fn decode_png_predictor(
+    data: &[u8],
+    row_length: usize,
+    bytes_per_pixel: usize,
+) -> Result<Vec<u8>, PdfiumError> {
+    let mut result = Vec::with_capacity(data.len());
+
+    let mut previous = vec![0u8; row_length];
+
+    for row in data.chunks(row_length + 1) {
+        let (filter, row) = row
+            .split_first()
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let mut current = row.to_vec();
+
+        for index in 0..current.len() {
+            let left = if index >= bytes_per_pixel {
+                current[index - bytes_per_pixel]
+            } else {
+                0
+            };
+
+            let above = previous[index];
+
+            let above_left = if index >= bytes_per_pixel {
+                previous[index - bytes_per_pixel]
+            } else {
+                0
+            };
+
+            let prediction = match filter {
+                0 => 0,
+                1 => left,
+                2 => above,
+                3 => ((left as u16 + above as u16) / 2) as u8,
+                4 => paeth(left, above, above_left),
+                _ => return Err(PdfiumError::UnsupportedPdfFileStructure),
+            };
+
+            current[index] = current[index].wrapping_add(prediction);
+        }
+
+        result.extend_from_slice(&current);
+
+        previous[..current.len()].copy_from_slice(&current);
+    }
+
+    Ok(result)
+}
+
+/// The Paeth predictor used by PNG filter type 4.
+fn paeth(left: u8, above: u8, above_left: u8) -> u8 {
+    let estimate = left as i16 + above as i16 - above_left as i16;
+
+    let left_distance = (estimate - left as i16).abs();
+    let above_distance = (estimate - above as i16).abs();
+    let above_left_distance = (estimate - above_left as i16).abs();
+
+    if left_distance <= above_distance && left_distance <= above_left_distance {
+        left
+    } else if above_distance <= above_left_distance {
+        above
+    } else {
+        above_left
+    }
+}
+
+/// Returns the value of the entry with the given key in the given dictionary as
+/// an unsigned integer, if the entry exists and is a direct integer.
+fn integer_entry(dictionary: &PdfRawDictionary, key: &str) -> Option<usize> {
+    let value = trim(dictionary.get(key)?);
+
+    if !is_integer(value) {
+        return None;
+    }
+
+    std::str::from_utf8(value).ok()?.parse().ok()
+}
+
+/// Returns the value of the entry with the given key in the given dictionary as an array
+/// of unsigned integers.
+fn integer_array_entry(
+    dictionary: &PdfRawDictionary,
+    key: &str,
+) -> Result<Vec<usize>, PdfiumError> {
+    parse_array(
+        dictionary
+            .get(key)
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?,
+    )?
+    .into_iter()
+    .map(|value| read_integer(value, &mut 0))
+    .collect()
+}
+
+/// Interprets the given bytes as a big-endian unsigned integer, as used for the fields
+/// of a cross-reference stream.
+fn big_endian_integer(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |value, byte| (value << 8) | *byte as usize)
+}
+
+/// Returns a number that has not previously been returned by this function in this process,
+/// for use in the temporary markers that identify objects whose object numbers Pdfium
+/// does not expose, so that they can be located in the bytes of a saved file.
+pub(crate) fn next_marker_id() -> u64 {
+    static NEXT_MARKER_ID: AtomicU64 = AtomicU64::new(0);
+
+    NEXT_MARKER_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Encodes the given text as a PDF text string. Text that can be written as printable ASCII
+/// is encoded as a literal string; all other text is encoded as a hexadecimal string
+/// in UTF-16BE format with a leading byte order mark.
+pub(crate) fn encode_text_string(text: &str) -> Vec<u8> {
+    if text.bytes().all(|byte| (b' '..=b'~').contains(&byte)) {
+        let mut result = vec![b'('];
+
+        for byte in text.bytes() {
+            if matches!(byte, b'(' | b')' | b'\\') {
+                result.push(b'\\');
+            }
+
+            result.push(byte);
+        }
+
+        result.push(b')');
+
+        result
+    } else {
+        let mut result = b"<FEFF".to_vec();
+
+        for unit in text.encode_utf16() {
+            result.extend_from_slice(format!("{unit:04X}").as_bytes());
+        }
+
+        result.push(b'>');
+
+        result
+    }
+}
+
+/// Encodes the given text as a PDF name, including the leading `/`.
+pub(crate) fn encode_name(name: &str) -> Vec<u8> {
+    let mut result = vec![b'/'];
+
+    for byte in name.bytes() {
+        if (b'!'..=b'~').contains(&byte) && !is_delimiter(byte) && byte != b'#' {
+            result.push(byte);
+        } else {
+            result.extend_from_slice(format!("#{byte:02X}").as_bytes());
+        }
+    }
+
+    result
+}
+
+/// Decodes the given PDF literal or hexadecimal string, returning `None` if the bytes
+/// are not a string. Text strings with a byte order mark are decoded as UTF-16BE or UTF-8;
+/// all other strings are treated as Latin-1.
+pub(crate) fn decode_text_string(bytes: &[u8]) -> Option<String> {
+    let text = trim(bytes);
+
+    let raw = if let Some(literal) = text.strip_prefix(b"(") {
+        decode_literal_string(literal.strip_suffix(b")")?)
+    } else if let Some(hex) = text.strip_prefix(b"<") {
+        let mut digits = hex
+            .strip_suffix(b">")?
+            .iter()
+            .filter(|byte| !is_whitespace(**byte))
+            .map(|byte| (*byte as char).to_digit(16))
+            .collect::<Option<Vec<_>>>()?;
+
+        if digits.len() % 2 == 1 {
+            digits.push(0);
+        }
+
+        digits
+            .chunks(2)
+            .map(|pair| (pair[0] * 16 + pair[1]) as u8)
+            .collect()
+    } else {
+        return None;
+    };
+
+    Some(decode_text(&raw))
+}
+
+/// Decodes the given bytes of a PDF text string or text stream. Text with a byte order mark
+/// is decoded as UTF-16BE or UTF-8; all other text is treated as Latin-1.
+pub(crate) fn decode_text(raw: &[u8]) -> String {
+    if let Some(utf16) = raw.strip_prefix(&[0xFE, 0xFF]) {
+        let units = utf16
+            .chunks(2)
+            .map(|pair| u16::from_be_bytes([pair[0], *pair.get(1).unwrap_or(&0)]))
+            .collect::<Vec<_>>();
+
+        String::from_utf16_lossy(&units)
+    } else if let Some(utf8) = raw.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        String::from_utf8_lossy(utf8).into_owned()
+    } else {
+        raw.iter().map(|byte| *byte as char).collect()
+    }
+}
+
+/// Decodes the escape sequences in the body of a PDF literal string.
+fn decode_literal_string(body: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(body.len());
+
+    let mut position = 0;
+
+    while position < body.len() {
+        let byte = body[position];
+
+        position += 1;
+
+        if byte != b'\\' {
+            result.push(byte);
+
+            continue;
+        }
+
+        let escaped = match body.get(position) {
+            Some(escaped) => *escaped,
+            None => break,
+        };
+
+        position += 1;
+
+        match escaped {
+            b'n' => result.push(b'\n'),
+            b'r' => result.push(b'\r'),
+            b't' => result.push(b'\t'),
+            b'b' => result.push(8),
+            b'f' => result.push(12),
+            b'0'..=b'7' => {
+                let mut value = (escaped - b'0') as u32;
+
+                for _ in 0..2 {
+                    match body.get(position) {
+                        Some(digit @ b'0'..=b'7') => {
+                            value = value * 8 + (digit - b'0') as u32;
+                            position += 1;
+                        }
+                        _ => break,
+                    }
+                }
+
+                result.push(value as u8);
+            }
+            b'\r' => {
+                // A backslash at the end of a line continues the string on the next line.
+
+                if body.get(position) == Some(&b'\n') {
+                    position += 1;
+                }
+            }
+            b'\n' => {}
+            _ => result.push(escaped),
+        }
+    }
+
+    result
+}
+
+/// Decodes the given PDF name, returning `None` if the bytes are not a name.
+pub(crate) fn decode_name(bytes: &[u8]) -> Option<String> {
+    let name = trim(bytes).strip_prefix(b"/")?;
+
+    let mut result = Vec::with_capacity(name.len());
+
+    let mut position = 0;
+
+    while position < name.len() {
+        let byte = name[position];
+
+        let escaped = name
+            .get(position + 1..position + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match (byte, escaped) {
+            (b'#', Some(escaped)) => {
+                result.push(escaped);
+                position += 3;
+            }
+            _ => {
+                result.push(byte);
+                position += 1;
+            }
+        }
+    }
+
+    Some(match String::from_utf8(result) {
+        Ok(name) => name,
+        Err(error) => error.as_bytes().iter().map(|byte| *byte as char).collect(),
+    })
+}
+
+/// Splits the given PDF array into the raw bytes of its elements, treating an indirect
+/// reference of the form `n g R` as a single element.
+pub(crate) fn parse_array(bytes: &[u8]) -> Result<Vec<&[u8]>, PdfiumError> {
+    let mut position = skip_whitespace(bytes, 0);
+
+    if bytes.get(position) != Some(&b'[') {
+        return Err(PdfiumError::UnsupportedPdfFileStructure);
+    }
+
+    position += 1;
+
+    let mut elements = Vec::new();
+
+    loop {
+        position = skip_whitespace(bytes, position);
+
+        match bytes.get(position) {
+            None => return Err(PdfiumError::UnsupportedPdfFileStructure),
+            Some(b']') => return Ok(elements),
+            Some(_) => {
+                let end = skip_value(bytes, position)?;
+
+                elements.push(&bytes[position..end]);
+
+                position = end;
+            }
+        }
+    }
+}
+
+/// Returns the raw bytes of the body of the given indirect object in a file that is not
+/// indexed by a cross-reference table, such as an FDF file. If the object is defined more
+/// than once, the last definition is used.
+pub(crate) fn find_indirect_object(bytes: &[u8], id: PdfObjectId) -> Option<&[u8]> {
+    let header = format!("{} {} obj", id.number, id.generation).into_bytes();
+
+    let mut search_end = bytes.len();
+
+    while let Some(start) = rfind(&bytes[..search_end], &header) {
+        let is_token_start = start == 0 || is_whitespace(bytes[start - 1]);
+
+        let body_start = skip_whitespace(bytes, start + header.len());
+
+        if is_token_start {
+            if let Ok(body_end) = skip_value(bytes, body_start) {
+                return Some(&bytes[body_start..body_end]);
+            }
+        }
+
+        search_end = start;
+    }
+
+    None
+}
+
+/// Returns the given bytes without any leading or trailing white space.
+pub(crate) fn trim(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|byte| !is_whitespace(*byte))
+        .unwrap_or(bytes.len());
+
+    let end = bytes
+        .iter()
+        .rposition(|byte| !is_whitespace(*byte))
+        .map_or(start, |end| end + 1);
+
+    &bytes[start..end]
+}
+
+#[inline]
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, 0 | b'\t' | b'\n' | 12 | b'\r' | b' ')
+}
+
+#[inline]
+fn is_delimiter(byte: u8) -> bool {
+    matches!(
+        byte,
+        b'(' | b')' | b'<' | b'>' | b'[' | b']' | b'{' | b'}' | b'/' | b'%'
+    )
+}
+
+/// Returns the position of the first byte at or after the given position that is neither
+/// white space nor part of a comment.
+fn skip_whitespace(bytes: &[u8], mut position: usize) -> usize {
+    while position < bytes.len() {
+        if is_whitespace(bytes[position]) {
+            position += 1;
+        } else if bytes[position] == b'%' {
+            while position < bytes.len() && !matches!(bytes[position], b'\r' | b'\n') {
+                position += 1;
+            }
+        } else {
+            break;
+        }
+    }
+
+    position
+}
+
+/// Returns the position immediately following the single token or composite object
+/// (string, array, or dictionary) starting at the given position.
+fn skip_object(bytes: &[u8], position: usize) -> Result<usize, PdfiumError> {
+    let error = PdfiumError::UnsupportedPdfFileStructure;
+
+    match bytes.get(position) {
+        None => Err(error),
+        Some(b'<') if bytes.get(position + 1) == Some(&b'<') => {
+            let mut position = position + 2;
+
+            loop {
+                position = skip_whitespace(bytes, position);
+
+                if bytes[position..].starts_with(b">>") {
+                    return Ok(position + 2);
+                }
+
+                position = skip_object(bytes, position)?;
+            }
+        }
+        Some(b'<') => find(&bytes[position..], b">")
+            .map(|end| position + end + 1)
+            .ok_or(error),
+        Some(b'(') => {
+            let mut depth = 0;
+
+            let mut position = position;
+
+            while let Some(byte) = bytes.get(position) {
+                match byte {
+                    b'\\' => position += 1,
+                    b'(' => depth += 1,
+                    b')' => {
+                        depth -= 1;
+
+                        if depth == 0 {
+                            return Ok(position + 1);
+                        }
+                    }
+                    _ => {}
+                }
+
+                position += 1;
+            }
+
+            Err(error)
+        }
+        Some(b'[') => {
+            let mut position = position + 1;
+
+            loop {
+                position = skip_whitespace(bytes, position);
+
+                if bytes.get(position) == Some(&b']') {
+                    return Ok(position + 1);
+                }
+
+                position = skip_object(bytes, position)?;
+            }
+        }
+        Some(b'/') => Ok(position + 1 + regular_token_length(&bytes[position + 1..])),
+        Some(_) => match regular_token_length(&bytes[position..]) {
+            0 => Err(error),
+            length => Ok(position + length),
+        },
+    }
+}
+
+/// Returns the position immediately following the single value starting at the given
+/// position, treating an indirect reference of the form `n g R` as a single value.
+fn skip_value(bytes: &[u8], position: usize) -> Result<usize, PdfiumError> {
+    let end = skip_object(bytes, position)?;
+
+    if !is_integer(&bytes[position..end]) {
+        return Ok(end);
+    }
+
+    let generation_start = skip_whitespace(bytes, end);
+
+    if let Ok(generation_end) = skip_object(bytes, generation_start) {
+        if is_integer(&bytes[generation_start..generation_end]) {
+            let keyword_start = skip_whitespace(bytes, generation_end);
+
+            if let Ok(keyword_end) = skip_object(bytes, keyword_start) {
+                if &bytes[keyword_start..keyword_end] == b"R" {
+                    return Ok(keyword_end);
+                }
+            }
+        }
+    }
+
+    Ok(end)
+}
+
+#[inline]
+fn regular_token_length(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take_while(|byte| !is_whitespace(**byte) && !is_delimiter(**byte))
+        .count()
+}
+
+#[inline]
+fn is_integer(bytes: &[u8]) -> bool {
+    !bytes.is_empty() && bytes.iter().all(u8::is_ascii_digit)
+}
+
+/// Reads the unsigned integer at or after the given position, advancing the position
+/// past it.
+fn read_integer(bytes: &[u8], position: &mut usize) -> Result<usize, PdfiumError> {
+    let start = skip_whitespace(bytes, *position);
+
+    let end = skip_object(bytes, start)?;
+
+    let value = std::str::from_utf8(&bytes[start..end])
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+    *position = end;
+
+    Ok(value)
+}
+
+#[inline]
+pub(crate) fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[inline]
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .rposition(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_file() -> Vec<u8> {
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+
+        let catalog_offset = bytes.len();
+
+        bytes.extend_from_slice(b"1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+
+        let pages_offset = bytes.len();
+
+        bytes.extend_from_slice(
+            b"2 0 obj\n<</Type/Pages/Kids[]/Count 0/Title (a \\) b) % comment\n>>\nendobj\n",
+        );
+
+        let xref_offset = bytes.len();
+
+        write!(
+            bytes,
+            "xref\n0 3\n0000000000 65535 f\r\n{catalog_offset:010} 00000 n\r\n\
+            {pages_offset:010} 00000 n\r\ntrailer\n<< /Size 3 /Root 1 0 R >>\n\
+            startxref\n{xref_offset}\n%%EOF\n"
+        )
+        .unwrap();
+
+        bytes
+    }
+
+    #[test]
+    fn test_read_objects() -> Result<(), PdfiumError> {
+        let update = PdfIncrementalUpdate::new(test_file())?;
+
+        let root = update.root()?;
+
+        assert_eq!(
+            root,
+            PdfObjectId {
+                number: 1,
+                generation: 0
+            }
+        );
+
+        let catalog = update.get_dictionary(root)?;
+
+        assert_eq!(catalog.get("Type"), Some(b"/Catalog".as_slice()));
+        assert_eq!(catalog.get_reference("Pages").map(|id| id.number), Some(2));
+
+        let pages = update.get_dictionary(catalog.get_reference("Pages").unwrap())?;
+
+        assert_eq!(pages.get("Kids"), Some(b"[]".as_slice()));
+        assert_eq!(pages.get("Count"), Some(b"0".as_slice()));
+        assert_eq!(pages.get("Title"), Some(b"(a \\) b)".as_slice()));
+
+        assert_eq!(update.find_object_containing(b"/Kids")?.number, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_update() -> Result<(), PdfiumError> {
+        let original = test_file();
+
+        let mut update = PdfIncrementalUpdate::new(original.clone())?;
+
+        let root = update.root()?;
+
+        let added = update.add_object(b"<< /Lang (en-US) >>".to_vec());
+
+        assert_eq!(added.number, 3);
+
+        let stream = update.add_object(b"<< /Length 5 >>\nstream\r\nhello\nendstream".to_vec());
+
+        let mut catalog = update.get_dictionary(root)?;
+
+        catalog.set("Extra", added.to_reference());
+        catalog.remove("Type");
+
+        update.set_object(root, catalog.to_bytes());
+
+        let (bytes, offsets) = update.write()?;
+
+        assert!(bytes.starts_with(&original));
+        assert!(bytes[offsets[&added.number]..].starts_with(b"<< /Lang (en-US) >>"));
+
+        let reread = PdfIncrementalUpdate::new(bytes)?;
+
+        let catalog = reread.get_dictionary(reread.root()?)?;
+
+        assert_eq!(catalog.get_reference("Extra"), Some(added));
+        assert!(!catalog.contains_key("Type"));
+        assert_eq!(
+            reread.get_dictionary(added)?.get("Lang"),
+            Some(b"(en-US)".as_slice())
+        );
+        assert_eq!(reread.get_stream_data(stream)?, b"hello".to_vec());
+        assert_eq!(reread.size, 5);
+
+        Ok(())
+    }
+
+    /// Returns a file whose cross-reference section is a compressed stream using the PNG Up
+    /// predictor, with the page tree stored in a compressed object stream.
+    fn test_xref_stream_file() -> Vec<u8> {
+        use miniz_oxide::deflate::compress_to_vec_zlib;
+
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+
+        let catalog_offset = bytes.len();
+
+        bytes.extend_from_slice(b"1 0 obj\n<< /Type /Catalog /Pages 2 0 R >>\nendobj\n");
+
+        let object_stream_offset = bytes.len();
+
+        let objects = compress_to_vec_zlib(b"2 0 << /Type /Pages /Kids [] /Count 0 >>", 6);
+
+        write!(
+            bytes,
+            "3 0 obj\n<< /Type /ObjStm /N 1 /First 4 /Filter /FlateDecode /Length {} >>\nstream\n",
+            objects.len()
+        )
+        .unwrap();
+
+        bytes.extend_from_slice(&objects);
+        bytes.extend_from_slice(b"\nendstream\nendobj\n");
+
+        let xref_offset = bytes.len();
+
+        let rows = [
+            [0, 0, 0, 0, 255],
+            [1, 0, 0, catalog_offset as u8, 0],
+            [2, 0, 0, 3, 0],
+            [1, 0, 0, object_stream_offset as u8, 0],
+            [1, 0, 0, xref_offset as u8, 0],
+        ];
+
+        let mut data = Vec::new();
+
+        let mut previous = [0u8; 5];
+
+        for row in rows.iter() {
+            data.push(2);
+            data.extend(
+                row.iter()
+                    .zip(previous.iter())
+                    .map(|(a, b)| a.wrapping_sub(*b)),
+            );
+
+            previous = *row;
+        }
+
+        let data = compress_to_vec_zlib(&data, 6);
+
+        write!(
+            bytes,
+            "4 0 obj\n<< /Type /XRef /Size 5 /Root 1 0 R /W [1 3 1] /Filter /FlateDecode \
+            /DecodeParms << /Columns 5 /Predictor 12 >> /Length {} >>\nstream\n",
+            data.len()
+        )
+        .unwrap();
+
+        bytes.extend_from_slice(&data);
+
+        write!(
+            bytes,
+            "\nendstream\nendobj\nstartxref\n{xref_offset}\n%%EOF\n"
+        )
+        .unwrap();
+
+        bytes
+    }
+
+    #[test]
+    fn test_xref_streams() -> Result<(), PdfiumError> {
+        let original = test_xref_stream_file();
+
+        let mut update = PdfIncrementalUpdate::new(original.clone())?;
+
+        let root = update.root()?;
+
+        assert_eq!(root.number, 1);
+        assert!(!update.trailer().contains_key("W"));
+
+        let pages = update.get_dictionary(PdfObjectId {
+            number: 2,
+            generation: 0,
+        })?;
+
+        assert_eq!(pages.get("Count"), Some(b"0".as_slice()));
+        assert_eq!(update.find_object_containing(b"/Count 0")?.number, 2);
+        assert!(update
+            .get_stream_data(PdfObjectId {
+                number: 3,
+                generation: 0,
+            })?
+            .starts_with(b"2 0 <<"));
+
+        let added = update.add_object(b"<< /Lang (en-US) >>".to_vec());
+
+        let mut catalog = update.get_dictionary(root)?;
+
+        catalog.set("Extra", added.to_reference());
+
+        update.set_object(root, catalog.to_bytes());
+
+        let (bytes, _) = update.write()?;
+
+        assert!(bytes.starts_with(&original));
+
+        // The update is written as an uncompressed cross-reference stream of its own.
+
+        let reread = PdfIncrementalUpdate::new(bytes)?;
+
+        assert!(reread.is_xref_stream);
+        assert_eq!(reread.size, 7);
+        assert_eq!(
+            reread
+                .get_dictionary(reread.root()?)?
+                .get_reference("Extra"),
+            Some(added)
+        );
+        assert_eq!(
+            reread
+                .get_dictionary(PdfObjectId {
+                    number: 2,
+                    generation: 0,
+                })?
+                .get("Type"),
+            Some(b"/Pages".as_slice())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_encrypted_file() {
+        let bytes = String::from_utf8(test_file())
+            .unwrap()
+            .replace("/Size 3", "/Size 3 /Encrypt 9 0 R")
+            .into_bytes();
+
+        assert!(matches!(
+            PdfIncrementalUpdate::new(bytes),
+            Err(PdfiumError::EncryptedDocumentUpdateNotSupported)
+        ));
+    }
+
+    #[test]
+    fn test_encode_strings() {
+        assert_eq!(
+            encode_text_string("a (b) \\c"),
+            b"(a \\(b\\) \\\\c)".to_vec()
+        );
+        assert_eq!(encode_text_string("é"), b"<FEFF00E9>".to_vec());
+        assert_eq!(
+            encode_name("adbe.pkcs7.detached"),
+            b"/adbe.pkcs7.detached".to_vec()
+        );
+        assert_eq!(encode_name("A B#"), b"/A#20B#23".to_vec());
+    }
+
+    #[test]
+    fn test_decode_strings() -> Result<(), PdfiumError> {
+        assert_eq!(
+            decode_text_string(b"(a \\(b\\) \\\\c\\351)"),
+            Some("a (b) \\cé".to_string())
+        );
+        assert_eq!(
+            decode_text_string(b"<FEFF00E9 0041>"),
+            Some("éA".to_string())
+        );
+        assert_eq!(decode_text_string(b"/Yes"), None);
+        assert_eq!(decode_name(b" /A#20B#23"), Some("A B#".to_string()));
+        assert_eq!(
+            parse_array(b"[ (a) /B 3 0 R [1 2] ]")?,
+            vec![
+                b"(a)".as_slice(),
+                b"/B".as_slice(),
+                b"3 0 R".as_slice(),
+                b"[1 2]".as_slice()
+            ]
+        );
+        assert_eq!(
+            find_indirect_object(
+                b"11 0 obj << /A 1 >> endobj 1 0 obj << /B 2 >> endobj",
+                PdfObjectId {
+                    number: 1,
+                    generation: 0
+                }
+            ),
+            Some(b"<< /B 2 >>".as_slice())
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,756 @@
+//! Defines the [PdfJavaScriptActions] struct, a collection of all the scripts carried by
+//! JavaScript actions in a `PdfDocument`.
+
+use crate::bindgen::{FPDF_DOCUMENT, FPDF_WCHAR};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::{
+    decode_name, decode_text, decode_text_string, parse_array, trim, PdfIncrementalUpdate,
+    PdfObjectId, PdfRawDictionary,
+};
+use crate::pdf::document::page::annotations::PdfPageAnnotationIndex;
+use crate::pdf::document::page::field::choice_appearance::resolve;
+use crate::pdf::document::pages::PdfPageIndex;
+use crate::utils::mem::create_byte_buffer;
+use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use std::collections::HashSet;
+use std::os::raw::{c_int, c_ulong};
+use std::ptr::null_mut;
+
+#[cfg(doc)]
+use crate::pdf::{action::unsupported::PdfActionUnsupported, document::PdfDocument};
+
+/// The zero-based index of a single [PdfJavaScript] inside its containing
+/// [PdfJavaScriptActions] collection.
+pub type PdfJavaScriptActionIndex = usize;
+
+/// The part of a [PdfDocument] that carries a [PdfJavaScript] script.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PdfJavaScriptSource {
+    /// A document-level script in the `/JavaScript` name tree of the document's `/Names`
+    /// dictionary. A PDF viewer runs these scripts when the document is opened.
+    NameTree,
+
+    /// The action run when the document is opened, set by the `/OpenAction` entry
+    /// of the document catalog.
+    OpenAction,
+
+    /// An additional action of the document, run before the document is closed, saved,
+    /// or printed.
+    DocumentAdditionalAction,
+
+    /// An additional action of the page with the given zero-based index, run when the page
+    /// is opened or closed.
+    PageAdditionalAction(PdfPageIndex),
+
+    /// The action, or one of the additional actions, of the annotation with the given
+    /// zero-based index on the page with the given zero-based index. Links and the widgets
+    /// of form fields are both annotations.
+    Annotation(PdfPageIndex, PdfPageAnnotationIndex),
+
+    /// An additional action of the form field with the given fully qualified name, where
+    /// the field is not itself a widget annotation on a page.
+    FormField(String),
+
+    /// The action of the bookmark with the given title.
+    Bookmark(String),
+}
+
+/// A single script carried by a JavaScript action in a [PdfDocument].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PdfJavaScript {
+    name: Option<String>,
+    script: String,
+    source: PdfJavaScriptSource,
+}
+
+impl PdfJavaScript {
+    /// Returns the name of this [PdfJavaScript], if any.
+    ///
+    /// Only document-level scripts, keyed by name in the `/JavaScript` name tree of the
+    /// document's `/Names` dictionary, are named.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the JavaScript source code of this [PdfJavaScript].
+    #[inline]
+    pub fn script(&self) -> &str {
+        self.script.as_str()
+    }
+
+    /// Returns the part of the document that carries this [PdfJavaScript].
+    #[inline]
+    pub fn source(&self) -> &PdfJavaScriptSource {
+        &self.source
+    }
+}
+
+/// The collection of all the scripts carried by JavaScript actions in a [PdfDocument].
+///
+/// Document-level scripts are read using Pdfium. Pdfium does not expose any other scripts,
+/// so the remaining scripts are read from a copy of the document saved to memory. The collection
+/// includes document-level scripts, the document's open action and additional actions, the
+/// additional actions of every page, the actions and additional actions of every annotation
+/// (including links and form field widgets), the additional actions of form fields, and the
+/// actions of bookmarks. Actions chained to another action through its `/Next` entry are
+/// included, listed with the same [PdfJavaScriptSource] as the first action in the chain.
+///
+/// To check whether the action of an individual link or bookmark is a JavaScript action,
+/// use the [PdfActionUnsupported::to_javascript_action()] function instead.
+pub struct PdfJavaScriptActions {
+    scripts: Vec<PdfJavaScript>,
+}
+
+impl PdfJavaScriptActions {
+    /// Collects the document-level scripts of the document with the given handle, followed
+    /// by the scripts of all the other JavaScript actions in the given saved copy of the document.
+    pub(crate) fn from_pdfium(
+        document_handle: FPDF_DOCUMENT,
+        update: &PdfIncrementalUpdate,
+        bindings: &dyn PdfiumLibraryBindings,
+    ) -> Result<Self, PdfiumError> {
+        let mut collector = PdfJavaScriptCollector {
+            update,
+            scripts: document_scripts(document_handle, bindings),
+        };
+
+        collector.collect()?;
+
+        Ok(PdfJavaScriptActions {
+            scripts: collector.scripts,
+        })
+    }
+
+    /// Returns the number of scripts in this [PdfJavaScriptActions] collection.
+    #[inline]
+    pub fn len(&self) -> PdfJavaScriptActionIndex {
+        self.scripts.len()
+    }
+
+    /// Returns `true` if this [PdfJavaScriptActions] collection is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.scripts.is_empty()
+    }
+
+    /// Returns a single [PdfJavaScript] from this [PdfJavaScriptActions] collection.
+    #[inline]
+    pub fn get(&self, index: PdfJavaScriptActionIndex) -> Result<&PdfJavaScript, PdfiumError> {
+        self.scripts
+            .get(index)
+            .ok_or(PdfiumError::JavaScriptActionIndexOutOfBounds)
+    }
+
+    /// Returns an iterator over all the scripts in this [PdfJavaScriptActions] collection.
+    #[inline]
+    pub fn iter(&self) -> PdfJavaScriptActionsIterator<'_> {
+        PdfJavaScriptActionsIterator::new(self)
+    }
+}
+
+/// An iterator over all the [PdfJavaScript] scripts in a [PdfJavaScriptActions] collection.
+pub struct PdfJavaScriptActionsIterator<'a> {
+    actions: &'a PdfJavaScriptActions,
+    next_index: PdfJavaScriptActionIndex,
+}
+
+impl<'a> PdfJavaScriptActionsIterator<'a> {
+    #[inline]
+    pub(crate) fn new(actions: &'a PdfJavaScriptActions) -> Self {
+        PdfJavaScriptActionsIterator {
+            actions,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfJavaScriptActionsIterator<'a> {
+    type Item = &'a PdfJavaScript;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.actions.get(self.next_index);
+
+        self.next_index += 1;
+
+        next.ok()
+    }
+}
+
+/// Limits the depth of any walk through the given saved document, in case the walk
+/// encounters a cycle.
+const MAX_DEPTH: usize = 32;
+
+/// Walks a saved document, collecting the scripts of all its JavaScript actions.
+struct PdfJavaScriptCollector<'a> {
+    update: &'a PdfIncrementalUpdate,
+    scripts: Vec<PdfJavaScript>,
+}
+
+impl<'a> PdfJavaScriptCollector<'a> {
+    fn collect(&mut self) -> Result<(), PdfiumError> {
+        let update = self.update;
+
+        let catalog = update.get_dictionary(update.root()?)?;
+
+        // Document-level scripts in the /JavaScript name tree are read using Pdfium.
+
+        if let Some(action) = catalog.get("OpenAction") {
+            // The open action may also be a destination array, which is not an action.
+
+            if trim(&resolve(update, action)).starts_with(b"<<") {
+                self.collect_action(action, None, &PdfJavaScriptSource::OpenAction)?;
+            }
+        }
+
+        if let Some(actions) = catalog.get("AA") {
+            self.collect_additional_actions(
+                actions,
+                &PdfJavaScriptSource::DocumentAdditionalAction,
+            )?;
+        }
+
+        // Annotations are often also form fields. Their additional actions are listed with
+        // the page they belong to, rather than again with the form fields.
+
+        let mut annotations = HashSet::new();
+
+        for (page_index, page) in page_dictionaries(update)?.iter().enumerate() {
+            let page_index = page_index as PdfPageIndex;
+
+            if let Some(actions) = page.get("AA") {
+                self.collect_additional_actions(
+                    actions,
+                    &PdfJavaScriptSource::PageAdditionalAction(page_index),
+                )?;
+            }
+
+            let elements = match page.get("Annots") {
+                Some(elements) => resolve(update, elements),
+                None => continue,
+            };
+
+            for (annotation_index, element) in parse_array(&elements)
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+            {
+                if let Some(id) = PdfObjectId::from_reference(element) {
+                    annotations.insert(id);
+                }
+
+                let annotation = match resolve_dictionary(update, element) {
+                    Some(annotation) => annotation,
+                    None => continue,
+                };
+
+                let source = PdfJavaScriptSource::Annotation(page_index, annotation_index);
+
+                if let Some(action) = annotation.get("A") {
+                    self.collect_action(action, None, &source)?;
+                }
+
+                if let Some(actions) = annotation.get("AA") {
+                    self.collect_additional_actions(actions, &source)?;
+                }
+            }
+        }
+
+        if let Some(form) = catalog
+            .get("AcroForm")
+            .and_then(|form| resolve_dictionary(update, form))
+        {
+            if let Some(fields) = form.get("Fields") {
+                self.collect_fields(&resolve(update, fields), "", &annotations, 0)?;
+            }
+        }
+
+        if let Some(outlines) = catalog
+            .get("Outlines")
+            .and_then(|outlines| resolve_dictionary(update, outlines))
+        {
+            if let Some(first) = outlines.get("First") {
+                self.collect_bookmarks(first, 0)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects the scripts of the given form fields, and of their descendants, skipping
+    /// fields that are also the given annotations.
+    fn collect_fields(
+        &mut self,
+        fields: &[u8],
+        parent_name: &str,
+        annotations: &HashSet<PdfObjectId>,
+        depth: usize,
+    ) -> Result<(), PdfiumError> {
+        if depth > MAX_DEPTH {
+            return Ok(());
+        }
+
+        for element in parse_array(fields).unwrap_or_default() {
+            let field = match resolve_dictionary(self.update, element) {
+                Some(field) => field,
+                None => continue,
+            };
+
+            // A field without a partial name shares the name of its parent.
+
+            let name = match field.get("T").and_then(decode_text_string) {
+                Some(partial_name) if parent_name.is_empty() => partial_name,
+                Some(partial_name) => format!("{}.{}", parent_name, partial_name),
+                None => parent_name.to_string(),
+            };
+
+            let is_annotation = PdfObjectId::from_reference(element)
+                .map(|id| annotations.contains(&id))
+                .unwrap_or(false);
+
+            if !is_annotation {
+                if let Some(actions) = field.get("AA") {
+                    self.collect_additional_actions(
+                        actions,
+                        &PdfJavaScriptSource::FormField(name.clone()),
+                    )?;
+                }
+            }
+
+            if let Some(kids) = field.get("Kids") {
+                self.collect_fields(&resolve(self.update, kids), &name, annotations, depth + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects the scripts of the actions of the given bookmark, its following siblings,
+    /// and their descendants.
+    fn collect_bookmarks(&mut self, first: &[u8], depth: usize) -> Result<(), PdfiumError> {
+        if depth > MAX_DEPTH {
+            return Ok(());
+        }
+
+        let mut visited = HashSet::new();
+
+        let mut next = first.to_vec();
+
+        while let Some(id) = PdfObjectId::from_reference(&next) {
+            if !visited.insert(id) {
+                break;
+            }
+
+            let bookmark = match self.update.get_dictionary(id) {
+                Ok(bookmark) => bookmark,
+                Err(_) => break,
+            };
+
+            if let Some(action) = bookmark.get("A") {
+                let title = bookmark
+                    .get("Title")
+                    .and_then(|title| decode_text_string(&resolve(self.update, title)))
+                    .unwrap_or_default();
+
+                self.collect_action(action, None, &PdfJavaScriptSource::Bookmark(title))?;
+            }
+
+            if let Some(first) = bookmark.get("First") {
+                self.collect_bookmarks(first, depth + 1)?;
+            }
+
+            next = match bookmark.get("Next") {
+                Some(next) => next.to_vec(),
+                None => break,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Collects the scripts of the actions in the given additional-actions dictionary.
+    fn collect_additional_actions(
+        &mut self,
+        actions: &[u8],
+        source: &PdfJavaScriptSource,
+    ) -> Result<(), PdfiumError> {
+        let actions = match resolve_dictionary(self.update, actions) {
+            Some(actions) => actions,
+            None => return Ok(()),
+        };
+
+        for key in actions
+            .keys()
+            .filter_map(|key| std::str::from_utf8(key).ok())
+        {
+            if let Some(action) = actions.get(key) {
+                self.collect_action(action, None, source)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Collects the script of the given action, if it is a JavaScript action, followed by
+    /// the scripts of the actions chained to it.
+    fn collect_action(
+        &mut self,
+        action: &[u8],
+        name: Option<String>,
+        source: &PdfJavaScriptSource,
+    ) -> Result<(), PdfiumError> {
+        self.collect_action_chain(action, name, source, &mut HashSet::new(), 0)
+    }
+
+    fn collect_action_chain(
+        &mut self,
+        action: &[u8],
+        name: Option<String>,
+        source: &PdfJavaScriptSource,
+        visited: &mut HashSet<PdfObjectId>,
+        depth: usize,
+    ) -> Result<(), PdfiumError> {
+        if depth > MAX_DEPTH {
+            return Ok(());
+        }
+
+        if let Some(id) = PdfObjectId::from_reference(action) {
+            if !visited.insert(id) {
+                return Ok(());
+            }
+        }
+
+        let action = match resolve_dictionary(self.update, action) {
+            Some(action) => action,
+            None => return Ok(()),
+        };
+
+        if let Some(script) = action_script(self.update, &action)? {
+            self.scripts.push(PdfJavaScript {
+                name,
+                script,
+                source: source.clone(),
+            });
+        }
+
+        // The next action may be a single action or an array of actions.
+
+        if let Some(next) = action.get("Next") {
+            let resolved = resolve(self.update, next);
+
+            match parse_array(&resolved) {
+                Ok(actions) => {
+                    for action in actions {
+                        self.collect_action_chain(action, None, source, visited, depth + 1)?;
+                    }
+                }
+                Err(_) => self.collect_action_chain(next, None, source, visited, depth + 1)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the document-level scripts of the document with the given handle, in the order
+/// of the `/JavaScript` name tree of the document's `/Names` dictionary.
+fn document_scripts(
+    document_handle: FPDF_DOCUMENT,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Vec<PdfJavaScript> {
+    let mut result = Vec::new();
+
+    // FPDFDoc_GetJavaScriptActionCount() returns -1 on error.
+
+    for index in 0..bindings.FPDFDoc_GetJavaScriptActionCount(document_handle) {
+        // Pdfium returns no action for a name tree entry that is not a valid JavaScript
+        // action, so a failure at one index does not mean there are no further scripts.
+
+        let handle = bindings.FPDFDoc_GetJavaScriptAction(document_handle, index as c_int);
+
+        if handle.is_null() {
+            continue;
+        }
+
+        let name = read_string(|buffer, buffer_length| {
+            bindings.FPDFJavaScriptAction_GetName(handle, buffer, buffer_length)
+        })
+        .filter(|name| !name.is_empty());
+
+        let script = read_string(|buffer, buffer_length| {
+            bindings.FPDFJavaScriptAction_GetScript(handle, buffer, buffer_length)
+        })
+        .unwrap_or_default();
+
+        bindings.FPDFDoc_CloseJavaScriptAction(handle);
+
+        result.push(PdfJavaScript {
+            name,
+            script,
+            source: PdfJavaScriptSource::NameTree,
+        });
+    }
+
+    result
+}
+
+/// Reads a UTF16-LE string from Pdfium using the given string accessor function.
+fn read_string(mut accessor: impl FnMut(*mut FPDF_WCHAR, c_ulong) -> c_ulong) -> Option<String> {
+    // Retrieving the string from Pdfium is a two-step operation. First, we call
+    // the accessor with a null buffer; this will retrieve the length of the string
+    // in bytes. If the length is zero, then the string is not available.
+
+    // If the length is non-zero, then we reserve a byte buffer of the given
+    // length and call the accessor again with a pointer to the buffer;
+    // this will write the string to the buffer in UTF16-LE format.
+
+    let buffer_length = accessor(null_mut(), 0);
+
+    if buffer_length == 0 {
+        return None;
+    }
+
+    let mut buffer = create_byte_buffer(buffer_length as usize);
+
+    let result = accessor(buffer.as_mut_ptr() as *mut FPDF_WCHAR, buffer_length);
+
+    debug_assert_eq!(result, buffer_length);
+
+    get_string_from_pdfium_utf16le_bytes(buffer)
+}
+
+/// Returns the script of the given action dictionary from the given saved document,
+/// or `None` if the action is not a JavaScript action. The script may be held in
+/// a text string or a text stream.
+pub(crate) fn action_script(
+    update: &PdfIncrementalUpdate,
+    action: &PdfRawDictionary,
+) -> Result<Option<String>, PdfiumError> {
+    if action.get("S").and_then(decode_name).as_deref() != Some("JavaScript") {
+        return Ok(None);
+    }
+
+    let script = match action.get("JS") {
+        Some(script) => script,
+        None => return Ok(None),
+    };
+
+    match PdfObjectId::from_reference(script) {
+        Some(id) if trim(update.get_object(id)?).starts_with(b"<<") => {
+            Ok(Some(decode_text(&update.get_stream_data(id)?)))
+        }
+        _ => Ok(decode_text_string(&resolve(update, script))),
+    }
+}
+
+/// Returns the dictionary held by the given value from the given saved document, following
+/// the value if it is an indirect reference.
+pub(crate) fn resolve_dictionary(
+    update: &PdfIncrementalUpdate,
+    value: &[u8],
+) -> Option<PdfRawDictionary> {
+    PdfRawDictionary::parse(&resolve(update, value)).ok()
+}
+
+/// Returns the dictionaries of all the pages in the given saved document, in page order.
+pub(crate) fn page_dictionaries(
+    update: &PdfIncrementalUpdate,
+) -> Result<Vec<PdfRawDictionary>, PdfiumError> {
+    let catalog = update.get_dictionary(update.root()?)?;
+
+    let mut result = Vec::new();
+
+    if let Some(pages) = catalog.get("Pages") {
+        collect_pages(update, pages, &mut result, 0);
+    }
+
+    Ok(result)
+}
+
+/// Adds the dictionaries of the pages in the given node of a page tree to the given list.
+fn collect_pages(
+    update: &PdfIncrementalUpdate,
+    node: &[u8],
+    result: &mut Vec<PdfRawDictionary>,
+    depth: usize,
+) {
+    let node = match resolve_dictionary(update, node) {
+        Some(node) if depth <= MAX_DEPTH => node,
+        _ => return,
+    };
+
+    match node.get("Kids") {
+        Some(kids) => {
+            for kid in parse_array(&resolve(update, kids)).unwrap_or_default() {
+                collect_pages(update, kid, result, depth + 1);
+            }
+        }
+        None => result.push(node),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_javascript_actions() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/form-test.pdf", None)?;
+
+        let actions = document.javascript_actions()?;
+
+        // The test file carries three document-level scripts, each held in a compressed
+        // stream, that check the version of the viewer.
+
+        let scripts = actions
+            .iter()
+            .filter(|action| action.source() == &PdfJavaScriptSource::NameTree)
+            .map(|action| (action.name().unwrap_or_default(), action.script()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(scripts.len(), 3);
+
+        assert_eq!(scripts[0].0, "!ADBE::0100_VersChkStrings");
+        assert!(scripts[0].1.contains("ADBE.LANGUAGE = \"ENU\";"));
+
+        assert_eq!(scripts[1].0, "!ADBE::0100_VersChkVars");
+        assert!(scripts[1].1.contains("ADBE.Reader_Value_Asked = false;"));
+
+        assert_eq!(scripts[2].0, "!ADBE::0200_VersChkCode_XFACheck");
+        assert!(scripts[2].1.contains("typeof(xfa_installed)"));
+
+        assert!(actions.get(actions.len()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_javascript_actions_from_every_source() -> Result<(), PdfiumError> {
+        use std::io::Write;
+
+        let pdfium = test_bind_to_pdfium();
+
+        let objects = [
+            "<< /Type /Catalog /Pages 2 0 R /Names << /JavaScript << /Names [(init) 6 0 R] >> >> \
+            /OpenAction << /S /JavaScript /JS (open();) /Next [<< /S /JavaScript /JS 7 0 R >>] >> \
+            /AA << /WC << /S /JavaScript /JS (close();) >> >> /Outlines 8 0 R \
+            /AcroForm << /Fields [4 0 R 10 0 R] >> >>",
+            "<< /Type /Pages /Kids [3 0 R] /Count 1 >>",
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 200 200] /Annots [5 0 R 4 0 R] \
+            /AA << /O << /S /JavaScript /JS (pageOpen();) >> >> >>",
+            "<< /Type /Annot /Subtype /Widget /FT /Tx /T (name) /Rect [10 10 90 30] \
+            /A << /S /JavaScript /JS (widget();) >> /AA << /K << /S /JavaScript /JS (key();) >> >> >>",
+            "<< /Type /Annot /Subtype /Link /Rect [10 100 90 120] \
+            /A << /S /JavaScript /JS <FEFF006C0069006E006B00280029003B> >> >>",
+            "<< /S /JavaScript /JS (init();) >>",
+            "<< /Length 8 >>\nstream\nchain();\nendstream",
+            "<< /Type /Outlines /First 9 0 R /Last 9 0 R /Count 1 >>",
+            "<< /Title (Run) /Parent 8 0 R /A << /S /JavaScript /JS (bookmark();) >> >>",
+            "<< /FT /Tx /T (total) /AA << /C << /S /JavaScript /JS (calculate();) >> >> >>",
+        ];
+
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+
+        let mut offsets = Vec::new();
+
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(bytes.len());
+
+            write!(bytes, "{} 0 obj\n{}\nendobj\n", index + 1, object).unwrap();
+        }
+
+        let xref_offset = bytes.len();
+
+        write!(
+            bytes,
+            "xref\n0 {}\n0000000000 65535 f\r\n",
+            objects.len() + 1
+        )
+        .unwrap();
+
+        for offset in offsets {
+            write!(bytes, "{offset:010} 00000 n\r\n").unwrap();
+        }
+
+        write!(
+            bytes,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .unwrap();
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        let actions = document.javascript_actions()?;
+
+        let scripts = actions
+            .iter()
+            .map(|action| (action.source().clone(), action.script()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            scripts,
+            vec![
+                (PdfJavaScriptSource::NameTree, "init();"),
+                (PdfJavaScriptSource::OpenAction, "open();"),
+                (PdfJavaScriptSource::OpenAction, "chain();"),
+                (PdfJavaScriptSource::DocumentAdditionalAction, "close();"),
+                (PdfJavaScriptSource::PageAdditionalAction(0), "pageOpen();"),
+                (PdfJavaScriptSource::Annotation(0, 0), "link();"),
+                (PdfJavaScriptSource::Annotation(0, 1), "widget();"),
+                (PdfJavaScriptSource::Annotation(0, 1), "key();"),
+                (
+                    PdfJavaScriptSource::FormField("total".to_string()),
+                    "calculate();"
+                ),
+                (
+                    PdfJavaScriptSource::Bookmark("Run".to_string()),
+                    "bookmark();"
+                ),
+            ]
+        );
+
+        assert_eq!(actions.get(0)?.name(), Some("init"));
+
+        let page = document.pages().get(0)?;
+
+        let key_stroke = page.annotations().iter().find_map(|annotation| {
+            annotation
+                .as_form_field()
+                .and_then(|field| field.javascript(PdfFormFieldActionEvent::KeyStroke))
+        });
+
+        assert_eq!(key_stroke, Some("key();".to_string()));
+
+        // Pdfium reports the actions of links and bookmarks as unsupported, but they can
+        // be checked for scripts on demand.
+
+        let script = |action: PdfAction| -> Option<String> {
+            assert_eq!(action.action_type(), PdfActionType::Unsupported);
+
+            match action {
+                PdfAction::Unsupported(action) => action
+                    .to_javascript_action()
+                    .map(|action| action.script().to_string()),
+                _ => None,
+            }
+        };
+
+        let link = page.links().iter().next().unwrap();
+
+        assert_eq!(script(link.action().unwrap()), Some("link();".to_string()));
+
+        let bookmark = document.bookmarks().root().unwrap();
+
+        assert_eq!(
+            script(bookmark.action().unwrap()),
+            Some("bookmark();".to_string())
+        );
+
+        Ok(())
+    }
+}
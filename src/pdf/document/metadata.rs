@@ -2,8 +2,15 @@
 
 use crate::bindgen::FPDF_DOCUMENT;
 use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::{encode_text_string, PdfIncrementalUpdate};
+use crate::pdf::document::xmp::{
+    generate_xmp, get_tag_from_xmp, read_xmp_metadata, set_tag_in_xmp, write_xmp_metadata,
+};
+use crate::utils::dates::date_time_to_pdf_string;
 use crate::utils::mem::create_byte_buffer;
 use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use chrono::{DateTime, Utc};
 use std::os::raw::c_void;
 use std::slice::Iter;
 
@@ -23,6 +30,35 @@ pub enum PdfDocumentMetadataTagType {
     ModificationDate,
 }
 
+impl PdfDocumentMetadataTagType {
+    /// All metadata tag types, in the order in which they are listed in a [PdfMetadata] collection.
+    const ALL: [PdfDocumentMetadataTagType; 8] = [
+        PdfDocumentMetadataTagType::Title,
+        PdfDocumentMetadataTagType::Author,
+        PdfDocumentMetadataTagType::Subject,
+        PdfDocumentMetadataTagType::Keywords,
+        PdfDocumentMetadataTagType::Creator,
+        PdfDocumentMetadataTagType::Producer,
+        PdfDocumentMetadataTagType::CreationDate,
+        PdfDocumentMetadataTagType::ModificationDate,
+    ];
+
+    /// Returns the key of this tag type in the document information dictionary.
+    #[inline]
+    pub(crate) fn as_key(&self) -> &'static str {
+        match self {
+            PdfDocumentMetadataTagType::Title => "Title",
+            PdfDocumentMetadataTagType::Author => "Author",
+            PdfDocumentMetadataTagType::Subject => "Subject",
+            PdfDocumentMetadataTagType::Keywords => "Keywords",
+            PdfDocumentMetadataTagType::Creator => "Creator",
+            PdfDocumentMetadataTagType::Producer => "Producer",
+            PdfDocumentMetadataTagType::CreationDate => "CreationDate",
+            PdfDocumentMetadataTagType::ModificationDate => "ModDate",
+        }
+    }
+}
+
 /// A single metadata tag in a [PdfDocument].
 #[derive(Debug, Clone, PartialEq)]
 pub struct PdfDocumentMetadataTag {
@@ -50,10 +86,25 @@ impl PdfDocumentMetadataTag {
 }
 
 /// A collection of all the metadata tags in a [PdfDocument].
+///
+/// Metadata tags are stored in the document information dictionary referenced by
+/// the `/Info` entry of the document's trailer. Pdfium cannot edit this dictionary directly,
+/// so tags changed using the [PdfMetadata::set()] and [PdfMetadata::remove()] functions are
+/// held by this collection and written to the document as an incremental update each time
+/// the document is saved. Since the update is appended to the bytes written by Pdfium,
+/// a document with changed metadata cannot be saved in encrypted form; save it using
+/// `PdfSaveMode::RemoveSecurity` instead.
+///
+/// The same applies to an XMP metadata stream set using the [PdfDocument::set_xmp_metadata()]
+/// function, which is also held by this collection until the document is saved. When the
+/// document is saved, tags changed using this collection are also written to the
+/// corresponding properties of the document's XMP metadata, so that the two stay consistent.
 pub struct PdfMetadata<'a> {
     document_handle: FPDF_DOCUMENT,
     bindings: &'a dyn PdfiumLibraryBindings,
     tags: Vec<PdfDocumentMetadataTag>,
+    xmp: Option<String>,
+    is_modified: bool,
 }
 
 impl<'a> PdfMetadata<'a> {
@@ -65,38 +116,14 @@ impl<'a> PdfMetadata<'a> {
             document_handle,
             bindings,
             tags: vec![],
+            xmp: None,
+            is_modified: false,
         };
 
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::Title) {
-            result.tags.push(tag);
-        }
-
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::Author) {
-            result.tags.push(tag);
-        }
-
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::Subject) {
-            result.tags.push(tag);
-        }
-
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::Keywords) {
-            result.tags.push(tag);
-        }
-
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::Creator) {
-            result.tags.push(tag);
-        }
-
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::Producer) {
-            result.tags.push(tag);
-        }
-
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::CreationDate) {
-            result.tags.push(tag);
-        }
-
-        if let Some(tag) = result.get(PdfDocumentMetadataTagType::ModificationDate) {
-            result.tags.push(tag);
+        for tag in PdfDocumentMetadataTagType::ALL {
+            if let Some(value) = result.get_raw_metadata_tag(tag.as_key()) {
+                result.tags.push(PdfDocumentMetadataTag::new(tag, value));
+            }
         }
 
         result
@@ -122,20 +149,164 @@ impl<'a> PdfMetadata<'a> {
 
     /// Returns one metadata tag from this [PdfMetadata] collection, if it is defined.
     pub fn get(&self, tag: PdfDocumentMetadataTagType) -> Option<PdfDocumentMetadataTag> {
-        let result = match tag {
-            PdfDocumentMetadataTagType::Title => self.get_raw_metadata_tag("Title"),
-            PdfDocumentMetadataTagType::Author => self.get_raw_metadata_tag("Author"),
-            PdfDocumentMetadataTagType::Subject => self.get_raw_metadata_tag("Subject"),
-            PdfDocumentMetadataTagType::Keywords => self.get_raw_metadata_tag("Keywords"),
-            PdfDocumentMetadataTagType::Creator => self.get_raw_metadata_tag("Creator"),
-            PdfDocumentMetadataTagType::Producer => self.get_raw_metadata_tag("Producer"),
-            PdfDocumentMetadataTagType::CreationDate => self.get_raw_metadata_tag("CreationDate"),
-            PdfDocumentMetadataTagType::ModificationDate => {
-                self.get_raw_metadata_tag("ModificationDate")
+        self.tags
+            .iter()
+            .find(|existing| existing.tag_type() == tag)
+            .cloned()
+    }
+
+    /// Sets the value of the given metadata tag in this [PdfMetadata] collection,
+    /// replacing any existing value.
+    ///
+    /// Dates should be given in the PDF date format `D:YYYYMMDDHHmmSSOHH'mm'`; use the
+    /// [PdfMetadata::set_creation_date()] and [PdfMetadata::set_modification_date()]
+    /// functions to set dates from `chrono` values.
+    pub fn set(&mut self, tag: PdfDocumentMetadataTagType, value: &str) {
+        let value = value.to_string();
+
+        match self
+            .tags
+            .iter_mut()
+            .find(|existing| existing.tag_type() == tag)
+        {
+            Some(existing) => existing.value = value,
+            None => {
+                self.tags.push(PdfDocumentMetadataTag::new(tag, value));
+
+                self.tags.sort_by_key(|existing| {
+                    PdfDocumentMetadataTagType::ALL
+                        .iter()
+                        .position(|tag| *tag == existing.tag_type())
+                });
             }
+        }
+
+        self.is_modified = true;
+    }
+
+    /// Sets the [PdfDocumentMetadataTagType::CreationDate] metadata tag in this
+    /// [PdfMetadata] collection to the given date and time.
+    #[inline]
+    pub fn set_creation_date(&mut self, date: DateTime<Utc>) {
+        self.set(
+            PdfDocumentMetadataTagType::CreationDate,
+            &date_time_to_pdf_string(date),
+        );
+    }
+
+    /// Sets the [PdfDocumentMetadataTagType::ModificationDate] metadata tag in this
+    /// [PdfMetadata] collection to the given date and time.
+    #[inline]
+    pub fn set_modification_date(&mut self, date: DateTime<Utc>) {
+        self.set(
+            PdfDocumentMetadataTagType::ModificationDate,
+            &date_time_to_pdf_string(date),
+        );
+    }
+
+    /// Removes the given metadata tag from this [PdfMetadata] collection, returning the
+    /// removed tag, if it was defined.
+    pub fn remove(&mut self, tag: PdfDocumentMetadataTagType) -> Option<PdfDocumentMetadataTag> {
+        let index = self
+            .tags
+            .iter()
+            .position(|existing| existing.tag_type() == tag)?;
+
+        self.is_modified = true;
+
+        Some(self.tags.remove(index))
+    }
+
+    /// Generates a new XMP packet containing the Dublin Core and other basic XMP properties
+    /// that correspond to the metadata tags in this [PdfMetadata] collection.
+    ///
+    /// The packet can be extended as required, e.g. with the PDF/A identification schema,
+    /// before being applied to the document using the [PdfDocument::set_xmp_metadata()]
+    /// function.
+    pub fn to_xmp(&self) -> String {
+        generate_xmp(self.tags.iter().map(|tag| (tag.tag_type(), tag.value())))
+    }
+
+    /// Returns the XMP packet set using the [PdfDocument::set_xmp_metadata()] function,
+    /// if any.
+    #[inline]
+    pub(crate) fn xmp(&self) -> Option<&str> {
+        self.xmp.as_deref()
+    }
+
+    /// Sets the XMP packet to be written as the document's metadata stream when the
+    /// document is saved, and replaces the metadata tags in this [PdfMetadata] collection
+    /// with the values of the corresponding properties in the packet. Tags without
+    /// a corresponding property in the packet are removed.
+    pub(crate) fn set_xmp(&mut self, xmp: &str) {
+        for tag in PdfDocumentMetadataTagType::ALL {
+            match get_tag_from_xmp(xmp, tag) {
+                Some(value) => self.set(tag, &value),
+                None => {
+                    self.remove(tag);
+                }
+            }
+        }
+
+        self.xmp = Some(xmp.to_string());
+        self.is_modified = true;
+    }
+
+    /// Returns `true` if any metadata tags in this [PdfMetadata] collection have been
+    /// changed since the document was loaded, or if an XMP packet has been set.
+    #[inline]
+    pub(crate) fn is_modified(&self) -> bool {
+        self.is_modified
+    }
+
+    /// Writes the metadata tags in this [PdfMetadata] collection to the document
+    /// information dictionary of the given update, creating the dictionary if necessary.
+    /// Entries in the dictionary other than the tags in this collection are left unchanged.
+    ///
+    /// The tags are also written to the corresponding properties of the document's XMP
+    /// metadata stream: either the XMP packet that has been set, or the document's existing
+    /// metadata stream, if it can be read. A document without XMP metadata is not given any.
+    pub(crate) fn apply_to(&self, update: &mut PdfIncrementalUpdate) -> Result<(), PdfiumError> {
+        let (info_id, mut info) = match update.trailer().get_reference("Info") {
+            Some(info_id) => (Some(info_id), update.get_dictionary(info_id)?),
+            None => (None, Default::default()),
         };
 
-        result.map(|value| PdfDocumentMetadataTag::new(tag, value))
+        for tag in PdfDocumentMetadataTagType::ALL {
+            match self.get(tag) {
+                Some(tag) => info.set(tag.tag_type().as_key(), encode_text_string(tag.value())),
+                None => info.remove(tag.as_key()),
+            }
+        }
+
+        match info_id {
+            Some(info_id) => update.set_object(info_id, info.to_bytes()),
+            None => {
+                let info_id = update.add_object(info.to_bytes());
+
+                update.set_trailer_entry("Info", info_id.to_reference());
+            }
+        }
+
+        let xmp = match self.xmp.as_ref() {
+            Some(xmp) => Some(xmp.clone()),
+            None => read_xmp_metadata(update).ok().flatten(),
+        };
+
+        if let Some(xmp) = xmp {
+            let synchronized =
+                PdfDocumentMetadataTagType::ALL
+                    .iter()
+                    .fold(xmp.clone(), |xmp, tag| {
+                        set_tag_in_xmp(&xmp, *tag, self.get(*tag).as_ref().map(|tag| tag.value()))
+                    });
+
+            if self.xmp.is_some() || synchronized != xmp {
+                write_xmp_metadata(update, &synchronized)?;
+            }
+        }
+
+        Ok(())
     }
 
     #[inline]
@@ -178,3 +349,109 @@ impl<'a> PdfMetadata<'a> {
         self.tags.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_set_and_remove_metadata() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        document
+            .pages_mut()
+            .create_page_at_end(PdfPagePaperSize::a4())?;
+
+        let metadata = document.metadata_mut();
+
+        metadata.set(PdfDocumentMetadataTagType::Title, "Invoice 1234");
+        metadata.set(PdfDocumentMetadataTagType::Author, "Accounts");
+        metadata.set(PdfDocumentMetadataTagType::Subject, "To be removed");
+        metadata.set_creation_date(Utc.with_ymd_and_hms(2024, 3, 1, 9, 30, 0).unwrap());
+
+        assert_eq!(
+            metadata.remove(PdfDocumentMetadataTagType::Subject),
+            Some(PdfDocumentMetadataTag::new(
+                PdfDocumentMetadataTagType::Subject,
+                "To be removed".to_string()
+            ))
+        );
+
+        let bytes = document.save_to_bytes()?;
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        let metadata = document.metadata();
+
+        assert_eq!(
+            metadata
+                .get(PdfDocumentMetadataTagType::Title)
+                .map(|tag| tag.value().to_string()),
+            Some("Invoice 1234".to_string())
+        );
+        assert!(metadata.get(PdfDocumentMetadataTagType::Subject).is_none());
+        assert_eq!(
+            metadata
+                .get(PdfDocumentMetadataTagType::CreationDate)
+                .map(|tag| tag.value().to_string()),
+            Some("D:20240301093000Z00'00'".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_changed_tags_update_xmp_metadata() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        document
+            .pages_mut()
+            .create_page_at_end(PdfPagePaperSize::a4())?;
+
+        document
+            .metadata_mut()
+            .set(PdfDocumentMetadataTagType::Title, "Draft");
+
+        let xmp = document.metadata().to_xmp();
+
+        document.set_xmp_metadata(&xmp);
+
+        document
+            .metadata_mut()
+            .set(PdfDocumentMetadataTagType::Title, "Final");
+
+        let bytes = document.save_to_bytes()?;
+
+        let mut document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        let xmp = document.xmp_metadata()?.unwrap();
+
+        assert!(xmp.contains(">Final</rdf:li>"));
+        assert!(!xmp.contains(">Draft</rdf:li>"));
+
+        // The existing metadata stream of a loaded document is also kept up to date.
+
+        document
+            .metadata_mut()
+            .set(PdfDocumentMetadataTagType::Author, "Accounts");
+
+        let bytes = document.save_to_bytes()?;
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        let xmp = document.xmp_metadata()?.unwrap();
+
+        assert!(xmp.contains(">Final</rdf:li>"));
+        assert!(
+            xmp.contains("<dc:creator><rdf:Seq><rdf:li>Accounts</rdf:li></rdf:Seq></dc:creator>")
+        );
+
+        Ok(())
+    }
+}
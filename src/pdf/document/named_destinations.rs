@@ -0,0 +1,226 @@
+//! Defines the [PdfNamedDestinations] struct, a collection of all the named `PdfDestination`
+//! objects in a `PdfDocument`.
+
+use crate::bindgen::FPDF_DOCUMENT;
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::destination::PdfDestination;
+use crate::utils::mem::create_byte_buffer;
+use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use std::os::raw::{c_int, c_long, c_void};
+use std::ptr::null_mut;
+
+#[cfg(doc)]
+use crate::pdf::document::PdfDocument;
+
+/// The zero-based index of a single named destination inside its containing
+/// [PdfNamedDestinations] collection.
+pub type PdfNamedDestinationIndex = usize;
+
+/// The collection of named [PdfDestination] objects inside a [PdfDocument].
+///
+/// A named destination gives a page and view a stable name that links, bookmarks, and
+/// other documents can refer to; for instance, a URL ending in `#nameddest=chapter2` asks
+/// a PDF viewer to open the destination named `chapter2`. The collection includes both the
+/// destinations in the document catalog's `/Dests` dictionary and those in the `/Dests`
+/// name tree of the document's `/Names` dictionary.
+pub struct PdfNamedDestinations<'a> {
+    document_handle: FPDF_DOCUMENT,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfNamedDestinations<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        document_handle: FPDF_DOCUMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfNamedDestinations {
+            document_handle,
+            bindings,
+        }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfNamedDestinations] collection.
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the number of named destinations in this [PdfNamedDestinations] collection.
+    #[inline]
+    pub fn len(&self) -> PdfNamedDestinationIndex {
+        self.bindings.FPDF_CountNamedDests(self.document_handle) as PdfNamedDestinationIndex
+    }
+
+    /// Returns `true` if this [PdfNamedDestinations] collection is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the name and [PdfDestination] of a single named destination from this
+    /// [PdfNamedDestinations] collection.
+    pub fn get(
+        &self,
+        index: PdfNamedDestinationIndex,
+    ) -> Result<(String, PdfDestination<'a>), PdfiumError> {
+        if index >= self.len() {
+            return Err(PdfiumError::NamedDestinationIndexOutOfBounds);
+        }
+
+        // Retrieving the name from Pdfium is a two-step operation. First, we call
+        // FPDF_GetNamedDest() with a null buffer; this will retrieve the length of
+        // the name in bytes. If the length is zero, then there is no such destination.
+
+        // If the length is non-zero, then we reserve a byte buffer of the given
+        // length and call FPDF_GetNamedDest() again with a pointer to the buffer;
+        // this will write the name to the buffer in UTF16-LE format.
+
+        let mut buffer_length: c_long = 0;
+
+        let handle = self.bindings.FPDF_GetNamedDest(
+            self.document_handle,
+            index as c_int,
+            null_mut(),
+            &mut buffer_length,
+        );
+
+        if handle.is_null() || buffer_length <= 0 {
+            return Err(PdfiumError::NamedDestinationIndexOutOfBounds);
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        let handle = self.bindings.FPDF_GetNamedDest(
+            self.document_handle,
+            index as c_int,
+            buffer.as_mut_ptr() as *mut c_void,
+            &mut buffer_length,
+        );
+
+        if handle.is_null() || buffer_length <= 0 {
+            // A buffer length of -1 indicates that our buffer was too small.
+
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        buffer.truncate(buffer_length as usize);
+
+        let name = get_string_from_pdfium_utf16le_bytes(buffer).unwrap_or_default();
+
+        Ok((
+            name,
+            PdfDestination::from_pdfium(self.document_handle, handle, self.bindings),
+        ))
+    }
+
+    /// Returns the [PdfDestination] with the given name, if any.
+    pub fn get_by_name(&self, name: &str) -> Option<PdfDestination<'a>> {
+        let handle = self
+            .bindings
+            .FPDF_GetNamedDestByName(self.document_handle, name);
+
+        if handle.is_null() {
+            None
+        } else {
+            Some(PdfDestination::from_pdfium(
+                self.document_handle,
+                handle,
+                self.bindings,
+            ))
+        }
+    }
+
+    /// Returns `true` if this [PdfNamedDestinations] collection contains a destination
+    /// with the given name.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get_by_name(name).is_some()
+    }
+
+    /// Returns an iterator over the name and [PdfDestination] of every named destination
+    /// in this [PdfNamedDestinations] collection.
+    #[inline]
+    pub fn iter(&self) -> PdfNamedDestinationsIterator<'_> {
+        PdfNamedDestinationsIterator::new(self)
+    }
+}
+
+/// An iterator over the name and [PdfDestination] of every named destination in a
+/// [PdfNamedDestinations] collection.
+pub struct PdfNamedDestinationsIterator<'a> {
+    destinations: &'a PdfNamedDestinations<'a>,
+    next_index: PdfNamedDestinationIndex,
+}
+
+impl<'a> PdfNamedDestinationsIterator<'a> {
+    #[inline]
+    pub(crate) fn new(destinations: &'a PdfNamedDestinations<'a>) -> Self {
+        PdfNamedDestinationsIterator {
+            destinations,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfNamedDestinationsIterator<'a> {
+    type Item = (String, PdfDestination<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_index < self.destinations.len() {
+            let next = self.destinations.get(self.next_index);
+
+            self.next_index += 1;
+
+            if let Ok(next) = next {
+                return Some(next);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_named_destinations() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/signatures-test.pdf", None)?;
+
+        let destinations = document.named_destinations();
+
+        // signatures-test.pdf defines two named destinations on its first page, whose names
+        // are prefixed with binary identifiers written by the tool that created the file.
+
+        assert!(!destinations.is_empty());
+        assert_eq!(destinations.len(), 2);
+        assert_eq!(destinations.iter().count(), destinations.len());
+
+        let (name, destination) = destinations
+            .iter()
+            .find(|(name, _)| name.ends_with("skip2content"))
+            .expect("signatures-test.pdf should define the skip2content destination");
+
+        assert_eq!(destination.page_index()?, 0);
+        assert!(destinations.contains(&name));
+
+        for (name, destination) in destinations.iter() {
+            assert!(!name.is_empty());
+
+            let found = destinations.get_by_name(&name).unwrap();
+
+            assert_eq!(found.page_index().ok(), destination.page_index().ok());
+        }
+
+        assert!(!destinations.contains("no-such-destination"));
+        assert!(destinations.get(destinations.len()).is_err());
+
+        Ok(())
+    }
+}
@@ -5,12 +5,15 @@ pub mod annotation;
 pub mod annotations;
 pub mod boundaries;
 pub mod field;
+pub mod form_session;
 pub(crate) mod index_cache;
 pub mod links;
 pub mod object;
 pub mod objects;
+pub mod progressive_render;
 pub mod render_config;
 pub mod size;
+pub mod struct_tree;
 pub mod text;
 
 #[cfg(feature = "paragraph")]
@@ -22,24 +25,33 @@ mod flatten; // Keep internal flatten operation private.
 use object::ownership::PdfPageObjectOwnership;
 
 use crate::bindgen::{
-    FLATTEN_FAIL, FLATTEN_NOTHINGTODO, FLATTEN_SUCCESS, FLAT_NORMALDISPLAY, FPDF_ANNOT, FPDF_DOCUMENT, FPDF_FORMHANDLE,
-    FPDF_PAGE,
+    FLAT_NORMALDISPLAY, FPDF_BITMAP, FPDF_BOOL, FPDF_COLORSCHEME, FPDF_DOCUMENT,
+    FPDF_FORMHANDLE, FPDF_PAGE, FPDF_RENDER_FAILED, FPDF_RENDER_TOBECONTINUED, IFSDK_PAUSE,
 };
+
+#[cfg(not(feature = "flatten"))]
+use crate::bindgen::{FLATTEN_FAIL, FLATTEN_NOTHINGTODO, FLATTEN_SUCCESS, FPDF_ANNOT};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::create_transform_setters;
 use crate::error::{PdfiumError, PdfiumInternalError};
 use crate::pdf::bitmap::{PdfBitmap, PdfBitmapFormat, Pixels};
 use crate::pdf::document::page::annotations::PdfPageAnnotations;
 use crate::pdf::document::page::boundaries::PdfPageBoundaries;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
+use crate::pdf::document::page::form_session::PdfFormSession;
 use crate::pdf::document::page::index_cache::PdfPageIndexCache;
 use crate::pdf::document::page::links::PdfPageLinks;
 #[cfg(target_arch = "wasm32")]
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
 use crate::pdf::document::page::objects::common::PdfPageObjectsCommon;
 use crate::pdf::document::page::objects::PdfPageObjects;
+use crate::pdf::document::page::progressive_render::PdfPageProgressiveRender;
 use crate::pdf::document::page::render_config::{PdfPageRenderSettings, PdfRenderConfig};
 use crate::pdf::document::page::size::PdfPagePaperSize;
+use crate::pdf::document::page::struct_tree::PdfStructTree;
 use crate::pdf::document::page::text::PdfPageText;
+use crate::pdf::document::page_labels::PdfPendingPageLabels;
+use crate::pdf::document::pages::PdfPageIndex;
 use crate::pdf::font::PdfFont;
 use crate::pdf::matrix::{PdfMatrix, PdfMatrixValue};
 use crate::pdf::points::PdfPoints;
@@ -47,6 +59,7 @@ use crate::pdf::rect::PdfRect;
 use std::collections::{hash_map::Entry, HashMap};
 use std::f32::consts::{FRAC_PI_2, PI};
 use std::os::raw::{c_double, c_int};
+use std::ptr::null_mut;
 
 #[cfg(doc)]
 use crate::pdf::document::{PdfDocument, PdfPages};
@@ -186,6 +199,7 @@ pub struct PdfPage<'a> {
     page_handle: FPDF_PAGE,
     form_handle: Option<FPDF_FORMHANDLE>,
     label: Option<String>,
+    page_labels: PdfPendingPageLabels,
     regeneration_strategy: PdfPageContentRegenerationStrategy,
     is_content_regeneration_required: bool,
     annotations: PdfPageAnnotations<'a>,
@@ -207,6 +221,8 @@ impl<'a> PdfPage<'a> {
         page_handle: FPDF_PAGE,
         form_handle: Option<FPDF_FORMHANDLE>,
         label: Option<String>,
+        page_labels: PdfPendingPageLabels,
+        pending_field_changes: PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         let mut result = PdfPage {
@@ -214,12 +230,14 @@ impl<'a> PdfPage<'a> {
             page_handle,
             form_handle,
             label,
+            page_labels,
             regeneration_strategy: PdfPageContentRegenerationStrategy::Manual,
             is_content_regeneration_required: false,
             annotations: PdfPageAnnotations::from_pdfium(
                 document_handle,
                 page_handle,
                 form_handle,
+                pending_field_changes,
                 bindings,
             ),
             boundaries: PdfPageBoundaries::from_pdfium(page_handle, bindings),
@@ -259,6 +277,9 @@ impl<'a> PdfPage<'a> {
     /// deselects sibling radio buttons, and runs any associated JavaScript.
     ///
     /// Returns `true` if PDFium handled the click, `false` otherwise.
+    ///
+    /// To simulate more complex interaction, such as typing into a text field,
+    /// use the [PdfPage::form_session()] function.
     pub fn click_at(&self, page_x: f64, page_y: f64) -> bool {
         if let Some(form_handle) = self.form_handle {
             // CRITICAL: PDFium requires FORM_OnAfterLoadPage to be called before
@@ -286,6 +307,19 @@ impl<'a> PdfPage<'a> {
         }
     }
 
+    /// Starts a new [PdfFormSession] for simulating user interaction with the interactive
+    /// form fields on this [PdfPage], such as clicking, typing, selecting text, and undoing
+    /// changes, through Pdfium's own form fill engine.
+    ///
+    /// Returns [PdfiumError::FormNotAvailable] if forms are not enabled for the
+    /// [PdfDocument] containing this [PdfPage].
+    pub fn form_session(&self) -> Result<PdfFormSession<'_>, PdfiumError> {
+        match self.form_handle {
+            Some(form_handle) => Ok(PdfFormSession::new(self, form_handle)),
+            None => Err(PdfiumError::FormNotAvailable),
+        }
+    }
+
     /// Returns the [PdfiumLibraryBindings] used by this [PdfPage].
     #[inline]
     pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
@@ -293,11 +327,27 @@ impl<'a> PdfPage<'a> {
     }
 
     /// Returns the label assigned to this [PdfPage], if any.
+    ///
+    /// This is the label stored in the document when this [PdfPage] was retrieved.
+    /// Use [PdfPage::current_label()] to take page labels set using
+    /// `PdfPages::set_page_labels()` into account.
     #[inline]
     pub fn label(&self) -> Option<&str> {
         self.label.as_deref()
     }
 
+    /// Returns the label this [PdfPage] will have when the document is next saved, if any.
+    ///
+    /// If page labels have been set using `PdfPages::set_page_labels()`, the label is
+    /// generated from those labels at this page's current index, so it reflects page labels
+    /// set after this [PdfPage] was retrieved. Otherwise, the label is the one returned
+    /// by [PdfPage::label()].
+    pub fn current_label(&self) -> Option<String> {
+        PdfPageIndexCache::get_index_for_page(self.document_handle, self.page_handle)
+            .and_then(|index| self.page_labels.label(index))
+            .unwrap_or_else(|| self.label.clone())
+    }
+
     /// Returns the width of this [PdfPage] in device-independent points.
     /// One point is 1/72 inches, roughly 0.358 mm.
     #[inline]
@@ -529,6 +579,14 @@ impl<'a> PdfPage<'a> {
         &mut self.objects
     }
 
+    /// Returns the tagged logical structure tree for this [PdfPage].
+    ///
+    /// An error will be returned if the containing [PdfDocument] is not a tagged PDF.
+    #[inline]
+    pub fn structure_tree(&self) -> Result<PdfStructTree<'_>, PdfiumError> {
+        PdfStructTree::from_pdfium(self.page_handle, None, self.bindings)
+    }
+
     /// Returns a list of all the distinct [PdfFont] instances used by the page text objects
     /// on this [PdfPage], if any.
     pub fn fonts(&self) -> Vec<PdfFont<'_>> {
@@ -717,6 +775,34 @@ impl<'a> PdfPage<'a> {
         self.render_into_bitmap_with_settings(bitmap, config.apply_to_page(self))
     }
 
+    /// Begins rendering this [PdfPage] into a new [PdfBitmap] using pixel dimensions, page rotation
+    /// settings, and rendering options configured in the given [PdfRenderConfig].
+    ///
+    /// Rendering is carried out progressively, over a series of calls to the
+    /// [PdfPageProgressiveRender::poll()] function on the returned [PdfPageProgressiveRender].
+    /// Each call takes a callback that can pause rendering, for instance once a time budget
+    /// has been exceeded. This allows complex pages to be rendered without blocking
+    /// for the full duration of the rendering.
+    ///
+    /// Pdfium's progressive rendering functions do not support custom transformation matrices
+    /// or clipping. An error will be returned if the given [PdfRenderConfig] applies either.
+    pub fn render_progressive(
+        &self,
+        config: &PdfRenderConfig,
+    ) -> Result<PdfPageProgressiveRender<'_>, PdfiumError> {
+        let settings = config.apply_to_page(self);
+
+        let bitmap = PdfBitmap::empty(
+            settings.width as Pixels,
+            settings.height as Pixels,
+            PdfBitmapFormat::from_pdfium(settings.format as u32)
+                .unwrap_or_else(|_| PdfBitmapFormat::default()),
+            self.bindings,
+        )?;
+
+        PdfPageProgressiveRender::new(self, bitmap, settings)
+    }
+
     /// Renders this [PdfPage] into the given [PdfBitmap] using the given [PdfRenderSettings].
     /// The size of the buffer backing the given bitmap must be sufficiently large to hold
     /// the rendered image or an error will be returned.
@@ -740,7 +826,25 @@ impl<'a> PdfPage<'a> {
             );
         }
 
-        if settings.do_render_form_data {
+        if let Some(color_scheme) = settings
+            .color_scheme
+            .as_ref()
+            .filter(|_| !settings.is_transformed_or_clipped)
+        {
+            // Render the PDF page into the bitmap buffer, forcing the colors of path and
+            // text objects. (Pdfium only supports color schemes in its progressive renderer,
+            // which cannot apply a custom transformation matrix.)
+
+            self.render_with_color_scheme_into_bitmap_handle(
+                bitmap_handle,
+                &settings,
+                color_scheme,
+            )?;
+
+            if settings.do_render_form_data {
+                self.render_form_data_into_bitmap_handle(bitmap_handle, &settings);
+            }
+        } else if settings.do_render_form_data {
             // Render the PDF page into the bitmap buffer, ignoring any custom transformation matrix.
             // (Custom transforms cannot be applied to the rendering of form fields.)
 
@@ -755,34 +859,7 @@ impl<'a> PdfPage<'a> {
                 settings.render_flags,
             );
 
-            if let Some(form_handle) = self.form_handle {
-                // Render user-supplied form data, if any, as an overlay on top of the page.
-
-                if let Some(form_field_highlight) = settings.form_field_highlight.as_ref() {
-                    for (form_field_type, (color, alpha)) in form_field_highlight.iter() {
-                        self.bindings.FPDF_SetFormFieldHighlightColor(
-                            form_handle,
-                            *form_field_type,
-                            *color,
-                        );
-
-                        self.bindings
-                            .FPDF_SetFormFieldHighlightAlpha(form_handle, *alpha);
-                    }
-                }
-
-                self.bindings.FPDF_FFLDraw(
-                    form_handle,
-                    bitmap_handle,
-                    self.page_handle,
-                    0,
-                    0,
-                    settings.width,
-                    settings.height,
-                    settings.rotate,
-                    settings.render_flags,
-                );
-            }
+            self.render_form_data_into_bitmap_handle(bitmap_handle, &settings);
         } else {
             // Render the PDF page into the bitmap buffer, applying any custom transformation matrix.
 
@@ -800,6 +877,91 @@ impl<'a> PdfPage<'a> {
         Ok(())
     }
 
+    /// Renders this [PdfPage] into the bitmap with the given handle using Pdfium's
+    /// progressive renderer, replacing the colors of path and text objects with those
+    /// in the given color scheme. Rendering runs to completion without pausing.
+    fn render_with_color_scheme_into_bitmap_handle(
+        &self,
+        bitmap_handle: FPDF_BITMAP,
+        settings: &PdfPageRenderSettings,
+        color_scheme: &FPDF_COLORSCHEME,
+    ) -> Result<(), PdfiumError> {
+        // Pdfium will not start a progressive rendering without a pause callback,
+        // so we supply one that never asks for a pause.
+
+        extern "C" fn never_pause(_pause: *mut IFSDK_PAUSE) -> FPDF_BOOL {
+            0
+        }
+
+        let mut pause = IFSDK_PAUSE {
+            version: 1,
+            NeedToPauseNow: Some(never_pause),
+            user: null_mut(),
+        };
+
+        let mut status = self.bindings.FPDF_RenderPageBitmapWithColorScheme_Start(
+            bitmap_handle,
+            self.page_handle,
+            0,
+            0,
+            settings.width,
+            settings.height,
+            settings.rotate,
+            settings.render_flags,
+            color_scheme,
+            &mut pause,
+        );
+
+        while status == FPDF_RENDER_TOBECONTINUED as c_int {
+            status = self
+                .bindings
+                .FPDF_RenderPage_Continue(self.page_handle, &mut pause);
+        }
+
+        self.bindings.FPDF_RenderPage_Close(self.page_handle);
+
+        if status == FPDF_RENDER_FAILED as c_int {
+            Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Renders user-supplied form data, if any, as an overlay on top of page content
+    /// already rendered into the bitmap with the given handle.
+    pub(crate) fn render_form_data_into_bitmap_handle(
+        &self,
+        bitmap_handle: FPDF_BITMAP,
+        settings: &PdfPageRenderSettings,
+    ) {
+        if let Some(form_handle) = self.form_handle {
+            if let Some(form_field_highlight) = settings.form_field_highlight.as_ref() {
+                for (form_field_type, (color, alpha)) in form_field_highlight.iter() {
+                    self.bindings.FPDF_SetFormFieldHighlightColor(
+                        form_handle,
+                        *form_field_type,
+                        *color,
+                    );
+
+                    self.bindings
+                        .FPDF_SetFormFieldHighlightAlpha(form_handle, *alpha);
+                }
+            }
+
+            self.bindings.FPDF_FFLDraw(
+                form_handle,
+                bitmap_handle,
+                self.page_handle,
+                0,
+                0,
+                settings.width,
+                settings.height,
+                settings.rotate,
+                settings.render_flags,
+            );
+        }
+    }
+
     // TODO: AJRC - 29/7/22 - remove deprecated PdfPage::get_bitmap_*() functions in 0.9.0
     // as part of tracking issue https://github.com/ajrcarey/pdfium-render/issues/36
     /// Renders this [PdfPage] into a new [PdfBitmap] using pixel dimensions, rotation settings,
@@ -961,7 +1123,28 @@ impl<'a> PdfPage<'a> {
     // Use a custom-written flatten operation, rather than Pdfium's built-in flatten. See:
     // https://github.com/ajrcarey/pdfium-render/issues/140
     pub fn flatten(&mut self) -> Result<(), PdfiumError> {
-        flatten::flatten(self.page_handle)
+        let result = flatten::flatten(
+            self.document_handle,
+            self.page_handle,
+            self.form_handle,
+            FLAT_NORMALDISPLAY,
+            self.bindings,
+        );
+
+        if result.is_ok() {
+            // Flattened widgets are removed from the page, so any changes waiting to be written
+            // to them when the document is saved are discarded.
+
+            self.annotations.discard_pending_field_changes();
+            self.regenerate_content()?;
+        }
+
+        // The form fill environment released the page during flattening, so the page
+        // must always be reloaded.
+
+        self.reload_in_place();
+
+        result
     }
 
     /// Flattens all annotations and form fields on this [PdfPage] into the page contents.
@@ -972,6 +1155,11 @@ impl<'a> PdfPage<'a> {
         // TODO: AJRC - 28/5/22 - consider allowing the caller to set the FLAT_NORMALDISPLAY or FLAT_PRINT flag.
         let flag = FLAT_NORMALDISPLAY;
 
+        // Flattened widgets are removed from the page, so any changes waiting to be written
+        // to them when the document is saved are discarded.
+
+        self.annotations.discard_pending_field_changes();
+
         // Trigger PDFium to automatically generate appearance streams for annotations
         // by rendering the page with the FPDF_ANNOT flag before flattening.
         // This ensures annotations without appearance streams get them generated automatically
@@ -1071,11 +1259,19 @@ impl<'a> PdfPage<'a> {
         let index = PdfPageIndexCache::get_index_for_page(self.document_handle, self.page_handle)
             .ok_or(PdfiumError::SourcePageIndexNotInCache)?;
 
+        self.annotations.discard_pending_field_changes();
+
         self.bindings
             .FPDFPage_Delete(self.document_handle, index as c_int);
 
         PdfPageIndexCache::delete_pages_at_index(self.document_handle, index, 1);
 
+        self.page_labels.delete_pages(
+            index,
+            1,
+            self.bindings.FPDF_GetPageCount(self.document_handle) as PdfPageIndex,
+        );
+
         Ok(())
     }
 
@@ -1261,6 +1457,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_page_flatten() -> Result<(), PdfiumError> {
+        // Flattens a page containing form fields, then checks that all annotations other than
+        // links have been removed and that their appearances have been added to the page content.
+
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/form-test.pdf", None)?;
+
+        let mut page = document.pages().get(0)?;
+
+        let object_count = page.objects().len();
+
+        assert!(page.annotations().len() > 0);
+
+        page.flatten()?;
+
+        assert!(page
+            .annotations()
+            .iter()
+            .all(|annotation| annotation.annotation_type() == PdfPageAnnotationType::Link));
+        assert!(page.objects().len() > object_count);
+
+        Ok(())
+    }
+
     #[test]
     fn test_rendered_image_dimension() -> Result<(), PdfiumError> {
         // Checks that downscaled dimensions are rounded correctly during page rendering.
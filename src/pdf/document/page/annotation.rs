@@ -69,6 +69,7 @@ use crate::pdf::document::page::annotation::unsupported::PdfPageUnsupportedAnnot
 use crate::pdf::document::page::annotation::watermark::PdfPageWatermarkAnnotation;
 use crate::pdf::document::page::annotation::widget::PdfPageWidgetAnnotation;
 use crate::pdf::document::page::annotation::xfa_widget::PdfPageXfaWidgetAnnotation;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::PdfFormField;
 use crate::pdf::document::page::object::ownership::PdfPageObjectOwnership;
 use crate::pdf::points::PdfPoints;
@@ -256,6 +257,7 @@ impl<'a> PdfPageAnnotation<'a> {
         page_handle: FPDF_PAGE,
         annotation_handle: FPDF_ANNOTATION,
         form_handle: Option<FPDF_FORMHANDLE>,
+        pending_field_changes: &PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         let annotation_type =
@@ -421,6 +423,7 @@ impl<'a> PdfPageAnnotation<'a> {
                     page_handle,
                     annotation_handle,
                     form_handle,
+                    pending_field_changes,
                     bindings,
                 ))
             }
@@ -430,6 +433,7 @@ impl<'a> PdfPageAnnotation<'a> {
                     page_handle,
                     annotation_handle,
                     form_handle,
+                    pending_field_changes,
                     bindings,
                 ))
             }
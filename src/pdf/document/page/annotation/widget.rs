@@ -7,6 +7,7 @@ use crate::pdf::document::page::annotation::attachment_points::PdfPageAnnotation
 use crate::pdf::document::page::annotation::objects::PdfPageAnnotationObjects;
 use crate::pdf::document::page::annotation::private::internal::PdfPageAnnotationPrivate;
 use crate::pdf::document::page::annotation::signature_appearance::SignatureAppearanceBuilder;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::PdfFormField;
 use crate::pdf::document::page::object::ownership::PdfPageObjectOwnership;
 use crate::pdf::document::page::objects::private::internal::PdfPageObjectsPrivate;
@@ -32,6 +33,7 @@ impl<'a> PdfPageWidgetAnnotation<'a> {
         page_handle: FPDF_PAGE,
         annotation_handle: FPDF_ANNOTATION,
         form_handle: Option<FPDF_FORMHANDLE>,
+        pending_field_changes: &PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         PdfPageWidgetAnnotation {
@@ -50,7 +52,7 @@ impl<'a> PdfPageWidgetAnnotation<'a> {
                 PdfFormField::from_pdfium_with_document(
                     form_handle,
                     annotation_handle,
-                    Some(document_handle),
+                    Some((document_handle, pending_field_changes)),
                     bindings,
                 )
             }),
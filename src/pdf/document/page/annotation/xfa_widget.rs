@@ -6,6 +6,7 @@ use crate::bindings::PdfiumLibraryBindings;
 use crate::pdf::document::page::annotation::attachment_points::PdfPageAnnotationAttachmentPoints;
 use crate::pdf::document::page::annotation::objects::PdfPageAnnotationObjects;
 use crate::pdf::document::page::annotation::private::internal::PdfPageAnnotationPrivate;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::PdfFormField;
 use crate::pdf::document::page::object::ownership::PdfPageObjectOwnership;
 use crate::pdf::document::page::objects::private::internal::PdfPageObjectsPrivate;
@@ -31,6 +32,7 @@ impl<'a> PdfPageXfaWidgetAnnotation<'a> {
         page_handle: FPDF_PAGE,
         annotation_handle: FPDF_ANNOTATION,
         form_handle: Option<FPDF_FORMHANDLE>,
+        pending_field_changes: &PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         PdfPageXfaWidgetAnnotation {
@@ -49,7 +51,7 @@ impl<'a> PdfPageXfaWidgetAnnotation<'a> {
                 PdfFormField::from_pdfium_with_document(
                     form_handle,
                     annotation_handle,
-                    Some(document_handle),
+                    Some((document_handle, pending_field_changes)),
                     bindings,
                 )
             }),
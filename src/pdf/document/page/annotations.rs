@@ -32,8 +32,15 @@ use crate::pdf::document::page::annotation::{
     PdfPageAnnotation, PdfPageAnnotationCommon, PdfPageAnnotationType,
 };
 #[cfg(feature = "pdfium_future")]
+use crate::pdf::document::incremental_update::next_marker_id;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
+#[cfg(feature = "pdfium_future")]
 use crate::pdf::document::page::field::PdfFormFieldType;
 #[cfg(feature = "pdfium_future")]
+use crate::pdf::document::page::field::radio_group::{
+    PdfFormButtonStyle, PdfRadioGroup, PdfRadioGroupWidget,
+};
+#[cfg(feature = "pdfium_future")]
 use crate::pdf::document::page::field::private::internal::PdfFormFieldFlags;
 #[cfg(feature = "pdfium_future")]
 use crate::pdf::rect::PdfRect;
@@ -56,6 +63,7 @@ pub struct PdfPageAnnotations<'a> {
     document_handle: FPDF_DOCUMENT,
     page_handle: FPDF_PAGE,
     form_handle: Option<FPDF_FORMHANDLE>,
+    pending_field_changes: PdfPendingFieldChanges,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -65,12 +73,14 @@ impl<'a> PdfPageAnnotations<'a> {
         document_handle: FPDF_DOCUMENT,
         page_handle: FPDF_PAGE,
         form_handle: Option<FPDF_FORMHANDLE>,
+        pending_field_changes: PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         PdfPageAnnotations {
             document_handle,
             page_handle,
             form_handle,
+            pending_field_changes,
             bindings,
         }
     }
@@ -95,6 +105,17 @@ impl<'a> PdfPageAnnotations<'a> {
         self.bindings
     }
 
+    /// Discards any changes waiting to be written to the form field widgets in this
+    /// [PdfPageAnnotations] collection when the containing `PdfDocument` is saved, as when
+    /// the widgets are about to be removed from the page.
+    pub(crate) fn discard_pending_field_changes(&self) {
+        for annotation in self.iter() {
+            if let Some(name) = annotation.name() {
+                self.pending_field_changes.discard_widget(&name);
+            }
+        }
+    }
+
 
     /// Returns the total number of annotations that have been added to the containing `PdfPage`.
     #[inline]
@@ -134,6 +155,7 @@ impl<'a> PdfPageAnnotations<'a> {
                 self.page_handle,
                 annotation_handle,
                 self.form_handle,
+                &self.pending_field_changes,
                 self.bindings,
             ))
         }
@@ -896,6 +918,7 @@ impl<'a> PdfPageAnnotations<'a> {
             self.page_handle(),
             annot_handle,
             Some(form_handle),
+            &self.pending_field_changes,
             self.bindings(),
         );
 
@@ -924,6 +947,148 @@ impl<'a> PdfPageAnnotations<'a> {
         Ok(annotation)
     }
 
+    /// Creates a new radio button group with the given name, containing one radio button widget
+    /// for each of the given bounding rectangles and export values, returning the new widgets
+    /// in the order given. Selected radio buttons show a filled dot; to use a different glyph,
+    /// use the [PdfPageAnnotations::create_radio_group_with_style()] function.
+    ///
+    /// The containing `PdfDocument` must have an interactive form; use
+    /// `PdfDocument::ensure_acro_form()` to create one if necessary.
+    ///
+    /// Pdfium cannot create a form field with more than one widget, so until the document
+    /// is saved each widget behaves as a separate radio button field sharing the group's name.
+    /// In particular, selecting one of the returned widgets does not deselect the others,
+    /// and each widget reports its own value rather than the value of the group. When the
+    /// document is saved, the widgets are written as the `/Kids` of a single radio button
+    /// field whose `/Opt` array holds the given export values, and each widget is given on and
+    /// off appearance streams. The widget selected before saving, if any, remains selected;
+    /// if more than one widget was selected, only the first of them remains selected. Since
+    /// the group is written as an incremental update appended to the bytes written by Pdfium,
+    /// a document containing a new radio button group cannot be saved in encrypted form.
+    ///
+    /// If any of the widgets cannot be created, the widgets already created for the group
+    /// are removed from the page before the error is returned.
+    ///
+    /// Widgets removed using [PdfPageAnnotations::delete_annotation()], or by deleting or
+    /// flattening their page, are removed from the group. If any other widget in the group
+    /// cannot be found when the document is saved, [PdfiumError::FormFieldWidgetNotFound]
+    /// is returned.
+    ///
+    /// If the containing `PdfPage` has a content regeneration strategy of
+    /// `PdfPageContentRegenerationStrategy::AutomaticOnEveryChange` then content regeneration
+    /// will be triggered on the page.
+    #[cfg(feature = "pdfium_future")]
+    #[inline]
+    pub fn create_radio_group(
+        &mut self,
+        name: &str,
+        widgets: &[(PdfRect, &str)],
+    ) -> Result<Vec<PdfPageWidgetAnnotation<'a>>, PdfiumError> {
+        self.create_radio_group_with_style(name, widgets, PdfFormButtonStyle::Circle)
+    }
+
+    /// Creates a new radio button group with the given name, containing one radio button widget
+    /// for each of the given bounding rectangles and export values, returning the new widgets
+    /// in the order given. Selected radio buttons show the glyph for the given
+    /// [PdfFormButtonStyle].
+    ///
+    /// See [PdfPageAnnotations::create_radio_group()] for details of how the group is written
+    /// to the containing `PdfDocument`.
+    #[cfg(feature = "pdfium_future")]
+    pub fn create_radio_group_with_style(
+        &mut self,
+        name: &str,
+        widgets: &[(PdfRect, &str)],
+        style: PdfFormButtonStyle,
+    ) -> Result<Vec<PdfPageWidgetAnnotation<'a>>, PdfiumError> {
+        let form_handle = self.form_handle.ok_or(PdfiumError::FormNotAvailable)?;
+
+        let group_id = next_marker_id();
+
+        let mut group = PdfRadioGroup {
+            name: name.to_string(),
+            style,
+            widgets: Vec::with_capacity(widgets.len()),
+        };
+
+        let mut result = Vec::with_capacity(widgets.len());
+
+        if let Err(error) =
+            self.create_radio_group_widgets(form_handle, group_id, widgets, &mut group, &mut result)
+        {
+            // Remove any widgets already created, so that they are not left on the page
+            // as independent radio button fields.
+
+            for annotation in result.iter().rev() {
+                let index = self
+                    .bindings()
+                    .FPDFPage_GetAnnotIndex(self.page_handle(), annotation.handle());
+
+                if index != -1 {
+                    self.bindings()
+                        .FPDFPage_RemoveAnnot(self.page_handle(), index);
+                }
+            }
+
+            return Err(error);
+        }
+
+        self.pending_field_changes.add_radio_group(group);
+
+        Ok(result)
+    }
+
+    /// Creates the radio button widgets of the given [PdfRadioGroup], adding each widget to
+    /// the given vector as soon as it is created.
+    #[cfg(feature = "pdfium_future")]
+    fn create_radio_group_widgets(
+        &mut self,
+        form_handle: FPDF_FORMHANDLE,
+        group_id: u64,
+        widgets: &[(PdfRect, &str)],
+        group: &mut PdfRadioGroup,
+        result: &mut Vec<PdfPageWidgetAnnotation<'a>>,
+    ) -> Result<(), PdfiumError> {
+        for (index, (rect, export_value)) in widgets.iter().enumerate() {
+            let annotation = self.create_widget_annotation(
+                form_handle,
+                &group.name,
+                PdfFormFieldType::RadioButton,
+                *rect,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(PdfFormFieldFlags::ButtonNoToggleToOff.bits()),
+            )?;
+
+            let handle = annotation.handle();
+
+            result.push(annotation);
+
+            // Pdfium provides no way to find the object number of an annotation, so we tag
+            // each widget with a unique annotation name that can be found in the saved bytes.
+
+            let marker = PdfRadioGroup::marker(group_id, index);
+
+            let bindings = self.bindings();
+
+            if !bindings.is_true(bindings.FPDFAnnot_SetStringValue_str(handle, "NM", &marker)) {
+                return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+            }
+
+            group.widgets.push(PdfRadioGroupWidget {
+                marker,
+                export_value: export_value.to_string(),
+                width: rect.width().value,
+                height: rect.height().value,
+            });
+        }
+
+        Ok(())
+    }
+
     // Convenience functions for creating and positioning markup annotations
     // in a single function call.
 
@@ -1124,6 +1289,10 @@ impl<'a> PdfPageAnnotations<'a> {
             return Err(PdfiumError::PageAnnotationIndexOutOfBounds);
         }
 
+        if let Some(name) = annotation.name() {
+            self.pending_field_changes.discard_widget(&name);
+        }
+
         if self.bindings().is_true(
             self.bindings()
                 .FPDFPage_RemoveAnnot(self.page_handle(), index),
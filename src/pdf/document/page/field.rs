@@ -2,22 +2,28 @@
 //! form field in a [PdfForm].
 
 pub mod button;
+pub mod button_appearance;
 pub mod checkbox;
+pub mod choice_appearance;
 pub mod combo;
 pub mod list;
 pub mod option;
 pub mod options;
+pub(crate) mod pending_changes;
 pub(crate) mod private; // Keep private so that the PdfFormFieldPrivate trait is not exposed.
 pub mod radio;
+pub mod radio_group;
 pub mod signature;
 pub mod text;
 pub mod text_appearance;
 pub mod unknown;
 
 use crate::bindgen::{
-    FPDF_ANNOTATION, FPDF_FORMFIELD_CHECKBOX, FPDF_FORMFIELD_COMBOBOX, FPDF_FORMFIELD_LISTBOX,
-    FPDF_FORMFIELD_PUSHBUTTON, FPDF_FORMFIELD_RADIOBUTTON, FPDF_FORMFIELD_SIGNATURE,
-    FPDF_FORMFIELD_TEXTFIELD, FPDF_FORMFIELD_UNKNOWN, FPDF_FORMHANDLE,
+    FPDF_ANNOTATION, FPDF_ANNOT_AACTION_CALCULATE, FPDF_ANNOT_AACTION_FORMAT,
+    FPDF_ANNOT_AACTION_KEY_STROKE, FPDF_ANNOT_AACTION_VALIDATE, FPDF_FORMFIELD_CHECKBOX,
+    FPDF_FORMFIELD_COMBOBOX, FPDF_FORMFIELD_LISTBOX, FPDF_FORMFIELD_PUSHBUTTON,
+    FPDF_FORMFIELD_RADIOBUTTON, FPDF_FORMFIELD_SIGNATURE, FPDF_FORMFIELD_TEXTFIELD,
+    FPDF_FORMFIELD_UNKNOWN, FPDF_FORMHANDLE,
 };
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
@@ -26,6 +32,7 @@ use crate::pdf::document::page::field::button::PdfFormPushButtonField;
 use crate::pdf::document::page::field::checkbox::PdfFormCheckboxField;
 use crate::pdf::document::page::field::combo::PdfFormComboBoxField;
 use crate::pdf::document::page::field::list::PdfFormListBoxField;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::private::internal::{
     PdfFormFieldFlags, PdfFormFieldPrivate,
 };
@@ -126,6 +133,48 @@ impl PdfFormFieldType {
     }
 }
 
+/// The form field events that can trigger one of the additional actions of a [PdfFormField].
+///
+/// For more information on form field additional actions, refer to Section 8.5.2 of
+/// The PDF Reference (Sixth Edition, PDF Format 1.7), starting on page 648.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PdfFormFieldActionEvent {
+    /// The user has typed a keystroke into a text field or combo box, or modified the
+    /// selection in a scrollable list box. The action can check the keystroke for validity
+    /// and reject or modify it.
+    KeyStroke,
+
+    /// The value of the field is about to be formatted for display.
+    Format,
+
+    /// The value of the field has changed. The action can check the new value for validity.
+    Validate,
+
+    /// The value of another field has changed, and the value of this field should be
+    /// recalculated.
+    Calculate,
+}
+
+impl PdfFormFieldActionEvent {
+    /// All the [PdfFormFieldActionEvent] values, in the order listed in the PDF specification.
+    pub const ALL: [PdfFormFieldActionEvent; 4] = [
+        PdfFormFieldActionEvent::KeyStroke,
+        PdfFormFieldActionEvent::Format,
+        PdfFormFieldActionEvent::Validate,
+        PdfFormFieldActionEvent::Calculate,
+    ];
+
+    #[inline]
+    pub(crate) fn as_pdfium(&self) -> c_int {
+        (match self {
+            PdfFormFieldActionEvent::KeyStroke => FPDF_ANNOT_AACTION_KEY_STROKE,
+            PdfFormFieldActionEvent::Format => FPDF_ANNOT_AACTION_FORMAT,
+            PdfFormFieldActionEvent::Validate => FPDF_ANNOT_AACTION_VALIDATE,
+            PdfFormFieldActionEvent::Calculate => FPDF_ANNOT_AACTION_CALCULATE,
+        }) as c_int
+    }
+}
+
 /// A single interactive form field in a [PdfForm].
 pub enum PdfFormField<'a> {
     PushButton(PdfFormPushButtonField<'a>),
@@ -150,7 +199,7 @@ impl<'a> PdfFormField<'a> {
     pub(crate) fn from_pdfium_with_document(
         form_handle: FPDF_FORMHANDLE,
         annotation_handle: FPDF_ANNOTATION,
-        document_handle: Option<crate::bindgen::FPDF_DOCUMENT>,
+        document: Option<(crate::bindgen::FPDF_DOCUMENT, &PdfPendingFieldChanges)>,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Option<Self> {
         let result = bindings.FPDFAnnot_GetFormFieldType(form_handle, annotation_handle);
@@ -163,21 +212,61 @@ impl<'a> PdfFormField<'a> {
             PdfFormFieldType::from_pdfium(result).unwrap_or(PdfFormFieldType::Unknown);
 
         Some(match form_field_type {
-            PdfFormFieldType::PushButton => PdfFormField::PushButton(
-                PdfFormPushButtonField::from_pdfium(form_handle, annotation_handle, bindings),
-            ),
-            PdfFormFieldType::Checkbox => PdfFormField::Checkbox(
-                PdfFormCheckboxField::from_pdfium(form_handle, annotation_handle, bindings),
-            ),
-            PdfFormFieldType::RadioButton => PdfFormField::RadioButton(
-                PdfFormRadioButtonField::from_pdfium(form_handle, annotation_handle, bindings),
-            ),
+            PdfFormFieldType::PushButton => {
+                if let Some((_, pending_field_changes)) = document {
+                    PdfFormField::PushButton(PdfFormPushButtonField::from_pdfium_with_document(
+                        form_handle,
+                        annotation_handle,
+                        pending_field_changes.clone(),
+                        bindings,
+                    ))
+                } else {
+                    PdfFormField::PushButton(PdfFormPushButtonField::from_pdfium(
+                        form_handle,
+                        annotation_handle,
+                        bindings,
+                    ))
+                }
+            }
+            PdfFormFieldType::Checkbox => {
+                if let Some((_, pending_field_changes)) = document {
+                    PdfFormField::Checkbox(PdfFormCheckboxField::from_pdfium_with_document(
+                        form_handle,
+                        annotation_handle,
+                        pending_field_changes.clone(),
+                        bindings,
+                    ))
+                } else {
+                    PdfFormField::Checkbox(PdfFormCheckboxField::from_pdfium(
+                        form_handle,
+                        annotation_handle,
+                        bindings,
+                    ))
+                }
+            }
+            PdfFormFieldType::RadioButton => {
+                if let Some((_, pending_field_changes)) = document {
+                    PdfFormField::RadioButton(PdfFormRadioButtonField::from_pdfium_with_document(
+                        form_handle,
+                        annotation_handle,
+                        pending_field_changes.clone(),
+                        bindings,
+                    ))
+                } else {
+                    PdfFormField::RadioButton(PdfFormRadioButtonField::from_pdfium(
+                        form_handle,
+                        annotation_handle,
+                        bindings,
+                    ))
+                }
+            }
             PdfFormFieldType::ComboBox => {
-                if let Some(doc_handle) = document_handle {
+                if let Some((doc_handle, pending_field_changes)) = document {
                     PdfFormField::ComboBox(PdfFormComboBoxField::from_pdfium_with_document(
                         form_handle,
                         annotation_handle,
                         doc_handle,
+                        pending_field_changes.clone(),
                         bindings,
                     ))
                 } else {
@@ -189,11 +278,12 @@ impl<'a> PdfFormField<'a> {
                 }
             }
             PdfFormFieldType::ListBox => {
-                if let Some(doc_handle) = document_handle {
+                if let Some((doc_handle, pending_field_changes)) = document {
                     PdfFormField::ListBox(PdfFormListBoxField::from_pdfium_with_document(
                         form_handle,
                         annotation_handle,
                         doc_handle,
+                        pending_field_changes.clone(),
                         bindings,
                     ))
                 } else {
@@ -205,7 +295,7 @@ impl<'a> PdfFormField<'a> {
                 }
             }
             PdfFormFieldType::Text => {
-                if let Some(doc_handle) = document_handle {
+                if let Some((doc_handle, _)) = document {
                     PdfFormField::Text(PdfFormTextField::from_pdfium_with_document(
                         form_handle,
                         annotation_handle,
@@ -320,6 +410,16 @@ impl<'a> PdfFormField<'a> {
         }
     }
 
+    /// Returns a mutable reference to the underlying [PdfFormComboBoxField]
+    /// for this [PdfFormField], if this form field has a field type of [PdfFormField::ComboBox].
+    #[inline]
+    pub fn as_combo_box_field_mut(&mut self) -> Option<&mut PdfFormComboBoxField<'a>> {
+        match self {
+            PdfFormField::ComboBox(field) => Some(field),
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the underlying [PdfFormListBoxField] for this [PdfFormField],
     /// if this form field has a field type of [PdfFormField::ListBox].
     #[inline]
@@ -330,6 +430,16 @@ impl<'a> PdfFormField<'a> {
         }
     }
 
+    /// Returns a mutable reference to the underlying [PdfFormListBoxField]
+    /// for this [PdfFormField], if this form field has a field type of [PdfFormField::ListBox].
+    #[inline]
+    pub fn as_list_box_field_mut(&mut self) -> Option<&mut PdfFormListBoxField<'a>> {
+        match self {
+            PdfFormField::ListBox(field) => Some(field),
+            _ => None,
+        }
+    }
+
     /// Returns a reference to the underlying [PdfFormSignatureField] for this [PdfFormField],
     /// if this form field has a field type of [PdfFormField::Signature].
     #[inline]
@@ -422,6 +532,13 @@ pub trait PdfFormFieldCommon {
     /// For more information on "submit form" actions, refer to Section 8.6.4 of
     /// The PDF Reference Manual, version 1.7, starting on page 702.
     fn set_is_exported_on_submit(&mut self, is_exported: bool) -> Result<(), PdfiumError>;
+
+    /// Returns the script of the JavaScript action this [PdfFormField] runs when the given
+    /// event occurs, if any.
+    ///
+    /// Only JavaScript actions are returned; Pdfium does not expose additional actions
+    /// of any other type.
+    fn javascript(&self, event: PdfFormFieldActionEvent) -> Option<String>;
 }
 
 // Blanket implementation for all PdfFormFieldCommon types.
@@ -477,6 +594,11 @@ where
     fn set_is_exported_on_submit(&mut self, is_exported: bool) -> Result<(), PdfiumError> {
         self.update_one_flag_impl(PdfFormFieldFlags::NoExport, !is_exported)
     }
+
+    #[inline]
+    fn javascript(&self, event: PdfFormFieldActionEvent) -> Option<String> {
+        self.javascript_impl(event)
+    }
 }
 
 impl<'a> PdfFormFieldPrivate<'a> for PdfFormField<'a> {
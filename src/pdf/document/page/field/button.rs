@@ -3,6 +3,8 @@
 
 use crate::bindgen::{FPDF_ANNOTATION, FPDF_FORMHANDLE};
 use crate::bindings::PdfiumLibraryBindings;
+use crate::pdf::document::page::field::button_appearance::{ButtonAppearanceBuilder, ButtonKind};
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::private::internal::PdfFormFieldPrivate;
 
 #[cfg(doc)]
@@ -22,6 +24,7 @@ use {
 pub struct PdfFormPushButtonField<'a> {
     form_handle: FPDF_FORMHANDLE,
     annotation_handle: FPDF_ANNOTATION,
+    pending_field_changes: Option<PdfPendingFieldChanges>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -35,6 +38,22 @@ impl<'a> PdfFormPushButtonField<'a> {
         PdfFormPushButtonField {
             form_handle,
             annotation_handle,
+            pending_field_changes: None,
+            bindings,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_pdfium_with_document(
+        form_handle: FPDF_FORMHANDLE,
+        annotation_handle: FPDF_ANNOTATION,
+        pending_field_changes: PdfPendingFieldChanges,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfFormPushButtonField {
+            form_handle,
+            annotation_handle,
+            pending_field_changes: Some(pending_field_changes),
             bindings,
         }
     }
@@ -44,6 +63,33 @@ impl<'a> PdfFormPushButtonField<'a> {
     pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
         self.bindings
     }
+
+    /// Returns a builder for generating the visual appearance of this [PdfFormPushButtonField].
+    ///
+    /// The builder generates normal and down appearances showing the button's caption and
+    /// icon, along with the button's `/MK` appearance characteristics.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// push_button.set_appearance()
+    ///     .with_caption("Submit")
+    ///     .with_down_caption("Submitting")
+    ///     .with_image(&image_object)?
+    ///     .with_caption_position(ButtonCaptionPosition::CaptionRightOfIcon)
+    ///     .apply()?;
+    /// ```
+    pub fn set_appearance(&self) -> ButtonAppearanceBuilder<'_> {
+        ButtonAppearanceBuilder::new(
+            self.annotation_handle,
+            self.pending_field_changes.clone(),
+            self.bindings,
+            ButtonKind::PushButton,
+            self.get_string_value("NM"),
+            String::new(),
+            false,
+        )
+    }
 }
 
 impl<'a> PdfFormFieldPrivate<'a> for PdfFormPushButtonField<'a> {
@@ -0,0 +1,1490 @@
+//! Defines types and builders for setting the visual appearance of checkbox, radio button,
+//! and push button fields using PDF content streams.
+//!
+//! # Overview
+//!
+//! Unlike text fields, Pdfium does not generate appearance streams for button fields, so
+//! buttons created with `PdfPageAnnotations::create_widget_annotation()` have no visible
+//! appearance of their own. This module provides a builder API for generating them.
+//!
+//! A checkbox or radio button widget needs normal (`/N`) and down (`/D`) appearances for
+//! both its on and off states, held in appearance state dictionaries alongside the widget's
+//! `/MK` appearance characteristics. Pdfium can only give a widget a single appearance
+//! stream for each appearance mode, replacing any appearance state dictionary, so
+//! [ButtonAppearanceBuilder::apply()] immediately gives the widget a normal appearance
+//! showing its current state, and records the complete set of appearances. When the
+//! containing document is saved, the complete appearances are written to the widget
+//! as an incremental update.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! if let Some(checkbox) = widget.form_field().and_then(|field| field.as_checkbox_field()) {
+//!     checkbox
+//!         .set_appearance()
+//!         .with_style(PdfFormButtonStyle::Cross)
+//!         .with_border_color(PdfColor::BLACK)
+//!         .with_background_color(PdfColor::WHITE)
+//!         .apply()?;
+//! }
+//! ```
+
+use crate::bindgen::{FPDF_ANNOTATION, FS_RECTF};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::appearance_mode::PdfAppearanceMode;
+use crate::pdf::bitmap::PdfBitmapFormat;
+use crate::pdf::color::PdfColor;
+use crate::pdf::document::bookmark_tree::format_number;
+use crate::pdf::document::incremental_update::{
+    encode_name, encode_text_string, next_marker_id, trim, PdfIncrementalUpdate, PdfObjectId,
+    PdfRawDictionary,
+};
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
+use crate::pdf::document::page::field::radio_group::{
+    circle_path, PdfFormButtonStyle, PdfRadioGroup,
+};
+use crate::pdf::document::page::object::image::PdfPageImageObject;
+
+/// Border styles for button widgets, corresponding to the `/S` entry in a widget's
+/// border style dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonBorderStyle {
+    /// A solid line surrounding the widget.
+    Solid,
+    /// A dashed line surrounding the widget.
+    Dashed,
+    /// A simulated embossed border that makes the widget appear raised above the page.
+    Beveled,
+    /// A simulated engraved border that makes the widget appear recessed into the page.
+    Inset,
+    /// A single line along the bottom of the widget.
+    Underline,
+}
+
+impl ButtonBorderStyle {
+    #[inline]
+    fn as_pdf_name(&self) -> &'static str {
+        match self {
+            ButtonBorderStyle::Solid => "/S",
+            ButtonBorderStyle::Dashed => "/D",
+            ButtonBorderStyle::Beveled => "/B",
+            ButtonBorderStyle::Inset => "/I",
+            ButtonBorderStyle::Underline => "/U",
+        }
+    }
+}
+
+/// The position of a push button's caption relative to its icon, corresponding to the
+/// `/TP` entry in a widget's appearance characteristics dictionary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonCaptionPosition {
+    /// Only the caption is drawn.
+    CaptionOnly,
+    /// Only the icon is drawn.
+    IconOnly,
+    /// The caption is drawn below the icon.
+    CaptionBelowIcon,
+    /// The caption is drawn above the icon.
+    CaptionAboveIcon,
+    /// The caption is drawn to the right of the icon.
+    CaptionRightOfIcon,
+    /// The caption is drawn to the left of the icon.
+    CaptionLeftOfIcon,
+    /// The caption is drawn over the icon.
+    CaptionOverlaidOnIcon,
+}
+
+impl ButtonCaptionPosition {
+    #[inline]
+    fn as_pdf_value(&self) -> u8 {
+        match self {
+            ButtonCaptionPosition::CaptionOnly => 0,
+            ButtonCaptionPosition::IconOnly => 1,
+            ButtonCaptionPosition::CaptionBelowIcon => 2,
+            ButtonCaptionPosition::CaptionAboveIcon => 3,
+            ButtonCaptionPosition::CaptionRightOfIcon => 4,
+            ButtonCaptionPosition::CaptionLeftOfIcon => 5,
+            ButtonCaptionPosition::CaptionOverlaidOnIcon => 6,
+        }
+    }
+}
+
+/// The type of button field whose appearance is being generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ButtonKind {
+    Checkbox,
+    RadioButton,
+    PushButton,
+}
+
+/// The pixels of a push button's icon.
+#[derive(Debug, Clone)]
+struct ButtonIcon {
+    width: u32,
+    height: u32,
+    /// `true` for DeviceGray pixels, `false` for DeviceRGB pixels.
+    is_gray: bool,
+    pixels: Vec<u8>,
+    alpha: Option<Vec<u8>>,
+}
+
+impl ButtonIcon {
+    fn from_image_object(image: &PdfPageImageObject) -> Result<Self, PdfiumError> {
+        let bitmap = image.get_raw_bitmap()?;
+
+        let width = bitmap.width().max(0) as u32;
+        let height = bitmap.height().max(0) as u32;
+
+        if width == 0 || height == 0 {
+            return Err(PdfiumError::ImageError);
+        }
+
+        let format = bitmap.format()?;
+
+        if format == PdfBitmapFormat::Gray {
+            // Rows in the bitmap buffer may be padded, so we copy each row separately.
+
+            let bytes = bitmap.as_raw_bytes();
+
+            let stride = bytes.len() / height as usize;
+
+            let pixels = bytes
+                .chunks(stride)
+                .flat_map(|row| row.iter().take(width as usize).copied())
+                .collect();
+
+            return Ok(ButtonIcon {
+                width,
+                height,
+                is_gray: true,
+                pixels,
+                alpha: None,
+            });
+        }
+
+        let rgba = bitmap.as_rgba_bytes();
+
+        let pixels = rgba
+            .chunks_exact(4)
+            .flat_map(|pixel| pixel[..3].iter().copied())
+            .collect();
+
+        let alpha = rgba
+            .chunks_exact(4)
+            .map(|pixel| pixel[3])
+            .collect::<Vec<_>>();
+
+        let has_alpha = format == PdfBitmapFormat::BGRA && alpha.iter().any(|alpha| *alpha < 255);
+
+        Ok(ButtonIcon {
+            width,
+            height,
+            is_gray: false,
+            pixels,
+            alpha: if has_alpha { Some(alpha) } else { None },
+        })
+    }
+
+    #[inline]
+    fn color_space(&self) -> &'static str {
+        if self.is_gray {
+            "/DeviceGray"
+        } else {
+            "/DeviceRGB"
+        }
+    }
+}
+
+/// Configuration for button field appearance rendering.
+#[derive(Debug, Clone)]
+pub(crate) struct ButtonAppearanceConfig {
+    kind: ButtonKind,
+    width: f32,
+    height: f32,
+    border_color: Option<PdfColor>,
+    border_width: f32,
+    border_style: ButtonBorderStyle,
+    background_color: Option<PdfColor>,
+    style: PdfFormButtonStyle,
+    glyph_color: PdfColor,
+    caption: Option<String>,
+    down_caption: Option<String>,
+    caption_position: Option<ButtonCaptionPosition>,
+    font_size: Option<f32>,
+    text_color: PdfColor,
+    icon: Option<ButtonIcon>,
+}
+
+impl ButtonAppearanceConfig {
+    pub(crate) fn new(kind: ButtonKind) -> Self {
+        Self {
+            kind,
+            width: 0.0,
+            height: 0.0,
+            border_color: Some(PdfColor::BLACK),
+            border_width: 1.0,
+            border_style: ButtonBorderStyle::Solid,
+            background_color: if kind == ButtonKind::PushButton {
+                Some(PdfColor::new(192, 192, 192, 255))
+            } else {
+                None
+            },
+            style: if kind == ButtonKind::RadioButton {
+                PdfFormButtonStyle::Circle
+            } else {
+                PdfFormButtonStyle::Check
+            },
+            glyph_color: PdfColor::BLACK,
+            caption: None,
+            down_caption: None,
+            caption_position: None,
+            font_size: None,
+            text_color: PdfColor::BLACK,
+            icon: None,
+        }
+    }
+
+    /// Returns the width of the border actually drawn, taking into account whether a border
+    /// color has been set.
+    #[inline]
+    fn effective_border_width(&self) -> f32 {
+        if self.border_color.is_some() {
+            self.border_width.max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the distance from each edge of the widget to the area available for
+    /// the widget's glyph, caption, or icon.
+    fn content_inset(&self) -> f32 {
+        let border_width = self.effective_border_width();
+
+        match self.border_style {
+            ButtonBorderStyle::Beveled | ButtonBorderStyle::Inset => border_width * 2.0 + 1.0,
+            _ => border_width + 1.0,
+        }
+    }
+
+    /// Returns the position of the caption relative to the icon, defaulting to a position
+    /// that draws whichever of the two have been set.
+    fn caption_position(&self) -> ButtonCaptionPosition {
+        match self.caption_position {
+            Some(position) => position,
+            None => match (self.icon.is_some(), self.caption.is_some()) {
+                (true, true) => ButtonCaptionPosition::CaptionBelowIcon,
+                (true, false) => ButtonCaptionPosition::IconOnly,
+                _ => ButtonCaptionPosition::CaptionOnly,
+            },
+        }
+    }
+
+    /// Returns the font size used for captions, either as configured or sized to fit
+    /// the widget.
+    fn caption_font_size(&self) -> f32 {
+        match self.font_size {
+            Some(font_size) if font_size > 0.0 => font_size,
+            _ => {
+                let available = self.height - self.content_inset() * 2.0;
+
+                let available = match self.caption_position() {
+                    ButtonCaptionPosition::CaptionBelowIcon
+                    | ButtonCaptionPosition::CaptionAboveIcon
+                        if self.icon.is_some() =>
+                    {
+                        available / 3.0
+                    }
+                    _ => available,
+                };
+
+                (available * 0.7).clamp(1.0, 12.0)
+            }
+        }
+    }
+
+    /// Builds the content stream drawing this button in the given state, in a coordinate
+    /// space with its origin at the bottom-left corner of the widget. The icon, if any,
+    /// is drawn using the `/FRM` form XObject in the stream's resource dictionary.
+    ///
+    /// Returns [PdfiumError::AppearanceTextNotEncodable] if the button's caption cannot
+    /// be drawn using the standard Helvetica font.
+    fn content(&self, is_on: bool, is_down: bool) -> Result<String, PdfiumError> {
+        let mut stream = String::new();
+
+        self.push_background(&mut stream, is_down);
+        self.push_border(&mut stream, is_down);
+
+        match self.kind {
+            ButtonKind::Checkbox | ButtonKind::RadioButton => {
+                if is_on {
+                    self.push_glyph(&mut stream);
+                }
+            }
+            ButtonKind::PushButton => self.push_caption_and_icon(&mut stream, is_down)?,
+        }
+
+        Ok(stream)
+    }
+
+    /// Returns the background color drawn in the given state. Down appearances use
+    /// a darker background, or a gray background if no background color is set.
+    fn background(&self, is_down: bool) -> Option<PdfColor> {
+        match (self.background_color, is_down) {
+            (Some(color), true) => Some(PdfColor::new(
+                (color.red() as f32 * 0.75) as u8,
+                (color.green() as f32 * 0.75) as u8,
+                (color.blue() as f32 * 0.75) as u8,
+                255,
+            )),
+            (None, true) => Some(PdfColor::new(192, 192, 192, 255)),
+            (color, false) => color,
+        }
+    }
+
+    fn push_background(&self, stream: &mut String, is_down: bool) {
+        if let Some(color) = self.background(is_down) {
+            let n = format_number;
+
+            if self.kind == ButtonKind::RadioButton {
+                let (x, y) = (self.width / 2.0, self.height / 2.0);
+
+                let radius = self.width.min(self.height) / 2.0;
+
+                stream.push_str(&format!(
+                    "q {} rg {} f Q\n",
+                    color_components(color),
+                    circle_path(x, y, radius)
+                ));
+            } else {
+                stream.push_str(&format!(
+                    "q {} rg 0 0 {} {} re f Q\n",
+                    color_components(color),
+                    n(self.width),
+                    n(self.height)
+                ));
+            }
+        }
+    }
+
+    fn push_border(&self, stream: &mut String, is_down: bool) {
+        let color = match self.border_color {
+            Some(color) if self.border_width > 0.0 => color,
+            _ => return,
+        };
+
+        let n = format_number;
+
+        let (w, h, b) = (self.width, self.height, self.border_width);
+
+        if self.kind == ButtonKind::RadioButton {
+            let dash = if self.border_style == ButtonBorderStyle::Dashed {
+                "[3] 0 d "
+            } else {
+                ""
+            };
+
+            stream.push_str(&format!(
+                "q {} RG {} w {}{} S Q\n",
+                color_components(color),
+                n(b),
+                dash,
+                circle_path(w / 2.0, h / 2.0, (w.min(h) - b) / 2.0)
+            ));
+
+            return;
+        }
+
+        // Beveled and inset borders draw a lighter and a darker band inside the border,
+        // with the bands swapped when a beveled button is pressed.
+
+        if matches!(
+            self.border_style,
+            ButtonBorderStyle::Beveled | ButtonBorderStyle::Inset
+        ) {
+            let (top_left, bottom_right) =
+                if self.border_style == ButtonBorderStyle::Beveled && !is_down {
+                    ("1 g", "0.5 g")
+                } else {
+                    ("0.5 g", "0.75 g")
+                };
+
+            stream.push_str(&format!(
+                "q {} {} {} m {} {} l {} {} l {} {} l {} {} l {} {} l f Q\n",
+                top_left,
+                n(b),
+                n(b),
+                n(b),
+                n(h - b),
+                n(w - b),
+                n(h - b),
+                n(w - b * 2.0),
+                n(h - b * 2.0),
+                n(b * 2.0),
+                n(h - b * 2.0),
+                n(b * 2.0),
+                n(b * 2.0),
+            ));
+
+            stream.push_str(&format!(
+                "q {} {} {} m {} {} l {} {} l {} {} l {} {} l {} {} l f Q\n",
+                bottom_right,
+                n(w - b),
+                n(h - b),
+                n(w - b),
+                n(b),
+                n(b),
+                n(b),
+                n(b * 2.0),
+                n(b * 2.0),
+                n(w - b * 2.0),
+                n(b * 2.0),
+                n(w - b * 2.0),
+                n(h - b * 2.0),
+            ));
+        }
+
+        let path = match self.border_style {
+            ButtonBorderStyle::Underline => {
+                format!("0 {} m {} {} l", n(b / 2.0), n(w), n(b / 2.0))
+            }
+            _ => format!("{} {} {} {} re", n(b / 2.0), n(b / 2.0), n(w - b), n(h - b)),
+        };
+
+        let dash = if self.border_style == ButtonBorderStyle::Dashed {
+            "[3] 0 d "
+        } else {
+            ""
+        };
+
+        stream.push_str(&format!(
+            "q {} RG {} w {}{} S Q\n",
+            color_components(color),
+            n(b),
+            dash,
+            path
+        ));
+    }
+
+    /// Draws the glyph of a selected checkbox or radio button. The built-in styles are drawn
+    /// as paths, so they need no font resources; custom styles use the ZapfDingbats font.
+    fn push_glyph(&self, stream: &mut String) {
+        let n = format_number;
+
+        let mut size = self.width.min(self.height) - self.content_inset() * 2.0;
+
+        if self.kind == ButtonKind::RadioButton {
+            // Keep the glyph inside the circular border.
+
+            size *= 0.75;
+        }
+
+        if size <= 0.0 {
+            return;
+        }
+
+        let (x, y) = ((self.width - size) / 2.0, (self.height - size) / 2.0);
+
+        let point = |u: f32, v: f32| format!("{} {}", n(x + u * size), n(y + v * size));
+
+        let color = color_components(self.glyph_color);
+
+        match self.style {
+            PdfFormButtonStyle::Circle => {
+                stream.push_str(&format!(
+                    "q {} rg {} f Q\n",
+                    color,
+                    circle_path(self.width / 2.0, self.height / 2.0, size / 3.0)
+                ));
+            }
+            PdfFormButtonStyle::Check => {
+                stream.push_str(&format!(
+                    "q {} RG {} w 1 J 1 j {} m {} l {} l S Q\n",
+                    color,
+                    n(size * 0.14),
+                    point(0.15, 0.5),
+                    point(0.4, 0.2),
+                    point(0.85, 0.8)
+                ));
+            }
+            PdfFormButtonStyle::Cross => {
+                stream.push_str(&format!(
+                    "q {} RG {} w 1 J {} m {} l {} m {} l S Q\n",
+                    color,
+                    n(size * 0.14),
+                    point(0.2, 0.2),
+                    point(0.8, 0.8),
+                    point(0.2, 0.8),
+                    point(0.8, 0.2)
+                ));
+            }
+            PdfFormButtonStyle::Custom(glyph) => {
+                // ZapfDingbats glyphs are roughly 0.8 em wide and 0.7 em tall.
+
+                stream.push_str(&format!(
+                    "q {} rg BT /ZaDb {} Tf {} Td ({}) Tj ET Q\n",
+                    color,
+                    n(size),
+                    point(0.1, 0.15),
+                    escape_bytes(&[glyph])
+                ));
+            }
+        }
+    }
+
+    fn push_caption_and_icon(&self, stream: &mut String, is_down: bool) -> Result<(), PdfiumError> {
+        let n = format_number;
+
+        let inset = self.content_inset();
+
+        let (x, y, w, h) = (
+            inset,
+            inset,
+            self.width - inset * 2.0,
+            self.height - inset * 2.0,
+        );
+
+        if w <= 0.0 || h <= 0.0 {
+            return Ok(());
+        }
+
+        let position = self.caption_position();
+
+        let caption = if is_down {
+            self.down_caption.as_ref().or(self.caption.as_ref())
+        } else {
+            self.caption.as_ref()
+        }
+        .filter(|_| position != ButtonCaptionPosition::IconOnly);
+
+        let icon = self
+            .icon
+            .as_ref()
+            .filter(|_| position != ButtonCaptionPosition::CaptionOnly);
+
+        let font_size = self.caption_font_size();
+
+        let line_height = font_size * 1.2;
+
+        let caption_width = caption
+            .map(|caption| text_width(caption, font_size) + 2.0)
+            .unwrap_or(0.0)
+            .min(w / 2.0);
+
+        // Divide the available area between the icon and the caption.
+
+        let full = (x, y, w, h);
+
+        let (icon_area, caption_area) = match (icon.is_some(), caption.is_some(), position) {
+            (true, true, ButtonCaptionPosition::CaptionBelowIcon) => (
+                (x, y + line_height, w, h - line_height),
+                (x, y, w, line_height),
+            ),
+            (true, true, ButtonCaptionPosition::CaptionAboveIcon) => (
+                (x, y, w, h - line_height),
+                (x, y + h - line_height, w, line_height),
+            ),
+            (true, true, ButtonCaptionPosition::CaptionRightOfIcon) => (
+                (x, y, w - caption_width, h),
+                (x + w - caption_width, y, caption_width, h),
+            ),
+            (true, true, ButtonCaptionPosition::CaptionLeftOfIcon) => (
+                (x + caption_width, y, w - caption_width, h),
+                (x, y, caption_width, h),
+            ),
+            _ => (full, full),
+        };
+
+        stream.push_str(&format!("q {} {} {} {} re W n\n", n(x), n(y), n(w), n(h)));
+
+        if let Some(icon) = icon {
+            let (area_x, area_y, area_w, area_h) = icon_area;
+
+            let scale = (area_w / icon.width as f32).min(area_h / icon.height as f32);
+
+            if scale > 0.0 {
+                let (icon_w, icon_h) = (icon.width as f32 * scale, icon.height as f32 * scale);
+
+                let (icon_x, icon_y) = (
+                    area_x + (area_w - icon_w) / 2.0,
+                    area_y + (area_h - icon_h) / 2.0,
+                );
+
+                // The icon's form XObject has a bounding box the size of the image in pixels,
+                // so we scale it to fit.
+
+                stream.push_str(&format!(
+                    "q {} 0 0 {} {} {} cm /FRM Do Q\n",
+                    n(scale),
+                    n(scale),
+                    n(icon_x),
+                    n(icon_y)
+                ));
+            }
+        }
+
+        if let Some(caption) = caption {
+            let (area_x, area_y, area_w, area_h) = caption_area;
+
+            let text_x = area_x + (area_w - text_width(caption, font_size)) / 2.0;
+
+            // Helvetica capitals are roughly 0.7 em tall.
+
+            let text_y = area_y + (area_h - font_size * 0.7) / 2.0;
+
+            stream.push_str(&format!(
+                "BT /Helv {} Tf {} rg {} {} Td ({}) Tj ET\n",
+                n(font_size),
+                color_components(self.text_color),
+                n(text_x),
+                n(text_y),
+                escape_text(caption)?
+            ));
+        }
+
+        stream.push_str("Q\n");
+
+        Ok(())
+    }
+
+    /// Returns the `/MK` appearance characteristics dictionary for this button, referring
+    /// to the given icon form XObject, if any.
+    fn appearance_characteristics(&self, icon: Option<PdfObjectId>) -> Vec<u8> {
+        let mut result = PdfRawDictionary::new();
+
+        if let Some(color) = self.border_color {
+            result.set("BC", format!("[{}]", color_components(color)));
+        }
+
+        if let Some(color) = self.background_color {
+            result.set("BG", format!("[{}]", color_components(color)));
+        }
+
+        match self.kind {
+            ButtonKind::Checkbox | ButtonKind::RadioButton => {
+                result.set(
+                    "CA",
+                    encode_text_string(&(self.style.glyph() as char).to_string()),
+                );
+            }
+            ButtonKind::PushButton => {
+                if let Some(caption) = self.caption.as_ref() {
+                    result.set("CA", encode_text_string(caption));
+                }
+
+                if let Some(caption) = self.down_caption.as_ref() {
+                    result.set("AC", encode_text_string(caption));
+                }
+
+                if let Some(icon) = icon {
+                    result.set("I", icon.to_reference());
+                }
+
+                result.set("TP", self.caption_position().as_pdf_value().to_string());
+            }
+        }
+
+        result.to_bytes()
+    }
+
+    /// Returns the `/BS` border style dictionary for this button.
+    fn border_style_dictionary(&self) -> Vec<u8> {
+        let mut result = PdfRawDictionary::new();
+
+        result.set("W", format_number(self.effective_border_width()));
+        result.set("S", self.border_style.as_pdf_name());
+
+        if self.border_style == ButtonBorderStyle::Dashed {
+            result.set("D", "[3]");
+        }
+
+        result.to_bytes()
+    }
+
+    /// Returns the `/DA` default appearance string for this button.
+    fn default_appearance(&self) -> String {
+        match self.kind {
+            ButtonKind::Checkbox | ButtonKind::RadioButton => {
+                format!("/ZaDb 0 Tf {} rg", color_components(self.glyph_color))
+            }
+            ButtonKind::PushButton => format!(
+                "/Helv {} Tf {} rg",
+                format_number(self.font_size.unwrap_or(0.0)),
+                color_components(self.text_color)
+            ),
+        }
+    }
+}
+
+/// Builder for constructing and applying the visual appearance of a checkbox, radio button,
+/// or push button field.
+///
+/// This builder collects configuration and generates PDF content streams that draw the
+/// button's border, background, and either its check glyph or its caption and icon.
+/// Appearances are generated for the normal (`/N`) and down (`/D`) appearance modes;
+/// checkboxes and radio buttons have both appearances for both their on and off states.
+///
+/// Pdfium cannot hold more than one appearance stream per appearance mode, so applying the
+/// builder immediately sets only the normal appearance of the widget's current state, which
+/// is shown when the page containing the widget is rendered or flattened. The complete set
+/// of appearances, along with the widget's `/MK` appearance characteristics and `/BS` border
+/// style, are written to the widget when the containing `PdfDocument` is saved. Until then,
+/// checking or unchecking the widget does not change its appearance, and a push button's
+/// icon and custom glyphs drawn using the ZapfDingbats font are not shown. Since the
+/// appearances are written as an incremental update appended to the bytes written by Pdfium,
+/// a document containing buttons with generated appearances cannot be saved in encrypted form.
+pub struct ButtonAppearanceBuilder<'a> {
+    bindings: &'a dyn PdfiumLibraryBindings,
+    annotation_handle: FPDF_ANNOTATION,
+    pending_field_changes: Option<PdfPendingFieldChanges>,
+    annotation_name: Option<String>,
+    on_state: String,
+    is_on: bool,
+    config: ButtonAppearanceConfig,
+}
+
+impl<'a> ButtonAppearanceBuilder<'a> {
+    const MARKER_PREFIX: &'static str = "pdfium-render-button-appearance-";
+
+    /// Creates a new builder for the given annotation with field information.
+    pub(crate) fn new(
+        annotation_handle: FPDF_ANNOTATION,
+        pending_field_changes: Option<PdfPendingFieldChanges>,
+        bindings: &'a dyn PdfiumLibraryBindings,
+        kind: ButtonKind,
+        annotation_name: Option<String>,
+        on_state: String,
+        is_on: bool,
+    ) -> Self {
+        Self {
+            bindings,
+            annotation_handle,
+            pending_field_changes,
+            annotation_name,
+            on_state,
+            is_on,
+            config: ButtonAppearanceConfig::new(kind),
+        }
+    }
+
+    /// Sets the border color. Borders are drawn in black by default.
+    pub fn with_border_color(mut self, color: PdfColor) -> Self {
+        self.config.border_color = Some(color);
+        self
+    }
+
+    /// Removes the border.
+    pub fn without_border(mut self) -> Self {
+        self.config.border_color = None;
+        self
+    }
+
+    /// Sets the border width in points. The default border width is 1 point.
+    pub fn with_border_width(mut self, width: f32) -> Self {
+        self.config.border_width = width;
+        self
+    }
+
+    /// Sets the border style. The default border style is [ButtonBorderStyle::Solid].
+    pub fn with_border_style(mut self, style: ButtonBorderStyle) -> Self {
+        self.config.border_style = style;
+        self
+    }
+
+    /// Sets the background color. Push buttons have a light gray background by default;
+    /// checkboxes and radio buttons have no background.
+    pub fn with_background_color(mut self, color: PdfColor) -> Self {
+        self.config.background_color = Some(color);
+        self
+    }
+
+    /// Removes the background.
+    pub fn without_background(mut self) -> Self {
+        self.config.background_color = None;
+        self
+    }
+
+    /// Sets the glyph drawn when a checkbox or radio button is selected. Checkboxes use
+    /// [PdfFormButtonStyle::Check] and radio buttons use [PdfFormButtonStyle::Circle]
+    /// by default. Push buttons have no glyph, so this setting is ignored for them.
+    pub fn with_style(mut self, style: PdfFormButtonStyle) -> Self {
+        self.config.style = style;
+        self
+    }
+
+    /// Sets the color of the glyph drawn when a checkbox or radio button is selected.
+    pub fn with_glyph_color(mut self, color: PdfColor) -> Self {
+        self.config.glyph_color = color;
+        self
+    }
+
+    /// Sets the caption of a push button.
+    pub fn with_caption(mut self, caption: &str) -> Self {
+        self.config.caption = Some(caption.to_string());
+        self
+    }
+
+    /// Sets the caption displayed while a push button is pressed. If not set, the normal
+    /// caption is displayed.
+    pub fn with_down_caption(mut self, caption: &str) -> Self {
+        self.config.down_caption = Some(caption.to_string());
+        self
+    }
+
+    /// Sets the position of a push button's caption relative to its icon. If not set,
+    /// captions are drawn below icons.
+    pub fn with_caption_position(mut self, position: ButtonCaptionPosition) -> Self {
+        self.config.caption_position = Some(position);
+        self
+    }
+
+    /// Sets the font size of a push button's caption in points. If not set, the caption
+    /// is sized to fit the button.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.config.font_size = Some(font_size);
+        self
+    }
+
+    /// Sets the color of a push button's caption.
+    pub fn with_text_color(mut self, color: PdfColor) -> Self {
+        self.config.text_color = color;
+        self
+    }
+
+    /// Sets the icon of a push button to the image held by the given [PdfPageImageObject].
+    /// The image is scaled proportionally to fit the button.
+    pub fn with_image(mut self, image: &PdfPageImageObject) -> Result<Self, PdfiumError> {
+        self.config.icon = Some(ButtonIcon::from_image_object(image)?);
+        Ok(self)
+    }
+
+    /// Applies the button appearance to the field.
+    ///
+    /// This sets the widget's normal appearance for its current state immediately, and records
+    /// the appearances of all states to be written when the containing `PdfDocument` is saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field is not attached to a document, if the button's caption
+    /// contains characters that cannot be drawn using the standard Helvetica font, or if
+    /// PDFium fails to set the appearance stream or to tag the widget so that it can be found
+    /// when the document is saved.
+    pub fn apply(self) -> Result<(), PdfiumError> {
+        let pending_field_changes = self
+            .pending_field_changes
+            .ok_or(PdfiumError::OwnershipNotAttachedToDocument)?;
+
+        let mut rect = FS_RECTF {
+            left: 0.0,
+            top: 0.0,
+            right: 0.0,
+            bottom: 0.0,
+        };
+
+        if !self.bindings.is_true(
+            self.bindings
+                .FPDFAnnot_GetRect(self.annotation_handle, &mut rect),
+        ) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        let mut config = self.config;
+
+        config.width = (rect.right - rect.left).abs();
+        config.height = (rect.top - rect.bottom).abs();
+
+        // Check that the captions can be drawn now, rather than when the document is saved.
+
+        for caption in config.caption.iter().chain(config.down_caption.iter()) {
+            escape_text(caption)?;
+        }
+
+        // Appearance streams set through Pdfium use the widget's rectangle as their
+        // bounding box, so we move the origin to the widget's bottom-left corner.
+
+        let content = format!(
+            "q 1 0 0 1 {} {} cm\n{}Q\n",
+            format_number(rect.left.min(rect.right)),
+            format_number(rect.bottom.min(rect.top)),
+            config.content(self.is_on, false)?
+        );
+
+        if !self.bindings.is_true(self.bindings.FPDFAnnot_SetAP_str(
+            self.annotation_handle,
+            PdfAppearanceMode::Normal.as_pdfium(),
+            &content,
+        )) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        // Pdfium provides no way to find the object number of an annotation, so we tag the
+        // widget with a unique annotation name that can be found in the saved bytes. Widgets
+        // already tagged by a new radio button group, or by an earlier call to this function,
+        // keep their existing tag.
+
+        let (marker, original_name) = match self.annotation_name {
+            Some(name)
+                if name.starts_with(PdfRadioGroup::MARKER_PREFIX)
+                    || name.starts_with(Self::MARKER_PREFIX) =>
+            {
+                (name, None)
+            }
+            original_name => {
+                let marker = format!("{}{}", Self::MARKER_PREFIX, next_marker_id());
+
+                if !self
+                    .bindings
+                    .is_true(self.bindings.FPDFAnnot_SetStringValue_str(
+                        self.annotation_handle,
+                        "NM",
+                        &marker,
+                    ))
+                {
+                    return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+                }
+
+                (marker, original_name)
+            }
+        };
+
+        pending_field_changes.set_button_appearance(PdfButtonAppearance {
+            marker,
+            original_name,
+            on_state: self.on_state.trim_start_matches('/').to_string(),
+            config,
+        });
+
+        Ok(())
+    }
+}
+
+/// The complete appearance of a single button widget, waiting to be written when the
+/// containing document is saved.
+pub(crate) struct PdfButtonAppearance {
+    /// The unique `/NM` value used to locate the widget in a saved file.
+    pub(crate) marker: String,
+
+    /// The widget's annotation name before it was replaced by the marker, if any.
+    pub(crate) original_name: Option<String>,
+
+    /// The name of the widget's on state, without the leading `/`, used if the widget
+    /// has no existing on state when the document is saved.
+    pub(crate) on_state: String,
+
+    config: ButtonAppearanceConfig,
+}
+
+impl PdfButtonAppearance {
+    /// Writes this appearance to its widget in the given saved document.
+    ///
+    /// Returns [PdfiumError::FormFieldWidgetNotFound] if the widget cannot be found in the
+    /// saved document.
+    pub(crate) fn apply_to(&self, update: &mut PdfIncrementalUpdate) -> Result<(), PdfiumError> {
+        let needle = format!("({})", self.marker);
+
+        let id = update
+            .find_object_containing(needle.as_bytes())
+            .map_err(|_| PdfiumError::FormFieldWidgetNotFound)?;
+
+        let mut dictionary = update.get_dictionary(id)?;
+
+        // Keep the widget's existing on state, if it has one, so that its appearance state
+        // continues to match its value.
+
+        let on_state = existing_on_state(&dictionary)
+            .or_else(|| {
+                dictionary
+                    .get("AS")
+                    .map(|state| trim(state).to_vec())
+                    .filter(|state| state.starts_with(b"/") && state.as_slice() != b"/Off")
+            })
+            .unwrap_or_else(|| encode_name(&self.on_state));
+
+        self.write_to(update, &mut dictionary, &on_state)?;
+
+        // Restore the widget's original name. The markers of widgets in new radio button
+        // groups are removed when the group is written.
+
+        match self.original_name.as_ref() {
+            Some(name) => dictionary.set("NM", encode_text_string(name)),
+            None => {
+                if self
+                    .marker
+                    .starts_with(ButtonAppearanceBuilder::MARKER_PREFIX)
+                {
+                    dictionary.remove("NM");
+                }
+            }
+        }
+
+        update.set_object(id, dictionary.to_bytes());
+
+        Ok(())
+    }
+
+    /// Sets the appearance entries of the given widget dictionary in the given saved document,
+    /// naming the on state of a checkbox or radio button using the given name, including
+    /// the leading `/`.
+    pub(crate) fn write_to(
+        &self,
+        update: &mut PdfIncrementalUpdate,
+        dictionary: &mut PdfRawDictionary,
+        on_state: &[u8],
+    ) -> Result<(), PdfiumError> {
+        let icon = match self.config.icon.as_ref() {
+            Some(icon) if self.config.kind == ButtonKind::PushButton => {
+                Some(write_icon(update, icon))
+            }
+            _ => None,
+        };
+
+        let mut resources = b"<</Font <</Helv <</Type /Font /Subtype /Type1 /BaseFont /Helvetica \
+            /Encoding /WinAnsiEncoding>> /ZaDb <</Type /Font /Subtype /Type1 \
+            /BaseFont /ZapfDingbats>>>>"
+            .to_vec();
+
+        if let Some(icon) = icon {
+            resources.extend_from_slice(b" /XObject <</FRM ");
+            resources.extend_from_slice(&icon.to_reference());
+            resources.extend_from_slice(b">>");
+        }
+
+        resources.extend_from_slice(b">>");
+
+        let mut stream = |is_on: bool, is_down: bool| -> Result<String, PdfiumError> {
+            let content = self.config.content(is_on, is_down)?;
+
+            let id = update.add_object(form_xobject(
+                self.config.width,
+                self.config.height,
+                &resources,
+                content.as_bytes(),
+            ));
+
+            Ok(String::from_utf8_lossy(&id.to_reference()).into_owned())
+        };
+
+        match self.config.kind {
+            ButtonKind::PushButton => {
+                let normal = stream(false, false)?;
+                let down = stream(false, true)?;
+
+                dictionary.set("AP", format!("<</N {} /D {}>>", normal, down));
+                dictionary.remove("AS");
+            }
+            ButtonKind::Checkbox | ButtonKind::RadioButton => {
+                let on_state = String::from_utf8_lossy(on_state).into_owned();
+
+                let normal = format!(
+                    "<<{} {} /Off {}>>",
+                    on_state,
+                    stream(true, false)?,
+                    stream(false, false)?
+                );
+
+                let down = format!(
+                    "<<{} {} /Off {}>>",
+                    on_state,
+                    stream(true, true)?,
+                    stream(false, true)?
+                );
+
+                dictionary.set("AP", format!("<</N {} /D {}>>", normal, down));
+
+                if !dictionary.contains_key("AS") {
+                    dictionary.set("AS", "/Off");
+                }
+            }
+        }
+
+        dictionary.set("MK", self.config.appearance_characteristics(icon));
+        dictionary.set("BS", self.config.border_style_dictionary());
+        dictionary.set("DA", encode_text_string(&self.config.default_appearance()));
+
+        Ok(())
+    }
+}
+
+/// Returns the name of the on state in the normal appearance state dictionary of the given
+/// widget, including the leading `/`, if the widget has one.
+fn existing_on_state(widget: &PdfRawDictionary) -> Option<Vec<u8>> {
+    let appearance = PdfRawDictionary::parse(widget.get("AP")?).ok()?;
+
+    let normal = PdfRawDictionary::parse(appearance.get("N")?).ok()?;
+
+    let on_state = normal.keys().find(|key| *key != b"Off")?;
+
+    Some([b"/", on_state].concat())
+}
+
+/// Writes the given icon to the given update as an image XObject wrapped in a form XObject
+/// with a bounding box the size of the image in pixels, returning the form XObject.
+fn write_icon(update: &mut PdfIncrementalUpdate, icon: &ButtonIcon) -> PdfObjectId {
+    let soft_mask = icon.alpha.as_ref().map(|alpha| {
+        let reference = update.add_object(stream_object(
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} \
+                /ColorSpace /DeviceGray /BitsPerComponent 8",
+                icon.width, icon.height
+            ),
+            alpha,
+        ));
+
+        String::from_utf8_lossy(&reference.to_reference()).into_owned()
+    });
+
+    let image = update.add_object(stream_object(
+        &format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} \
+            /ColorSpace {} /BitsPerComponent 8{}",
+            icon.width,
+            icon.height,
+            icon.color_space(),
+            soft_mask
+                .map(|soft_mask| format!(" /SMask {}", soft_mask))
+                .unwrap_or_default()
+        ),
+        &icon.pixels,
+    ));
+
+    let content = format!("q {} 0 0 {} 0 0 cm /Img Do Q", icon.width, icon.height);
+
+    update.add_object(form_xobject(
+        icon.width as f32,
+        icon.height as f32,
+        format!(
+            "<</XObject <</Img {}>>>>",
+            String::from_utf8_lossy(&image.to_reference())
+        )
+        .as_bytes(),
+        content.as_bytes(),
+    ))
+}
+
+/// Returns a complete form XObject stream with the given size, resources, and content.
+pub(crate) fn form_xobject(width: f32, height: f32, resources: &[u8], content: &[u8]) -> Vec<u8> {
+    stream_object(
+        &format!(
+            "/Type /XObject /Subtype /Form /BBox [0 0 {} {}] /Resources {}",
+            format_number(width),
+            format_number(height),
+            String::from_utf8_lossy(resources)
+        ),
+        content,
+    )
+}
+
+/// Returns a complete stream object with the given dictionary entries and data.
+pub(crate) fn stream_object(entries: &str, data: &[u8]) -> Vec<u8> {
+    let mut result = format!("<<{} /Length {}>>\nstream\n", entries, data.len()).into_bytes();
+
+    result.extend_from_slice(data);
+    result.extend_from_slice(b"\nendstream");
+
+    result
+}
+
+/// Returns the components of the given color as operands for the `rg` and `RG` operators.
+pub(crate) fn color_components(color: PdfColor) -> String {
+    format!(
+        "{} {} {}",
+        format_number(color.red() as f32 / 255.0),
+        format_number(color.green() as f32 / 255.0),
+        format_number(color.blue() as f32 / 255.0)
+    )
+}
+
+/// Returns the approximate width of the given text in Helvetica at the given font size.
+/// This is a rough approximation - proper implementation would require font metrics.
+#[inline]
+pub(crate) fn text_width(text: &str, font_size: f32) -> f32 {
+    text.chars().count() as f32 * font_size * 0.5
+}
+
+/// Escapes the given text for use in a literal string in a content stream drawn using
+/// a standard font with WinAnsiEncoding.
+///
+/// Returns [PdfiumError::AppearanceTextNotEncodable] if the text contains characters that
+/// WinAnsiEncoding cannot represent.
+pub(crate) fn escape_text(text: &str) -> Result<String, PdfiumError> {
+    let bytes = text
+        .chars()
+        .map(win_ansi_byte)
+        .collect::<Option<Vec<_>>>()
+        .ok_or(PdfiumError::AppearanceTextNotEncodable)?;
+
+    Ok(escape_bytes(&bytes))
+}
+
+/// Escapes the given character codes for use in a literal string in a content stream.
+/// Codes outside the printable ASCII range are written as octal escapes, so that the
+/// content stream remains ASCII.
+pub(crate) fn escape_bytes(bytes: &[u8]) -> String {
+    let mut result = String::with_capacity(bytes.len());
+
+    for byte in bytes {
+        match byte {
+            b'\\' | b'(' | b')' => {
+                result.push('\\');
+                result.push(*byte as char);
+            }
+            b' '..=b'~' => result.push(*byte as char),
+            _ => result.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+
+    result
+}
+
+/// Returns the character code of the given character in WinAnsiEncoding, if it has one.
+fn win_ansi_byte(c: char) -> Option<u8> {
+    let byte = match c {
+        ' '..='~' | '\u{a0}'..='\u{ff}' => c as u8,
+        '\u{20ac}' => 0x80,
+        '\u{201a}' => 0x82,
+        '\u{0192}' => 0x83,
+        '\u{201e}' => 0x84,
+        '\u{2026}' => 0x85,
+        '\u{2020}' => 0x86,
+        '\u{2021}' => 0x87,
+        '\u{02c6}' => 0x88,
+        '\u{2030}' => 0x89,
+        '\u{0160}' => 0x8a,
+        '\u{2039}' => 0x8b,
+        '\u{0152}' => 0x8c,
+        '\u{017d}' => 0x8e,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' => 0x97,
+        '\u{02dc}' => 0x98,
+        '\u{2122}' => 0x99,
+        '\u{0161}' => 0x9a,
+        '\u{203a}' => 0x9b,
+        '\u{0153}' => 0x9c,
+        '\u{017e}' => 0x9e,
+        '\u{0178}' => 0x9f,
+        _ => return None,
+    };
+
+    Some(byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "pdfium_future")]
+    use crate::prelude::*;
+
+    #[cfg(feature = "pdfium_future")]
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    #[cfg(feature = "pdfium_future")]
+    fn test_checkbox_appearance() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+        let mut document = pdfium.create_new_pdf()?;
+
+        document.ensure_acro_form()?;
+
+        let form_handle = document.init_form_fill_environment()?;
+
+        let mut page = document
+            .pages_mut()
+            .create_page_at_start(PdfPagePaperSize::a4())?;
+
+        let mut widget = page.annotations_mut().create_widget_annotation(
+            form_handle,
+            "Agree",
+            PdfFormFieldType::Checkbox,
+            PdfRect::new_from_values(700.0, 100.0, 714.0, 114.0),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+
+        let checkbox = widget
+            .form_field_mut()
+            .and_then(|field| field.as_checkbox_field_mut())
+            .unwrap();
+
+        checkbox
+            .set_appearance()
+            .with_style(PdfFormButtonStyle::Cross)
+            .with_background_color(PdfColor::WHITE)
+            .apply()?;
+
+        checkbox.set_checked(true)?;
+
+        let bytes = document.save_to_bytes()?;
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(text.contains("/AP <</N <</Yes "));
+        assert!(text.contains(" /D <</Yes "));
+        assert!(text.contains("/AS /Yes"));
+        assert!(text.contains("/MK <</BC [0 0 0]\n/BG [1 1 1]\n/CA (8)\n>>"));
+        assert!(text.contains("/BS <</W 1\n/S /S\n>>"));
+        assert!(!text.contains(ButtonAppearanceBuilder::MARKER_PREFIX));
+
+        // Pdfium must read the saved on and off states back.
+
+        let mut document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        document.init_form_fill_environment()?;
+
+        let page = document.pages().first()?;
+
+        let mut widget = page.annotations().get(0)?;
+
+        let checkbox = widget
+            .as_form_field_mut()
+            .and_then(|field| field.as_checkbox_field_mut())
+            .unwrap();
+
+        assert!(checkbox.is_checked()?);
+
+        checkbox.set_checked(false)?;
+
+        assert!(!checkbox.is_checked()?);
+
+        checkbox.set_checked(true)?;
+
+        assert!(checkbox.is_checked()?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "pdfium_future")]
+    fn test_checkbox_appearance_before_save() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+        let mut document = pdfium.create_new_pdf()?;
+
+        document.ensure_acro_form()?;
+
+        let form_handle = document.init_form_fill_environment()?;
+
+        let mut page = document
+            .pages_mut()
+            .create_page_at_start(PdfPagePaperSize::a4())?;
+
+        let mut widget = page.annotations_mut().create_widget_annotation(
+            form_handle,
+            "Agree",
+            PdfFormFieldType::Checkbox,
+            PdfRect::new_from_values(600.0, 100.0, 640.0, 140.0),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+
+        let checkbox = widget
+            .form_field_mut()
+            .and_then(|field| field.as_checkbox_field_mut())
+            .unwrap();
+
+        checkbox.set_checked(true)?;
+
+        checkbox
+            .set_appearance()
+            .with_style(PdfFormButtonStyle::Cross)
+            .with_background_color(PdfColor::new(255, 0, 0, 255))
+            .apply()?;
+
+        // The generated appearance must not change the checkbox's on state.
+
+        assert!(checkbox.is_checked()?);
+
+        let red_pixels = |page: &PdfPage| -> Result<usize, PdfiumError> {
+            Ok(page
+                .render_with_config(
+                    &PdfRenderConfig::new()
+                        .scale_page_by_factor(1.0)
+                        .render_form_data(false),
+                )?
+                .as_rgba_bytes()
+                .chunks_exact(4)
+                .filter(|pixel| pixel[0] > 200 && pixel[1] < 80 && pixel[2] < 80)
+                .count())
+        };
+
+        // The checkbox's red background is drawn when the page is rendered, and remains
+        // when the page is flattened, without first saving the document.
+
+        assert!(red_pixels(&page)? > 500);
+
+        page.flatten()?;
+
+        assert_eq!(page.annotations().len(), 0);
+        assert!(red_pixels(&page)? > 500);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_button_appearance_content() {
+        let mut config = ButtonAppearanceConfig::new(ButtonKind::Checkbox);
+
+        config.width = 20.0;
+        config.height = 20.0;
+
+        let off = config.content(false, false).unwrap();
+        let on = config.content(true, false).unwrap();
+        let down = config.content(false, true).unwrap();
+
+        assert_eq!(off, "q 0 0 0 RG 1 w 0.5 0.5 19 19 re S Q\n");
+        assert!(on.starts_with(&off));
+        assert!(on.contains(" m ") && on.ends_with(" l S Q\n"));
+        assert!(down.starts_with("q 0.7529412 0.7529412 0.7529412 rg 0 0 20 20 re f Q\n"));
+
+        assert_eq!(
+            config.appearance_characteristics(None),
+            b"<</BC [0 0 0]\n/CA (4)\n>>".to_vec()
+        );
+
+        let mut config = ButtonAppearanceConfig::new(ButtonKind::PushButton);
+
+        config.width = 100.0;
+        config.height = 20.0;
+        config.caption = Some("OK (é)".to_string());
+
+        let content = config.content(false, false).unwrap();
+
+        assert!(content.contains("BT /Helv 11.2 Tf 0 0 0 rg 33.2 6.08 Td"));
+        assert!(content.contains("(OK \\(\\351\\)) Tj ET"));
+        assert!(content.contains("q 2 2 96 16 re W n\n"));
+
+        config.icon = Some(ButtonIcon {
+            width: 2,
+            height: 1,
+            is_gray: false,
+            pixels: vec![255, 0, 0, 0, 0, 255],
+            alpha: Some(vec![255, 0]),
+        });
+
+        assert_eq!(
+            config.caption_position(),
+            ButtonCaptionPosition::CaptionBelowIcon
+        );
+
+        let content = config.content(false, false).unwrap();
+
+        assert!(content.contains("/FRM Do Q\n"));
+
+        assert_eq!(escape_text("\u{20ac}5").unwrap(), "\\2005");
+        assert!(matches!(
+            escape_text("\u{4e2d}"),
+            Err(PdfiumError::AppearanceTextNotEncodable)
+        ));
+    }
+}
@@ -4,6 +4,8 @@
 use crate::bindgen::{FPDF_ANNOTATION, FPDF_FORMHANDLE};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
+use crate::pdf::document::page::field::button_appearance::{ButtonAppearanceBuilder, ButtonKind};
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::private::internal::PdfFormFieldPrivate;
 
 #[cfg(doc)]
@@ -23,6 +25,7 @@ use {
 pub struct PdfFormCheckboxField<'a> {
     form_handle: FPDF_FORMHANDLE,
     annotation_handle: FPDF_ANNOTATION,
+    pending_field_changes: Option<PdfPendingFieldChanges>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -36,6 +39,22 @@ impl<'a> PdfFormCheckboxField<'a> {
         PdfFormCheckboxField {
             form_handle,
             annotation_handle,
+            pending_field_changes: None,
+            bindings,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_pdfium_with_document(
+        form_handle: FPDF_FORMHANDLE,
+        annotation_handle: FPDF_ANNOTATION,
+        pending_field_changes: PdfPendingFieldChanges,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfFormCheckboxField {
+            form_handle,
+            annotation_handle,
+            pending_field_changes: Some(pending_field_changes),
             bindings,
         }
     }
@@ -141,6 +160,35 @@ impl<'a> PdfFormCheckboxField<'a> {
         self.set_string_value("AS", &value_with_slash)?;
         self.set_value_impl(&value_without_slash)
     }
+
+    /// Returns a builder for generating the visual appearance of this [PdfFormCheckboxField].
+    ///
+    /// Checkboxes created with `create_widget_annotation()` have no appearance streams of
+    /// their own, so some viewers display them as blank and they disappear when flattened.
+    /// The builder generates normal and down appearances for both the checked and unchecked
+    /// states, along with the checkbox's `/MK` appearance characteristics.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// checkbox.set_appearance()
+    ///     .with_style(PdfFormButtonStyle::Check)
+    ///     .with_border_color(PdfColor::BLACK)
+    ///     .with_background_color(PdfColor::WHITE)
+    ///     .apply()?;
+    /// ```
+    pub fn set_appearance(&self) -> ButtonAppearanceBuilder<'_> {
+        ButtonAppearanceBuilder::new(
+            self.annotation_handle,
+            self.pending_field_changes.clone(),
+            self.bindings,
+            ButtonKind::Checkbox,
+            self.get_string_value("NM"),
+            self.export_value_impl()
+                .unwrap_or_else(|| "Yes".to_string()),
+            self.is_checked().unwrap_or(false),
+        )
+    }
 }
 
 impl<'a> PdfFormFieldPrivate<'a> for PdfFormCheckboxField<'a> {
@@ -158,4 +206,12 @@ impl<'a> PdfFormFieldPrivate<'a> for PdfFormCheckboxField<'a> {
     fn bindings(&self) -> &dyn PdfiumLibraryBindings {
         self.bindings
     }
+
+    fn pending_on_state(&self) -> Option<String> {
+        let marker = self.get_string_value("NM")?;
+
+        self.pending_field_changes
+            .as_ref()?
+            .button_on_state(&marker)
+    }
 }
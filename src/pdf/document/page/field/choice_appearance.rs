@@ -0,0 +1,949 @@
+//! Defines types and builders for setting the visual appearance of combo box and list box
+//! fields using PDF content streams.
+//!
+//! # Overview
+//!
+//! Changing the value of a choice field does not always rebuild the field's appearance
+//! stream, so the field may continue to display its previous selection. This module
+//! provides a builder API for regenerating the appearance of a choice field from its
+//! current value and options.
+//!
+//! Combo boxes are drawn showing their current value alongside a drop-down arrow. List boxes
+//! are drawn showing as many of their options as fit in the field, starting from the field's
+//! top index (`/TI`), with selected options highlighted. Text is drawn using the font and
+//! font size in the field's default appearance string (`/DA`) and is clipped to the field.
+//!
+//! Pdfium can only give a widget an appearance stream without resources, so
+//! [ChoiceFieldAppearanceBuilder::apply()] immediately sets an appearance that uses the
+//! page's fonts and records the appearance's styling. When the containing document is saved,
+//! the appearance is generated again from the field's value and options at that time, using
+//! the font from the form's default resources (`/DR`), and written as an incremental update.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! if let Some(list_box) = widget.form_field_mut().and_then(|field| field.as_list_box_field_mut()) {
+//!     list_box.set_values(&["Red", "Blue"])?;
+//!
+//!     list_box
+//!         .set_appearance()
+//!         .with_border_color(PdfColor::BLACK)
+//!         .with_background_color(PdfColor::WHITE)
+//!         .apply()?;
+//! }
+//! ```
+
+use crate::bindgen::{FPDF_ANNOTATION, FS_RECTF};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::appearance_mode::PdfAppearanceMode;
+use crate::pdf::color::PdfColor;
+use crate::pdf::document::bookmark_tree::format_number;
+use crate::pdf::document::incremental_update::{
+    decode_text_string, encode_text_string, next_marker_id, parse_array, trim,
+    PdfIncrementalUpdate, PdfObjectId, PdfRawDictionary,
+};
+use crate::pdf::document::page::field::button_appearance::{
+    color_components, escape_text, form_xobject,
+};
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
+
+/// The type of choice field whose appearance is being generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChoiceKind {
+    ComboBox,
+    ListBox,
+}
+
+/// Configuration for choice field appearance rendering.
+#[derive(Debug, Clone)]
+pub(crate) struct ChoiceAppearanceConfig {
+    kind: ChoiceKind,
+    width: f32,
+    height: f32,
+    value: Option<String>,
+    options: Vec<(String, bool)>,
+    top_index: usize,
+    font_name: String,
+    font_size: f32,
+    text_color: PdfColor,
+    border_color: Option<PdfColor>,
+    border_width: f32,
+    background_color: Option<PdfColor>,
+    highlight_color: PdfColor,
+}
+
+impl ChoiceAppearanceConfig {
+    /// The padding between the field's border and its text, in points.
+    const PADDING: f32 = 2.0;
+
+    pub(crate) fn new(kind: ChoiceKind, default_appearance: Option<&str>) -> Self {
+        let (font_name, font_size, text_color) =
+            parse_default_appearance(default_appearance.unwrap_or_default());
+
+        Self {
+            kind,
+            width: 0.0,
+            height: 0.0,
+            value: None,
+            options: Vec::new(),
+            top_index: 0,
+            font_name,
+            font_size,
+            text_color,
+            border_color: None,
+            border_width: 1.0,
+            background_color: None,
+            // The highlight color used for selected list box items by Acrobat.
+            highlight_color: PdfColor::new(153, 193, 218, 255),
+        }
+    }
+
+    /// Returns the width of the border actually drawn, taking into account whether a border
+    /// color has been set.
+    #[inline]
+    fn effective_border_width(&self) -> f32 {
+        if self.border_color.is_some() {
+            self.border_width.max(0.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the font size used for text, either as given in the default appearance
+    /// string or, if that is zero, sized to fit the field.
+    fn effective_font_size(&self) -> f32 {
+        if self.font_size > 0.0 {
+            return self.font_size;
+        }
+
+        match self.kind {
+            ChoiceKind::ComboBox => {
+                ((self.height - self.effective_border_width() * 2.0) * 0.7).clamp(1.0, 12.0)
+            }
+            ChoiceKind::ListBox => 12.0,
+        }
+    }
+
+    /// Builds the content stream drawing this field, in a coordinate space with its origin
+    /// at the bottom-left corner of the widget.
+    fn content(&self) -> Result<String, PdfiumError> {
+        let n = format_number;
+
+        let (w, h) = (self.width, self.height);
+
+        let b = self.effective_border_width();
+
+        let mut stream = String::new();
+
+        if let Some(color) = self.background_color {
+            stream.push_str(&format!(
+                "q {} rg 0 0 {} {} re f Q\n",
+                color_components(color),
+                n(w),
+                n(h)
+            ));
+        }
+
+        if let Some(color) = self.border_color.filter(|_| b > 0.0) {
+            stream.push_str(&format!(
+                "q {} RG {} w {} {} {} {} re S Q\n",
+                color_components(color),
+                n(b),
+                n(b / 2.0),
+                n(b / 2.0),
+                n(w - b),
+                n(h - b)
+            ));
+        }
+
+        match self.kind {
+            ChoiceKind::ComboBox => self.push_combo_box(&mut stream)?,
+            ChoiceKind::ListBox => self.push_list_box(&mut stream)?,
+        }
+
+        Ok(stream)
+    }
+
+    /// Draws the drop-down arrow and the current value of a combo box.
+    fn push_combo_box(&self, stream: &mut String) -> Result<(), PdfiumError> {
+        let n = format_number;
+
+        let (w, h) = (self.width, self.height);
+
+        let b = self.effective_border_width();
+
+        let (inner_w, inner_h) = (w - b * 2.0, h - b * 2.0);
+
+        if inner_w <= 0.0 || inner_h <= 0.0 {
+            return Ok(());
+        }
+
+        let arrow_width = inner_h.min(inner_w / 3.0);
+
+        let arrow_left = w - b - arrow_width;
+
+        let (center_x, center_y) = (arrow_left + arrow_width / 2.0, h / 2.0);
+
+        let size = arrow_width * 0.4;
+
+        stream.push_str(&format!(
+            "q 0.75 g {} {} {} {} re f Q\n",
+            n(arrow_left),
+            n(b),
+            n(arrow_width),
+            n(inner_h)
+        ));
+
+        stream.push_str(&format!(
+            "q 0 g {} {} m {} {} l {} {} l f Q\n",
+            n(center_x - size / 2.0),
+            n(center_y + size / 4.0),
+            n(center_x + size / 2.0),
+            n(center_y + size / 4.0),
+            n(center_x),
+            n(center_y - size / 4.0)
+        ));
+
+        let value = match self.value.as_ref() {
+            Some(value) if !value.is_empty() => value,
+            _ => return Ok(()),
+        };
+
+        let font_size = self.effective_font_size();
+
+        stream.push_str(&format!(
+            "/Tx BMC\nq {} {} {} {} re W n\n",
+            n(b),
+            n(b),
+            n(inner_w - arrow_width),
+            n(inner_h)
+        ));
+
+        self.push_text(
+            stream,
+            value,
+            b + Self::PADDING,
+            (h - font_size * 0.7) / 2.0,
+            font_size,
+        )?;
+
+        stream.push_str("Q\nEMC\n");
+
+        Ok(())
+    }
+
+    /// Draws the options of a list box that fit in the field, starting from the field's
+    /// top index, highlighting selected options.
+    fn push_list_box(&self, stream: &mut String) -> Result<(), PdfiumError> {
+        let n = format_number;
+
+        let (w, h) = (self.width, self.height);
+
+        let b = self.effective_border_width();
+
+        let (inner_w, inner_h) = (w - b * 2.0, h - b * 2.0);
+
+        if inner_w <= 0.0 || inner_h <= 0.0 {
+            return Ok(());
+        }
+
+        let font_size = self.effective_font_size();
+
+        let row_height = font_size * 1.2;
+
+        stream.push_str(&format!(
+            "/Tx BMC\nq {} {} {} {} re W n\n",
+            n(b),
+            n(b),
+            n(inner_w),
+            n(inner_h)
+        ));
+
+        let mut row_top = h - b - 1.0;
+
+        for (label, is_selected) in self.options.iter().skip(self.top_index) {
+            if row_top <= b {
+                // All remaining rows fall outside the field.
+
+                break;
+            }
+
+            let row_bottom = row_top - row_height;
+
+            if *is_selected {
+                stream.push_str(&format!(
+                    "q {} rg {} {} {} {} re f Q\n",
+                    color_components(self.highlight_color),
+                    n(b),
+                    n(row_bottom),
+                    n(inner_w),
+                    n(row_height)
+                ));
+            }
+
+            if !label.is_empty() {
+                self.push_text(
+                    stream,
+                    label,
+                    b + Self::PADDING,
+                    row_bottom + (row_height - font_size * 0.7) / 2.0,
+                    font_size,
+                )?;
+            }
+
+            row_top = row_bottom;
+        }
+
+        stream.push_str("Q\nEMC\n");
+
+        Ok(())
+    }
+
+    fn push_text(
+        &self,
+        stream: &mut String,
+        text: &str,
+        x: f32,
+        y: f32,
+        font_size: f32,
+    ) -> Result<(), PdfiumError> {
+        stream.push_str(&format!(
+            "BT /{} {} Tf {} rg {} {} Td ({}) Tj ET\n",
+            self.font_name,
+            format_number(font_size),
+            color_components(self.text_color),
+            format_number(x),
+            format_number(y),
+            escape_text(text)?
+        ));
+
+        Ok(())
+    }
+
+    /// Returns the `/DA` default appearance string for this field.
+    fn default_appearance(&self) -> String {
+        format!(
+            "/{} {} Tf {} rg",
+            self.font_name,
+            format_number(self.font_size),
+            color_components(self.text_color)
+        )
+    }
+}
+
+/// Builder for constructing and applying the visual appearance of a combo box or list box
+/// field.
+///
+/// This builder generates a PDF content stream from the field's current value and options.
+/// Combo boxes show their current value and a drop-down arrow; list boxes show the options
+/// that fit in the field, starting from the field's top index, with selected options
+/// highlighted. The font, font size, and text color are taken from the field's default
+/// appearance string unless overridden.
+///
+/// The appearance is set as soon as [ChoiceFieldAppearanceBuilder::apply()] is called.
+/// When the containing `PdfDocument` is saved, the appearance is generated again from the
+/// field's value and options at that time, so changing the field's value after calling
+/// [ChoiceFieldAppearanceBuilder::apply()] does not leave the saved field showing its
+/// previous value. Since this is done as an incremental update appended to the bytes
+/// written by Pdfium, a document containing choice fields with generated appearances
+/// cannot be saved in encrypted form.
+pub struct ChoiceFieldAppearanceBuilder<'a> {
+    bindings: &'a dyn PdfiumLibraryBindings,
+    annotation_handle: FPDF_ANNOTATION,
+    pending_field_changes: Option<PdfPendingFieldChanges>,
+    annotation_name: Option<String>,
+    config: ChoiceAppearanceConfig,
+}
+
+impl<'a> ChoiceFieldAppearanceBuilder<'a> {
+    const MARKER_PREFIX: &'static str = "pdfium-render-choice-appearance-";
+
+    /// Creates a new builder for the given annotation with field information.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn new(
+        annotation_handle: FPDF_ANNOTATION,
+        pending_field_changes: Option<PdfPendingFieldChanges>,
+        bindings: &'a dyn PdfiumLibraryBindings,
+        kind: ChoiceKind,
+        annotation_name: Option<String>,
+        value: Option<String>,
+        options: Vec<(String, bool)>,
+        default_appearance: Option<String>,
+    ) -> Self {
+        let mut config = ChoiceAppearanceConfig::new(kind, default_appearance.as_deref());
+
+        let mut top_index = 0.0;
+
+        if kind == ChoiceKind::ListBox
+            && bindings.is_true(bindings.FPDFAnnot_GetNumberValue(
+                annotation_handle,
+                "TI",
+                &mut top_index,
+            ))
+        {
+            config.top_index = (top_index.max(0.0) as usize).min(options.len());
+        }
+
+        config.value = value;
+        config.options = options;
+
+        Self {
+            bindings,
+            annotation_handle,
+            pending_field_changes,
+            annotation_name,
+            config,
+        }
+    }
+
+    /// Sets the font size in points, overriding the font size in the field's default
+    /// appearance string. A font size of zero sizes the text to fit the field.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.config.font_size = font_size.max(0.0);
+        self
+    }
+
+    /// Sets the text color, overriding the text color in the field's default
+    /// appearance string.
+    pub fn with_text_color(mut self, color: PdfColor) -> Self {
+        self.config.text_color = color;
+        self
+    }
+
+    /// Sets the border color. By default, no border is drawn.
+    pub fn with_border_color(mut self, color: PdfColor) -> Self {
+        self.config.border_color = Some(color);
+        self
+    }
+
+    /// Sets the border width in points. The default border width is 1 point.
+    pub fn with_border_width(mut self, width: f32) -> Self {
+        self.config.border_width = width;
+        self
+    }
+
+    /// Sets the background color. By default, no background is drawn.
+    pub fn with_background_color(mut self, color: PdfColor) -> Self {
+        self.config.background_color = Some(color);
+        self
+    }
+
+    /// Sets the color used to highlight the selected options of a list box.
+    pub fn with_highlight_color(mut self, color: PdfColor) -> Self {
+        self.config.highlight_color = color;
+        self
+    }
+
+    /// Applies the choice field appearance to the field.
+    ///
+    /// This immediately sets the normal (`/N`) appearance stream of the field, and records
+    /// the appearance to be generated again from the field's value and options, with the
+    /// correct font resources, when the containing `PdfDocument` is saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the field is not attached to a document, if the field's text
+    /// contains characters that cannot be drawn using a standard font, or if PDFium fails
+    /// to set the appearance stream.
+    pub fn apply(self) -> Result<(), PdfiumError> {
+        let pending_field_changes = self
+            .pending_field_changes
+            .ok_or(PdfiumError::OwnershipNotAttachedToDocument)?;
+
+        let mut rect = FS_RECTF {
+            left: 0.0,
+            top: 0.0,
+            right: 0.0,
+            bottom: 0.0,
+        };
+
+        if !self.bindings.is_true(
+            self.bindings
+                .FPDFAnnot_GetRect(self.annotation_handle, &mut rect),
+        ) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        let mut config = self.config;
+
+        config.width = (rect.right - rect.left).abs();
+        config.height = (rect.top - rect.bottom).abs();
+
+        // Appearance streams set through Pdfium use the widget's rectangle as their
+        // bounding box, so we move the origin to the widget's bottom-left corner.
+
+        let content = format!(
+            "q 1 0 0 1 {} {} cm\n{}Q\n",
+            format_number(rect.left.min(rect.right)),
+            format_number(rect.bottom.min(rect.top)),
+            config.content()?
+        );
+
+        if !self.bindings.is_true(self.bindings.FPDFAnnot_SetAP_str(
+            self.annotation_handle,
+            PdfAppearanceMode::Normal.as_pdfium(),
+            &content,
+        )) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        // Pdfium provides no way to find the object number of an annotation, so we tag the
+        // widget with a unique annotation name that can be found in the saved bytes.
+
+        let (marker, original_name) = match self.annotation_name {
+            Some(name) if name.starts_with(Self::MARKER_PREFIX) => (name, None),
+            original_name => {
+                let marker = format!("{}{}", Self::MARKER_PREFIX, next_marker_id());
+
+                if !self
+                    .bindings
+                    .is_true(self.bindings.FPDFAnnot_SetStringValue_str(
+                        self.annotation_handle,
+                        "NM",
+                        &marker,
+                    ))
+                {
+                    return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+                }
+
+                (marker, original_name)
+            }
+        };
+
+        pending_field_changes.set_choice_appearance(PdfChoiceAppearance {
+            marker,
+            original_name,
+            config,
+        });
+
+        Ok(())
+    }
+}
+
+/// The appearance of a single choice field widget, waiting to be written when the
+/// containing document is saved.
+pub(crate) struct PdfChoiceAppearance {
+    /// The unique `/NM` value used to locate the widget in a saved file.
+    pub(crate) marker: String,
+
+    /// The widget's annotation name before it was replaced by the marker, if any.
+    pub(crate) original_name: Option<String>,
+
+    config: ChoiceAppearanceConfig,
+}
+
+impl PdfChoiceAppearance {
+    /// Writes this appearance to its widget in the given saved document, drawing the value
+    /// and options of the widget's field as saved.
+    ///
+    /// Returns [PdfiumError::FormFieldWidgetNotFound] if the widget cannot be found in the
+    /// saved document.
+    pub(crate) fn apply_to(&self, update: &mut PdfIncrementalUpdate) -> Result<(), PdfiumError> {
+        let needle = format!("({})", self.marker);
+
+        let id = update
+            .find_object_containing(needle.as_bytes())
+            .map_err(|_| PdfiumError::FormFieldWidgetNotFound)?;
+
+        let mut dictionary = update.get_dictionary(id)?;
+
+        // The field's value may have changed since the appearance was generated, so the
+        // appearance is drawn from the field as saved rather than from the values recorded
+        // when the appearance was applied.
+
+        let mut config = self.config.clone();
+
+        read_field_state(update, &dictionary, &mut config);
+
+        // Use the form's definition of the font, if it has one; otherwise, assume the font
+        // is one of the standard fonts using its usual form resource name.
+
+        let font = form_font(update, &config.font_name).unwrap_or_else(|| {
+            let base_font = match config.font_name.as_str() {
+                "Cour" => "Courier",
+                "TiRo" => "Times-Roman",
+                _ => "Helvetica",
+            };
+
+            format!(
+                "<</Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding>>",
+                base_font
+            )
+            .into_bytes()
+        });
+
+        let resources = [
+            format!("<</Font <</{} ", config.font_name).into_bytes(),
+            font,
+            b">>>>".to_vec(),
+        ]
+        .concat();
+
+        let appearance = update.add_object(form_xobject(
+            config.width,
+            config.height,
+            &resources,
+            config.content()?.as_bytes(),
+        ));
+
+        dictionary.set(
+            "AP",
+            [b"<</N ".to_vec(), appearance.to_reference(), b">>".to_vec()].concat(),
+        );
+        dictionary.set("DA", encode_text_string(&config.default_appearance()));
+
+        match self.original_name.as_ref() {
+            Some(name) => dictionary.set("NM", encode_text_string(name)),
+            None => dictionary.remove("NM"),
+        }
+
+        update.set_object(id, dictionary.to_bytes());
+
+        Ok(())
+    }
+}
+
+/// Sets the value, options, and top index of the given appearance configuration from the
+/// given widget's field in the given saved document. Field entries not present in the widget
+/// are inherited from its ancestors.
+fn read_field_state(
+    update: &PdfIncrementalUpdate,
+    widget: &PdfRawDictionary,
+    config: &mut ChoiceAppearanceConfig,
+) {
+    let values = match inherited_entry(update, widget, "V") {
+        Some(value) if trim(&value).starts_with(b"[") => parse_array(&value)
+            .map(|elements| {
+                elements
+                    .into_iter()
+                    .filter_map(|element| decode_text_string(&resolve(update, element)))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        Some(value) => decode_text_string(&value).into_iter().collect(),
+        None => Vec::new(),
+    };
+
+    // Each option is either a text string, or an array holding the option's export value
+    // followed by the label displayed for it.
+
+    let options = inherited_entry(update, widget, "Opt")
+        .and_then(|options| {
+            parse_array(&options).ok().map(|elements| {
+                elements
+                    .into_iter()
+                    .filter_map(|element| option_entry(update, element))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .unwrap_or_default();
+
+    // The /I array of selected option indices is only consulted when the field has no
+    // value, since Pdfium does not always update it when the value changes.
+
+    let indices = inherited_entry(update, widget, "I")
+        .and_then(|indices| {
+            parse_array(&indices).ok().map(|elements| {
+                elements
+                    .into_iter()
+                    .filter_map(|element| {
+                        std::str::from_utf8(trim(element))
+                            .ok()?
+                            .parse::<usize>()
+                            .ok()
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .unwrap_or_default();
+
+    config.value = values.first().map(|value| {
+        options
+            .iter()
+            .find(|(export_value, _)| export_value == value)
+            .map(|(_, label)| label.clone())
+            .unwrap_or_else(|| value.clone())
+    });
+
+    config.top_index = inherited_entry(update, widget, "TI")
+        .and_then(|top_index| std::str::from_utf8(trim(&top_index)).ok()?.parse().ok())
+        .unwrap_or(0_usize)
+        .min(options.len());
+
+    config.options = options
+        .into_iter()
+        .enumerate()
+        .map(|(index, (export_value, label))| {
+            let is_selected = if values.is_empty() {
+                indices.contains(&index)
+            } else {
+                values.contains(&export_value)
+            };
+
+            (label, is_selected)
+        })
+        .collect();
+}
+
+/// Returns the export value and label of the given element of a choice field's `/Opt` array.
+pub(crate) fn option_entry(
+    update: &PdfIncrementalUpdate,
+    element: &[u8],
+) -> Option<(String, String)> {
+    let element = resolve(update, element);
+
+    if trim(&element).starts_with(b"[") {
+        let pair = parse_array(&element).ok()?;
+
+        let export_value = decode_text_string(&resolve(update, pair.first()?))?;
+
+        let label = match pair.get(1) {
+            Some(label) => decode_text_string(&resolve(update, label))?,
+            None => export_value.clone(),
+        };
+
+        Some((export_value, label))
+    } else {
+        let text = decode_text_string(&element)?;
+
+        Some((text.clone(), text))
+    }
+}
+
+/// Returns the value of the given field entry of the given widget in the given saved
+/// document, taken from the nearest of the widget and its ancestors that defines it.
+fn inherited_entry(
+    update: &PdfIncrementalUpdate,
+    widget: &PdfRawDictionary,
+    key: &str,
+) -> Option<Vec<u8>> {
+    // Limit the depth of the search, in case the field hierarchy contains a cycle.
+
+    const MAX_DEPTH: usize = 32;
+
+    let mut dictionary = widget.clone();
+
+    for _ in 0..MAX_DEPTH {
+        if let Some(value) = dictionary.get(key) {
+            return Some(resolve(update, value));
+        }
+
+        dictionary = update
+            .get_dictionary(dictionary.get_reference("Parent")?)
+            .ok()?;
+    }
+
+    None
+}
+
+/// Returns the given value from the given saved document, following it if it is an
+/// indirect reference.
+pub(crate) fn resolve(update: &PdfIncrementalUpdate, value: &[u8]) -> Vec<u8> {
+    match PdfObjectId::from_reference(value) {
+        Some(id) => update
+            .get_object(id)
+            .map(|object| object.to_vec())
+            .unwrap_or_default(),
+        None => value.to_vec(),
+    }
+}
+
+/// Returns the font with the given resource name in the default resources of the given
+/// saved document's interactive form, if any.
+fn form_font(update: &PdfIncrementalUpdate, name: &str) -> Option<Vec<u8>> {
+    let resolve = |value: &[u8]| -> Option<PdfRawDictionary> {
+        match PdfObjectId::from_reference(value) {
+            Some(id) => update.get_dictionary(id).ok(),
+            None => PdfRawDictionary::parse(value).ok(),
+        }
+    };
+
+    let root = update.get_dictionary(update.root().ok()?).ok()?;
+
+    let form = resolve(root.get("AcroForm")?)?;
+
+    let resources = resolve(form.get("DR")?)?;
+
+    let fonts = resolve(resources.get("Font")?)?;
+
+    fonts.get(name).map(|font| font.to_vec())
+}
+
+/// Parses the font resource name, font size, and text color from the given default
+/// appearance string, falling back to automatically sized black Helvetica.
+fn parse_default_appearance(default_appearance: &str) -> (String, f32, PdfColor) {
+    let mut font_name = "Helv".to_string();
+    let mut font_size = 0.0;
+    let mut text_color = PdfColor::BLACK;
+
+    let tokens = default_appearance.split_whitespace().collect::<Vec<_>>();
+
+    let component = |token: &str| -> Option<u8> {
+        token
+            .parse::<f32>()
+            .ok()
+            .map(|value| (value.clamp(0.0, 1.0) * 255.0).round() as u8)
+    };
+
+    for (index, token) in tokens.iter().enumerate() {
+        match *token {
+            "Tf" if index >= 2 => {
+                if let Some(name) = tokens[index - 2].strip_prefix('/') {
+                    font_name = name.to_string();
+                }
+
+                font_size = tokens[index - 1].parse().unwrap_or(0.0);
+            }
+            "g" if index >= 1 => {
+                if let Some(gray) = component(tokens[index - 1]) {
+                    text_color = PdfColor::new(gray, gray, gray, 255);
+                }
+            }
+            "rg" if index >= 3 => {
+                if let (Some(red), Some(green), Some(blue)) = (
+                    component(tokens[index - 3]),
+                    component(tokens[index - 2]),
+                    component(tokens[index - 1]),
+                ) {
+                    text_color = PdfColor::new(red, green, blue, 255);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    (font_name, font_size, text_color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "pdfium_future")]
+    use crate::prelude::*;
+
+    #[cfg(feature = "pdfium_future")]
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    #[cfg(feature = "pdfium_future")]
+    fn test_list_box_appearance() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+        let mut document = pdfium.create_new_pdf()?;
+
+        document.ensure_acro_form()?;
+
+        let form_handle = document.init_form_fill_environment()?;
+
+        let mut page = document
+            .pages_mut()
+            .create_page_at_start(PdfPagePaperSize::a4())?;
+
+        let mut widget = page.annotations_mut().create_widget_annotation(
+            form_handle,
+            "Colours",
+            PdfFormFieldType::ListBox,
+            PdfRect::new_from_values(700.0, 100.0, 740.0, 250.0),
+            Some(&["Red", "Green", "Blue"]),
+            None,
+            None,
+            Some("/Helv 10 Tf 0 g"),
+            None,
+            None,
+        )?;
+
+        let list_box = widget
+            .form_field_mut()
+            .and_then(|field| field.as_list_box_field_mut())
+            .unwrap();
+
+        list_box.set_value("Green")?;
+        list_box.set_appearance().apply()?;
+
+        let bytes = document.save_to_bytes()?;
+        let text = String::from_utf8_lossy(&bytes);
+
+        // The field is 40 points wide and 150 points tall, with no border, so each 12 point
+        // row is drawn 12 points below the one above it, starting 1 point below the top.
+
+        let red_row = "q 0.6 0.75686276 0.85490197 rg 0 137 40 12 re f Q\n";
+        let green_row = "q 0.6 0.75686276 0.85490197 rg 0 125 40 12 re f Q\n";
+        let blue_row = "q 0.6 0.75686276 0.85490197 rg 0 113 40 12 re f Q\n";
+
+        assert!(text.contains("/AP <</N "));
+        assert!(text.contains(&format!(
+            "{}BT /Helv 10 Tf 0 0 0 rg 2 127.5 Td (Green) Tj ET\n",
+            green_row
+        )));
+        assert!(!text.contains(red_row));
+        assert!(!text.contains(blue_row));
+        assert!(!text.contains(ChoiceFieldAppearanceBuilder::MARKER_PREFIX));
+
+        // Changing the value after applying the appearance must be reflected in the
+        // appearance written when the document is saved.
+
+        widget
+            .form_field_mut()
+            .and_then(|field| field.as_list_box_field_mut())
+            .unwrap()
+            .set_value("Blue")?;
+
+        let bytes = document.save_to_bytes()?;
+        let text = String::from_utf8_lossy(&bytes);
+
+        let appearance = &text[text.rfind("/Tx BMC").unwrap()..];
+
+        assert!(appearance.contains(blue_row));
+        assert!(!appearance.contains(green_row));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_choice_appearance_content() {
+        let (font_name, font_size, text_color) = parse_default_appearance("/Cour 9 Tf 1 0 0 rg");
+
+        assert_eq!(font_name, "Cour");
+        assert_eq!(font_size, 9.0);
+        assert_eq!(
+            (text_color.red(), text_color.green(), text_color.blue()),
+            (255, 0, 0)
+        );
+
+        let mut config = ChoiceAppearanceConfig::new(ChoiceKind::ListBox, Some("/Helv 10 Tf 0 g"));
+
+        config.width = 100.0;
+        config.height = 30.0;
+        config.top_index = 1;
+        config.options = vec![
+            ("Red".to_string(), true),
+            ("Green".to_string(), true),
+            ("Blue".to_string(), false),
+            ("Black".to_string(), false),
+        ];
+
+        let content = config.content().unwrap();
+
+        assert!(content.starts_with("/Tx BMC\nq 0 0 100 30 re W n\n"));
+        assert!(!content.contains("(Red)"));
+        assert!(content.contains("q 0.6 0.75686276 0.85490197 rg 0 17 100 12 re f Q\n"));
+        assert!(content.contains("BT /Helv 10 Tf 0 0 0 rg 2 19.5 Td (Green) Tj ET\n"));
+        assert!(content.contains("2 7.5 Td (Blue)"));
+        assert!(content.contains("2 -4.5 Td (Black)"));
+        assert!(content.ends_with("Q\nEMC\n"));
+
+        let mut config = ChoiceAppearanceConfig::new(ChoiceKind::ComboBox, None);
+
+        config.width = 100.0;
+        config.height = 20.0;
+        config.value = Some("Green".to_string());
+        config.border_color = Some(PdfColor::BLACK);
+
+        let content = config.content().unwrap();
+
+        assert!(content.contains("q 0.75 g 81 1 18 18 re f Q\n"));
+        assert!(content.contains("/Tx BMC\nq 1 1 80 18 re W n\n"));
+        assert!(content.contains("BT /Helv 12 Tf 0 0 0 rg 3 5.8 Td (Green) Tj ET\n"));
+    }
+}
@@ -4,7 +4,11 @@
 use crate::bindgen::{FPDF_ANNOTATION, FPDF_DOCUMENT, FPDF_FORMHANDLE};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
+use crate::pdf::document::page::field::choice_appearance::{
+    ChoiceFieldAppearanceBuilder, ChoiceKind,
+};
 use crate::pdf::document::page::field::options::PdfFormFieldOptions;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::private::internal::{
     PdfFormFieldFlags, PdfFormFieldPrivate,
 };
@@ -28,6 +32,7 @@ pub struct PdfFormComboBoxField<'a> {
     form_handle: FPDF_FORMHANDLE,
     annotation_handle: FPDF_ANNOTATION,
     document_handle: Option<FPDF_DOCUMENT>,
+    pending_field_changes: Option<PdfPendingFieldChanges>,
     options: PdfFormFieldOptions<'a>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
@@ -43,7 +48,14 @@ impl<'a> PdfFormComboBoxField<'a> {
             form_handle,
             annotation_handle,
             document_handle: None,
-            options: PdfFormFieldOptions::from_pdfium(form_handle, annotation_handle, bindings),
+            pending_field_changes: None,
+            options: PdfFormFieldOptions::from_pdfium(
+                form_handle,
+                annotation_handle,
+                None,
+                None,
+                bindings,
+            ),
             bindings,
         }
     }
@@ -53,13 +65,21 @@ impl<'a> PdfFormComboBoxField<'a> {
         form_handle: FPDF_FORMHANDLE,
         annotation_handle: FPDF_ANNOTATION,
         document_handle: FPDF_DOCUMENT,
+        pending_field_changes: PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         PdfFormComboBoxField {
             form_handle,
             annotation_handle,
             document_handle: Some(document_handle),
-            options: PdfFormFieldOptions::from_pdfium(form_handle, annotation_handle, bindings),
+            pending_field_changes: Some(pending_field_changes.clone()),
+            options: PdfFormFieldOptions::from_pdfium(
+                form_handle,
+                annotation_handle,
+                Some(document_handle),
+                Some(pending_field_changes),
+                bindings,
+            ),
             bindings,
         }
     }
@@ -75,6 +95,12 @@ impl<'a> PdfFormComboBoxField<'a> {
         &self.options
     }
 
+    #[cfg(feature = "pdfium_future")]
+    /// Returns a mutable collection of the selectable options in this [PdfFormComboBoxField].
+    pub fn options_mut(&mut self) -> &mut PdfFormFieldOptions<'a> {
+        &mut self.options
+    }
+
     /// Returns the current value of this [PdfFormComboBoxField] object, if any.
     /// 
     /// For editable combo boxes (where [PdfFormComboBoxField::has_editable_text_box()] returns `true`),
@@ -414,6 +440,40 @@ impl<'a> PdfFormComboBoxField<'a> {
             is_commit_on_selection_change,
         )
     }
+
+    /// Returns a builder for regenerating the visual appearance of this [PdfFormComboBoxField]
+    /// from its current value.
+    ///
+    /// Combo boxes are drawn showing their current value alongside a drop-down arrow, using
+    /// the font, font size, and text color in the field's default appearance string.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// combo_box.set_value("Germany")?;
+    ///
+    /// combo_box.set_appearance()
+    ///     .with_border_color(PdfColor::BLACK)
+    ///     .apply()?;
+    /// ```
+    pub fn set_appearance(&self) -> ChoiceFieldAppearanceBuilder<'_> {
+        let options = self
+            .options()
+            .iter()
+            .map(|option| (option.label().cloned().unwrap_or_default(), option.is_set()))
+            .collect();
+
+        ChoiceFieldAppearanceBuilder::new(
+            self.annotation_handle,
+            self.pending_field_changes.clone(),
+            self.bindings,
+            ChoiceKind::ComboBox,
+            self.get_string_value("NM"),
+            self.value(),
+            options,
+            self.get_string_value("DA"),
+        )
+    }
 }
 
 impl<'a> PdfFormFieldPrivate<'a> for PdfFormComboBoxField<'a> {
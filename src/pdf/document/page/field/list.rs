@@ -4,7 +4,11 @@
 use crate::bindgen::{FPDF_ANNOTATION, FPDF_DOCUMENT, FPDF_FORMHANDLE};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
+use crate::pdf::document::page::field::choice_appearance::{
+    ChoiceFieldAppearanceBuilder, ChoiceKind,
+};
 use crate::pdf::document::page::field::options::PdfFormFieldOptions;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::private::internal::{
     PdfFormFieldFlags, PdfFormFieldPrivate,
 };
@@ -29,6 +33,7 @@ pub struct PdfFormListBoxField<'a> {
     form_handle: FPDF_FORMHANDLE,
     annotation_handle: FPDF_ANNOTATION,
     document_handle: Option<FPDF_DOCUMENT>,
+    pending_field_changes: Option<PdfPendingFieldChanges>,
     options: PdfFormFieldOptions<'a>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
@@ -44,7 +49,14 @@ impl<'a> PdfFormListBoxField<'a> {
             form_handle,
             annotation_handle,
             document_handle: None,
-            options: PdfFormFieldOptions::from_pdfium(form_handle, annotation_handle, bindings),
+            pending_field_changes: None,
+            options: PdfFormFieldOptions::from_pdfium(
+                form_handle,
+                annotation_handle,
+                None,
+                None,
+                bindings,
+            ),
             bindings,
         }
     }
@@ -54,13 +66,21 @@ impl<'a> PdfFormListBoxField<'a> {
         form_handle: FPDF_FORMHANDLE,
         annotation_handle: FPDF_ANNOTATION,
         document_handle: FPDF_DOCUMENT,
+        pending_field_changes: PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         PdfFormListBoxField {
             form_handle,
             annotation_handle,
             document_handle: Some(document_handle),
-            options: PdfFormFieldOptions::from_pdfium(form_handle, annotation_handle, bindings),
+            pending_field_changes: Some(pending_field_changes.clone()),
+            options: PdfFormFieldOptions::from_pdfium(
+                form_handle,
+                annotation_handle,
+                Some(document_handle),
+                Some(pending_field_changes),
+                bindings,
+            ),
             bindings,
         }
     }
@@ -76,6 +96,12 @@ impl<'a> PdfFormListBoxField<'a> {
         &self.options
     }
 
+    #[cfg(feature = "pdfium_future")]
+    /// Returns a mutable collection of the selectable options in this [PdfFormListBoxField].
+    pub fn options_mut(&mut self) -> &mut PdfFormFieldOptions<'a> {
+        &mut self.options
+    }
+
     /// Returns the current value(s) of this [PdfFormListBoxField] object.
     /// 
     /// For multi-select list boxes (where [PdfFormListBoxField::is_multiselect()] returns `true`),
@@ -301,30 +327,105 @@ impl<'a> PdfFormListBoxField<'a> {
     /// ([PdfFormListBoxField::is_multiselect()] returns `true`). The values should match
     /// the labels of available options in this list box.
     ///
-    /// **Note:** This implementation has limitations. For multi-select list boxes, the PDF
-    /// specification requires the "V" entry to be an array, but PDFium's API only allows
-    /// setting string values. This method attempts to work around this by setting the first
-    /// value, which may not fully support multi-select. Proper multi-select support would
-    /// require direct PDF dictionary manipulation to set V as an array.
+    /// When every value matches the label of an option, the selection is made using PDFium's
+    /// form fill API, which stores all the selected options in the field's "V" entry and
+    /// regenerates the field's appearance stream. Otherwise, this method falls back to direct
+    /// annotation manipulation, which can only store the first value; the field's appearance
+    /// is then not rebuilt, so call [PdfFormListBoxField::set_appearance()] to redraw the
+    /// field with its new selection.
     pub fn set_values(&mut self, values: &[&str]) -> Result<(), PdfiumError> {
+        let selection = self
+            .options()
+            .iter()
+            .map(|option| {
+                let is_selected = option
+                    .label()
+                    .map(|label| values.contains(&label.as_str()))
+                    .unwrap_or(false);
+
+                (option.index() as i32, is_selected)
+            })
+            .collect::<Vec<_>>();
+
+        let selected_count = selection.iter().filter(|(_, is_selected)| *is_selected).count();
+
+        if selected_count == values.len() {
+            if let Some((page_handle, page_annotation_handle)) =
+                self.find_page_and_annotation_handle_for_annotation()
+            {
+                let is_selected = self.set_selection_via_form_fill_with_page_and_annotation(
+                    &selection,
+                    page_handle,
+                    page_annotation_handle,
+                );
+
+                // Close handles
+                self.bindings().FPDFPage_CloseAnnot(page_annotation_handle);
+                self.bindings().FPDF_ClosePage(page_handle);
+
+                if is_selected {
+                    return self.set_string_value(
+                        "M",
+                        &crate::utils::dates::date_time_to_pdf_string(Utc::now()),
+                    );
+                }
+            }
+        }
+
         if values.is_empty() {
             // Clear all selections by setting empty value
             return self.set_value_impl("");
         }
-        
-        if values.len() == 1 {
-            // Single value - use the standard set_value
-            return self.set_value_impl(values[0]);
-        }
-        
-        // For multiple values, PDFium's API limitation means we can only set one value
-        // as a string. The PDF spec requires V to be an array for multi-select, but
-        // FPDFAnnot_SetStringValue only sets strings. We'll set the first value for now.
-        // TODO: Implement proper multi-select by directly manipulating the PDF dictionary
-        // to set V as an array of option values or indices.
+
+        // FPDFAnnot_SetStringValue only sets strings, so without the form fill API
+        // we can only store the first value.
         self.set_value_impl(values[0])
     }
 
+    /// Selects exactly the given options using the form fill API with known page and
+    /// annotation handles, returning `true` if every selected option was set.
+    fn set_selection_via_form_fill_with_page_and_annotation(
+        &self,
+        selection: &[(i32, bool)],
+        page_handle: crate::bindgen::FPDF_PAGE,
+        annotation_handle: crate::bindgen::FPDF_ANNOTATION,
+    ) -> bool {
+        let form_handle = self.form_handle();
+        let bindings = self.bindings();
+
+        // Ensure FORM_OnAfterLoadPage is called
+        bindings.FORM_OnAfterLoadPage(page_handle, form_handle);
+
+        if !bindings.is_true(bindings.FORM_SetFocusedAnnot(form_handle, annotation_handle)) {
+            return false;
+        }
+
+        // Deselect options before selecting, so a single-selection list box ends up with
+        // the requested option selected. Deselecting an option that is not selected may
+        // report failure, so only the results of selecting options are checked.
+
+        for (index, _) in selection.iter().filter(|(_, is_selected)| !*is_selected) {
+            bindings.FORM_SetIndexSelected(form_handle, page_handle, *index, bindings.FALSE());
+        }
+
+        let is_selected = selection
+            .iter()
+            .filter(|(_, is_selected)| *is_selected)
+            .all(|(index, _)| {
+                bindings.is_true(bindings.FORM_SetIndexSelected(
+                    form_handle,
+                    page_handle,
+                    *index,
+                    bindings.TRUE(),
+                ))
+            });
+
+        // Kill focus to save the value and trigger appearance stream regeneration
+        bindings.FORM_ForceToKillFocus(form_handle);
+
+        is_selected
+    }
+
     /// Returns `true` if the option items of this [PdfFormListBoxField] should be sorted
     /// alphabetically.
     ///
@@ -389,6 +490,41 @@ impl<'a> PdfFormListBoxField<'a> {
             is_commit_on_selection_change,
         )
     }
+
+    /// Returns a builder for regenerating the visual appearance of this [PdfFormListBoxField]
+    /// from its current selection.
+    ///
+    /// List boxes are drawn showing as many options as fit in the field, starting from the
+    /// field's top index, with selected options highlighted. Text is drawn using the font,
+    /// font size, and text color in the field's default appearance string.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// list_box.set_values(&["Red", "Blue"])?;
+    ///
+    /// list_box.set_appearance()
+    ///     .with_highlight_color(PdfColor::YELLOW)
+    ///     .apply()?;
+    /// ```
+    pub fn set_appearance(&self) -> ChoiceFieldAppearanceBuilder<'_> {
+        let options = self
+            .options()
+            .iter()
+            .map(|option| (option.label().cloned().unwrap_or_default(), option.is_set()))
+            .collect();
+
+        ChoiceFieldAppearanceBuilder::new(
+            self.annotation_handle,
+            self.pending_field_changes.clone(),
+            self.bindings,
+            ChoiceKind::ListBox,
+            self.get_string_value("NM"),
+            None,
+            options,
+            self.get_string_value("DA"),
+        )
+    }
 }
 
 impl<'a> PdfFormFieldPrivate<'a> for PdfFormListBoxField<'a> {
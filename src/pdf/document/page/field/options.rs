@@ -1,22 +1,54 @@
 //! Defines the [PdfFormFieldOptions] struct, a collection of all the selectable options
 //! displayed in a combo box or list box form field.
 
-use crate::bindgen::{FPDF_ANNOTATION, FPDF_FORMHANDLE, FPDF_WCHAR};
+use crate::bindgen::{FPDF_ANNOTATION, FPDF_DOCUMENT, FPDF_FORMHANDLE, FPDF_WCHAR};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::{
+    decode_text_string, parse_array, trim, PdfIncrementalUpdate, PdfObjectId, PdfRawDictionary,
+};
+use crate::pdf::document::page::field::choice_appearance::{option_entry, resolve};
 use crate::pdf::document::page::field::option::PdfFormFieldOption;
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::utils::mem::create_byte_buffer;
 use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use std::collections::HashMap;
 use std::ops::{Range, RangeInclusive};
 use std::os::raw::c_int;
 
+#[cfg(feature = "pdfium_future")]
+use {
+    crate::bindgen::{FPDF_PAGE, FS_RECTF},
+    crate::error::PdfiumInternalError,
+    crate::utils::dates::date_time_to_pdf_string,
+    crate::utils::utf16le::get_pdfium_utf16le_bytes_from_str,
+    chrono::Utc,
+};
+
 /// The zero-based index of a single [PdfFormFieldOption] inside its [PdfFormFieldOptions] collection.
 pub type PdfFormFieldOptionIndex = usize;
 
 /// A collection of all selectable options in a list box or check box form field widget.
+///
+/// When compiled with the `pdfium_future` feature, options can be added to and removed from
+/// the collection using the [PdfFormFieldOptions::add_option()], [PdfFormFieldOptions::insert_option()],
+/// [PdfFormFieldOptions::remove_option()], [PdfFormFieldOptions::clear()], and
+/// [PdfFormFieldOptions::set_options()] functions. Each of these rewrites the `/Opt` array of
+/// the containing form field, keeping any selected options selected where they still exist.
+///
+/// The export values of the existing options are read from a copy of the document saved to
+/// memory the first time the collection is edited, and are remembered for later edits made
+/// through the same collection. When replacing many options at once, prefer
+/// [PdfFormFieldOptions::set_options()], which rewrites the `/Opt` array only once.
 pub struct PdfFormFieldOptions<'a> {
     form_handle: FPDF_FORMHANDLE,
     annotation_handle: FPDF_ANNOTATION,
+    #[cfg_attr(not(feature = "pdfium_future"), allow(dead_code))]
+    document_handle: Option<FPDF_DOCUMENT>,
+    #[cfg_attr(not(feature = "pdfium_future"), allow(dead_code))]
+    pending_field_changes: Option<PdfPendingFieldChanges>,
+    #[cfg_attr(not(feature = "pdfium_future"), allow(dead_code))]
+    option_pairs: Option<Vec<(String, String)>>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -25,11 +57,16 @@ impl<'a> PdfFormFieldOptions<'a> {
     pub(crate) fn from_pdfium(
         form_handle: FPDF_FORMHANDLE,
         annotation_handle: FPDF_ANNOTATION,
+        document_handle: Option<FPDF_DOCUMENT>,
+        pending_field_changes: Option<PdfPendingFieldChanges>,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         PdfFormFieldOptions {
             form_handle,
             annotation_handle,
+            document_handle,
+            pending_field_changes,
+            option_pairs: None,
             bindings,
         }
     }
@@ -136,6 +173,543 @@ impl<'a> PdfFormFieldOptions<'a> {
     }
 }
 
+#[cfg(feature = "pdfium_future")]
+impl<'a> PdfFormFieldOptions<'a> {
+    /// Adds a new option with the given display label and export value to the end of this
+    /// [PdfFormFieldOptions] collection. The export value is the value stored in the form
+    /// field when the option is selected.
+    #[inline]
+    pub fn add_option(&mut self, label: &str, export_value: &str) -> Result<(), PdfiumError> {
+        self.insert_option(self.len(), label, export_value)
+    }
+
+    /// Inserts a new option with the given display label and export value into this
+    /// [PdfFormFieldOptions] collection at the given index, moving any following options
+    /// down by one position.
+    pub fn insert_option(
+        &mut self,
+        index: PdfFormFieldOptionIndex,
+        label: &str,
+        export_value: &str,
+    ) -> Result<(), PdfiumError> {
+        let mut entries = self.entries();
+
+        if index > entries.len() {
+            return Err(PdfiumError::FormFieldOptionIndexOutOfBounds);
+        }
+
+        entries.insert(
+            index,
+            PdfFormFieldOptionEntry {
+                label: label.to_string(),
+                export_value: export_value.to_string(),
+                is_set: false,
+            },
+        );
+
+        self.write_entries(entries)
+    }
+
+    /// Removes the option at the given index from this [PdfFormFieldOptions] collection.
+    /// If the option was selected, it is deselected.
+    pub fn remove_option(&mut self, index: PdfFormFieldOptionIndex) -> Result<(), PdfiumError> {
+        let mut entries = self.entries();
+
+        if index >= entries.len() {
+            return Err(PdfiumError::FormFieldOptionIndexOutOfBounds);
+        }
+
+        entries.remove(index);
+
+        self.write_entries(entries)
+    }
+
+    /// Removes all options from this [PdfFormFieldOptions] collection, clearing the value
+    /// of the containing form field.
+    #[inline]
+    pub fn clear(&mut self) -> Result<(), PdfiumError> {
+        self.write_entries(Vec::new())
+    }
+
+    /// Replaces all options in this [PdfFormFieldOptions] collection with the given
+    /// `(label, export value)` pairs. Any currently selected option whose export value matches
+    /// the export value of one of the new options remains selected.
+    pub fn set_options(&mut self, options: &[(&str, &str)]) -> Result<(), PdfiumError> {
+        let selected = self
+            .entries()
+            .into_iter()
+            .filter(|entry| entry.is_set)
+            .map(|entry| entry.export_value)
+            .collect::<Vec<_>>();
+
+        let entries = options
+            .iter()
+            .map(|(label, export_value)| PdfFormFieldOptionEntry {
+                label: label.to_string(),
+                export_value: export_value.to_string(),
+                is_set: selected.iter().any(|selected| selected == export_value),
+            })
+            .collect();
+
+        self.write_entries(entries)
+    }
+
+    /// Returns the label, export value, and selection state of every option in this
+    /// [PdfFormFieldOptions] collection.
+    ///
+    /// Pdfium only exposes option labels, so the export values are read from the form
+    /// field's `/Opt` array in a copy of the document saved to memory. The export values
+    /// are then remembered, so that later edits only need to save the document again if
+    /// the options have since been changed elsewhere. If the collection is not attached to
+    /// a document, or the field's options cannot be read, each option is assumed to export
+    /// its label.
+    fn entries(&mut self) -> Vec<PdfFormFieldOptionEntry> {
+        let options = self.iter().collect::<Vec<_>>();
+
+        let labels = options
+            .iter()
+            .map(|option| option.label().cloned().unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        let is_current = self.option_pairs.as_ref().map_or(false, |pairs| {
+            pairs.len() == labels.len()
+                && pairs
+                    .iter()
+                    .zip(labels.iter())
+                    .all(|((_, label), expected)| label == expected)
+        });
+
+        if !is_current {
+            self.option_pairs = self
+                .document_handle
+                .and_then(|document_handle| {
+                    let name = self.field_name()?;
+
+                    read_option_pairs(document_handle, self.bindings)
+                        .ok()?
+                        .remove(&name)
+                })
+                .filter(|pairs| pairs.len() == options.len());
+        }
+
+        options
+            .into_iter()
+            .zip(labels)
+            .map(|(option, label)| {
+                let export_value = match self.option_pairs.as_ref() {
+                    Some(pairs) => pairs[option.index()].0.clone(),
+                    None => label.clone(),
+                };
+
+                PdfFormFieldOptionEntry {
+                    label,
+                    export_value,
+                    is_set: option.is_set(),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the fully qualified name of the containing form field, if any.
+    #[inline]
+    fn field_name(&self) -> Option<String> {
+        get_form_field_name(self.form_handle, self.annotation_handle, self.bindings)
+    }
+
+    /// Rewrites the `/Opt` array of the containing form field using the given entries,
+    /// then updates the form field's selection to match.
+    fn write_entries(&mut self, entries: Vec<PdfFormFieldOptionEntry>) -> Result<(), PdfiumError> {
+        // The existing selection refers to option indices that are about to change,
+        // so we clear the field's value before rewriting the options. Pdfium cannot remove
+        // the value, so it is emptied here; an empty value left in place when the document
+        // is saved is removed then.
+
+        self.set_string_value("V", "")?;
+
+        if let Some(pending_field_changes) = self.pending_field_changes.as_ref() {
+            if let Some(name) = self.field_name() {
+                pending_field_changes.clear_choice_value(name);
+            }
+        }
+
+        let labels = entries
+            .iter()
+            .map(|entry| get_pdfium_utf16le_bytes_from_str(&entry.label))
+            .collect::<Vec<_>>();
+
+        let label_ptrs = labels
+            .iter()
+            .map(|label| label.as_ptr() as *const FPDF_WCHAR)
+            .collect::<Vec<_>>();
+
+        let result = if entries
+            .iter()
+            .all(|entry| entry.label == entry.export_value)
+        {
+            self.bindings.FPDFAnnot_SetFormFieldOptionArray(
+                self.form_handle,
+                self.annotation_handle,
+                label_ptrs.as_ptr(),
+                label_ptrs.len(),
+            )
+        } else {
+            let export_values = entries
+                .iter()
+                .map(|entry| get_pdfium_utf16le_bytes_from_str(&entry.export_value))
+                .collect::<Vec<_>>();
+
+            let export_value_ptrs = export_values
+                .iter()
+                .map(|export_value| export_value.as_ptr() as *const FPDF_WCHAR)
+                .collect::<Vec<_>>();
+
+            self.bindings
+                .FPDFAnnot_SetFormFieldOptionArrayWithExportValues(
+                    self.form_handle,
+                    self.annotation_handle,
+                    export_value_ptrs.as_ptr(),
+                    label_ptrs.as_ptr(),
+                    label_ptrs.len(),
+                )
+        };
+
+        if let Err(error) = self.bindings.to_result(result) {
+            self.option_pairs = None;
+
+            return Err(error);
+        }
+
+        self.option_pairs = Some(
+            entries
+                .iter()
+                .map(|entry| (entry.export_value.clone(), entry.label.clone()))
+                .collect(),
+        );
+
+        let selected = entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.is_set)
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        self.select(&selected, &entries)
+    }
+
+    /// Selects the options at the given indices using Pdfium's form fill API, so that the
+    /// form field's `/V` and `/I` entries and its widget appearance are regenerated by Pdfium.
+    /// If the page containing the form field cannot be located, the form field's value is
+    /// set directly instead; in this case the appearance is not regenerated.
+    fn select(
+        &mut self,
+        selected: &[PdfFormFieldOptionIndex],
+        entries: &[PdfFormFieldOptionEntry],
+    ) -> Result<(), PdfiumError> {
+        let (page_handle, annotation_handle) = match self.find_page_and_annotation_handle() {
+            Some(handles) => handles,
+            None => {
+                return match selected.first() {
+                    Some(index) => self.set_string_value("V", &entries[*index].export_value),
+                    None => Ok(()),
+                };
+            }
+        };
+
+        self.bindings
+            .FORM_OnAfterLoadPage(page_handle, self.form_handle);
+
+        if self.bindings.is_true(
+            self.bindings
+                .FORM_SetFocusedAnnot(self.form_handle, annotation_handle),
+        ) {
+            for index in selected {
+                self.bindings.FORM_SetIndexSelected(
+                    self.form_handle,
+                    page_handle,
+                    *index as c_int,
+                    self.bindings.TRUE(),
+                );
+            }
+
+            // Killing focus commits the selection and regenerates the widget appearance.
+
+            self.bindings.FORM_ForceToKillFocus(self.form_handle);
+        }
+
+        self.bindings.FPDFPage_CloseAnnot(annotation_handle);
+        self.bindings.FPDF_ClosePage(page_handle);
+
+        Ok(())
+    }
+
+    /// Sets the string value associated with the given key in the annotation dictionary
+    /// of the form field containing this [PdfFormFieldOptions] collection, updating the
+    /// annotation's modification date.
+    fn set_string_value(&self, key: &str, value: &str) -> Result<(), PdfiumError> {
+        for (key, value) in [
+            ("M", date_time_to_pdf_string(Utc::now()).as_str()),
+            (key, value),
+        ] {
+            if !self
+                .bindings
+                .is_true(self.bindings.FPDFAnnot_SetStringValue_str(
+                    self.annotation_handle,
+                    key,
+                    value,
+                ))
+            {
+                return Err(PdfiumError::PdfiumLibraryInternalError(
+                    PdfiumInternalError::Unknown,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Locates the page containing the form field that owns this [PdfFormFieldOptions]
+    /// collection, returning handles to the page and to the form field's annotation on that page.
+    /// Both handles must be closed by the caller.
+    ///
+    /// The annotation is identified by its fully qualified field name together with its
+    /// rectangle, so that a widget of a different field that happens to occupy the same
+    /// position on another page is not mistaken for it.
+    fn find_page_and_annotation_handle(&self) -> Option<(FPDF_PAGE, FPDF_ANNOTATION)> {
+        let document_handle = self.document_handle?;
+
+        let name = self.field_name()?;
+
+        let mut target = FS_RECTF {
+            left: 0.0,
+            top: 0.0,
+            right: 0.0,
+            bottom: 0.0,
+        };
+
+        if !self.bindings.is_true(
+            self.bindings
+                .FPDFAnnot_GetRect(self.annotation_handle, &mut target),
+        ) {
+            return None;
+        }
+
+        for page_index in 0..self.bindings.FPDF_GetPageCount(document_handle) {
+            let page_handle = self.bindings.FPDF_LoadPage(document_handle, page_index);
+
+            if page_handle.is_null() {
+                continue;
+            }
+
+            for annotation_index in 0..self.bindings.FPDFPage_GetAnnotCount(page_handle) {
+                let annotation_handle = self
+                    .bindings
+                    .FPDFPage_GetAnnot(page_handle, annotation_index);
+
+                if annotation_handle.is_null() {
+                    continue;
+                }
+
+                let mut rect = FS_RECTF {
+                    left: 0.0,
+                    top: 0.0,
+                    right: 0.0,
+                    bottom: 0.0,
+                };
+
+                const EPSILON: f32 = 0.01;
+
+                if self.bindings.is_true(
+                    self.bindings
+                        .FPDFAnnot_GetRect(annotation_handle, &mut rect),
+                ) && (rect.left - target.left).abs() < EPSILON
+                    && (rect.top - target.top).abs() < EPSILON
+                    && (rect.right - target.right).abs() < EPSILON
+                    && (rect.bottom - target.bottom).abs() < EPSILON
+                    && get_form_field_name(self.form_handle, annotation_handle, self.bindings)
+                        .as_deref()
+                        == Some(name.as_str())
+                {
+                    return Some((page_handle, annotation_handle));
+                }
+
+                self.bindings.FPDFPage_CloseAnnot(annotation_handle);
+            }
+
+            self.bindings.FPDF_ClosePage(page_handle);
+        }
+
+        None
+    }
+}
+
+/// Returns the fully qualified name of the form field owning the given widget annotation, if any.
+#[cfg(feature = "pdfium_future")]
+fn get_form_field_name(
+    form_handle: FPDF_FORMHANDLE,
+    annotation_handle: FPDF_ANNOTATION,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Option<String> {
+    let buffer_length = bindings.FPDFAnnot_GetFormFieldName(
+        form_handle,
+        annotation_handle,
+        std::ptr::null_mut(),
+        0,
+    );
+
+    if buffer_length == 0 {
+        return None;
+    }
+
+    let mut buffer = create_byte_buffer(buffer_length as usize);
+
+    let result = bindings.FPDFAnnot_GetFormFieldName(
+        form_handle,
+        annotation_handle,
+        buffer.as_mut_ptr() as *mut FPDF_WCHAR,
+        buffer_length,
+    );
+
+    debug_assert_eq!(result, buffer_length);
+
+    get_string_from_pdfium_utf16le_bytes(buffer)
+}
+
+/// A single option in a [PdfFormFieldOptions] collection being rewritten.
+#[cfg(feature = "pdfium_future")]
+struct PdfFormFieldOptionEntry {
+    label: String,
+    export_value: String,
+    is_set: bool,
+}
+
+/// The `(export value, label)` pairs of the options of every choice field in a document,
+/// keyed by the fully qualified name of each field.
+pub(crate) type PdfFormFieldOptionPairs = HashMap<String, Vec<(String, String)>>;
+
+/// Reads the export value and label of every option of every choice field in the document
+/// with the given handle.
+///
+/// Pdfium only exposes the labels of options, so the document is saved to memory and the
+/// `/Opt` arrays of its fields are read from the saved bytes. The copy is saved without
+/// encryption so that the strings in the arrays can be read. This is comparatively expensive
+/// for large documents.
+pub(crate) fn read_option_pairs(
+    document_handle: FPDF_DOCUMENT,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Result<PdfFormFieldOptionPairs, PdfiumError> {
+    let update = PdfIncrementalUpdate::from_document(document_handle, bindings)?;
+
+    let mut result = HashMap::new();
+
+    visit_fields(&update, |name, _, field| {
+        if let Some(options) = field.get("Opt") {
+            let pairs = parse_array(&resolve(&update, options))
+                .map(|elements| {
+                    elements
+                        .into_iter()
+                        .filter_map(|element| option_entry(&update, element))
+                        .collect()
+                })
+                .unwrap_or_default();
+
+            result.entry(name.to_string()).or_insert(pairs);
+        }
+    })?;
+
+    Ok(result)
+}
+
+/// Removes the `/V` and `/I` entries of every choice field with one of the given fully
+/// qualified names, and of its widgets, where the field's value is empty in the given
+/// saved document.
+///
+/// Pdfium cannot remove entries from a field dictionary, so the value of a choice field
+/// whose options are rewritten is emptied instead; this removes it when the document is saved.
+pub(crate) fn remove_empty_choice_values(
+    update: &mut PdfIncrementalUpdate,
+    names: &[String],
+) -> Result<(), PdfiumError> {
+    let mut emptied = Vec::new();
+
+    visit_fields(update, |name, id, field| {
+        if names.iter().any(|candidate| candidate == name)
+            && field.get("V").and_then(decode_text_string).as_deref() == Some("")
+        {
+            emptied.push((id, field.clone()));
+        }
+    })?;
+
+    for (id, mut field) in emptied {
+        field.remove("V");
+        field.remove("I");
+
+        update.set_object(id, field.to_bytes());
+    }
+
+    Ok(())
+}
+
+/// Calls the given function with the fully qualified name, object id, and dictionary of
+/// every field and widget in the interactive form of the given saved document.
+fn visit_fields(
+    update: &PdfIncrementalUpdate,
+    mut visit: impl FnMut(&str, PdfObjectId, &PdfRawDictionary),
+) -> Result<(), PdfiumError> {
+    let root = update.get_dictionary(update.root()?)?;
+
+    let form = match root.get("AcroForm") {
+        Some(form) => PdfRawDictionary::parse(&resolve(update, form))?,
+        None => return Ok(()),
+    };
+
+    if let Some(fields) = form.get("Fields") {
+        visit_field_array(update, &resolve(update, fields), "", &mut visit, 0);
+    }
+
+    Ok(())
+}
+
+/// Calls the given function for each field in the given array of fields, and for each
+/// of their descendants.
+fn visit_field_array(
+    update: &PdfIncrementalUpdate,
+    fields: &[u8],
+    parent_name: &str,
+    visit: &mut impl FnMut(&str, PdfObjectId, &PdfRawDictionary),
+    depth: usize,
+) {
+    // Limit the depth of the search, in case the field hierarchy contains a cycle.
+
+    const MAX_DEPTH: usize = 32;
+
+    if depth > MAX_DEPTH {
+        return;
+    }
+
+    for element in parse_array(fields).unwrap_or_default() {
+        let (id, field) = match PdfObjectId::from_reference(trim(element))
+            .and_then(|id| Some((id, update.get_dictionary(id).ok()?)))
+        {
+            Some(field) => field,
+            None => continue,
+        };
+
+        // A field without a partial name shares the name of its parent.
+
+        let name = match field.get("T").and_then(decode_text_string) {
+            Some(partial_name) if parent_name.is_empty() => partial_name,
+            Some(partial_name) => format!("{}.{}", parent_name, partial_name),
+            None => parent_name.to_string(),
+        };
+
+        visit(&name, id, &field);
+
+        if let Some(kids) = field.get("Kids") {
+            visit_field_array(update, &resolve(update, kids), &name, visit, depth + 1);
+        }
+    }
+}
+
 /// An iterator over all the [PdfFormFieldOption] objects in a [PdfFormFieldOptions] collection.
 pub struct PdfFormFieldOptionsIterator<'a> {
     options: &'a PdfFormFieldOptions<'a>,
@@ -163,3 +737,90 @@ impl<'a> Iterator for PdfFormFieldOptionsIterator<'a> {
         next.ok()
     }
 }
+
+#[cfg(all(test, feature = "pdfium_future"))]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_edit_form_field_options() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+        let mut document = pdfium.create_new_pdf()?;
+
+        document.ensure_acro_form()?;
+
+        let form_handle = document.init_form_fill_environment()?;
+
+        let mut page = document
+            .pages_mut()
+            .create_page_at_start(PdfPagePaperSize::a4())?;
+
+        let mut widget = page.annotations_mut().create_widget_annotation(
+            form_handle,
+            "Country",
+            PdfFormFieldType::ComboBox,
+            PdfRect::new_from_values(700.0, 100.0, 720.0, 250.0),
+            Some(&["France", "Germany"]),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+
+        let combo = widget
+            .form_field_mut()
+            .and_then(|field| field.as_combo_box_field_mut())
+            .unwrap();
+
+        combo.set_value("Germany")?;
+
+        let options = combo.options_mut();
+
+        options.add_option("Spain", "ES")?;
+        options.insert_option(0, "Austria", "AT")?;
+        options.remove_option(1)?;
+
+        assert!(options.insert_option(10, "Italy", "IT").is_err());
+
+        let labels = |options: &PdfFormFieldOptions| -> Vec<Option<String>> {
+            options
+                .iter()
+                .map(|option| option.label().cloned())
+                .collect()
+        };
+
+        assert_eq!(
+            labels(options),
+            vec![
+                Some("Austria".to_string()),
+                Some("Germany".to_string()),
+                Some("Spain".to_string())
+            ]
+        );
+        assert!(options.get(1)?.is_set());
+
+        // The export values read before the first edit are remembered across later edits.
+
+        assert_eq!(
+            options
+                .entries()
+                .into_iter()
+                .map(|entry| entry.export_value)
+                .collect::<Vec<_>>(),
+            vec!["AT".to_string(), "Germany".to_string(), "ES".to_string()]
+        );
+
+        options.set_options(&[("Germany", "Germany"), ("Italy", "IT")])?;
+
+        assert!(options.get(0)?.is_set());
+        assert!(!options.get(1)?.is_set());
+
+        options.clear()?;
+
+        assert!(options.is_empty());
+
+        Ok(())
+    }
+}
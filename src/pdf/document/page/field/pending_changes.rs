@@ -0,0 +1,180 @@
+//! Defines the [PdfPendingFieldChanges] struct, holding the changes made to the form fields
+//! of a single `PdfDocument` that Pdfium cannot make by itself.
+//!
+//! These changes are recorded when they are made, and written to the document as an
+//! incremental update when the document is saved. Each document owns its own set of
+//! pending changes, shared with the pages, annotations, and form fields retrieved from it.
+
+use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::PdfIncrementalUpdate;
+use crate::pdf::document::page::field::button_appearance::PdfButtonAppearance;
+use crate::pdf::document::page::field::choice_appearance::PdfChoiceAppearance;
+use crate::pdf::document::page::field::options::remove_empty_choice_values;
+use crate::pdf::document::page::field::radio_group::PdfRadioGroup;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// The changes to the form fields of a single document that are waiting to be written
+/// when the document is saved.
+#[derive(Default)]
+struct PdfPendingFieldChangeSet {
+    radio_groups: Vec<PdfRadioGroup>,
+    button_appearances: Vec<PdfButtonAppearance>,
+    choice_appearances: Vec<PdfChoiceAppearance>,
+    cleared_choice_values: Vec<String>,
+}
+
+/// A handle to the changes to the form fields of a single document that are waiting to be
+/// written when the document is saved. Cloning the handle shares the same set of changes.
+#[derive(Clone, Default)]
+pub(crate) struct PdfPendingFieldChanges {
+    changes: Arc<Mutex<PdfPendingFieldChangeSet>>,
+}
+
+impl PdfPendingFieldChanges {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, PdfPendingFieldChangeSet> {
+        // The change set is never left partially updated, so it remains usable even if
+        // another thread panicked while holding the lock.
+
+        self.changes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records a new radio button group.
+    #[cfg_attr(not(feature = "pdfium_future"), allow(dead_code))]
+    pub(crate) fn add_radio_group(&self, group: PdfRadioGroup) {
+        self.lock().radio_groups.push(group);
+    }
+
+    /// Returns the on state recorded with the generated appearance of the checkbox or
+    /// radio button widget tagged with the given annotation name, if any.
+    pub(crate) fn button_on_state(&self, marker: &str) -> Option<String> {
+        self.lock()
+            .button_appearances
+            .iter()
+            .find(|appearance| appearance.marker == marker)
+            .map(|appearance| appearance.on_state.clone())
+    }
+
+    /// Records the generated appearance of a checkbox, radio button, or push button widget,
+    /// replacing any appearance previously recorded for the same widget.
+    pub(crate) fn set_button_appearance(&self, mut appearance: PdfButtonAppearance) {
+        let mut changes = self.lock();
+
+        if let Some(index) = changes
+            .button_appearances
+            .iter()
+            .position(|existing| existing.marker == appearance.marker)
+        {
+            let existing = changes.button_appearances.remove(index);
+
+            if appearance.original_name.is_none() {
+                appearance.original_name = existing.original_name;
+            }
+        }
+
+        changes.button_appearances.push(appearance);
+    }
+
+    /// Records the generated appearance of a combo box or list box widget, replacing any
+    /// appearance previously recorded for the same widget.
+    pub(crate) fn set_choice_appearance(&self, mut appearance: PdfChoiceAppearance) {
+        let mut changes = self.lock();
+
+        if let Some(index) = changes
+            .choice_appearances
+            .iter()
+            .position(|existing| existing.marker == appearance.marker)
+        {
+            let existing = changes.choice_appearances.remove(index);
+
+            if appearance.original_name.is_none() {
+                appearance.original_name = existing.original_name;
+            }
+        }
+
+        changes.choice_appearances.push(appearance);
+    }
+
+    /// Records that the value of the choice field with the given fully qualified name was
+    /// emptied, so that the value can be removed if it is still empty when saved.
+    #[cfg_attr(not(feature = "pdfium_future"), allow(dead_code))]
+    pub(crate) fn clear_choice_value(&self, name: String) {
+        let mut changes = self.lock();
+
+        if !changes.cleared_choice_values.contains(&name) {
+            changes.cleared_choice_values.push(name);
+        }
+    }
+
+    /// Returns `true` if no changes are waiting to be written.
+    pub(crate) fn is_empty(&self) -> bool {
+        let changes = self.lock();
+
+        changes.radio_groups.is_empty()
+            && changes.button_appearances.is_empty()
+            && changes.choice_appearances.is_empty()
+            && changes.cleared_choice_values.is_empty()
+    }
+
+    /// Discards any changes waiting to be written to the widget tagged with the given
+    /// annotation name, as when the widget is removed from its page.
+    pub(crate) fn discard_widget(&self, marker: &str) {
+        let mut changes = self.lock();
+
+        for group in changes.radio_groups.iter_mut() {
+            group.widgets.retain(|widget| widget.marker != marker);
+        }
+
+        changes
+            .radio_groups
+            .retain(|group| !group.widgets.is_empty());
+
+        changes
+            .button_appearances
+            .retain(|appearance| appearance.marker != marker);
+
+        changes
+            .choice_appearances
+            .retain(|appearance| appearance.marker != marker);
+    }
+
+    /// Writes all pending changes to the given update of the document's saved bytes.
+    pub(crate) fn apply_to(&self, update: &mut PdfIncrementalUpdate) -> Result<(), PdfiumError> {
+        let changes = self.lock();
+
+        for group in changes.radio_groups.iter() {
+            group.apply_to(update, &changes.button_appearances)?;
+        }
+
+        // Appearances of widgets in new radio button groups were written with their groups.
+
+        for appearance in changes.button_appearances.iter() {
+            let is_grouped = changes.radio_groups.iter().any(|group| {
+                group
+                    .widgets
+                    .iter()
+                    .any(|widget| widget.marker == appearance.marker)
+            });
+
+            if !is_grouped {
+                appearance.apply_to(update)?;
+            }
+        }
+
+        // Emptied choice field values are removed before choice field appearances are drawn
+        // from the fields' values.
+
+        remove_empty_choice_values(update, &changes.cleared_choice_values)?;
+
+        for appearance in changes.choice_appearances.iter() {
+            appearance.apply_to(update)?;
+        }
+
+        Ok(())
+    }
+}
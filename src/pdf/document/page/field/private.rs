@@ -17,7 +17,7 @@ pub(crate) mod internal {
     use crate::bindings::PdfiumLibraryBindings;
     use crate::error::{PdfiumError, PdfiumInternalError};
     use crate::pdf::appearance_mode::PdfAppearanceMode;
-    use crate::pdf::document::page::field::PdfFormFieldCommon;
+    use crate::pdf::document::page::field::{PdfFormFieldActionEvent, PdfFormFieldCommon};
     use crate::utils::dates::date_time_to_pdf_string;
     use crate::utils::mem::create_byte_buffer;
     use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
@@ -183,6 +183,13 @@ pub(crate) mod internal {
         /// Returns the [PdfiumLibraryBindings] used by this [PdfFormField].
         fn bindings(&self) -> &dyn PdfiumLibraryBindings;
 
+        /// Returns the on state recorded with a generated appearance of this [PdfFormField]
+        /// that has not yet been written to the document, if any.
+        #[inline]
+        fn pending_on_state(&self) -> Option<String> {
+            None
+        }
+
         /// Internal implementation of [PdfFormFieldCommon::name()].
         fn name_impl(&self) -> Option<String> {
             // Retrieving the field name from Pdfium is a two-step operation. First, we call
@@ -296,6 +303,15 @@ pub(crate) mod internal {
         /// Internal implementation of `export_value()` function shared by on/off form field widgets
         /// such as checkbox and radio button fields. Not exposed directly by [PdfFormFieldCommon].
         fn export_value_impl(&self) -> Option<String> {
+            // Pdfium takes the on state of a checkbox or radio button from the keys of its
+            // normal appearance state dictionary. A generated appearance replaces the dictionary
+            // with a single stream until the document is saved, so until then we use the on state
+            // recorded with the generated appearance instead.
+
+            if let Some(on_state) = self.pending_on_state() {
+                return Some(on_state);
+            }
+
             // Retrieving the export value from Pdfium is a two-step operation. First, we call
             // FPDFAnnot_GetFormFieldExportValue() with a null buffer; this will retrieve the length of
             // the export value text in bytes. If the length is zero, then the export value is not set.
@@ -491,6 +507,47 @@ pub(crate) mod internal {
             }
         }
 
+        /// Internal implementation of [PdfFormFieldCommon::javascript()].
+        fn javascript_impl(&self, event: PdfFormFieldActionEvent) -> Option<String> {
+            // Retrieving the script from Pdfium is a two-step operation. First, we call
+            // FPDFAnnot_GetFormAdditionalActionJavaScript() with a null buffer; this will
+            // retrieve the length of the script in bytes, including a two-byte terminator.
+            // If the length is zero, an error occurred; if the length is two, then there is
+            // no script for the event.
+
+            // If the length is greater than two, then we reserve a byte buffer of the given
+            // length and call FPDFAnnot_GetFormAdditionalActionJavaScript() again with a
+            // pointer to the buffer; this will write the script to the buffer in UTF16LE format.
+
+            let buffer_length = self.bindings().FPDFAnnot_GetFormAdditionalActionJavaScript(
+                self.form_handle(),
+                self.annotation_handle(),
+                event.as_pdfium(),
+                std::ptr::null_mut(),
+                0,
+            );
+
+            if buffer_length <= 2 {
+                // There is no script for this event.
+
+                return None;
+            }
+
+            let mut buffer = create_byte_buffer(buffer_length as usize);
+
+            let result = self.bindings().FPDFAnnot_GetFormAdditionalActionJavaScript(
+                self.form_handle(),
+                self.annotation_handle(),
+                event.as_pdfium(),
+                buffer.as_mut_ptr() as *mut FPDF_WCHAR,
+                buffer_length,
+            );
+
+            debug_assert_eq!(result, buffer_length);
+
+            get_string_from_pdfium_utf16le_bytes(buffer).filter(|script| !script.is_empty())
+        }
+
         /// Returns the currently set appearance stream for this form field, if any.
         fn appearance_stream_impl(&self) -> Option<String> {
             self.get_string_value("AS")
@@ -4,6 +4,8 @@
 use crate::bindgen::{FPDF_ANNOTATION, FPDF_FORMHANDLE};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
+use crate::pdf::document::page::field::button_appearance::{ButtonAppearanceBuilder, ButtonKind};
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::field::private::internal::{
     PdfFormFieldFlags, PdfFormFieldPrivate,
 };
@@ -25,6 +27,7 @@ use {
 pub struct PdfFormRadioButtonField<'a> {
     form_handle: FPDF_FORMHANDLE,
     annotation_handle: FPDF_ANNOTATION,
+    pending_field_changes: Option<PdfPendingFieldChanges>,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -38,6 +41,22 @@ impl<'a> PdfFormRadioButtonField<'a> {
         PdfFormRadioButtonField {
             form_handle,
             annotation_handle,
+            pending_field_changes: None,
+            bindings,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn from_pdfium_with_document(
+        form_handle: FPDF_FORMHANDLE,
+        annotation_handle: FPDF_ANNOTATION,
+        pending_field_changes: PdfPendingFieldChanges,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfFormRadioButtonField {
+            form_handle,
+            annotation_handle,
+            pending_field_changes: Some(pending_field_changes),
             bindings,
         }
     }
@@ -154,6 +173,25 @@ impl<'a> PdfFormRadioButtonField<'a> {
         self.set_value_impl(normalized_value)
     }
 
+    /// Deselects the radio button of this [PdfFormRadioButtonField] object, as when importing
+    /// the off state for its control group.
+    ///
+    /// Only the widget's appearance state is changed. The value of a control group belongs
+    /// to the group's parent field, so the widget's own value is only set if the widget has
+    /// no parent field and is therefore the field itself.
+    pub(crate) fn set_unchecked(&mut self) -> Result<(), PdfiumError> {
+        self.set_string_value("AS", "/Off")?;
+
+        if self.bindings.is_true(
+            self.bindings
+                .FPDFAnnot_HasKey(self.annotation_handle, "Parent"),
+        ) {
+            Ok(())
+        } else {
+            self.set_value_impl("Off")
+        }
+    }
+
     /// Returns `true` if exactly one radio button in the control group containing this
     /// [PdfFormRadioButtonField] must be selected at all times. If so, then toggling the
     /// currently selected radio button is not possible. If `false`, then toggling the
@@ -206,6 +244,33 @@ impl<'a> PdfFormRadioButtonField<'a> {
             is_group_in_unison,
         )
     }
+
+    /// Returns a builder for generating the visual appearance of this [PdfFormRadioButtonField].
+    ///
+    /// The builder generates normal and down appearances for both the selected and unselected
+    /// states, along with the radio button's `/MK` appearance characteristics. Radio buttons
+    /// are drawn as circles.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// radio_button.set_appearance()
+    ///     .with_style(PdfFormButtonStyle::Circle)
+    ///     .with_glyph_color(PdfColor::BLUE)
+    ///     .apply()?;
+    /// ```
+    pub fn set_appearance(&self) -> ButtonAppearanceBuilder<'_> {
+        ButtonAppearanceBuilder::new(
+            self.annotation_handle,
+            self.pending_field_changes.clone(),
+            self.bindings,
+            ButtonKind::RadioButton,
+            self.get_string_value("NM"),
+            self.export_value_impl()
+                .unwrap_or_else(|| "Yes".to_string()),
+            self.is_checked().unwrap_or(false),
+        )
+    }
 }
 
 impl<'a> PdfFormFieldPrivate<'a> for PdfFormRadioButtonField<'a> {
@@ -223,4 +288,12 @@ impl<'a> PdfFormFieldPrivate<'a> for PdfFormRadioButtonField<'a> {
     fn bindings(&self) -> &dyn PdfiumLibraryBindings {
         self.bindings
     }
+
+    fn pending_on_state(&self) -> Option<String> {
+        let marker = self.get_string_value("NM")?;
+
+        self.pending_field_changes
+            .as_ref()?
+            .button_on_state(&marker)
+    }
 }
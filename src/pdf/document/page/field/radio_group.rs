@@ -0,0 +1,526 @@
+//! Defines the [PdfFormButtonStyle] enum, controlling the appearance of the selected state of
+//! checkbox and radio button widgets, along with the internal representation of radio button
+//! groups created by `PdfPageAnnotations::create_radio_group()`.
+//!
+//! Pdfium cannot create a form field with more than one widget, so each widget in a new
+//! radio button group is initially created by Pdfium as an independent field sharing the
+//! group's name. The widgets are tagged with a unique annotation name, and the group is
+//! recorded in the pending field changes of the containing document; when the document is
+//! saved, the widgets are located in the saved file and rewritten as the kids of a single
+//! parent field.
+
+use crate::error::PdfiumError;
+use crate::pdf::document::bookmark_tree::format_number;
+use crate::pdf::document::incremental_update::{
+    encode_text_string, PdfIncrementalUpdate, PdfObjectId, PdfRawDictionary,
+};
+use crate::pdf::document::page::field::button_appearance::PdfButtonAppearance;
+use crate::pdf::document::page::field::private::internal::PdfFormFieldFlags;
+
+/// The glyph drawn inside a checkbox or radio button widget when it is selected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PdfFormButtonStyle {
+    /// A filled dot, drawn inside the widget's circular border.
+    Circle,
+
+    /// A check mark.
+    Check,
+
+    /// A diagonal cross.
+    Cross,
+
+    /// The glyph with the given character code in the ZapfDingbats font, for instance
+    /// `b'u'` for a filled diamond or `b'H'` for a filled star.
+    Custom(u8),
+}
+
+impl PdfFormButtonStyle {
+    /// Returns the character code of the ZapfDingbats glyph used for this style. This is the
+    /// value of the `/CA` entry in a widget's appearance characteristics dictionary.
+    pub(crate) fn glyph(&self) -> u8 {
+        match self {
+            PdfFormButtonStyle::Circle => b'l',
+            PdfFormButtonStyle::Check => b'4',
+            PdfFormButtonStyle::Cross => b'8',
+            PdfFormButtonStyle::Custom(glyph) => *glyph,
+        }
+    }
+}
+
+impl Default for PdfFormButtonStyle {
+    #[inline]
+    fn default() -> Self {
+        PdfFormButtonStyle::Circle
+    }
+}
+
+/// A single widget in a [PdfRadioGroup].
+pub(crate) struct PdfRadioGroupWidget {
+    /// The unique `/NM` value used to locate this widget in a saved file.
+    pub(crate) marker: String,
+    pub(crate) export_value: String,
+    pub(crate) width: f32,
+    pub(crate) height: f32,
+}
+
+/// A radio button group whose widgets must be joined under a single parent field
+/// when the containing document is saved.
+pub(crate) struct PdfRadioGroup {
+    pub(crate) name: String,
+    pub(crate) style: PdfFormButtonStyle,
+    pub(crate) widgets: Vec<PdfRadioGroupWidget>,
+}
+
+impl PdfRadioGroup {
+    /// The prefix of the annotation names used to tag the widgets of new groups.
+    pub(crate) const MARKER_PREFIX: &'static str = "pdfium-render-radio-group-";
+
+    /// Returns the annotation name used to tag the widget at the given index in a new group.
+    #[cfg_attr(not(feature = "pdfium_future"), allow(dead_code))]
+    pub(crate) fn marker(group_id: u64, index: usize) -> String {
+        format!("{}{group_id}-{index}", Self::MARKER_PREFIX)
+    }
+
+    /// Rewrites the widgets of this group in the given saved document as the kids of a single
+    /// parent field, keeping the selection made before the document was saved. If more than
+    /// one widget was selected, the first of them remains selected. Widgets with a matching
+    /// entry in the given button appearances are drawn using that appearance rather than
+    /// the group's style.
+    ///
+    /// Returns [PdfiumError::FormFieldWidgetNotFound] if a widget cannot be found in the
+    /// saved document.
+    pub(crate) fn apply_to(
+        &self,
+        update: &mut PdfIncrementalUpdate,
+        appearances: &[PdfButtonAppearance],
+    ) -> Result<(), PdfiumError> {
+        let mut widgets = Vec::with_capacity(self.widgets.len());
+
+        for widget in self.widgets.iter() {
+            let needle = format!("({})", widget.marker);
+
+            let id = update
+                .find_object_containing(needle.as_bytes())
+                .map_err(|_| PdfiumError::FormFieldWidgetNotFound)?;
+
+            widgets.push((id, widget, update.get_dictionary(id)?));
+        }
+
+        if widgets.is_empty() {
+            return Ok(());
+        }
+
+        // Until the document is saved, each widget is a separate field, so a widget is
+        // selected if either its appearance state or its own value is set to anything
+        // other than the off state.
+
+        let selected = widgets.iter().position(|(_, _, dictionary)| {
+            dictionary.get("AS").map(is_on_value).unwrap_or(false)
+                || dictionary.get("V").map(is_on_value).unwrap_or(false)
+        });
+
+        // The parent and its kids refer to one another, so we reserve the parent's object
+        // number before writing the kids.
+
+        let parent_id = update.add_object(Vec::new());
+
+        let mut kids = Vec::new();
+
+        let mut options = Vec::new();
+
+        for (index, (id, widget, mut dictionary)) in widgets.into_iter().enumerate() {
+            // The field entries created by Pdfium move to the parent. When a radio button
+            // field has an /Opt array, the on state of each kid is named after its index in
+            // the array, so export values need not be valid PDF names.
+
+            for key in ["T", "FT", "Ff", "V", "DV", "Opt", "NM", "AS", "AP", "MK"] {
+                dictionary.remove(key);
+            }
+
+            let on_state = format!("/{}", index);
+
+            match appearances
+                .iter()
+                .find(|appearance| appearance.marker == widget.marker)
+            {
+                Some(appearance) => {
+                    appearance.write_to(update, &mut dictionary, on_state.as_bytes())?
+                }
+                None => {
+                    let normal = format!(
+                        "<<{} {} /Off {}>>",
+                        on_state,
+                        String::from_utf8_lossy(
+                            &update
+                                .add_object(self.appearance_stream(widget, true))
+                                .to_reference()
+                        ),
+                        String::from_utf8_lossy(
+                            &update
+                                .add_object(self.appearance_stream(widget, false))
+                                .to_reference()
+                        ),
+                    );
+
+                    dictionary.set("AP", format!("<</N {normal}>>"));
+                    dictionary.set("MK", self.appearance_characteristics());
+                }
+            }
+
+            dictionary.set("Parent", parent_id.to_reference());
+            dictionary.set(
+                "AS",
+                if selected == Some(index) {
+                    on_state
+                } else {
+                    "/Off".to_string()
+                },
+            );
+            dictionary.set("F", "4");
+
+            update.set_object(id, dictionary.to_bytes());
+
+            kids.push(id);
+            options.push(encode_text_string(&widget.export_value));
+        }
+
+        let flags = PdfFormFieldFlags::ButtonIsRadio | PdfFormFieldFlags::ButtonNoToggleToOff;
+
+        let mut parent = PdfRawDictionary::new();
+
+        parent.set("FT", "/Btn");
+        parent.set("Ff", flags.bits().to_string());
+        parent.set("T", encode_text_string(&self.name));
+        parent.set("Kids", reference_array(&kids));
+        parent.set(
+            "Opt",
+            [b"[".to_vec(), options.join(&b' '), b"]".to_vec()].concat(),
+        );
+        parent.set(
+            "V",
+            match selected {
+                Some(index) => format!("/{}", index),
+                None => "/Off".to_string(),
+            },
+        );
+        parent.set("DA", "(/ZaDb 0 Tf 0 g)");
+
+        update.set_object(parent_id, parent.to_bytes());
+
+        set_form_fields(update, &kids, parent_id)
+    }
+
+    /// Returns the `/MK` appearance characteristics dictionary for the widgets in this group.
+    fn appearance_characteristics(&self) -> Vec<u8> {
+        let mut result = b"<</BC [0] /CA ".to_vec();
+
+        result.extend_from_slice(&encode_text_string(
+            &(self.style.glyph() as char).to_string(),
+        ));
+        result.extend_from_slice(b">>");
+
+        result
+    }
+
+    /// Returns a complete form XObject stream drawing the given widget in its on or off state.
+    fn appearance_stream(&self, widget: &PdfRadioGroupWidget, is_on: bool) -> Vec<u8> {
+        let content = radio_button_content(self.style, widget.width, widget.height, is_on);
+
+        let mut result = format!(
+            "<</Type /XObject /Subtype /Form /BBox [0 0 {} {}] \
+            /Resources <</Font <</ZaDb <</Type /Font /Subtype /Type1 /BaseFont /ZapfDingbats>>>>>> \
+            /Length {}>>\nstream\n",
+            format_number(widget.width),
+            format_number(widget.height),
+            content.len()
+        )
+        .into_bytes();
+
+        result.extend_from_slice(&content);
+        result.extend_from_slice(b"\nendstream");
+
+        result
+    }
+}
+
+/// Returns `true` if the given raw `/AS` or `/V` value refers to any state other than
+/// the off state.
+fn is_on_value(value: &[u8]) -> bool {
+    let value = value
+        .iter()
+        .copied()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .collect::<Vec<_>>();
+
+    !matches!(value.as_slice(), b"" | b"/Off" | b"(Off)" | b"()" | b"<>")
+}
+
+/// Replaces the given kids with the given parent field in the `/Fields` array of the
+/// interactive form dictionary of the given saved document.
+fn set_form_fields(
+    update: &mut PdfIncrementalUpdate,
+    kids: &[PdfObjectId],
+    parent: PdfObjectId,
+) -> Result<(), PdfiumError> {
+    let update_fields = |fields: Option<&[u8]>| -> Result<Vec<u8>, PdfiumError> {
+        let mut fields = match fields {
+            Some(fields) => PdfObjectId::from_reference_array(fields)
+                .ok_or(PdfiumError::UnsupportedPdfFileStructure)?,
+            None => Vec::new(),
+        };
+
+        fields.retain(|field| !kids.contains(field));
+        fields.push(parent);
+
+        Ok(reference_array(&fields))
+    };
+
+    let set_fields = |update: &mut PdfIncrementalUpdate,
+                      form: &mut PdfRawDictionary|
+     -> Result<(), PdfiumError> {
+        match form.get_reference("Fields") {
+            Some(fields_id) => {
+                let fields = update_fields(Some(update.get_object(fields_id)?))?;
+
+                update.set_object(fields_id, fields);
+            }
+            None => {
+                let fields = update_fields(form.get("Fields"))?;
+
+                form.set("Fields", fields);
+            }
+        }
+
+        Ok(())
+    };
+
+    let root_id = update.root()?;
+
+    let mut root = update.get_dictionary(root_id)?;
+
+    match root.get_reference("AcroForm") {
+        Some(form_id) => {
+            let mut form = update.get_dictionary(form_id)?;
+
+            set_fields(update, &mut form)?;
+
+            update.set_object(form_id, form.to_bytes());
+        }
+        None => {
+            let mut form = match root.get("AcroForm") {
+                Some(form) => PdfRawDictionary::parse(form)?,
+                None => PdfRawDictionary::new(),
+            };
+
+            set_fields(update, &mut form)?;
+
+            root.set("AcroForm", form.to_bytes());
+
+            update.set_object(root_id, root.to_bytes());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns an array of indirect references to the given objects.
+fn reference_array(objects: &[PdfObjectId]) -> Vec<u8> {
+    [
+        b"[".to_vec(),
+        objects
+            .iter()
+            .map(|object| object.to_reference())
+            .collect::<Vec<_>>()
+            .join(&b' '),
+        b"]".to_vec(),
+    ]
+    .concat()
+}
+
+/// Returns the content stream drawing a radio button of the given size in the given style,
+/// either selected or not. Every radio button has a circular border; a selected button
+/// also shows the style's glyph, or a filled dot for [PdfFormButtonStyle::Circle].
+pub(crate) fn radio_button_content(
+    style: PdfFormButtonStyle,
+    width: f32,
+    height: f32,
+    is_on: bool,
+) -> Vec<u8> {
+    let size = width.min(height);
+
+    let (x, y) = (width / 2.0, height / 2.0);
+
+    let mut result = format!("q 0 G 1 w {} S Q\n", circle_path(x, y, size / 2.0 - 0.5));
+
+    if is_on {
+        match style {
+            PdfFormButtonStyle::Circle => {
+                result.push_str(&format!("q 0 g {} f Q\n", circle_path(x, y, size / 4.0)));
+            }
+            _ => {
+                // ZapfDingbats glyphs are roughly 0.8 em wide and 0.7 em tall.
+
+                let font_size = size * 0.6;
+
+                let glyph = match style.glyph() {
+                    glyph @ (b'(' | b')' | b'\\') => format!("\\{}", glyph as char),
+                    glyph => (glyph as char).to_string(),
+                };
+
+                result.push_str(&format!(
+                    "q 0 g BT /ZaDb {} Tf {} {} Td ({}) Tj ET Q\n",
+                    format_number(font_size),
+                    format_number(x - font_size * 0.4),
+                    format_number(y - font_size * 0.35),
+                    glyph
+                ));
+            }
+        }
+    }
+
+    result.into_bytes()
+}
+
+/// Returns the path construction operators for a circle with the given center and radius,
+/// approximated by four Bézier curves.
+pub(crate) fn circle_path(x: f32, y: f32, radius: f32) -> String {
+    const KAPPA: f32 = 0.552_284_8;
+
+    let k = radius * KAPPA;
+
+    let n = format_number;
+
+    format!(
+        "{} {} m {} {} {} {} {} {} c {} {} {} {} {} {} c {} {} {} {} {} {} c {} {} {} {} {} {} c h",
+        n(x + radius),
+        n(y),
+        n(x + radius),
+        n(y + k),
+        n(x + k),
+        n(y + radius),
+        n(x),
+        n(y + radius),
+        n(x - k),
+        n(y + radius),
+        n(x - radius),
+        n(y + k),
+        n(x - radius),
+        n(y),
+        n(x - radius),
+        n(y - k),
+        n(x - k),
+        n(y - radius),
+        n(x),
+        n(y - radius),
+        n(x + k),
+        n(y - radius),
+        n(x + radius),
+        n(y - k),
+        n(x + radius),
+        n(y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "pdfium_future")]
+    use crate::prelude::*;
+
+    #[cfg(feature = "pdfium_future")]
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    #[cfg(feature = "pdfium_future")]
+    fn test_create_radio_group() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+        let mut document = pdfium.create_new_pdf()?;
+
+        document.ensure_acro_form()?;
+        document.init_form_fill_environment()?;
+
+        let mut page = document
+            .pages_mut()
+            .create_page_at_start(PdfPagePaperSize::a4())?;
+
+        let mut widgets = page.annotations_mut().create_radio_group_with_style(
+            "Size",
+            &[
+                (
+                    PdfRect::new_from_values(700.0, 100.0, 712.0, 112.0),
+                    "Small",
+                ),
+                (
+                    PdfRect::new_from_values(700.0, 130.0, 712.0, 142.0),
+                    "Large",
+                ),
+            ],
+            PdfFormButtonStyle::Check,
+        )?;
+
+        assert_eq!(widgets.len(), 2);
+
+        widgets[1]
+            .form_field_mut()
+            .and_then(|field| field.as_radio_button_field_mut())
+            .unwrap()
+            .set_checked()?;
+
+        let bytes = document.save_to_bytes()?;
+        let text = String::from_utf8_lossy(&bytes);
+
+        assert!(text.contains("/Kids ["));
+        assert!(text.contains("/Opt [(Small) (Large)]"));
+        assert!(text.contains("/MK <</BC [0] /CA (4)>>"));
+        assert!(text.contains("/V /1"));
+        assert!(text.contains("/AS /1"));
+        assert!(!text.contains("pdfium-render-radio-group-"));
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        let fields = document
+            .pages()
+            .first()?
+            .annotations()
+            .iter()
+            .filter_map(|annotation| {
+                let field = annotation.as_form_field()?.as_radio_button_field()?;
+
+                Some((field.name()?, field.is_checked().ok()?))
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            fields,
+            vec![("Size".to_string(), false), ("Size".to_string(), true)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_radio_button_content() {
+        assert_eq!(circle_path(10.0, 10.0, 0.0), "10 10 m 10 10 10 10 10 10 c 10 10 10 10 10 10 c 10 10 10 10 10 10 c 10 10 10 10 10 10 c h");
+
+        let off = radio_button_content(PdfFormButtonStyle::Check, 20.0, 10.0, false);
+        let on = radio_button_content(PdfFormButtonStyle::Check, 20.0, 10.0, true);
+
+        assert!(on.starts_with(&off));
+        assert!(String::from_utf8(on)
+            .unwrap()
+            .contains("/ZaDb 6 Tf 7.6 2.9 Td (4) Tj"));
+
+        let custom = radio_button_content(PdfFormButtonStyle::Custom(b')'), 10.0, 10.0, true);
+
+        assert!(String::from_utf8(custom).unwrap().contains("(\\)) Tj"));
+
+        assert_eq!(PdfFormButtonStyle::default().glyph(), b'l');
+
+        assert!(is_on_value(b"/Small"));
+        assert!(is_on_value(b"(1)"));
+        assert!(!is_on_value(b" /Off"));
+        assert!(!is_on_value(b"(Off)"));
+        assert!(!is_on_value(b""));
+    }
+}
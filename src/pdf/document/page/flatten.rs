@@ -1,6 +1,638 @@
-use crate::bindgen::FPDF_PAGE;
-use crate::error::PdfiumError;
+//! Defines a custom flatten operation for a single `PdfPage`, used in place of Pdfium's
+//! built-in `FPDFPage_Flatten()` function when the `flatten` crate feature is enabled.
+//!
+//! Each annotation's normal appearance stream is drawn on the page as a form XObject,
+//! positioned according to the annotation's `/Rect` and the appearance stream's `/BBox`
+//! and `/Matrix`. The annotation itself is then removed from the page.
 
-pub(crate) fn flatten(page_handle: FPDF_PAGE) -> Result<(), PdfiumError> {
-    unimplemented!()
+use crate::bindgen::{
+    FLAT_PRINT, FPDF_ANNOT, FPDF_ANNOT_FLAG_HIDDEN, FPDF_ANNOT_FLAG_NOVIEW, FPDF_ANNOT_FLAG_PRINT,
+    FPDF_ANNOT_LINK, FPDF_ANNOT_POPUP, FPDF_DOCUMENT, FPDF_FORMHANDLE, FPDF_PAGE, FS_MATRIX,
+    FS_RECTF,
+};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::{PdfiumError, PdfiumInternalError};
+use crate::pdf::document::bookmark_tree::format_number;
+use crate::pdf::document::incremental_update::{
+    decode_name, parse_array, trim, PdfIncrementalUpdate, PdfObjectId, PdfRawDictionary,
+};
+use crate::pdf::document::page::field::button_appearance::stream_object;
+use crate::pdf::document::page::field::choice_appearance::resolve;
+use crate::pdf::document::page::index_cache::PdfPageIndexCache;
+use std::os::raw::c_int;
+
+/// The normal appearance stream of an annotation, together with the bounding box and matrix
+/// that, along with the annotation's `/Rect`, determine where the appearance is drawn on the page.
+#[derive(Copy, Clone)]
+struct PdfAppearanceStream {
+    id: PdfObjectId,
+    bbox: FS_RECTF,
+    matrix: FS_MATRIX,
+}
+
+/// Flattens all annotations and form fields on the page with the given `FPDF_PAGE` handle
+/// into the page's content.
+///
+/// The given `flag` should be either `FLAT_NORMALDISPLAY` or `FLAT_PRINT`. Annotations that
+/// would not be visible in the chosen mode - because of their hidden, no-view, or print
+/// annotation flags - are removed from the page without being drawn. Popup annotations
+/// are never drawn, since they are only displayed on demand by an interactive viewer.
+/// Link annotations carry navigation rather than appearance, and are left in place.
+///
+/// Pdfium does not expose the appearance streams of annotations, so the document is saved to
+/// memory and the appearance streams are read from the saved bytes. They are drawn onto a new
+/// page appended to the saved copy, which is then loaded and imported into the page as a
+/// single form XObject. If any step fails, an error is returned and no annotation is removed.
+///
+/// The caller is responsible for regenerating the content of the page and reloading it
+/// once this function returns.
+pub(crate) fn flatten(
+    document_handle: FPDF_DOCUMENT,
+    page_handle: FPDF_PAGE,
+    form_handle: Option<FPDF_FORMHANDLE>,
+    flag: u32,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Result<(), PdfiumError> {
+    if let Some(form_handle) = form_handle {
+        // Commit any text still being edited in a focused form field, so that its value
+        // is reflected in the field's appearance stream.
+
+        bindings.FORM_ForceToKillFocus(form_handle);
+    }
+
+    generate_appearance_streams(page_handle, form_handle, bindings);
+
+    if let Some(form_handle) = form_handle {
+        // The form fill environment keeps its own references to the widget annotations
+        // on the page; release them before any widgets are removed.
+
+        bindings.FORM_OnBeforeClosePage(page_handle, form_handle);
+    }
+
+    let mut update = PdfIncrementalUpdate::from_document(document_handle, bindings)?;
+
+    let appearances = read_appearance_streams(&update, document_handle, page_handle)?;
+
+    let annotation_count = bindings.FPDFPage_GetAnnotCount(page_handle);
+
+    if appearances.len() != annotation_count.max(0) as usize {
+        return Err(PdfiumError::PageFlattenFailure);
+    }
+
+    // Annotations are drawn in the order of the page's /Annots array, so that annotations
+    // later in the array are drawn over earlier ones, as a viewer would display them.
+
+    let mut content = String::new();
+
+    let mut resources = String::new();
+
+    let mut is_link = Vec::with_capacity(appearances.len());
+
+    for (index, appearance) in appearances.iter().enumerate() {
+        let annotation_handle = bindings.FPDFPage_GetAnnot(page_handle, index as c_int);
+
+        if annotation_handle.is_null() {
+            return Err(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ));
+        }
+
+        let subtype = bindings.FPDFAnnot_GetSubtype(annotation_handle) as u32;
+
+        let is_drawn = subtype != FPDF_ANNOT_LINK
+            && subtype != FPDF_ANNOT_POPUP
+            && is_visible(bindings.FPDFAnnot_GetFlags(annotation_handle) as u32, flag);
+
+        let mut rect = FS_RECTF {
+            left: 0.0,
+            bottom: 0.0,
+            right: 0.0,
+            top: 0.0,
+        };
+
+        let is_rect_valid =
+            bindings.is_true(bindings.FPDFAnnot_GetRect(annotation_handle, &mut rect));
+
+        bindings.FPDFPage_CloseAnnot(annotation_handle);
+
+        is_link.push(subtype == FPDF_ANNOT_LINK);
+
+        let appearance = match appearance {
+            Some(appearance) if is_drawn => appearance,
+            _ => continue,
+        };
+
+        if !is_rect_valid {
+            return Err(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ));
+        }
+
+        let matrix = appearance_matrix(rect, appearance.bbox, appearance.matrix);
+
+        content.push_str(&format!(
+            "q {} {} {} {} {} {} cm /Ap{} Do Q\n",
+            format_number(matrix.a),
+            format_number(matrix.b),
+            format_number(matrix.c),
+            format_number(matrix.d),
+            format_number(matrix.e),
+            format_number(matrix.f),
+            index
+        ));
+
+        resources.push_str(&format!(
+            "/Ap{} {} ",
+            index,
+            String::from_utf8_lossy(&appearance.id.to_reference())
+        ));
+    }
+
+    if !content.is_empty() {
+        let media_box = page_media_box(page_handle, bindings);
+
+        append_page(&mut update, media_box, &resources, &content)?;
+
+        draw_appended_page(document_handle, page_handle, update, bindings)?;
+    }
+
+    for (index, is_link) in is_link.iter().enumerate().rev() {
+        if !is_link {
+            bindings.FPDFPage_RemoveAnnot(page_handle, index as c_int);
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns `true` if an annotation with the given annotation flags should be drawn
+/// when flattening a page using the given `FLAT_NORMALDISPLAY` or `FLAT_PRINT` flag.
+fn is_visible(annotation_flags: u32, flag: u32) -> bool {
+    if annotation_flags & FPDF_ANNOT_FLAG_HIDDEN != 0 {
+        false
+    } else if flag == FLAT_PRINT {
+        annotation_flags & FPDF_ANNOT_FLAG_PRINT != 0
+    } else {
+        annotation_flags & FPDF_ANNOT_FLAG_NOVIEW == 0
+    }
+}
+
+/// Renders the page with the given `FPDF_PAGE` handle into a scratch bitmap, triggering Pdfium
+/// to generate appearance streams for any annotations and form fields that do not yet have them.
+fn generate_appearance_streams(
+    page_handle: FPDF_PAGE,
+    form_handle: Option<FPDF_FORMHANDLE>,
+    bindings: &dyn PdfiumLibraryBindings,
+) {
+    let width = (bindings.FPDF_GetPageWidthF(page_handle) as c_int).clamp(1, 2000);
+    let height = (bindings.FPDF_GetPageHeightF(page_handle) as c_int).clamp(1, 2000);
+
+    let bitmap = bindings.FPDFBitmap_Create(width, height, 1);
+
+    if bitmap.is_null() {
+        return;
+    }
+
+    bindings.FPDF_RenderPageBitmap(
+        bitmap,
+        page_handle,
+        0,
+        0,
+        width,
+        height,
+        0,
+        FPDF_ANNOT as c_int,
+    );
+
+    if let Some(form_handle) = form_handle {
+        bindings.FORM_OnAfterLoadPage(page_handle, form_handle);
+
+        bindings.FPDF_FFLDraw(
+            form_handle,
+            bitmap,
+            page_handle,
+            0,
+            0,
+            width,
+            height,
+            0,
+            FPDF_ANNOT as c_int,
+        );
+    }
+
+    bindings.FPDFBitmap_Destroy(bitmap);
+}
+
+/// Returns the media box of the page with the given `FPDF_PAGE` handle, falling back to
+/// the page's width and height if the page does not define a media box.
+fn page_media_box(page_handle: FPDF_PAGE, bindings: &dyn PdfiumLibraryBindings) -> FS_RECTF {
+    let mut media_box = FS_RECTF {
+        left: 0.0,
+        bottom: 0.0,
+        right: 0.0,
+        top: 0.0,
+    };
+
+    if !bindings.is_true(bindings.FPDFPage_GetMediaBox(
+        page_handle,
+        &mut media_box.left,
+        &mut media_box.bottom,
+        &mut media_box.right,
+        &mut media_box.top,
+    )) {
+        media_box = FS_RECTF {
+            left: 0.0,
+            bottom: 0.0,
+            right: bindings.FPDF_GetPageWidthF(page_handle),
+            top: bindings.FPDF_GetPageHeightF(page_handle),
+        };
+    }
+
+    media_box
+}
+
+/// Returns the normal appearance stream of every annotation on the page with the given
+/// `FPDF_PAGE` handle from the given saved copy of the document with the given handle, in the
+/// same order as the page's annotations. The appearance stream of an annotation is `None` if the
+/// annotation has no appearance in its current appearance state.
+///
+/// Returns an error if the page cannot be found in the saved copy, since the appearances of
+/// its annotations could not then be positioned correctly.
+fn read_appearance_streams(
+    update: &PdfIncrementalUpdate,
+    document_handle: FPDF_DOCUMENT,
+    page_handle: FPDF_PAGE,
+) -> Result<Vec<Option<PdfAppearanceStream>>, PdfiumError> {
+    let page = PdfPageIndexCache::get_index_for_page(document_handle, page_handle)
+        .and_then(|index| find_page(update, index as usize))
+        .ok_or(PdfiumError::PageFlattenFailure)?;
+
+    // Pdfium numbers the annotations of a page in the order of the page's /Annots array.
+
+    let annotations = match page.get("Annots") {
+        Some(annotations) => resolve(update, annotations),
+        None => return Ok(Vec::new()),
+    };
+
+    Ok(parse_array(&annotations)?
+        .into_iter()
+        .map(|element| appearance_stream(update, element))
+        .collect())
+}
+
+/// Returns the dictionary of the page with the given zero-based index in the given saved
+/// document, walking down the document's page tree.
+fn find_page(update: &PdfIncrementalUpdate, index: usize) -> Option<PdfRawDictionary> {
+    // Limit the depth of the search, in case the page tree contains a cycle.
+
+    const MAX_DEPTH: usize = 32;
+
+    let root = update.get_dictionary(update.root().ok()?).ok()?;
+
+    let mut node = update.get_dictionary(root.get_reference("Pages")?).ok()?;
+
+    let mut remaining = index;
+
+    for _ in 0..MAX_DEPTH {
+        let kids = PdfObjectId::from_reference_array(&resolve(update, node.get("Kids")?))?;
+
+        let mut next = None;
+
+        for id in kids {
+            let kid = update.get_dictionary(id).ok()?;
+
+            if kid.contains_key("Kids") {
+                let count = number(&resolve(update, kid.get("Count")?))? as usize;
+
+                if remaining < count {
+                    next = Some(kid);
+
+                    break;
+                }
+
+                remaining -= count;
+            } else if remaining == 0 {
+                return Some(kid);
+            } else {
+                remaining -= 1;
+            }
+        }
+
+        node = next?;
+    }
+
+    None
+}
+
+/// Returns the normal appearance stream of the annotation held by the given element of
+/// a page's `/Annots` array, if any.
+fn appearance_stream(update: &PdfIncrementalUpdate, element: &[u8]) -> Option<PdfAppearanceStream> {
+    let annotation = PdfRawDictionary::parse(&resolve(update, element)).ok()?;
+
+    let appearance = PdfRawDictionary::parse(&resolve(update, annotation.get("AP")?)).ok()?;
+
+    let mut id = PdfObjectId::from_reference(trim(appearance.get("N")?))?;
+
+    let mut stream = update.get_dictionary(id).ok()?;
+
+    if !stream.contains_key("BBox") {
+        // The normal appearance is a subdictionary of appearance streams, one for each of
+        // the annotation's appearance states; the annotation's /AS entry selects the stream.
+
+        let state = decode_name(annotation.get("AS")?)?;
+
+        id = PdfObjectId::from_reference(trim(stream.get(&state)?))?;
+
+        stream = update.get_dictionary(id).ok()?;
+    }
+
+    let bbox = numbers(&resolve(update, stream.get("BBox")?))?;
+
+    if bbox.len() != 4 {
+        return None;
+    }
+
+    let matrix = match stream.get("Matrix") {
+        Some(matrix) => numbers(&resolve(update, matrix)).filter(|matrix| matrix.len() == 6)?,
+        None => vec![1.0, 0.0, 0.0, 1.0, 0.0, 0.0],
+    };
+
+    Some(PdfAppearanceStream {
+        id,
+        bbox: FS_RECTF {
+            left: bbox[0].min(bbox[2]),
+            bottom: bbox[1].min(bbox[3]),
+            right: bbox[0].max(bbox[2]),
+            top: bbox[1].max(bbox[3]),
+        },
+        matrix: FS_MATRIX {
+            a: matrix[0],
+            b: matrix[1],
+            c: matrix[2],
+            d: matrix[3],
+            e: matrix[4],
+            f: matrix[5],
+        },
+    })
+}
+
+/// Parses the given PDF number.
+#[inline]
+fn number(bytes: &[u8]) -> Option<f32> {
+    std::str::from_utf8(trim(bytes)).ok()?.parse().ok()
+}
+
+/// Parses the given PDF array of numbers.
+fn numbers(bytes: &[u8]) -> Option<Vec<f32>> {
+    parse_array(bytes).ok()?.into_iter().map(number).collect()
+}
+
+/// Returns the matrix that maps an appearance stream with the given bounding box and matrix
+/// onto the given annotation rectangle, following the algorithm given in section 12.5.5 of the
+/// PDF specification: the stream's /BBox is transformed by its /Matrix, and the bounding box of
+/// the result is then scaled and translated to fit the /Rect.
+///
+/// The returned matrix does not include the stream's /Matrix, which is applied when the
+/// stream is drawn as a form XObject.
+fn appearance_matrix(rect: FS_RECTF, bbox: FS_RECTF, matrix: FS_MATRIX) -> FS_MATRIX {
+    let corners = [
+        (bbox.left, bbox.bottom),
+        (bbox.left, bbox.top),
+        (bbox.right, bbox.bottom),
+        (bbox.right, bbox.top),
+    ]
+    .map(|(x, y)| {
+        (
+            matrix.a * x + matrix.c * y + matrix.e,
+            matrix.b * x + matrix.d * y + matrix.f,
+        )
+    });
+
+    let (mut left, mut bottom) = corners[0];
+    let (mut right, mut top) = corners[0];
+
+    for (x, y) in corners.iter() {
+        left = left.min(*x);
+        bottom = bottom.min(*y);
+        right = right.max(*x);
+        top = top.max(*y);
+    }
+
+    let rect_left = rect.left.min(rect.right);
+    let rect_bottom = rect.bottom.min(rect.top);
+
+    // An appearance stream whose transformed bounding box is empty in either direction
+    // is translated but not scaled in that direction.
+
+    let scale_x = if right > left {
+        (rect.right - rect.left).abs() / (right - left)
+    } else {
+        1.0
+    };
+
+    let scale_y = if top > bottom {
+        (rect.top - rect.bottom).abs() / (top - bottom)
+    } else {
+        1.0
+    };
+
+    FS_MATRIX {
+        a: scale_x,
+        b: 0.0,
+        c: 0.0,
+        d: scale_y,
+        e: rect_left - left * scale_x,
+        f: rect_bottom - bottom * scale_y,
+    }
+}
+
+/// Appends a new page with the given media box, XObject resources, and content to the end
+/// of the page tree of the given saved document.
+fn append_page(
+    update: &mut PdfIncrementalUpdate,
+    media_box: FS_RECTF,
+    x_objects: &str,
+    content: &str,
+) -> Result<(), PdfiumError> {
+    let root = update.get_dictionary(update.root()?)?;
+
+    let pages_id = root
+        .get_reference("Pages")
+        .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+    let mut pages = update.get_dictionary(pages_id)?;
+
+    let kids = resolve(
+        update,
+        pages
+            .get("Kids")
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?,
+    );
+
+    let count = pages
+        .get("Count")
+        .and_then(|count| number(&resolve(update, count)))
+        .ok_or(PdfiumError::UnsupportedPdfFileStructure)? as usize;
+
+    let contents = update.add_object(stream_object("", content.as_bytes()));
+
+    let page = update.add_object(
+        format!(
+            "<</Type /Page /Parent {} /MediaBox [{} {} {} {}] /Resources <</XObject <<{}>>>> /Contents {}>>",
+            String::from_utf8_lossy(&pages_id.to_reference()),
+            format_number(media_box.left),
+            format_number(media_box.bottom),
+            format_number(media_box.right),
+            format_number(media_box.top),
+            x_objects,
+            String::from_utf8_lossy(&contents.to_reference()),
+        )
+        .into_bytes(),
+    );
+
+    let mut new_kids = b"[".to_vec();
+
+    for kid in parse_array(&kids)? {
+        new_kids.extend_from_slice(trim(kid));
+        new_kids.push(b' ');
+    }
+
+    new_kids.extend_from_slice(&page.to_reference());
+    new_kids.push(b']');
+
+    pages.set("Kids", new_kids);
+    pages.set("Count", (count + 1).to_string().into_bytes());
+
+    update.set_object(pages_id, pages.to_bytes());
+
+    Ok(())
+}
+
+/// Loads the given saved document and draws its last page onto the page with the given
+/// `FPDF_PAGE` handle as a single form XObject.
+fn draw_appended_page(
+    document_handle: FPDF_DOCUMENT,
+    page_handle: FPDF_PAGE,
+    update: PdfIncrementalUpdate,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Result<(), PdfiumError> {
+    let (bytes, _) = update.write()?;
+
+    // The saved bytes must outlive the document loaded from them.
+
+    let source = bindings.FPDF_LoadMemDocument64(&bytes, None);
+
+    if source.is_null() {
+        return Err(PdfiumError::PdfiumLibraryInternalError(
+            PdfiumInternalError::Unknown,
+        ));
+    }
+
+    let x_object = bindings.FPDF_NewXObjectFromPage(
+        document_handle,
+        source,
+        bindings.FPDF_GetPageCount(source) - 1,
+    );
+
+    let result = if x_object.is_null() {
+        Err(PdfiumError::PdfiumLibraryInternalError(
+            PdfiumInternalError::Unknown,
+        ))
+    } else {
+        let object_handle = bindings.FPDF_NewFormObjectFromXObject(x_object);
+
+        bindings.FPDF_CloseXObject(x_object);
+
+        if object_handle.is_null() {
+            Err(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ))
+        } else {
+            bindings.FPDFPage_InsertObject(page_handle, object_handle);
+
+            Ok(())
+        }
+    };
+
+    bindings.FPDF_CloseDocument(source);
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(left: f32, bottom: f32, right: f32, top: f32) -> FS_RECTF {
+        FS_RECTF {
+            left,
+            bottom,
+            right,
+            top,
+        }
+    }
+
+    fn matrix(a: f32, b: f32, c: f32, d: f32, e: f32, f: f32) -> FS_MATRIX {
+        FS_MATRIX { a, b, c, d, e, f }
+    }
+
+    fn assert_matrix_eq(actual: FS_MATRIX, expected: FS_MATRIX) {
+        let actual = [actual.a, actual.b, actual.c, actual.d, actual.e, actual.f];
+        let expected = [
+            expected.a, expected.b, expected.c, expected.d, expected.e, expected.f,
+        ];
+
+        for (actual, expected) in actual.iter().zip(expected.iter()) {
+            assert!((actual - expected).abs() < 1e-4, "{:?}", actual);
+        }
+    }
+
+    #[test]
+    fn test_appearance_matrix() {
+        // A bounding box anchored at the origin is moved onto the annotation rectangle.
+
+        assert_matrix_eq(
+            appearance_matrix(
+                rect(100.0, 200.0, 150.0, 220.0),
+                rect(0.0, 0.0, 50.0, 20.0),
+                matrix(1.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+            ),
+            matrix(1.0, 0.0, 0.0, 1.0, 100.0, 200.0),
+        );
+
+        // A bounding box matching the annotation rectangle is left in place.
+
+        assert_matrix_eq(
+            appearance_matrix(
+                rect(100.0, 200.0, 150.0, 220.0),
+                rect(100.0, 200.0, 150.0, 220.0),
+                matrix(1.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+            ),
+            matrix(1.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+        );
+
+        // A bounding box of a different size is scaled to fit the annotation rectangle.
+
+        assert_matrix_eq(
+            appearance_matrix(
+                rect(100.0, 200.0, 200.0, 250.0),
+                rect(0.0, 0.0, 50.0, 100.0),
+                matrix(1.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+            ),
+            matrix(2.0, 0.0, 0.0, 0.5, 100.0, 200.0),
+        );
+
+        // A widget rotated by 90 degrees has a /Matrix that rotates its bounding box; the
+        // rotated bounding box is then moved onto the annotation rectangle. The /Matrix itself
+        // is applied when the appearance stream is drawn.
+
+        assert_matrix_eq(
+            appearance_matrix(
+                rect(100.0, 200.0, 120.0, 250.0),
+                rect(0.0, 0.0, 50.0, 20.0),
+                matrix(0.0, 1.0, -1.0, 0.0, 0.0, 0.0),
+            ),
+            matrix(1.0, 0.0, 0.0, 1.0, 120.0, 200.0),
+        );
+    }
 }
@@ -0,0 +1,469 @@
+//! Defines the [PdfFormSession] struct, exposing functionality related to simulating
+//! user interaction with the interactive form fields on a single `PdfPage`.
+
+use crate::bindgen::{
+    FWL_EVENTFLAG_FWL_EVENTFLAG_AltKey, FWL_EVENTFLAG_FWL_EVENTFLAG_ControlKey,
+    FWL_EVENTFLAG_FWL_EVENTFLAG_MetaKey, FWL_EVENTFLAG_FWL_EVENTFLAG_ShiftKey,
+    FWL_VKEYCODE_FWL_VKEY_Back, FWL_VKEYCODE_FWL_VKEY_Delete, FWL_VKEYCODE_FWL_VKEY_Down,
+    FWL_VKEYCODE_FWL_VKEY_End, FWL_VKEYCODE_FWL_VKEY_Escape, FWL_VKEYCODE_FWL_VKEY_Home,
+    FWL_VKEYCODE_FWL_VKEY_Insert, FWL_VKEYCODE_FWL_VKEY_Left, FWL_VKEYCODE_FWL_VKEY_Next,
+    FWL_VKEYCODE_FWL_VKEY_Prior, FWL_VKEYCODE_FWL_VKEY_Return, FWL_VKEYCODE_FWL_VKEY_Right,
+    FWL_VKEYCODE_FWL_VKEY_Space, FWL_VKEYCODE_FWL_VKEY_Tab, FWL_VKEYCODE_FWL_VKEY_Up, FPDF_BOOL,
+    FPDF_FORMHANDLE, FPDF_WIDESTRING,
+};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::page::field::private::internal::PdfFormFieldPrivate;
+use crate::pdf::document::page::field::PdfFormField;
+use crate::pdf::document::page::PdfPage;
+use crate::pdf::points::PdfPoints;
+use crate::utils::mem::create_byte_buffer;
+use crate::utils::utf16le::{
+    get_pdfium_utf16le_bytes_from_str, get_string_from_pdfium_utf16le_bytes,
+};
+use bitflags::bitflags;
+use std::os::raw::{c_int, c_ulong, c_void};
+use std::ptr::null_mut;
+
+bitflags! {
+    /// The modifier keys held down during a simulated key press in a [PdfFormSession].
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct PdfFormKeyModifiers: u32 {
+        const SHIFT = FWL_EVENTFLAG_FWL_EVENTFLAG_ShiftKey;
+        const CONTROL = FWL_EVENTFLAG_FWL_EVENTFLAG_ControlKey;
+        const ALT = FWL_EVENTFLAG_FWL_EVENTFLAG_AltKey;
+        const META = FWL_EVENTFLAG_FWL_EVENTFLAG_MetaKey;
+    }
+}
+
+/// A non-character key that can be pressed in a [PdfFormSession]. Keys that produce
+/// characters should be typed using [PdfFormSession::type_text()] instead.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PdfFormKey {
+    Backspace,
+    Tab,
+    Return,
+    Escape,
+    Space,
+    PageUp,
+    PageDown,
+    End,
+    Home,
+    Left,
+    Up,
+    Right,
+    Down,
+    Insert,
+    Delete,
+}
+
+impl PdfFormKey {
+    #[inline]
+    pub(crate) fn as_pdfium(&self) -> c_int {
+        (match self {
+            PdfFormKey::Backspace => FWL_VKEYCODE_FWL_VKEY_Back,
+            PdfFormKey::Tab => FWL_VKEYCODE_FWL_VKEY_Tab,
+            PdfFormKey::Return => FWL_VKEYCODE_FWL_VKEY_Return,
+            PdfFormKey::Escape => FWL_VKEYCODE_FWL_VKEY_Escape,
+            PdfFormKey::Space => FWL_VKEYCODE_FWL_VKEY_Space,
+            PdfFormKey::PageUp => FWL_VKEYCODE_FWL_VKEY_Prior,
+            PdfFormKey::PageDown => FWL_VKEYCODE_FWL_VKEY_Next,
+            PdfFormKey::End => FWL_VKEYCODE_FWL_VKEY_End,
+            PdfFormKey::Home => FWL_VKEYCODE_FWL_VKEY_Home,
+            PdfFormKey::Left => FWL_VKEYCODE_FWL_VKEY_Left,
+            PdfFormKey::Up => FWL_VKEYCODE_FWL_VKEY_Up,
+            PdfFormKey::Right => FWL_VKEYCODE_FWL_VKEY_Right,
+            PdfFormKey::Down => FWL_VKEYCODE_FWL_VKEY_Down,
+            PdfFormKey::Insert => FWL_VKEYCODE_FWL_VKEY_Insert,
+            PdfFormKey::Delete => FWL_VKEYCODE_FWL_VKEY_Delete,
+        }) as c_int
+    }
+}
+
+/// A session of simulated user interaction with the interactive form fields on a single [PdfPage].
+///
+/// Every interaction is routed through Pdfium's own form fill engine, exactly as if a user
+/// were clicking and typing in a PDF viewer. Field behaviour defined by the document -
+/// maximum lengths, comb fields, and any JavaScript keystroke, format, validate, and
+/// calculate actions - is applied just as it would be interactively, which makes a
+/// [PdfFormSession] useful for headless testing of form filling behaviour.
+///
+/// Changes to the focused field are committed when the field loses focus, either
+/// explicitly by calling [PdfFormSession::kill_focus()], implicitly by focusing another
+/// field, or automatically when the [PdfFormSession] is dropped.
+///
+/// Create a [PdfFormSession] by calling the [PdfPage::form_session()] function.
+pub struct PdfFormSession<'a> {
+    page: &'a PdfPage<'a>,
+    form_handle: FPDF_FORMHANDLE,
+}
+
+impl<'a> PdfFormSession<'a> {
+    pub(crate) fn new(page: &'a PdfPage<'a>, form_handle: FPDF_FORMHANDLE) -> Self {
+        // Pdfium requires FORM_OnAfterLoadPage() to be called before any form interaction
+        // with the page; it creates the form widgets for the page.
+
+        page.bindings()
+            .FORM_OnAfterLoadPage(page.page_handle(), form_handle);
+
+        PdfFormSession { page, form_handle }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfFormSession].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.page.bindings()
+    }
+
+    /// Returns the [PdfPage] this [PdfFormSession] interacts with.
+    #[inline]
+    pub fn page(&self) -> &'a PdfPage<'a> {
+        self.page
+    }
+
+    /// Simulates moving the mouse to the given position on the page.
+    pub fn mouse_move(&self, x: PdfPoints, y: PdfPoints) -> Result<(), PdfiumError> {
+        self.to_result(self.bindings().FORM_OnMouseMove(
+            self.form_handle,
+            self.page.page_handle(),
+            0,
+            x.value as f64,
+            y.value as f64,
+        ))
+    }
+
+    /// Simulates clicking the left mouse button at the given position on the page.
+    /// Clicking a form field gives it focus; clicking a checkbox or radio button also
+    /// toggles its state.
+    pub fn click(&self, x: PdfPoints, y: PdfPoints) -> Result<(), PdfiumError> {
+        let bindings = self.bindings();
+
+        let page_x = x.value as f64;
+        let page_y = y.value as f64;
+
+        // Pdfium tracks the widget under the mouse cursor, so we move the mouse into position
+        // before pressing the button.
+
+        bindings.FORM_OnMouseMove(self.form_handle, self.page.page_handle(), 0, page_x, page_y);
+
+        let is_down = bindings.is_true(bindings.FORM_OnLButtonDown(
+            self.form_handle,
+            self.page.page_handle(),
+            0,
+            page_x,
+            page_y,
+        ));
+
+        let is_up = bindings.is_true(bindings.FORM_OnLButtonUp(
+            self.form_handle,
+            self.page.page_handle(),
+            0,
+            page_x,
+            page_y,
+        ));
+
+        if is_down || is_up {
+            Ok(())
+        } else {
+            Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure)
+        }
+    }
+
+    /// Simulates double-clicking the left mouse button at the given position on the page.
+    /// Double-clicking in a text field typically selects the word under the mouse cursor.
+    pub fn double_click(&self, x: PdfPoints, y: PdfPoints) -> Result<(), PdfiumError> {
+        self.click(x, y)?;
+
+        self.to_result(self.bindings().FORM_OnLButtonDoubleClick(
+            self.form_handle,
+            self.page.page_handle(),
+            0,
+            x.value as f64,
+            y.value as f64,
+        ))
+    }
+
+    /// Gives focus to the form field at the given position on the page, without clicking it.
+    /// Returns `true` if there is a form field at the given position and it now has focus.
+    /// If there is no form field at the given position, focus is removed from any
+    /// currently focused field.
+    pub fn focus_at(&self, x: PdfPoints, y: PdfPoints) -> bool {
+        self.bindings().is_true(self.bindings().FORM_OnFocus(
+            self.form_handle,
+            self.page.page_handle(),
+            0,
+            x.value as f64,
+            y.value as f64,
+        ))
+    }
+
+    /// Gives focus to the given [PdfFormField], which must be on the page this
+    /// [PdfFormSession] interacts with.
+    pub fn focus(&self, field: &PdfFormField) -> Result<(), PdfiumError> {
+        self.to_result(
+            self.bindings()
+                .FORM_SetFocusedAnnot(self.form_handle, field.annotation_handle()),
+        )
+    }
+
+    /// Removes focus from the currently focused form field, if any, committing any changes
+    /// made to the field's value. Pdfium runs the field's format, validate, and calculate
+    /// actions at this point.
+    pub fn kill_focus(&self) -> Result<(), PdfiumError> {
+        self.to_result(self.bindings().FORM_ForceToKillFocus(self.form_handle))
+    }
+
+    /// Simulates typing the given text into the currently focused form field, one character
+    /// at a time. Each character is subject to the field's keystroke actions and limits,
+    /// so characters may be changed or rejected; for instance, typing stops taking effect
+    /// once a field's maximum length is reached.
+    pub fn type_text(&self, text: &str) -> Result<(), PdfiumError> {
+        // Pdfium expects one UTF-16 code unit per character event.
+
+        for code_unit in text.encode_utf16() {
+            self.type_char(code_unit as c_int)?;
+        }
+
+        Ok(())
+    }
+
+    /// Simulates typing a single UTF-16 code unit into the currently focused form field.
+    fn type_char(&self, code_unit: c_int) -> Result<(), PdfiumError> {
+        self.to_result(self.bindings().FORM_OnChar(
+            self.form_handle,
+            self.page.page_handle(),
+            code_unit,
+            0,
+        ))
+    }
+
+    /// Simulates pressing and releasing the given key while holding down the given modifier keys.
+    pub fn press_key(
+        &self,
+        key: PdfFormKey,
+        modifiers: PdfFormKeyModifiers,
+    ) -> Result<(), PdfiumError> {
+        let bindings = self.bindings();
+
+        let result = bindings.FORM_OnKeyDown(
+            self.form_handle,
+            self.page.page_handle(),
+            key.as_pdfium(),
+            modifiers.bits() as c_int,
+        );
+
+        // Pdfium does not currently implement FORM_OnKeyUp(), so its return value is ignored.
+
+        bindings.FORM_OnKeyUp(
+            self.form_handle,
+            self.page.page_handle(),
+            key.as_pdfium(),
+            modifiers.bits() as c_int,
+        );
+
+        self.to_result(result)
+    }
+
+    /// Returns the text in the currently focused form field, if any.
+    pub fn focused_text(&self) -> Option<String> {
+        self.read_string(|buffer, buffer_length| {
+            self.bindings().FORM_GetFocusedText(
+                self.form_handle,
+                self.page.page_handle(),
+                buffer,
+                buffer_length,
+            )
+        })
+    }
+
+    /// Returns the currently selected text in the focused text field or editable
+    /// combo box, if any.
+    pub fn selected_text(&self) -> Option<String> {
+        self.read_string(|buffer, buffer_length| {
+            self.bindings().FORM_GetSelectedText(
+                self.form_handle,
+                self.page.page_handle(),
+                buffer,
+                buffer_length,
+            )
+        })
+    }
+
+    /// Selects all the text in the focused text field or editable combo box.
+    pub fn select_all(&self) -> Result<(), PdfiumError> {
+        self.to_result(
+            self.bindings()
+                .FORM_SelectAllText(self.form_handle, self.page.page_handle()),
+        )
+    }
+
+    /// Replaces the currently selected text in the focused text field or editable combo box
+    /// with the given text, leaving the caret after the inserted text. If no text is
+    /// selected, the given text is inserted at the caret position.
+    pub fn replace_selection(&self, text: &str) {
+        self.bindings().FORM_ReplaceSelection(
+            self.form_handle,
+            self.page.page_handle(),
+            get_pdfium_utf16le_bytes_from_str(text).as_ptr() as FPDF_WIDESTRING,
+        );
+    }
+
+    /// Replaces the currently selected text in the focused text field or editable combo box
+    /// with the given text, leaving the inserted text selected. If no text is selected,
+    /// the given text is inserted at the caret position.
+    pub fn replace_and_keep_selection(&self, text: &str) {
+        self.bindings().FORM_ReplaceAndKeepSelection(
+            self.form_handle,
+            self.page.page_handle(),
+            get_pdfium_utf16le_bytes_from_str(text).as_ptr() as FPDF_WIDESTRING,
+        );
+    }
+
+    /// Returns `true` if the focused form field can undo its most recent change.
+    #[inline]
+    pub fn can_undo(&self) -> bool {
+        self.bindings().is_true(
+            self.bindings()
+                .FORM_CanUndo(self.form_handle, self.page.page_handle()),
+        )
+    }
+
+    /// Returns `true` if the focused form field can redo its most recently undone change.
+    #[inline]
+    pub fn can_redo(&self) -> bool {
+        self.bindings().is_true(
+            self.bindings()
+                .FORM_CanRedo(self.form_handle, self.page.page_handle()),
+        )
+    }
+
+    /// Undoes the most recent change to the focused form field.
+    pub fn undo(&self) -> Result<(), PdfiumError> {
+        self.to_result(
+            self.bindings()
+                .FORM_Undo(self.form_handle, self.page.page_handle()),
+        )
+    }
+
+    /// Redoes the most recently undone change to the focused form field.
+    pub fn redo(&self) -> Result<(), PdfiumError> {
+        self.to_result(
+            self.bindings()
+                .FORM_Redo(self.form_handle, self.page.page_handle()),
+        )
+    }
+
+    /// Reads a UTF16-LE string from Pdfium using the given string accessor function.
+    fn read_string(&self, accessor: impl Fn(*mut c_void, c_ulong) -> c_ulong) -> Option<String> {
+        // Retrieving the text from Pdfium is a two-step operation. First, we call
+        // the accessor with a null buffer; this will retrieve the length of the text
+        // in bytes. If the length is zero, then there is no text.
+
+        // If the length is non-zero, then we reserve a byte buffer of the given
+        // length and call the accessor again with a pointer to the buffer;
+        // this will write the text to the buffer in UTF16-LE format.
+
+        let buffer_length = accessor(null_mut(), 0);
+
+        if buffer_length == 0 {
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        let result = accessor(buffer.as_mut_ptr() as *mut c_void, buffer_length);
+
+        debug_assert_eq!(result, buffer_length);
+
+        get_string_from_pdfium_utf16le_bytes(buffer)
+    }
+
+    #[inline]
+    fn to_result(&self, result: FPDF_BOOL) -> Result<(), PdfiumError> {
+        if self.bindings().is_true(result) {
+            Ok(())
+        } else {
+            Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure)
+        }
+    }
+}
+
+impl<'a> Drop for PdfFormSession<'a> {
+    /// Ends this [PdfFormSession], committing any changes to the focused form field.
+    fn drop(&mut self) {
+        let bindings = self.bindings();
+
+        bindings.FORM_ForceToKillFocus(self.form_handle);
+        bindings.FORM_OnBeforeClosePage(self.page.page_handle(), self.form_handle);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_form_session_type_and_undo() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/form-test.pdf", None)?;
+
+        let page = document.pages().first()?;
+
+        // The first page of form-test.pdf starts with the applicant's family name, a plain
+        // single-line text field.
+
+        let bounds = page
+            .annotations()
+            .iter()
+            .find(|annotation| {
+                annotation
+                    .as_form_field()
+                    .and_then(|field| field.as_text_field())
+                    .and_then(|field| field.name())
+                    .as_deref()
+                    == Some("form1[0].#subform[0].Pt1Line1a_FamilyName[0]")
+            })
+            .expect("form-test.pdf should have a family name text field on its first page")
+            .bounds()?;
+
+        let session = page.form_session()?;
+
+        let x = (bounds.left() + bounds.right()) / 2.0;
+        let y = (bounds.top() + bounds.bottom()) / 2.0;
+
+        session.click(x, y)?;
+        session.select_all()?;
+        session.replace_selection("");
+        session.type_text("Hello")?;
+
+        assert_eq!(session.focused_text().as_deref(), Some("Hello"));
+
+        session.select_all()?;
+
+        assert_eq!(session.selected_text().as_deref(), Some("Hello"));
+
+        session.replace_selection("World");
+
+        assert_eq!(session.focused_text().as_deref(), Some("World"));
+        assert!(session.can_undo());
+
+        session.undo()?;
+
+        assert_eq!(session.focused_text().as_deref(), Some("Hello"));
+        assert!(session.can_redo());
+
+        session.redo()?;
+
+        assert_eq!(session.focused_text().as_deref(), Some("World"));
+
+        session.press_key(PdfFormKey::Backspace, PdfFormKeyModifiers::empty())?;
+
+        assert_eq!(session.focused_text().as_deref(), Some("Worl"));
+
+        session.kill_focus()?;
+
+        Ok(())
+    }
+}
@@ -209,6 +209,62 @@ impl PdfPageIndexCache {
         }
     }
 
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+        feature = "pdfium_6406",
+        feature = "pdfium_6337",
+        feature = "pdfium_6295",
+        feature = "pdfium_6259",
+        feature = "pdfium_6164",
+        feature = "pdfium_6124",
+        feature = "pdfium_6110",
+        feature = "pdfium_6084",
+        feature = "pdfium_6043",
+    ))]
+    /// Adjusts all cached [PdfPageIndex] values for the given document as necessary to accommodate
+    /// a reordering of the document's pages. The page currently at each index position `i`
+    /// moves to index position `new_indices[i]`.
+    fn reorder(&mut self, document: FPDF_DOCUMENT, new_indices: &[PdfPageIndex]) {
+        // Remove every affected page from the cache before re-adding any of them, so that
+        // no page's new index position collides with another page's old one.
+
+        let mut reordered = Vec::new();
+
+        for (index, new_index) in new_indices.iter().enumerate() {
+            if let Some(page) = self
+                .indices_by_page
+                .get(&(document, index as PdfPageIndex))
+                .copied()
+            {
+                if let Some(props) = self.remove(document, page) {
+                    reordered.push((page, *new_index, props.content_regeneration_strategy));
+                }
+            }
+        }
+
+        for (page, index, content_regeneration_strategy) in reordered {
+            self.set(
+                document,
+                page,
+                PdfPageCachedProperties {
+                    index,
+                    content_regeneration_strategy,
+                },
+            );
+        }
+    }
+
     #[inline]
     fn lock() -> MutexGuard<'static, PdfPageIndexCache> {
         PAGE_INDEX_CACHE.lock().unwrap()
@@ -283,6 +339,37 @@ impl PdfPageIndexCache {
     ) {
         Self::lock().delete(document, index, count);
     }
+
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+        feature = "pdfium_6406",
+        feature = "pdfium_6337",
+        feature = "pdfium_6295",
+        feature = "pdfium_6259",
+        feature = "pdfium_6164",
+        feature = "pdfium_6124",
+        feature = "pdfium_6110",
+        feature = "pdfium_6084",
+        feature = "pdfium_6043",
+    ))]
+    /// Adjusts all cached [PdfPageIndex] values for the given document as necessary to accommodate
+    /// a reordering of the document's pages. The page currently at each index position `i`
+    /// moves to index position `new_indices[i]`.
+    #[inline]
+    pub(crate) fn reorder_pages(document: FPDF_DOCUMENT, new_indices: &[PdfPageIndex]) {
+        Self::lock().reorder(document, new_indices);
+    }
 }
 
 unsafe impl Send for PdfPageIndexCache {}
@@ -2,6 +2,7 @@
 
 pub(crate) mod group;
 pub(crate) mod image;
+pub(crate) mod marks;
 pub(crate) mod ownership;
 pub(crate) mod path;
 pub(crate) mod private; // Keep private so that the PdfPageObjectPrivate trait is not exposed.
@@ -23,6 +24,7 @@ use crate::pdf::document::page::annotation::objects::PdfPageAnnotationObjects;
 use crate::pdf::document::page::annotation::private::internal::PdfPageAnnotationPrivate;
 use crate::pdf::document::page::annotation::{PdfPageAnnotation, PdfPageAnnotationCommon};
 use crate::pdf::document::page::object::image::PdfPageImageObject;
+use crate::pdf::document::page::object::marks::PdfPageObjectMarks;
 use crate::pdf::document::page::object::path::PdfPagePathObject;
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
 use crate::pdf::document::page::object::shading::PdfPageShadingObject;
@@ -693,6 +695,18 @@ pub trait PdfPageObjectCommon<'a> {
     /// Returns the internal `FPDF_PAGEOBJECT` handle for this [PdfPageObject].
     fn page_object_handle(&self) -> crate::bindgen::FPDF_PAGEOBJECT;
 
+    /// Returns the collection of content marks attached to this [PdfPageObject].
+    ///
+    /// Content marks place this [PdfPageObject] into one or more marked content sequences.
+    /// In a tagged PDF, marked content sequences carrying an `MCID` parameter link the
+    /// page object to an element in the document's logical structure tree; content
+    /// that is not part of the logical structure, such as running headers, is marked
+    /// as an `Artifact`.
+    fn marks(&self) -> &PdfPageObjectMarks<'a>;
+
+    /// Returns a mutable collection of content marks attached to this [PdfPageObject].
+    fn marks_mut(&mut self) -> &mut PdfPageObjectMarks<'a>;
+
     /// Sets the blend mode that will be applied when painting this [PdfPageObject].
     ///
     /// Note that Pdfium does not currently expose a function to read the currently set blend mode.
@@ -915,6 +929,16 @@ where
         self.reset_matrix_impl(other.matrix()?)
     }
 
+    #[inline]
+    fn marks(&self) -> &PdfPageObjectMarks<'a> {
+        self.marks_impl()
+    }
+
+    #[inline]
+    fn marks_mut(&mut self) -> &mut PdfPageObjectMarks<'a> {
+        self.marks_mut_impl()
+    }
+
     #[inline]
     fn set_blend_mode(&mut self, blend_mode: PdfPageObjectBlendMode) -> Result<(), PdfiumError> {
         self.bindings()
@@ -1261,6 +1285,16 @@ impl<'a> PdfPageObjectPrivate<'a> for PdfPageObject<'a> {
         self.unwrap_as_trait_mut().set_ownership(ownership);
     }
 
+    #[inline]
+    fn marks_impl(&self) -> &PdfPageObjectMarks<'a> {
+        self.unwrap_as_trait().marks_impl()
+    }
+
+    #[inline]
+    fn marks_mut_impl(&mut self) -> &mut PdfPageObjectMarks<'a> {
+        self.unwrap_as_trait_mut().marks_mut_impl()
+    }
+
     #[inline]
     fn add_object_to_page(&mut self, page_objects: &mut PdfPageObjects) -> Result<(), PdfiumError> {
         self.unwrap_as_trait_mut().add_object_to_page(page_objects)
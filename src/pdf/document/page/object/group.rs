@@ -750,6 +750,8 @@ impl<'a> PdfPageGroupObject<'a> {
             self.bindings,
         )?;
 
+        destination.pages().page_labels().insert_pages(index, 1);
+
         Ok(())
     }
 
@@ -9,6 +9,7 @@ use crate::error::{PdfiumError, PdfiumInternalError};
 use crate::pdf::bitmap::PdfBitmap;
 use crate::pdf::bitmap::Pixels;
 use crate::pdf::color_space::PdfColorSpace;
+use crate::pdf::document::page::object::marks::PdfPageObjectMarks;
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
 use crate::pdf::document::page::object::{PdfPageObject, PdfPageObjectOwnership};
 use crate::pdf::document::PdfDocument;
@@ -73,6 +74,7 @@ pub struct PdfPageImageObject<'a> {
     object_handle: FPDF_PAGEOBJECT,
     ownership: PdfPageObjectOwnership,
     bindings: &'a dyn PdfiumLibraryBindings,
+    marks: PdfPageObjectMarks<'a>,
 }
 
 impl<'a> PdfPageImageObject<'a> {
@@ -86,6 +88,7 @@ impl<'a> PdfPageImageObject<'a> {
             object_handle,
             ownership,
             bindings,
+            marks: PdfPageObjectMarks::from_pdfium(object_handle, ownership, bindings),
         }
     }
 
@@ -203,6 +206,11 @@ impl<'a> PdfPageImageObject<'a> {
                 object_handle: handle,
                 ownership: PdfPageObjectOwnership::unowned(),
                 bindings,
+                marks: PdfPageObjectMarks::from_pdfium(
+                    handle,
+                    PdfPageObjectOwnership::unowned(),
+                    bindings,
+                ),
             })
         }
     }
@@ -1050,6 +1058,17 @@ impl<'a> PdfPageObjectPrivate<'a> for PdfPageImageObject<'a> {
     #[inline]
     fn set_ownership(&mut self, ownership: PdfPageObjectOwnership) {
         self.ownership = ownership;
+        self.marks.set_ownership(ownership);
+    }
+
+    #[inline]
+    fn marks_impl(&self) -> &PdfPageObjectMarks<'a> {
+        &self.marks
+    }
+
+    #[inline]
+    fn marks_mut_impl(&mut self) -> &mut PdfPageObjectMarks<'a> {
+        &mut self.marks
     }
 
     #[inline]
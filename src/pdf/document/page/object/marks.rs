@@ -0,0 +1,811 @@
+//! Defines the [PdfPageObjectMarks] struct, a collection of all the content marks
+//! attached to a single `PdfPageObject`, and the [PdfPageObjectMark] struct, exposing
+//! functionality related to a single content mark.
+
+use crate::bindgen::{
+    FPDF_DOCUMENT, FPDF_OBJECT_NUMBER, FPDF_OBJECT_STRING, FPDF_PAGEOBJECT, FPDF_PAGEOBJECTMARK,
+};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::{PdfiumError, PdfiumInternalError};
+use crate::pdf::document::page::{
+    PdfPage, PdfPageContentRegenerationStrategy, PdfPageIndexCache, PdfPageObjectOwnership,
+};
+use crate::utils::mem::create_byte_buffer;
+use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use std::os::raw::{c_int, c_ulong};
+
+#[cfg(doc)]
+use crate::pdf::document::page::object::PdfPageObject;
+
+/// The name of the content mark parameter that holds a marked content ID.
+const MARKED_CONTENT_ID_PARAM: &str = "MCID";
+
+/// The zero-based index of a single [PdfPageObjectMark] inside its containing
+/// [PdfPageObjectMarks] collection.
+pub type PdfPageObjectMarkIndex = usize;
+
+/// The value of a single parameter in the property list of a [PdfPageObjectMark].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfPageObjectMarkParamValue {
+    /// A numeric value. Pdfium returns all numeric parameters as integers; any fractional
+    /// component is truncated.
+    Number(i32),
+
+    /// A string value. The raw bytes of the string can be retrieved using the
+    /// [PdfPageObjectMark::get_blob_param()] function.
+    String(String),
+
+    /// The value is of a type that Pdfium does not support retrieving, such as a name,
+    /// an array, or a dictionary.
+    Unsupported,
+}
+
+/// A single content mark attached to a [PdfPageObject].
+///
+/// Content marks identify the marked content sequences that a page object belongs to.
+/// Each mark has a tag name - for instance, `Span`, `P`, or `Artifact` - and an optional
+/// property list of key/value parameters. Tagged PDF documents use the `MCID` parameter
+/// to link runs of marked content to elements in the document's logical structure tree.
+pub struct PdfPageObjectMark<'a> {
+    mark_handle: FPDF_PAGEOBJECTMARK,
+    object_handle: FPDF_PAGEOBJECT,
+    ownership: PdfPageObjectOwnership,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfPageObjectMark<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        mark_handle: FPDF_PAGEOBJECTMARK,
+        object_handle: FPDF_PAGEOBJECT,
+        ownership: PdfPageObjectOwnership,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfPageObjectMark {
+            mark_handle,
+            object_handle,
+            ownership,
+            bindings,
+        }
+    }
+
+    /// Returns the internal `FPDF_PAGEOBJECTMARK` handle for this [PdfPageObjectMark].
+    #[inline]
+    pub(crate) fn mark_handle(&self) -> FPDF_PAGEOBJECTMARK {
+        self.mark_handle
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfPageObjectMark].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the tag name of this [PdfPageObjectMark], for instance `Span` or `Artifact`.
+    pub fn name(&self) -> Option<String> {
+        // Retrieving the tag name from Pdfium is a two-step operation. First, we call
+        // FPDFPageObjMark_GetName() with a null buffer; this will retrieve the length of
+        // the name in bytes. If the length is zero, then there is no name.
+
+        // If the length is non-zero, then we reserve a byte buffer of the given
+        // length and call FPDFPageObjMark_GetName() again with a pointer to the buffer;
+        // this will write the name to the buffer in UTF16-LE format.
+
+        let mut buffer_length: c_ulong = 0;
+
+        if !self.bindings.is_true(self.bindings.FPDFPageObjMark_GetName(
+            self.mark_handle,
+            std::ptr::null_mut(),
+            0,
+            &mut buffer_length,
+        )) || buffer_length == 0
+        {
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        if !self.bindings.is_true(self.bindings.FPDFPageObjMark_GetName(
+            self.mark_handle,
+            buffer.as_mut_ptr() as *mut _,
+            buffer_length,
+            &mut buffer_length,
+        )) {
+            return None;
+        }
+
+        get_string_from_pdfium_utf16le_bytes(buffer)
+    }
+
+    /// Returns the number of parameters in the property list of this [PdfPageObjectMark].
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bindings
+            .FPDFPageObjMark_CountParams(self.mark_handle)
+            .max(0) as usize
+    }
+
+    /// Returns `true` if the property list of this [PdfPageObjectMark] contains no parameters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the key of the parameter at the given index in the property list
+    /// of this [PdfPageObjectMark].
+    pub fn param_key(&self, index: usize) -> Option<String> {
+        let mut buffer_length: c_ulong = 0;
+
+        if !self
+            .bindings
+            .is_true(self.bindings.FPDFPageObjMark_GetParamKey(
+                self.mark_handle,
+                index as c_ulong,
+                std::ptr::null_mut(),
+                0,
+                &mut buffer_length,
+            ))
+            || buffer_length == 0
+        {
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        if !self
+            .bindings
+            .is_true(self.bindings.FPDFPageObjMark_GetParamKey(
+                self.mark_handle,
+                index as c_ulong,
+                buffer.as_mut_ptr() as *mut _,
+                buffer_length,
+                &mut buffer_length,
+            ))
+        {
+            return None;
+        }
+
+        get_string_from_pdfium_utf16le_bytes(buffer)
+    }
+
+    /// Returns `true` if the property list of this [PdfPageObjectMark] contains a parameter
+    /// with the given key.
+    pub fn has_param(&self, key: &str) -> bool {
+        (0..self.len()).any(|index| self.param_key(index).as_deref() == Some(key))
+    }
+
+    /// Returns the value of the parameter with the given key in the property list
+    /// of this [PdfPageObjectMark], if any.
+    #[inline]
+    pub fn get_param(&self, key: &str) -> Option<PdfPageObjectMarkParamValue> {
+        if self.has_param(key) {
+            Some(self.param_value(key))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of the numeric parameter with the given key in the property list
+    /// of this [PdfPageObjectMark], if any, as an integer.
+    pub fn get_int_param(&self, key: &str) -> Option<i32> {
+        let mut value: c_int = 0;
+
+        if self
+            .bindings
+            .is_true(self.bindings.FPDFPageObjMark_GetParamIntValue(
+                self.mark_handle,
+                key,
+                &mut value,
+            ))
+        {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(any(feature = "pdfium_future", feature = "pdfium_7543"))]
+    /// Returns the value of the numeric parameter with the given key in the property list
+    /// of this [PdfPageObjectMark], if any, as a floating-point value.
+    pub fn get_float_param(&self, key: &str) -> Option<f32> {
+        let mut value: f32 = 0.0;
+
+        if self
+            .bindings
+            .is_true(self.bindings.FPDFPageObjMark_GetParamFloatValue(
+                self.mark_handle,
+                key,
+                &mut value,
+            ))
+        {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the value of the string parameter with the given key in the property list
+    /// of this [PdfPageObjectMark], if any.
+    pub fn get_string_param(&self, key: &str) -> Option<String> {
+        let mut buffer_length: c_ulong = 0;
+
+        if !self
+            .bindings
+            .is_true(self.bindings.FPDFPageObjMark_GetParamStringValue(
+                self.mark_handle,
+                key,
+                std::ptr::null_mut(),
+                0,
+                &mut buffer_length,
+            ))
+        {
+            return None;
+        }
+
+        if buffer_length == 0 {
+            return Some(String::new());
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        if !self
+            .bindings
+            .is_true(self.bindings.FPDFPageObjMark_GetParamStringValue(
+                self.mark_handle,
+                key,
+                buffer.as_mut_ptr() as *mut _,
+                buffer_length,
+                &mut buffer_length,
+            ))
+        {
+            return None;
+        }
+
+        Some(get_string_from_pdfium_utf16le_bytes(buffer).unwrap_or_default())
+    }
+
+    /// Returns the raw bytes of the string parameter with the given key in the property list
+    /// of this [PdfPageObjectMark], if any.
+    pub fn get_blob_param(&self, key: &str) -> Option<Vec<u8>> {
+        let mut buffer_length: c_ulong = 0;
+
+        if !self
+            .bindings
+            .is_true(self.bindings.FPDFPageObjMark_GetParamBlobValue(
+                self.mark_handle,
+                key,
+                std::ptr::null_mut(),
+                0,
+                &mut buffer_length,
+            ))
+        {
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        if buffer_length > 0
+            && !self
+                .bindings
+                .is_true(self.bindings.FPDFPageObjMark_GetParamBlobValue(
+                    self.mark_handle,
+                    key,
+                    buffer.as_mut_ptr() as *mut _,
+                    buffer_length,
+                    &mut buffer_length,
+                ))
+        {
+            return None;
+        }
+
+        Some(buffer)
+    }
+
+    /// Sets the numeric parameter with the given key in the property list of this
+    /// [PdfPageObjectMark] to the given integer value, adding the parameter if it
+    /// does not already exist.
+    ///
+    /// An error will be returned if the containing [PdfPageObject] is not attached to a document.
+    pub fn set_int_param(&mut self, key: &str, value: i32) -> Result<(), PdfiumError> {
+        let document_handle = document_handle(&self.ownership)?;
+
+        self.apply_param_mutation(self.bindings.FPDFPageObjMark_SetIntParam(
+            document_handle,
+            self.object_handle,
+            self.mark_handle,
+            key,
+            value,
+        ))
+    }
+
+    #[cfg(any(feature = "pdfium_future", feature = "pdfium_7543"))]
+    /// Sets the numeric parameter with the given key in the property list of this
+    /// [PdfPageObjectMark] to the given floating-point value, adding the parameter if it
+    /// does not already exist.
+    ///
+    /// An error will be returned if the containing [PdfPageObject] is not attached to a document.
+    pub fn set_float_param(&mut self, key: &str, value: f32) -> Result<(), PdfiumError> {
+        let document_handle = document_handle(&self.ownership)?;
+
+        self.apply_param_mutation(self.bindings.FPDFPageObjMark_SetFloatParam(
+            document_handle,
+            self.object_handle,
+            self.mark_handle,
+            key,
+            value,
+        ))
+    }
+
+    /// Sets the string parameter with the given key in the property list of this
+    /// [PdfPageObjectMark] to the given value, adding the parameter if it does not already exist.
+    ///
+    /// An error will be returned if the containing [PdfPageObject] is not attached to a document.
+    pub fn set_string_param(&mut self, key: &str, value: &str) -> Result<(), PdfiumError> {
+        let document_handle = document_handle(&self.ownership)?;
+
+        self.apply_param_mutation(self.bindings.FPDFPageObjMark_SetStringParam(
+            document_handle,
+            self.object_handle,
+            self.mark_handle,
+            key,
+            value,
+        ))
+    }
+
+    /// Sets the string parameter with the given key in the property list of this
+    /// [PdfPageObjectMark] to the given raw bytes, adding the parameter if it does not
+    /// already exist.
+    ///
+    /// An error will be returned if the containing [PdfPageObject] is not attached to a document.
+    pub fn set_blob_param(&mut self, key: &str, value: &[u8]) -> Result<(), PdfiumError> {
+        let document_handle = document_handle(&self.ownership)?;
+
+        self.apply_param_mutation(self.bindings.FPDFPageObjMark_SetBlobParam(
+            document_handle,
+            self.object_handle,
+            self.mark_handle,
+            key,
+            value.as_ptr() as _,
+            value.len() as c_ulong,
+        ))
+    }
+
+    /// Removes the parameter with the given key from the property list of this [PdfPageObjectMark].
+    #[inline]
+    pub fn remove_param(&mut self, key: &str) -> Result<(), PdfiumError> {
+        self.apply_param_mutation(self.bindings.FPDFPageObjMark_RemoveParam(
+            self.object_handle,
+            self.mark_handle,
+            key,
+        ))
+    }
+
+    /// Returns the marked content ID held in the `MCID` parameter of this [PdfPageObjectMark],
+    /// if any.
+    #[inline]
+    pub fn marked_content_id(&self) -> Option<i32> {
+        self.get_int_param(MARKED_CONTENT_ID_PARAM)
+    }
+
+    /// Sets the marked content ID held in the `MCID` parameter of this [PdfPageObjectMark].
+    ///
+    /// The marked content ID should match the marked content ID referred to by the
+    /// structure element that this marked content belongs to.
+    #[inline]
+    pub fn set_marked_content_id(&mut self, mcid: i32) -> Result<(), PdfiumError> {
+        self.set_int_param(MARKED_CONTENT_ID_PARAM, mcid)
+    }
+
+    /// Returns an iterator over all the parameters in the property list of this
+    /// [PdfPageObjectMark], as key/value pairs.
+    #[inline]
+    pub fn params(&self) -> PdfPageObjectMarkParamsIterator<'_> {
+        PdfPageObjectMarkParamsIterator::new(self)
+    }
+
+    /// Retrieves the value of the parameter with the given key, which is assumed to exist
+    /// in the property list of this [PdfPageObjectMark].
+    fn param_value(&self, key: &str) -> PdfPageObjectMarkParamValue {
+        let value = match self
+            .bindings
+            .FPDFPageObjMark_GetParamValueType(self.mark_handle, key)
+            as u32
+        {
+            FPDF_OBJECT_NUMBER => self
+                .get_int_param(key)
+                .map(PdfPageObjectMarkParamValue::Number),
+            FPDF_OBJECT_STRING => self
+                .get_string_param(key)
+                .map(PdfPageObjectMarkParamValue::String),
+            _ => None,
+        };
+
+        value.unwrap_or(PdfPageObjectMarkParamValue::Unsupported)
+    }
+
+    /// Converts the result of a Pdfium parameter mutation into a [Result], regenerating
+    /// the containing page's content stream as necessary.
+    fn apply_param_mutation(&self, result: crate::bindgen::FPDF_BOOL) -> Result<(), PdfiumError> {
+        if self.bindings.is_true(result) {
+            regenerate_content_after_mutation(&self.ownership, self.bindings)
+        } else {
+            Err(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ))
+        }
+    }
+}
+
+/// An iterator over all the parameters in the property list of a [PdfPageObjectMark].
+pub struct PdfPageObjectMarkParamsIterator<'a> {
+    mark: &'a PdfPageObjectMark<'a>,
+    next_index: usize,
+}
+
+impl<'a> PdfPageObjectMarkParamsIterator<'a> {
+    #[inline]
+    pub(crate) fn new(mark: &'a PdfPageObjectMark<'a>) -> Self {
+        PdfPageObjectMarkParamsIterator {
+            mark,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfPageObjectMarkParamsIterator<'a> {
+    type Item = (String, PdfPageObjectMarkParamValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_index < self.mark.len() {
+            let key = self.mark.param_key(self.next_index);
+
+            self.next_index += 1;
+
+            if let Some(key) = key {
+                let value = self.mark.param_value(&key);
+
+                return Some((key, value));
+            }
+        }
+
+        None
+    }
+}
+
+/// The collection of [PdfPageObjectMark] objects attached to a single [PdfPageObject].
+///
+/// Marks are listed from the outermost marked content sequence to the innermost.
+pub struct PdfPageObjectMarks<'a> {
+    object_handle: FPDF_PAGEOBJECT,
+    ownership: PdfPageObjectOwnership,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfPageObjectMarks<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        object_handle: FPDF_PAGEOBJECT,
+        ownership: PdfPageObjectOwnership,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfPageObjectMarks {
+            object_handle,
+            ownership,
+            bindings,
+        }
+    }
+
+    /// Updates the ownership hierarchy used by this [PdfPageObjectMarks] collection
+    /// to match that of its containing [PdfPageObject].
+    #[inline]
+    pub(crate) fn set_ownership(&mut self, ownership: PdfPageObjectOwnership) {
+        self.ownership = ownership;
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfPageObjectMarks] collection.
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the number of content marks in this [PdfPageObjectMarks] collection.
+    #[inline]
+    pub fn len(&self) -> PdfPageObjectMarkIndex {
+        self.bindings
+            .FPDFPageObj_CountMarks(self.object_handle)
+            .max(0) as PdfPageObjectMarkIndex
+    }
+
+    /// Returns `true` if this [PdfPageObjectMarks] collection is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a single [PdfPageObjectMark] from this [PdfPageObjectMarks] collection.
+    pub fn get(&self, index: PdfPageObjectMarkIndex) -> Result<PdfPageObjectMark<'a>, PdfiumError> {
+        if index >= self.len() {
+            return Err(PdfiumError::PageObjectMarkIndexOutOfBounds);
+        }
+
+        let handle = self
+            .bindings
+            .FPDFPageObj_GetMark(self.object_handle, index as c_ulong);
+
+        if handle.is_null() {
+            Err(PdfiumError::PageObjectMarkIndexOutOfBounds)
+        } else {
+            Ok(PdfPageObjectMark::from_pdfium(
+                handle,
+                self.object_handle,
+                self.ownership,
+                self.bindings,
+            ))
+        }
+    }
+
+    /// Returns the first [PdfPageObjectMark] in this [PdfPageObjectMarks] collection
+    /// with the given tag name, if any.
+    pub fn find(&self, name: &str) -> Option<PdfPageObjectMark<'a>> {
+        (0..self.len())
+            .filter_map(|index| self.get(index).ok())
+            .find(|mark| mark.name().as_deref() == Some(name))
+    }
+
+    /// Returns the marked content ID of the containing [PdfPageObject], if any.
+    ///
+    /// The marked content ID is taken from the `MCID` parameter of the first content mark
+    /// that defines one.
+    pub fn marked_content_id(&self) -> Option<i32> {
+        #[cfg(any(
+            feature = "pdfium_future",
+            feature = "pdfium_7543",
+            feature = "pdfium_7350",
+            feature = "pdfium_7215",
+            feature = "pdfium_7123",
+            feature = "pdfium_6996",
+            feature = "pdfium_6721",
+            feature = "pdfium_6666",
+            feature = "pdfium_6611",
+        ))]
+        {
+            match self
+                .bindings
+                .FPDFPageObj_GetMarkedContentID(self.object_handle)
+            {
+                -1 => None,
+                mcid => Some(mcid),
+            }
+        }
+
+        #[cfg(not(any(
+            feature = "pdfium_future",
+            feature = "pdfium_7543",
+            feature = "pdfium_7350",
+            feature = "pdfium_7215",
+            feature = "pdfium_7123",
+            feature = "pdfium_6996",
+            feature = "pdfium_6721",
+            feature = "pdfium_6666",
+            feature = "pdfium_6611",
+        )))]
+        {
+            self.iter().find_map(|mark| mark.marked_content_id())
+        }
+    }
+
+    /// Adds a new [PdfPageObjectMark] with the given tag name to this [PdfPageObjectMarks]
+    /// collection, returning the new mark. The new mark becomes the innermost marked
+    /// content sequence containing the [PdfPageObject].
+    ///
+    /// For instance, to exclude generated content such as page numbers or watermarks from
+    /// the logical structure of a tagged document, add a mark named `Artifact`.
+    pub fn add(&mut self, name: &str) -> Result<PdfPageObjectMark<'a>, PdfiumError> {
+        let handle = self.bindings.FPDFPageObj_AddMark(self.object_handle, name);
+
+        if handle.is_null() {
+            Err(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ))
+        } else {
+            regenerate_content_after_mutation(&self.ownership, self.bindings)?;
+
+            Ok(PdfPageObjectMark::from_pdfium(
+                handle,
+                self.object_handle,
+                self.ownership,
+                self.bindings,
+            ))
+        }
+    }
+
+    /// Adds a new [PdfPageObjectMark] with the given tag name to this [PdfPageObjectMarks]
+    /// collection, setting its `MCID` parameter to the given marked content ID.
+    /// This links the [PdfPageObject] to the structure element that refers to the same
+    /// marked content ID.
+    ///
+    /// An error will be returned if the containing [PdfPageObject] is not attached to a document.
+    pub fn add_with_marked_content_id(
+        &mut self,
+        name: &str,
+        mcid: i32,
+    ) -> Result<PdfPageObjectMark<'a>, PdfiumError> {
+        // Check for a document before adding the mark, so we don't leave behind
+        // a mark without a marked content ID.
+
+        document_handle(&self.ownership)?;
+
+        let mut mark = self.add(name)?;
+
+        mark.set_marked_content_id(mcid)?;
+
+        Ok(mark)
+    }
+
+    /// Removes the given [PdfPageObjectMark] from this [PdfPageObjectMarks] collection.
+    pub fn remove(&mut self, mark: PdfPageObjectMark<'a>) -> Result<(), PdfiumError> {
+        if self.bindings.is_true(
+            self.bindings
+                .FPDFPageObj_RemoveMark(self.object_handle, mark.mark_handle()),
+        ) {
+            regenerate_content_after_mutation(&self.ownership, self.bindings)
+        } else {
+            Err(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ))
+        }
+    }
+
+    /// Removes the [PdfPageObjectMark] at the given index from this [PdfPageObjectMarks] collection.
+    #[inline]
+    pub fn remove_at_index(&mut self, index: PdfPageObjectMarkIndex) -> Result<(), PdfiumError> {
+        let mark = self.get(index)?;
+
+        self.remove(mark)
+    }
+
+    /// Returns an iterator over all the [PdfPageObjectMark] objects in this
+    /// [PdfPageObjectMarks] collection.
+    #[inline]
+    pub fn iter(&self) -> PdfPageObjectMarksIterator<'_> {
+        PdfPageObjectMarksIterator::new(self)
+    }
+}
+
+/// An iterator over all the [PdfPageObjectMark] objects in a [PdfPageObjectMarks] collection.
+pub struct PdfPageObjectMarksIterator<'a> {
+    marks: &'a PdfPageObjectMarks<'a>,
+    next_index: PdfPageObjectMarkIndex,
+}
+
+impl<'a> PdfPageObjectMarksIterator<'a> {
+    #[inline]
+    pub(crate) fn new(marks: &'a PdfPageObjectMarks<'a>) -> Self {
+        PdfPageObjectMarksIterator {
+            marks,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfPageObjectMarksIterator<'a> {
+    type Item = PdfPageObjectMark<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.marks.get(self.next_index);
+
+        self.next_index += 1;
+
+        next.ok()
+    }
+}
+
+/// Returns the handle of the document bound to the given ownership hierarchy. Pdfium
+/// requires a document handle when setting the parameters of a content mark.
+fn document_handle(ownership: &PdfPageObjectOwnership) -> Result<FPDF_DOCUMENT, PdfiumError> {
+    match ownership {
+        PdfPageObjectOwnership::Document(ownership) => Ok(ownership.document_handle()),
+        PdfPageObjectOwnership::Page(ownership) => Ok(ownership.document_handle()),
+        PdfPageObjectOwnership::AttachedAnnotation(ownership) => Ok(ownership.document_handle()),
+        PdfPageObjectOwnership::UnattachedAnnotation(ownership) => Ok(ownership.document_handle()),
+        PdfPageObjectOwnership::Unowned => Err(PdfiumError::OwnershipNotAttachedToDocument),
+    }
+}
+
+/// Regenerates the content stream of the page containing a marked page object, taking
+/// the page's content regeneration strategy into account.
+fn regenerate_content_after_mutation(
+    ownership: &PdfPageObjectOwnership,
+    bindings: &dyn PdfiumLibraryBindings,
+) -> Result<(), PdfiumError> {
+    let (document_handle, page_handle) = match ownership {
+        PdfPageObjectOwnership::Page(ownership) => {
+            (ownership.document_handle(), ownership.page_handle())
+        }
+        PdfPageObjectOwnership::AttachedAnnotation(ownership) => {
+            (ownership.document_handle(), ownership.page_handle())
+        }
+        _ => return Ok(()),
+    };
+
+    match PdfPageIndexCache::get_content_regeneration_strategy_for_page(
+        document_handle,
+        page_handle,
+    ) {
+        Some(PdfPageContentRegenerationStrategy::AutomaticOnEveryChange) => {
+            PdfPage::regenerate_content_immut_for_handle(page_handle, bindings)
+        }
+        Some(_) => Ok(()),
+        None => Err(PdfiumError::SourcePageIndexNotInCache),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_page_object_marks() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        let mut page = document
+            .pages_mut()
+            .create_page_at_start(PdfPagePaperSize::a4())?;
+
+        let mut artifact = page.objects_mut().create_path_object_rect(
+            PdfRect::new_from_values(100.0, 100.0, 200.0, 200.0),
+            None,
+            None,
+            Some(PdfColor::RED),
+        )?;
+
+        let mut span = page.objects_mut().create_path_object_rect(
+            PdfRect::new_from_values(300.0, 300.0, 400.0, 400.0),
+            None,
+            None,
+            Some(PdfColor::BLUE),
+        )?;
+
+        assert!(artifact.marks().is_empty());
+
+        let mut mark = artifact.marks_mut().add("Artifact")?;
+
+        mark.set_string_param("Type", "Pagination")?;
+        mark.set_int_param("Count", 3)?;
+        mark.set_blob_param("Data", &[1, 2, 3])?;
+
+        span.marks_mut().add_with_marked_content_id("Span", 7)?;
+
+        assert_eq!(artifact.marks().len(), 1);
+
+        let mark = artifact.marks().find("Artifact").unwrap();
+
+        assert_eq!(mark.name().as_deref(), Some("Artifact"));
+        assert_eq!(mark.len(), 3);
+        assert_eq!(mark.get_string_param("Type").as_deref(), Some("Pagination"));
+        assert_eq!(mark.get_int_param("Count"), Some(3));
+        assert_eq!(mark.get_blob_param("Data"), Some(vec![1, 2, 3]));
+        assert_eq!(
+            mark.get_param("Count"),
+            Some(PdfPageObjectMarkParamValue::Number(3))
+        );
+        assert_eq!(mark.get_param("Missing"), None);
+
+        assert_eq!(artifact.marks().marked_content_id(), None);
+        assert_eq!(span.marks().marked_content_id(), Some(7));
+
+        let found = page.objects().find_by_marked_content_id(7);
+
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].page_object_handle(), span.page_object_handle());
+
+        artifact.marks_mut().remove_at_index(0)?;
+
+        assert!(artifact.marks().is_empty());
+
+        Ok(())
+    }
+}
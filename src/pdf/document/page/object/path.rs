@@ -8,6 +8,7 @@ use crate::bindgen::{
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::{PdfiumError, PdfiumInternalError};
 use crate::pdf::color::PdfColor;
+use crate::pdf::document::page::object::marks::PdfPageObjectMarks;
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
 use crate::pdf::document::page::object::{
     PdfPageObject, PdfPageObjectCommon, PdfPageObjectOwnership,
@@ -154,6 +155,7 @@ pub struct PdfPagePathObject<'a> {
     object_handle: FPDF_PAGEOBJECT,
     ownership: PdfPageObjectOwnership,
     bindings: &'a dyn PdfiumLibraryBindings,
+    marks: PdfPageObjectMarks<'a>,
     current_point_x: PdfPoints,
     current_point_y: PdfPoints,
 }
@@ -169,6 +171,7 @@ impl<'a> PdfPagePathObject<'a> {
             object_handle,
             ownership,
             bindings,
+            marks: PdfPageObjectMarks::from_pdfium(object_handle, ownership, bindings),
             current_point_x: PdfPoints::ZERO,
             current_point_y: PdfPoints::ZERO,
         }
@@ -228,6 +231,11 @@ impl<'a> PdfPagePathObject<'a> {
                 object_handle: handle,
                 ownership: PdfPageObjectOwnership::unowned(),
                 bindings,
+                marks: PdfPageObjectMarks::from_pdfium(
+                    handle,
+                    PdfPageObjectOwnership::unowned(),
+                    bindings,
+                ),
                 current_point_x: x,
                 current_point_y: y,
             };
@@ -922,6 +930,17 @@ impl<'a> PdfPageObjectPrivate<'a> for PdfPagePathObject<'a> {
     #[inline]
     fn set_ownership(&mut self, ownership: PdfPageObjectOwnership) {
         self.ownership = ownership;
+        self.marks.set_ownership(ownership);
+    }
+
+    #[inline]
+    fn marks_impl(&self) -> &PdfPageObjectMarks<'a> {
+        &self.marks
+    }
+
+    #[inline]
+    fn marks_mut_impl(&mut self) -> &mut PdfPageObjectMarks<'a> {
+        &mut self.marks
     }
 
     #[inline]
@@ -15,6 +15,7 @@ pub(crate) mod internal {
     use crate::error::{PdfiumError, PdfiumInternalError};
     use crate::pdf::document::page::annotation::objects::PdfPageAnnotationObjects;
     use crate::pdf::document::page::object::group::PdfPageGroupObject;
+    use crate::pdf::document::page::object::marks::PdfPageObjectMarks;
     use crate::pdf::document::page::object::{
         PdfPageObject, PdfPageObjectCommon, PdfPageObjectOwnership, PdfPageObjectType,
     };
@@ -43,6 +44,12 @@ pub(crate) mod internal {
         /// Sets the ownership hierarchy for this [PdfPageObject].
         fn set_ownership(&mut self, ownership: PdfPageObjectOwnership);
 
+        /// Returns the collection of content marks attached to this [PdfPageObject].
+        fn marks_impl(&self) -> &PdfPageObjectMarks<'a>;
+
+        /// Returns a mutable collection of content marks attached to this [PdfPageObject].
+        fn marks_mut_impl(&mut self) -> &mut PdfPageObjectMarks<'a>;
+
         /// Adds this [PdfPageObject] to the given [PdfPageObjects] collection.
         #[inline]
         fn add_object_to_page(
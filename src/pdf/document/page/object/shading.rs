@@ -4,6 +4,7 @@
 use crate::bindgen::{FPDF_DOCUMENT, FPDF_PAGEOBJECT};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
+use crate::pdf::document::page::object::marks::PdfPageObjectMarks;
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
 use crate::pdf::document::page::object::{PdfPageObject, PdfPageObjectOwnership};
 use crate::pdf::matrix::{PdfMatrix, PdfMatrixValue};
@@ -18,6 +19,7 @@ pub struct PdfPageShadingObject<'a> {
     object_handle: FPDF_PAGEOBJECT,
     ownership: PdfPageObjectOwnership,
     bindings: &'a dyn PdfiumLibraryBindings,
+    marks: PdfPageObjectMarks<'a>,
 }
 
 impl<'a> PdfPageShadingObject<'a> {
@@ -30,6 +32,7 @@ impl<'a> PdfPageShadingObject<'a> {
             object_handle,
             ownership,
             bindings,
+            marks: PdfPageObjectMarks::from_pdfium(object_handle, ownership, bindings),
         }
     }
 
@@ -68,6 +71,17 @@ impl<'a> PdfPageObjectPrivate<'a> for PdfPageShadingObject<'a> {
     #[inline]
     fn set_ownership(&mut self, ownership: PdfPageObjectOwnership) {
         self.ownership = ownership;
+        self.marks.set_ownership(ownership);
+    }
+
+    #[inline]
+    fn marks_impl(&self) -> &PdfPageObjectMarks<'a> {
+        &self.marks
+    }
+
+    #[inline]
+    fn marks_mut_impl(&mut self) -> &mut PdfPageObjectMarks<'a> {
+        &mut self.marks
     }
 
     #[inline]
@@ -15,6 +15,7 @@ use crate::bindgen::{
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::{PdfiumError, PdfiumInternalError};
 use crate::pdf::document::fonts::ToPdfFontToken;
+use crate::pdf::document::page::object::marks::PdfPageObjectMarks;
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
 use crate::pdf::document::page::object::{
     PdfPageObject, PdfPageObjectCommon, PdfPageObjectOwnership,
@@ -166,6 +167,7 @@ pub struct PdfPageTextObject<'a> {
     object_handle: FPDF_PAGEOBJECT,
     ownership: PdfPageObjectOwnership,
     bindings: &'a dyn PdfiumLibraryBindings,
+    marks: PdfPageObjectMarks<'a>,
 }
 
 impl<'a> PdfPageTextObject<'a> {
@@ -179,6 +181,7 @@ impl<'a> PdfPageTextObject<'a> {
             object_handle,
             ownership,
             bindings,
+            marks: PdfPageObjectMarks::from_pdfium(object_handle, ownership, bindings),
         }
     }
 
@@ -228,6 +231,11 @@ impl<'a> PdfPageTextObject<'a> {
                 object_handle: handle,
                 ownership: PdfPageObjectOwnership::unowned(),
                 bindings,
+                marks: PdfPageObjectMarks::from_pdfium(
+                    handle,
+                    PdfPageObjectOwnership::unowned(),
+                    bindings,
+                ),
             };
 
             result.set_text(text)?;
@@ -519,6 +527,17 @@ impl<'a> PdfPageObjectPrivate<'a> for PdfPageTextObject<'a> {
     #[inline]
     fn set_ownership(&mut self, ownership: PdfPageObjectOwnership) {
         self.ownership = ownership;
+        self.marks.set_ownership(ownership);
+    }
+
+    #[inline]
+    fn marks_impl(&self) -> &PdfPageObjectMarks<'a> {
+        &self.marks
+    }
+
+    #[inline]
+    fn marks_mut_impl(&mut self) -> &mut PdfPageObjectMarks<'a> {
+        &mut self.marks
     }
 
     #[inline]
@@ -4,6 +4,7 @@
 use crate::bindgen::{FPDF_DOCUMENT, FPDF_PAGEOBJECT};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::{PdfiumError, PdfiumInternalError};
+use crate::pdf::document::page::object::marks::PdfPageObjectMarks;
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
 use crate::pdf::document::page::object::{PdfPageObject, PdfPageObjectOwnership};
 use crate::pdf::document::page::objects::common::{PdfPageObjectIndex, PdfPageObjectsIterator};
@@ -36,6 +37,7 @@ pub struct PdfPageXObjectFormObject<'a> {
     object_handle: FPDF_PAGEOBJECT,
     ownership: PdfPageObjectOwnership,
     bindings: &'a dyn PdfiumLibraryBindings,
+    marks: PdfPageObjectMarks<'a>,
 }
 
 impl<'a> PdfPageXObjectFormObject<'a> {
@@ -48,6 +50,7 @@ impl<'a> PdfPageXObjectFormObject<'a> {
             object_handle,
             ownership,
             bindings,
+            marks: PdfPageObjectMarks::from_pdfium(object_handle, ownership, bindings),
         }
     }
 
@@ -148,6 +151,17 @@ impl<'a> PdfPageObjectPrivate<'a> for PdfPageXObjectFormObject<'a> {
     #[inline]
     fn set_ownership(&mut self, ownership: PdfPageObjectOwnership) {
         self.ownership = ownership;
+        self.marks.set_ownership(ownership);
+    }
+
+    #[inline]
+    fn marks_impl(&self) -> &PdfPageObjectMarks<'a> {
+        &self.marks
+    }
+
+    #[inline]
+    fn marks_mut_impl(&mut self) -> &mut PdfPageObjectMarks<'a> {
+        &mut self.marks
     }
 
     #[inline]
@@ -27,6 +27,7 @@ use image_023::{DynamicImage, GenericImageView};
 
 #[cfg(doc)]
 use {
+    crate::pdf::document::page::struct_tree::element::PdfStructElement,
     crate::pdf::document::page::PdfPage,
     crate::pdf::document::page::PdfPageContentRegenerationStrategy,
     crate::pdf::document::page::PdfPageObjects,
@@ -109,6 +110,19 @@ pub trait PdfPageObjectsCommon<'a> {
         PdfRect::new_from_values(bottom, left, top, right)
     }
 
+    /// Returns all the [PdfPageObject] objects in this page objects collection that belong
+    /// to the marked content sequence with the given marked content ID.
+    ///
+    /// In a tagged document, this retrieves the page objects that make up the content of
+    /// a [PdfStructElement], using the marked content IDs returned by the
+    /// [PdfStructElement::marked_content_ids()] function. Objects nested inside
+    /// [PdfPageXObjectFormObject] objects are not searched.
+    fn find_by_marked_content_id(&'a self, mcid: i32) -> Vec<PdfPageObject<'a>> {
+        self.iter()
+            .filter(|object| object.marks().marked_content_id() == Some(mcid))
+            .collect()
+    }
+
     /// Adds the given [PdfPageObject] to this page objects collection. The object's
     /// memory ownership will be transferred to the [PdfPage] containing this page objects
     /// collection, and the updated page object will be returned.
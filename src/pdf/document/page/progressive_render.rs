@@ -0,0 +1,333 @@
+//! Defines the [PdfPageProgressiveRender] struct, exposing functionality related to rendering
+//! a single `PdfPage` incrementally over a series of pausable steps.
+
+use crate::bindgen::{
+    FPDF_BOOL, FPDF_RENDER_DONE, FPDF_RENDER_FAILED, FPDF_RENDER_READY, FPDF_RENDER_TOBECONTINUED,
+    IFSDK_PAUSE,
+};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::bitmap::PdfBitmap;
+use crate::pdf::document::page::render_config::PdfPageRenderSettings;
+use crate::pdf::document::page::PdfPage;
+use std::os::raw::{c_int, c_void};
+
+#[cfg(doc)]
+use crate::pdf::document::page::render_config::PdfRenderConfig;
+
+/// The progress of a [PdfPageProgressiveRender] operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PdfPageRenderStatus {
+    /// Rendering has not yet started.
+    Ready,
+
+    /// Rendering was paused before it finished. Call [PdfPageProgressiveRender::poll()]
+    /// again to continue rendering.
+    ToBeContinued,
+
+    /// Rendering is complete.
+    Done,
+
+    /// Pdfium was unable to render the page.
+    Failed,
+}
+
+impl PdfPageRenderStatus {
+    #[inline]
+    pub(crate) fn from_pdfium(status: c_int) -> Result<Self, PdfiumError> {
+        match status as u32 {
+            FPDF_RENDER_READY => Ok(PdfPageRenderStatus::Ready),
+            FPDF_RENDER_TOBECONTINUED => Ok(PdfPageRenderStatus::ToBeContinued),
+            FPDF_RENDER_DONE => Ok(PdfPageRenderStatus::Done),
+            FPDF_RENDER_FAILED => Ok(PdfPageRenderStatus::Failed),
+            _ => Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure),
+        }
+    }
+}
+
+/// An in-progress rendering of a single [PdfPage] into a [PdfBitmap].
+///
+/// Rendering a complex page - for instance, a large engineering drawing containing hundreds of
+/// thousands of path segments - can take several seconds. Rather than blocking until the
+/// page is fully rendered, a [PdfPageProgressiveRender] renders the page in a series of steps.
+/// Each call to [PdfPageProgressiveRender::poll()] continues rendering until either the page
+/// is complete or the given "should pause" callback returns `true`, allowing the caller to
+/// enforce a time budget, interleave other work, or abandon the rendering entirely.
+///
+/// Create a [PdfPageProgressiveRender] by calling the [PdfPage::render_progressive()] function.
+/// Dropping a [PdfPageProgressiveRender] before rendering is complete cancels the rendering.
+pub struct PdfPageProgressiveRender<'a> {
+    page: &'a PdfPage<'a>,
+    bitmap: PdfBitmap<'a>,
+    settings: PdfPageRenderSettings,
+    status: PdfPageRenderStatus,
+    session: PdfPageProgressiveRenderSession<'a>,
+}
+
+impl<'a> PdfPageProgressiveRender<'a> {
+    pub(crate) fn new(
+        page: &'a PdfPage<'a>,
+        bitmap: PdfBitmap<'a>,
+        settings: PdfPageRenderSettings,
+    ) -> Result<Self, PdfiumError> {
+        if settings.is_transformed_or_clipped {
+            // Pdfium's progressive rendering functions cannot apply a transformation
+            // matrix or a clipping rectangle.
+
+            return Err(PdfiumError::ProgressiveRenderTransformationNotSupported);
+        }
+
+        Ok(PdfPageProgressiveRender {
+            page,
+            bitmap,
+            settings,
+            status: PdfPageRenderStatus::Ready,
+            session: PdfPageProgressiveRenderSession {
+                page,
+                is_open: false,
+            },
+        })
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfPageProgressiveRender].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.page.bindings()
+    }
+
+    /// Returns the current status of this [PdfPageProgressiveRender].
+    #[inline]
+    pub fn status(&self) -> PdfPageRenderStatus {
+        self.status
+    }
+
+    /// Returns `true` if this [PdfPageProgressiveRender] has finished rendering the page.
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        self.status == PdfPageRenderStatus::Done
+    }
+
+    /// Continues rendering the page, calling the given callback periodically to check
+    /// whether rendering should be paused. Rendering pauses as soon as the callback returns
+    /// `true`; the status of the rendering is then returned.
+    ///
+    /// Call this function repeatedly until it returns [PdfPageRenderStatus::Done].
+    /// Calling this function once rendering is done has no effect.
+    ///
+    /// An error will be returned if Pdfium is unable to render the page.
+    pub fn poll(
+        &mut self,
+        mut should_pause: impl FnMut() -> bool,
+    ) -> Result<PdfPageRenderStatus, PdfiumError> {
+        match self.status {
+            PdfPageRenderStatus::Done => return Ok(PdfPageRenderStatus::Done),
+            PdfPageRenderStatus::Failed => {
+                return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure)
+            }
+            _ => {}
+        }
+
+        // Pdfium passes the IFSDK_PAUSE struct back to our callback function. We use the
+        // struct's user data pointer to carry a reference to the caller's closure.
+
+        let mut callback: &mut dyn FnMut() -> bool = &mut should_pause;
+
+        let mut pause = IFSDK_PAUSE {
+            version: 1,
+            NeedToPauseNow: Some(need_to_pause_now),
+            user: &mut callback as *mut &mut dyn FnMut() -> bool as *mut c_void,
+        };
+
+        let bindings = self.page.bindings();
+
+        let status = if self.status == PdfPageRenderStatus::Ready {
+            if self.settings.do_clear_bitmap_before_rendering {
+                // Clear the bitmap buffer by setting every pixel to a known color.
+
+                bindings.FPDFBitmap_FillRect(
+                    self.bitmap.handle(),
+                    0,
+                    0,
+                    self.settings.width,
+                    self.settings.height,
+                    self.settings.clear_color,
+                );
+            }
+
+            self.session.is_open = true;
+
+            match self.settings.color_scheme.as_ref() {
+                Some(color_scheme) => bindings.FPDF_RenderPageBitmapWithColorScheme_Start(
+                    self.bitmap.handle(),
+                    self.page.page_handle(),
+                    0,
+                    0,
+                    self.settings.width,
+                    self.settings.height,
+                    self.settings.rotate,
+                    self.settings.render_flags,
+                    color_scheme,
+                    &mut pause,
+                ),
+                None => bindings.FPDF_RenderPageBitmap_Start(
+                    self.bitmap.handle(),
+                    self.page.page_handle(),
+                    0,
+                    0,
+                    self.settings.width,
+                    self.settings.height,
+                    self.settings.rotate,
+                    self.settings.render_flags,
+                    &mut pause,
+                ),
+            }
+        } else {
+            bindings.FPDF_RenderPage_Continue(self.page.page_handle(), &mut pause)
+        };
+
+        self.status = PdfPageRenderStatus::from_pdfium(status)?;
+
+        match self.status {
+            PdfPageRenderStatus::Done => {
+                self.session.close();
+
+                if self.settings.do_render_form_data {
+                    self.page
+                        .render_form_data_into_bitmap_handle(self.bitmap.handle(), &self.settings);
+                }
+
+                self.bitmap
+                    .set_byte_order_from_render_settings(&self.settings);
+
+                Ok(PdfPageRenderStatus::Done)
+            }
+            PdfPageRenderStatus::Failed => {
+                self.session.close();
+
+                Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure)
+            }
+            status => Ok(status),
+        }
+    }
+
+    /// Renders the remainder of the page without pausing, returning the finished [PdfBitmap].
+    pub fn finish(mut self) -> Result<PdfBitmap<'a>, PdfiumError> {
+        self.poll(|| false)?;
+
+        Ok(self.into_bitmap())
+    }
+
+    /// Returns an immutable reference to the [PdfBitmap] this [PdfPageProgressiveRender]
+    /// is rendering into. The bitmap may be only partially rendered if rendering is not yet done.
+    #[inline]
+    pub fn bitmap(&self) -> &PdfBitmap<'a> {
+        &self.bitmap
+    }
+
+    /// Consumes this [PdfPageProgressiveRender], returning the [PdfBitmap] it was rendering into.
+    /// Any rendering still outstanding is cancelled, so the bitmap may be only partially rendered.
+    #[inline]
+    pub fn into_bitmap(mut self) -> PdfBitmap<'a> {
+        self.session.close();
+
+        self.bitmap
+    }
+}
+
+/// Releases the resources Pdfium holds for a progressive rendering when the rendering
+/// finishes or is abandoned.
+struct PdfPageProgressiveRenderSession<'a> {
+    page: &'a PdfPage<'a>,
+    is_open: bool,
+}
+
+impl<'a> PdfPageProgressiveRenderSession<'a> {
+    fn close(&mut self) {
+        if self.is_open {
+            self.page
+                .bindings()
+                .FPDF_RenderPage_Close(self.page.page_handle());
+
+            self.is_open = false;
+        }
+    }
+}
+
+impl<'a> Drop for PdfPageProgressiveRenderSession<'a> {
+    /// Closes this [PdfPageProgressiveRenderSession], cancelling any outstanding rendering.
+    #[inline]
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+// The callback function invoked by Pdfium.
+extern "C" fn need_to_pause_now(pause: *mut IFSDK_PAUSE) -> FPDF_BOOL {
+    let should_pause = unsafe {
+        let callback = &mut *((*pause).user as *mut &mut dyn FnMut() -> bool);
+
+        callback()
+    };
+
+    if should_pause {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_progressive_render_matches_render() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/path-test.pdf", None)?;
+
+        let page = document.pages().get(0)?;
+
+        let config = PdfRenderConfig::new().set_target_width(500);
+
+        let expected = page.render_with_config(&config)?.as_raw_bytes();
+
+        let mut render = page.render_progressive(&config)?;
+
+        assert_eq!(render.status(), PdfPageRenderStatus::Ready);
+
+        let mut polls = 0;
+
+        while render.poll(|| true)? != PdfPageRenderStatus::Done {
+            polls += 1;
+
+            assert!(polls < 100_000);
+        }
+
+        assert!(render.is_done());
+        assert_eq!(render.into_bitmap().as_raw_bytes(), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_progressive_render_rejects_transformation() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/path-test.pdf", None)?;
+
+        let page = document.pages().get(0)?;
+
+        let config = PdfRenderConfig::new()
+            .set_target_width(500)
+            .clip(0, 0, 100, 100);
+
+        assert!(matches!(
+            page.render_progressive(&config),
+            Err(PdfiumError::ProgressiveRenderTransformationNotSupported)
+        ));
+
+        Ok(())
+    }
+}
@@ -2,10 +2,10 @@
 //! the rendering of [PdfBitmap] objects from one or more [PdfPage] objects.
 
 use crate::bindgen::{
-    FPDF_ANNOT, FPDF_CONVERT_FILL_TO_STROKE, FPDF_DWORD, FPDF_GRAYSCALE, FPDF_LCD_TEXT,
-    FPDF_NO_NATIVETEXT, FPDF_PRINTING, FPDF_RENDER_FORCEHALFTONE, FPDF_RENDER_LIMITEDIMAGECACHE,
-    FPDF_RENDER_NO_SMOOTHIMAGE, FPDF_RENDER_NO_SMOOTHPATH, FPDF_RENDER_NO_SMOOTHTEXT,
-    FPDF_REVERSE_BYTE_ORDER, FS_MATRIX, FS_RECTF,
+    FPDF_ANNOT, FPDF_COLORSCHEME, FPDF_CONVERT_FILL_TO_STROKE, FPDF_DWORD, FPDF_GRAYSCALE,
+    FPDF_LCD_TEXT, FPDF_NO_NATIVETEXT, FPDF_PRINTING, FPDF_RENDER_FORCEHALFTONE,
+    FPDF_RENDER_LIMITEDIMAGECACHE, FPDF_RENDER_NO_SMOOTHIMAGE, FPDF_RENDER_NO_SMOOTHPATH,
+    FPDF_RENDER_NO_SMOOTHTEXT, FPDF_REVERSE_BYTE_ORDER, FS_MATRIX, FS_RECTF,
 };
 use crate::create_transform_setters;
 use crate::error::PdfiumError;
@@ -53,6 +53,73 @@ impl PdfBitmapConfig {
     }
 }
 
+/// A set of colors that replace the colors of path and text objects during rendering of
+/// a [PdfPage], as set by calling [PdfRenderConfig::set_color_scheme()].
+///
+/// Forcing colors in this way is useful for dark mode and high contrast rendering. Only
+/// path and text objects are affected; images, shadings, and form data are rendered using
+/// their original colors.
+#[derive(Debug, Copy, Clone)]
+pub struct PdfRenderColorScheme {
+    path_fill_color: PdfColor,
+    path_stroke_color: PdfColor,
+    text_fill_color: PdfColor,
+    text_stroke_color: PdfColor,
+}
+
+impl PdfRenderColorScheme {
+    /// Creates a new [PdfRenderColorScheme] from the given fill and stroke colors for
+    /// path and text objects.
+    #[inline]
+    pub const fn new(
+        path_fill_color: PdfColor,
+        path_stroke_color: PdfColor,
+        text_fill_color: PdfColor,
+        text_stroke_color: PdfColor,
+    ) -> Self {
+        PdfRenderColorScheme {
+            path_fill_color,
+            path_stroke_color,
+            text_fill_color,
+            text_stroke_color,
+        }
+    }
+
+    /// Returns the color used to fill path objects.
+    #[inline]
+    pub fn path_fill_color(&self) -> PdfColor {
+        self.path_fill_color
+    }
+
+    /// Returns the color used to stroke path objects.
+    #[inline]
+    pub fn path_stroke_color(&self) -> PdfColor {
+        self.path_stroke_color
+    }
+
+    /// Returns the color used to fill text objects.
+    #[inline]
+    pub fn text_fill_color(&self) -> PdfColor {
+        self.text_fill_color
+    }
+
+    /// Returns the color used to stroke text objects.
+    #[inline]
+    pub fn text_stroke_color(&self) -> PdfColor {
+        self.text_stroke_color
+    }
+
+    #[inline]
+    pub(crate) fn as_pdfium(&self) -> FPDF_COLORSCHEME {
+        FPDF_COLORSCHEME {
+            path_fill_color: self.path_fill_color.as_pdfium_argb_color(),
+            path_stroke_color: self.path_stroke_color.as_pdfium_argb_color(),
+            text_fill_color: self.text_fill_color.as_pdfium_argb_color(),
+            text_stroke_color: self.text_stroke_color.as_pdfium_argb_color(),
+        }
+    }
+}
+
 /// Configures the scaling, rotation, and rendering settings that should be applied to
 /// a [PdfPage] to create a [PdfBitmap] for that page. [PdfRenderConfig] can accommodate pages of
 /// different sizes while correctly maintaining each page's aspect ratio, automatically
@@ -87,6 +154,7 @@ pub struct PdfRenderConfig {
     form_field_highlight: Option<Vec<(PdfFormFieldType, PdfColor)>>,
     transformation_matrix: PdfMatrix,
     clip_rect: Option<(Pixels, Pixels, Pixels, Pixels)>,
+    color_scheme: Option<PdfRenderColorScheme>,
 
     // The fields below set Pdfium's page rendering flags. Coverage for the
     // FPDF_DEBUG_INFO and FPDF_NO_CATCH flags is omitted since they are obsolete.
@@ -128,6 +196,7 @@ impl PdfRenderConfig {
             form_field_highlight: None,
             transformation_matrix: PdfMatrix::IDENTITY,
             clip_rect: None,
+            color_scheme: None,
             do_set_flag_render_annotations: true,
             do_set_flag_use_lcd_text_rendering: false,
             do_set_flag_no_native_text: false,
@@ -437,6 +506,20 @@ impl PdfRenderConfig {
         self
     }
 
+    /// Forces path and text objects to be rendered using the colors in the given
+    /// [PdfRenderColorScheme] rather than their own colors. The default is to render
+    /// every object using its own colors.
+    ///
+    /// Pdfium can only apply a color scheme when rendering without a custom transformation
+    /// matrix or clipping rectangle. The color scheme is ignored if any transformation
+    /// or clipping is applied.
+    #[inline]
+    pub fn set_color_scheme(mut self, color_scheme: PdfRenderColorScheme) -> Self {
+        self.color_scheme = Some(color_scheme);
+
+        self
+    }
+
     /// Controls whether form data widgets and user-supplied form data should be included
     /// during rendering of the [PdfPage]. The default is `true`.
     ///
@@ -966,6 +1049,12 @@ impl PdfRenderConfig {
                     bottom: output_height as f32,
                 }
             },
+            is_transformed_or_clipped: self.transformation_matrix != PdfMatrix::IDENTITY
+                || self.clip_rect.is_some(),
+            color_scheme: self
+                .color_scheme
+                .as_ref()
+                .map(PdfRenderColorScheme::as_pdfium),
             render_flags: render_flags as c_int,
             is_reversed_byte_order_flag_set: self.do_set_flag_reverse_byte_order,
         }
@@ -993,6 +1082,8 @@ pub(crate) struct PdfPageRenderSettings {
     pub(crate) form_field_highlight: Option<Vec<(c_int, (FPDF_DWORD, u8))>>,
     pub(crate) matrix: FS_MATRIX,
     pub(crate) clipping: FS_RECTF,
+    pub(crate) is_transformed_or_clipped: bool,
+    pub(crate) color_scheme: Option<FPDF_COLORSCHEME>,
     pub(crate) render_flags: c_int,
     pub(crate) is_reversed_byte_order_flag_set: bool,
 }
@@ -1047,6 +1138,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_color_scheme_render_config() -> Result<(), PdfiumError> {
+        let scheme = PdfRenderColorScheme::new(
+            PdfColor::new(1, 2, 3, 255),
+            PdfColor::WHITE,
+            PdfColor::new(0x12, 0x34, 0x56, 0x78),
+            PdfColor::BLACK,
+        );
+
+        let render_settings =
+            get_render_settings_from_config(PdfRenderConfig::new().set_color_scheme(scheme))?;
+
+        let color_scheme = render_settings.color_scheme.unwrap();
+
+        assert_eq!(color_scheme.path_fill_color, 0xFF010203);
+        assert_eq!(color_scheme.path_stroke_color, 0xFFFFFFFF);
+        assert_eq!(color_scheme.text_fill_color, 0x78123456);
+        assert_eq!(color_scheme.text_stroke_color, 0xFF000000);
+
+        assert!(get_render_settings_from_config(PdfRenderConfig::new())?
+            .color_scheme
+            .is_none());
+
+        Ok(())
+    }
+
     fn get_render_settings_from_config(
         config: PdfRenderConfig,
     ) -> Result<PdfPageRenderSettings, PdfiumError> {
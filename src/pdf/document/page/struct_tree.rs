@@ -0,0 +1,238 @@
+//! Defines the [PdfStructTree] struct, exposing functionality related to the tagged
+//! logical structure tree of a single `PdfPage`.
+
+#[cfg(any(
+    feature = "pdfium_future",
+    feature = "pdfium_7543",
+    feature = "pdfium_7350",
+    feature = "pdfium_7215",
+    feature = "pdfium_7123",
+    feature = "pdfium_6996",
+    feature = "pdfium_6721",
+    feature = "pdfium_6666",
+    feature = "pdfium_6611",
+    feature = "pdfium_6569",
+    feature = "pdfium_6555",
+    feature = "pdfium_6490",
+))]
+pub mod attributes;
+pub mod element;
+
+use crate::bindgen::{FPDF_PAGE, FPDF_STRUCTTREE};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::page::struct_tree::element::PdfStructElement;
+use crate::pdf::document::page::PdfPage;
+use std::os::raw::c_int;
+
+#[cfg(doc)]
+use crate::pdf::document::PdfDocument;
+
+/// The zero-based index of a single [PdfStructElement] inside its containing
+/// [PdfStructTree] or parent [PdfStructElement].
+pub type PdfStructElementIndex = usize;
+
+/// The logical structure tree of a single [PdfPage] in a tagged [PdfDocument].
+///
+/// The structure tree describes the logical organization of the page's content - headings,
+/// paragraphs, figures, tables, and so on - independently of the order in which that content
+/// is drawn. Assistive technologies rely on the structure tree to present a document to users
+/// with disabilities. Each [PdfStructElement] in the tree may be linked to one or more runs of
+/// marked content on the page using marked content IDs.
+///
+/// Only the structure elements that refer to the page used to create this [PdfStructTree]
+/// are included in the tree.
+pub struct PdfStructTree<'a> {
+    handle: FPDF_STRUCTTREE,
+
+    // Pdfium requires the page to remain open for as long as the structure tree is open.
+    // When the structure tree is retrieved via the document, we take ownership of the page
+    // so it is closed only after the structure tree has been closed.
+    #[allow(dead_code)]
+    page: Option<PdfPage<'a>>,
+
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfStructTree<'a> {
+    /// Loads the structure tree for the page with the given `FPDF_PAGE` handle.
+    pub(crate) fn from_pdfium(
+        page_handle: FPDF_PAGE,
+        page: Option<PdfPage<'a>>,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Result<Self, PdfiumError> {
+        let handle = bindings.FPDF_StructTree_GetForPage(page_handle);
+
+        if handle.is_null() {
+            // Pdfium only creates a structure tree for pages in tagged documents.
+
+            Err(PdfiumError::DocumentNotTagged)
+        } else {
+            Ok(PdfStructTree {
+                handle,
+                page,
+                bindings,
+            })
+        }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfStructTree].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the number of top-level structure elements in this [PdfStructTree].
+    #[inline]
+    pub fn len(&self) -> PdfStructElementIndex {
+        self.bindings
+            .FPDF_StructTree_CountChildren(self.handle)
+            .max(0) as PdfStructElementIndex
+    }
+
+    /// Returns `true` if this [PdfStructTree] contains no structure elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a single top-level [PdfStructElement] from this [PdfStructTree].
+    pub fn get(&self, index: PdfStructElementIndex) -> Result<PdfStructElement<'_>, PdfiumError> {
+        if index >= self.len() {
+            return Err(PdfiumError::StructElementIndexOutOfBounds);
+        }
+
+        let handle = self
+            .bindings
+            .FPDF_StructTree_GetChildAtIndex(self.handle, index as c_int);
+
+        if handle.is_null() {
+            Err(PdfiumError::StructElementIndexOutOfBounds)
+        } else {
+            Ok(PdfStructElement::from_pdfium(handle, self.bindings))
+        }
+    }
+
+    /// Returns an iterator over the top-level structure elements in this [PdfStructTree].
+    ///
+    /// Use the [PdfStructElement::iter_children()] function to descend into the tree.
+    #[inline]
+    pub fn iter(&self) -> PdfStructTreeIterator<'_> {
+        PdfStructTreeIterator::new(self)
+    }
+
+    /// Returns all the structure elements in this [PdfStructTree], in depth-first order,
+    /// that refer to the given marked content ID.
+    ///
+    /// Marked content IDs link structure elements to the runs of marked content on the page.
+    pub fn find_by_marked_content_id(&self, mcid: i32) -> Vec<PdfStructElement<'_>> {
+        let mut result = Vec::new();
+
+        let mut pending = self.iter().collect::<Vec<_>>();
+
+        pending.reverse();
+
+        while let Some(element) = pending.pop() {
+            let mut children = element.iter_children().collect::<Vec<_>>();
+
+            children.reverse();
+
+            if element.marked_content_ids().contains(&mcid) {
+                result.push(element);
+            }
+
+            pending.append(&mut children);
+        }
+
+        result
+    }
+}
+
+impl<'a> Drop for PdfStructTree<'a> {
+    /// Closes this [PdfStructTree], releasing held memory.
+    #[inline]
+    fn drop(&mut self) {
+        self.bindings.FPDF_StructTree_Close(self.handle);
+    }
+}
+
+/// An iterator over the top-level [PdfStructElement] objects in a [PdfStructTree].
+pub struct PdfStructTreeIterator<'a> {
+    tree: &'a PdfStructTree<'a>,
+    next_index: PdfStructElementIndex,
+}
+
+impl<'a> PdfStructTreeIterator<'a> {
+    #[inline]
+    pub(crate) fn new(tree: &'a PdfStructTree<'a>) -> Self {
+        PdfStructTreeIterator {
+            tree,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfStructTreeIterator<'a> {
+    type Item = PdfStructElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_index < self.tree.len() {
+            let next = self.tree.get(self.next_index);
+
+            self.next_index += 1;
+
+            if let Ok(next) = next {
+                return Some(next);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_struct_tree_for_tagged_document() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/form-test.pdf", None)?;
+
+        let structure = document.structure_tree();
+
+        assert!(structure.is_tagged());
+
+        let tree = structure.get(0)?;
+
+        assert!(!tree.is_empty());
+
+        for element in tree.iter() {
+            assert!(element.element_type().is_some());
+            assert!(element.parent().is_none());
+
+            for child in element.iter_children() {
+                assert!(child.parent().is_some());
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_struct_tree_for_untagged_document() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.load_pdf_from_file("./test/text-test.pdf", None)?;
+
+        assert!(!document.structure_tree().is_tagged());
+        assert!(matches!(
+            document.pages().get(0)?.structure_tree(),
+            Err(PdfiumError::DocumentNotTagged)
+        ));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,361 @@
+//! Defines the [PdfStructElementAttributes] struct, a collection of all the
+//! [PdfStructElementAttribute] objects attached to a single `PdfStructElement`.
+
+use crate::bindgen::{
+    FPDF_OBJECT_ARRAY, FPDF_OBJECT_BOOLEAN, FPDF_OBJECT_NAME, FPDF_OBJECT_NUMBER,
+    FPDF_OBJECT_STRING, FPDF_STRUCTELEMENT, FPDF_STRUCTELEMENT_ATTR, FPDF_STRUCTELEMENT_ATTR_VALUE,
+};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::utils::mem::create_byte_buffer;
+use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use std::os::raw::{c_int, c_ulong, c_void};
+
+#[cfg(doc)]
+use crate::pdf::document::page::struct_tree::element::PdfStructElement;
+
+/// The zero-based index of a single [PdfStructElementAttribute] inside its containing
+/// [PdfStructElementAttributes] collection.
+pub type PdfStructElementAttributeIndex = usize;
+
+/// The value of a single named entry in a [PdfStructElementAttribute].
+#[derive(Debug, Clone, PartialEq)]
+pub enum PdfStructElementAttributeValue {
+    Boolean(bool),
+    Number(f32),
+    String(String),
+    Name(String),
+    Array(Vec<PdfStructElementAttributeValue>),
+
+    /// The value is of a type that Pdfium does not support retrieving, such as a dictionary.
+    Unsupported,
+}
+
+impl PdfStructElementAttributeValue {
+    pub(crate) fn from_pdfium(
+        handle: FPDF_STRUCTELEMENT_ATTR_VALUE,
+        bindings: &dyn PdfiumLibraryBindings,
+    ) -> Self {
+        match bindings.FPDF_StructElement_Attr_GetType(handle) as u32 {
+            FPDF_OBJECT_BOOLEAN => {
+                let mut value = bindings.FALSE();
+
+                if bindings
+                    .is_true(bindings.FPDF_StructElement_Attr_GetBooleanValue(handle, &mut value))
+                {
+                    PdfStructElementAttributeValue::Boolean(bindings.is_true(value))
+                } else {
+                    PdfStructElementAttributeValue::Unsupported
+                }
+            }
+            FPDF_OBJECT_NUMBER => {
+                let mut value = 0.0;
+
+                if bindings
+                    .is_true(bindings.FPDF_StructElement_Attr_GetNumberValue(handle, &mut value))
+                {
+                    PdfStructElementAttributeValue::Number(value)
+                } else {
+                    PdfStructElementAttributeValue::Unsupported
+                }
+            }
+            object_type @ (FPDF_OBJECT_STRING | FPDF_OBJECT_NAME) => {
+                // Retrieving the string value from Pdfium is a two-step operation. First, we call
+                // FPDF_StructElement_Attr_GetStringValue() with a null buffer; this will retrieve
+                // the length of the value in bytes. We then reserve a byte buffer of the given
+                // length and call the function again to write the value to the buffer
+                // in UTF16-LE format.
+
+                let mut buffer_length: c_ulong = 0;
+
+                if !bindings.is_true(bindings.FPDF_StructElement_Attr_GetStringValue(
+                    handle,
+                    std::ptr::null_mut(),
+                    0,
+                    &mut buffer_length,
+                )) {
+                    return PdfStructElementAttributeValue::Unsupported;
+                }
+
+                let mut buffer = create_byte_buffer(buffer_length as usize);
+
+                bindings.FPDF_StructElement_Attr_GetStringValue(
+                    handle,
+                    buffer.as_mut_ptr() as *mut c_void,
+                    buffer_length,
+                    &mut buffer_length,
+                );
+
+                let value = get_string_from_pdfium_utf16le_bytes(buffer).unwrap_or_default();
+
+                if object_type == FPDF_OBJECT_NAME {
+                    PdfStructElementAttributeValue::Name(value)
+                } else {
+                    PdfStructElementAttributeValue::String(value)
+                }
+            }
+            FPDF_OBJECT_ARRAY => PdfStructElementAttributeValue::Array(
+                (0..bindings
+                    .FPDF_StructElement_Attr_CountChildren(handle)
+                    .max(0))
+                    .map(|index| bindings.FPDF_StructElement_Attr_GetChildAtIndex(handle, index))
+                    .filter(|child| !child.is_null())
+                    .map(|child| PdfStructElementAttributeValue::from_pdfium(child, bindings))
+                    .collect(),
+            ),
+            _ => PdfStructElementAttributeValue::Unsupported,
+        }
+    }
+}
+
+/// A single attribute object attached to a [PdfStructElement].
+///
+/// An attribute object is a map of named values. Attribute objects are typically grouped
+/// by the application or standard that owns them; for instance, the `Layout` owner defines
+/// attributes controlling the visual placement of content, such as `Placement` and `BBox`.
+pub struct PdfStructElementAttribute<'a> {
+    handle: FPDF_STRUCTELEMENT_ATTR,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfStructElementAttribute<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        handle: FPDF_STRUCTELEMENT_ATTR,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfStructElementAttribute { handle, bindings }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfStructElementAttribute].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the number of named values in this [PdfStructElementAttribute].
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bindings
+            .FPDF_StructElement_Attr_GetCount(self.handle)
+            .max(0) as usize
+    }
+
+    /// Returns `true` if this [PdfStructElementAttribute] contains no named values.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the name of the value at the given index in this [PdfStructElementAttribute].
+    pub fn name(&self, index: usize) -> Option<String> {
+        let mut buffer_length: c_ulong = 0;
+
+        if !self
+            .bindings
+            .is_true(self.bindings.FPDF_StructElement_Attr_GetName(
+                self.handle,
+                index as c_int,
+                std::ptr::null_mut(),
+                0,
+                &mut buffer_length,
+            ))
+            || buffer_length == 0
+        {
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        if !self
+            .bindings
+            .is_true(self.bindings.FPDF_StructElement_Attr_GetName(
+                self.handle,
+                index as c_int,
+                buffer.as_mut_ptr() as *mut c_void,
+                buffer_length,
+                &mut buffer_length,
+            ))
+        {
+            return None;
+        }
+
+        // The name is returned as a null-terminated byte string.
+
+        if let Some(position) = buffer.iter().position(|byte| *byte == 0) {
+            buffer.truncate(position);
+        }
+
+        String::from_utf8(buffer).ok()
+    }
+
+    /// Returns the value with the given name in this [PdfStructElementAttribute], if any.
+    pub fn get(&self, name: &str) -> Option<PdfStructElementAttributeValue> {
+        let handle = self
+            .bindings
+            .FPDF_StructElement_Attr_GetValue(self.handle, name);
+
+        if handle.is_null() {
+            None
+        } else {
+            Some(PdfStructElementAttributeValue::from_pdfium(
+                handle,
+                self.bindings,
+            ))
+        }
+    }
+
+    /// Returns an iterator over all the named values in this [PdfStructElementAttribute].
+    #[inline]
+    pub fn iter(&self) -> PdfStructElementAttributeIterator<'_> {
+        PdfStructElementAttributeIterator::new(self)
+    }
+}
+
+/// An iterator over all the named values in a [PdfStructElementAttribute].
+pub struct PdfStructElementAttributeIterator<'a> {
+    attribute: &'a PdfStructElementAttribute<'a>,
+    next_index: usize,
+}
+
+impl<'a> PdfStructElementAttributeIterator<'a> {
+    #[inline]
+    pub(crate) fn new(attribute: &'a PdfStructElementAttribute<'a>) -> Self {
+        PdfStructElementAttributeIterator {
+            attribute,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfStructElementAttributeIterator<'a> {
+    type Item = (String, PdfStructElementAttributeValue);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_index < self.attribute.len() {
+            let name = self.attribute.name(self.next_index);
+
+            self.next_index += 1;
+
+            if let Some(name) = name {
+                if let Some(value) = self.attribute.get(&name) {
+                    return Some((name, value));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// The collection of [PdfStructElementAttribute] objects attached to a single [PdfStructElement].
+pub struct PdfStructElementAttributes<'a> {
+    element_handle: FPDF_STRUCTELEMENT,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfStructElementAttributes<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        element_handle: FPDF_STRUCTELEMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfStructElementAttributes {
+            element_handle,
+            bindings,
+        }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfStructElementAttributes] collection.
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the number of attribute objects in this [PdfStructElementAttributes] collection.
+    #[inline]
+    pub fn len(&self) -> PdfStructElementAttributeIndex {
+        self.bindings
+            .FPDF_StructElement_GetAttributeCount(self.element_handle)
+            .max(0) as PdfStructElementAttributeIndex
+    }
+
+    /// Returns `true` if this [PdfStructElementAttributes] collection is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a single [PdfStructElementAttribute] from this [PdfStructElementAttributes] collection.
+    pub fn get(
+        &self,
+        index: PdfStructElementAttributeIndex,
+    ) -> Result<PdfStructElementAttribute<'a>, PdfiumError> {
+        if index >= self.len() {
+            return Err(PdfiumError::StructElementAttributeIndexOutOfBounds);
+        }
+
+        let handle = self
+            .bindings
+            .FPDF_StructElement_GetAttributeAtIndex(self.element_handle, index as c_int);
+
+        if handle.is_null() {
+            // Pdfium returns NULL for attribute objects that are not dictionaries.
+
+            Err(PdfiumError::StructElementAttributeIndexOutOfBounds)
+        } else {
+            Ok(PdfStructElementAttribute::from_pdfium(
+                handle,
+                self.bindings,
+            ))
+        }
+    }
+
+    /// Returns the value with the given name from the first attribute object in this
+    /// [PdfStructElementAttributes] collection that defines it, if any.
+    pub fn find(&self, name: &str) -> Option<PdfStructElementAttributeValue> {
+        self.iter().find_map(|attribute| attribute.get(name))
+    }
+
+    /// Returns an iterator over all the attribute objects in this
+    /// [PdfStructElementAttributes] collection.
+    #[inline]
+    pub fn iter(&self) -> PdfStructElementAttributesIterator<'_> {
+        PdfStructElementAttributesIterator::new(self)
+    }
+}
+
+/// An iterator over all the [PdfStructElementAttribute] objects in a
+/// [PdfStructElementAttributes] collection.
+pub struct PdfStructElementAttributesIterator<'a> {
+    attributes: &'a PdfStructElementAttributes<'a>,
+    next_index: PdfStructElementAttributeIndex,
+}
+
+impl<'a> PdfStructElementAttributesIterator<'a> {
+    #[inline]
+    pub(crate) fn new(attributes: &'a PdfStructElementAttributes<'a>) -> Self {
+        PdfStructElementAttributesIterator {
+            attributes,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfStructElementAttributesIterator<'a> {
+    type Item = PdfStructElementAttribute<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.next_index < self.attributes.len() {
+            let next = self.attributes.get(self.next_index);
+
+            self.next_index += 1;
+
+            if let Ok(next) = next {
+                return Some(next);
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,310 @@
+//! Defines the [PdfStructElement] struct, exposing functionality related to a single
+//! structure element in a `PdfStructTree`.
+
+use crate::bindgen::FPDF_STRUCTELEMENT;
+use crate::bindings::PdfiumLibraryBindings;
+use crate::pdf::document::page::struct_tree::PdfStructElementIndex;
+use crate::utils::mem::create_byte_buffer;
+use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use std::os::raw::{c_int, c_ulong, c_void};
+
+#[cfg(any(
+    feature = "pdfium_future",
+    feature = "pdfium_7543",
+    feature = "pdfium_7350",
+    feature = "pdfium_7215",
+    feature = "pdfium_7123",
+    feature = "pdfium_6996",
+    feature = "pdfium_6721",
+    feature = "pdfium_6666",
+    feature = "pdfium_6611",
+    feature = "pdfium_6569",
+    feature = "pdfium_6555",
+    feature = "pdfium_6490",
+))]
+use crate::pdf::document::page::struct_tree::attributes::PdfStructElementAttributes;
+
+#[cfg(doc)]
+use crate::pdf::document::page::struct_tree::PdfStructTree;
+
+/// A single structure element in a [PdfStructTree].
+///
+/// Each structure element has a structure type - for instance, `P` for a paragraph, `H1` for
+/// a first-level heading, or `Figure` for an illustration - along with optional descriptive
+/// properties such as alternate text and a language identifier. Structure elements can contain
+/// other structure elements, forming a tree, and can refer to runs of marked content on the page
+/// using marked content IDs.
+#[derive(Copy, Clone)]
+pub struct PdfStructElement<'a> {
+    handle: FPDF_STRUCTELEMENT,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfStructElement<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        handle: FPDF_STRUCTELEMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfStructElement { handle, bindings }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfStructElement].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the structure type of this [PdfStructElement], as given by its `/S` entry.
+    /// Common structure types include `Document`, `P`, `H1` through `H6`, `Figure`, `Table`,
+    /// and `Span`, although documents may define their own custom structure types.
+    #[inline]
+    pub fn element_type(&self) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings
+                .FPDF_StructElement_GetType(self.handle, buffer, buffer_length)
+        })
+    }
+
+    /// Returns the object type of this [PdfStructElement], as given by its `/Type` entry.
+    /// This is usually `StructElem`, if it is present at all.
+    #[inline]
+    pub fn object_type(&self) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings
+                .FPDF_StructElement_GetObjType(self.handle, buffer, buffer_length)
+        })
+    }
+
+    /// Returns the title of this [PdfStructElement], as given by its `/T` entry, if any.
+    #[inline]
+    pub fn title(&self) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings
+                .FPDF_StructElement_GetTitle(self.handle, buffer, buffer_length)
+        })
+    }
+
+    /// Returns the alternate description of this [PdfStructElement], as given by its
+    /// `/Alt` entry, if any. Alternate descriptions are most commonly used to describe
+    /// the content of figures to users who cannot see them.
+    #[inline]
+    pub fn alt_text(&self) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings
+                .FPDF_StructElement_GetAltText(self.handle, buffer, buffer_length)
+        })
+    }
+
+    /// Returns the replacement text of this [PdfStructElement], as given by its
+    /// `/ActualText` entry, if any. The replacement text should be used in place of
+    /// the element's content when extracting text, for instance for hyphenated words
+    /// or for text drawn as an image.
+    #[inline]
+    pub fn actual_text(&self) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings
+                .FPDF_StructElement_GetActualText(self.handle, buffer, buffer_length)
+        })
+    }
+
+    /// Returns the natural language of this [PdfStructElement], as given by its
+    /// `/Lang` entry, if any. The language is expressed as a language identifier
+    /// such as `en-US`.
+    #[inline]
+    pub fn language(&self) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings
+                .FPDF_StructElement_GetLang(self.handle, buffer, buffer_length)
+        })
+    }
+
+    /// Returns the unique identifier of this [PdfStructElement], as given by its
+    /// `/ID` entry, if any.
+    #[inline]
+    pub fn id(&self) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings
+                .FPDF_StructElement_GetID(self.handle, buffer, buffer_length)
+        })
+    }
+
+    /// Returns the value of the string or name attribute with the given name in this
+    /// [PdfStructElement]'s `/A` attributes dictionary, if any.
+    #[inline]
+    pub fn string_attribute(&self, name: &str) -> Option<String> {
+        self.get_string_value(|buffer, buffer_length| {
+            self.bindings.FPDF_StructElement_GetStringAttribute(
+                self.handle,
+                name,
+                buffer,
+                buffer_length,
+            )
+        })
+    }
+
+    /// Returns the first marked content ID referred to by this [PdfStructElement], if any.
+    ///
+    /// Use the [PdfStructElement::marked_content_ids()] function to retrieve all the
+    /// marked content IDs referred to by this element.
+    #[inline]
+    pub fn marked_content_id(&self) -> Option<i32> {
+        match self
+            .bindings
+            .FPDF_StructElement_GetMarkedContentID(self.handle)
+        {
+            -1 => None,
+            mcid => Some(mcid),
+        }
+    }
+
+    /// Returns all the marked content IDs referred to by this [PdfStructElement].
+    ///
+    /// Each marked content ID identifies a run of marked content on the page that
+    /// belongs to this element. The returned list does not include the marked content IDs
+    /// of any child structure elements.
+    pub fn marked_content_ids(&self) -> Vec<i32> {
+        let count = self
+            .bindings
+            .FPDF_StructElement_GetMarkedContentIdCount(self.handle);
+
+        (0..count.max(0))
+            .map(|index| {
+                self.bindings
+                    .FPDF_StructElement_GetMarkedContentIdAtIndex(self.handle, index)
+            })
+            .filter(|mcid| *mcid >= 0)
+            .collect()
+    }
+
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+    ))]
+    /// Returns the collection of attribute objects attached to this [PdfStructElement].
+    #[inline]
+    pub fn attributes(&self) -> PdfStructElementAttributes<'a> {
+        PdfStructElementAttributes::from_pdfium(self.handle, self.bindings)
+    }
+
+    /// Returns the parent of this [PdfStructElement], if any. Top-level elements in
+    /// a [PdfStructTree] do not have a parent.
+    pub fn parent(&self) -> Option<PdfStructElement<'a>> {
+        let handle = self.bindings.FPDF_StructElement_GetParent(self.handle);
+
+        if handle.is_null() {
+            None
+        } else {
+            Some(PdfStructElement::from_pdfium(handle, self.bindings))
+        }
+    }
+
+    /// Returns the number of direct children of this [PdfStructElement].
+    ///
+    /// The count includes children that are references to marked content rather than
+    /// structure elements. Such children are skipped by [PdfStructElement::iter_children()].
+    #[inline]
+    pub fn children_len(&self) -> PdfStructElementIndex {
+        self.bindings
+            .FPDF_StructElement_CountChildren(self.handle)
+            .max(0) as PdfStructElementIndex
+    }
+
+    /// Returns the direct child of this [PdfStructElement] at the given index,
+    /// if that child is a structure element.
+    pub fn get_child(&self, index: PdfStructElementIndex) -> Option<PdfStructElement<'a>> {
+        if index >= self.children_len() {
+            return None;
+        }
+
+        let handle = self
+            .bindings
+            .FPDF_StructElement_GetChildAtIndex(self.handle, index as c_int);
+
+        if handle.is_null() {
+            None
+        } else {
+            Some(PdfStructElement::from_pdfium(handle, self.bindings))
+        }
+    }
+
+    /// Returns an iterator over the direct children of this [PdfStructElement] that are
+    /// themselves structure elements.
+    #[inline]
+    pub fn iter_children(&self) -> PdfStructElementChildrenIterator<'a> {
+        PdfStructElementChildrenIterator::new(*self)
+    }
+
+    /// Retrieves a UTF-16LE string value from Pdfium using the given retrieval function.
+    fn get_string_value(&self, f: impl Fn(*mut c_void, c_ulong) -> c_ulong) -> Option<String> {
+        // Retrieving a string value from Pdfium is a two-step operation. First, we call
+        // the retrieval function with a null buffer; this will retrieve the length of
+        // the value in bytes. If the length is zero, then the value is not defined.
+
+        // If the length is non-zero, then we reserve a byte buffer of the given
+        // length and call the retrieval function again with a pointer to the buffer;
+        // this will write the value to the buffer in UTF16-LE format.
+
+        let buffer_length = f(std::ptr::null_mut(), 0);
+
+        if buffer_length == 0 {
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        let result = f(buffer.as_mut_ptr() as *mut c_void, buffer_length);
+
+        assert_eq!(result, buffer_length);
+
+        get_string_from_pdfium_utf16le_bytes(buffer).filter(|value| !value.is_empty())
+    }
+}
+
+/// An iterator over the direct children of a [PdfStructElement] that are
+/// themselves structure elements.
+pub struct PdfStructElementChildrenIterator<'a> {
+    parent: PdfStructElement<'a>,
+    next_index: PdfStructElementIndex,
+}
+
+impl<'a> PdfStructElementChildrenIterator<'a> {
+    #[inline]
+    pub(crate) fn new(parent: PdfStructElement<'a>) -> Self {
+        PdfStructElementChildrenIterator {
+            parent,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfStructElementChildrenIterator<'a> {
+    type Item = PdfStructElement<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Children that refer to marked content or to other objects rather than to
+        // structure elements are skipped.
+
+        while self.next_index < self.parent.children_len() {
+            let next = self.parent.get_child(self.next_index);
+
+            self.next_index += 1;
+
+            if next.is_some() {
+                return next;
+            }
+        }
+
+        None
+    }
+}
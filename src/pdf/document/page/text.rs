@@ -6,10 +6,11 @@ pub mod chars;
 pub mod search;
 pub mod segment;
 pub mod segments;
+pub mod web_links;
 
 use crate::bindgen::{FPDF_TEXTPAGE, FPDF_WCHAR, FPDF_WIDESTRING};
 use crate::bindings::PdfiumLibraryBindings;
-use crate::error::PdfiumError;
+use crate::error::{PdfiumError, PdfiumInternalError};
 use crate::pdf::document::page::annotation::PdfPageAnnotation;
 use crate::pdf::document::page::annotation::PdfPageAnnotationCommon;
 use crate::pdf::document::page::object::private::internal::PdfPageObjectPrivate;
@@ -17,6 +18,7 @@ use crate::pdf::document::page::object::text::PdfPageTextObject;
 use crate::pdf::document::page::text::chars::{PdfPageTextCharIndex, PdfPageTextChars};
 use crate::pdf::document::page::text::search::{PdfPageTextSearch, PdfSearchOptions};
 use crate::pdf::document::page::text::segments::PdfPageTextSegments;
+use crate::pdf::document::page::text::web_links::PdfPageWebLinks;
 use crate::pdf::document::page::PdfPage;
 use crate::pdf::points::PdfPoints;
 use crate::pdf::rect::PdfRect;
@@ -41,6 +43,9 @@ use std::ptr::null_mut;
 /// and the order in which they appear visually during rendering (and thus the order in
 /// which they are read by a user) may not necessarily match.
 ///
+/// Use the [PdfPageText::web_links()] function to retrieve the URLs that appear as plain text
+/// in the character collection.
+///
 /// [PdfPageText] implements both the [ToString] and the [Display] traits.
 pub struct PdfPageText<'a> {
     text_page_handle: FPDF_TEXTPAGE,
@@ -399,6 +404,23 @@ impl<'a> PdfPageText<'a> {
             ))
         }
     }
+
+    /// Returns the collection of web links detected in the characters of the containing
+    /// [PdfPage]. Only URLs and email addresses written as plain text are detected;
+    /// use the `PdfPage::links()` function to retrieve the link annotations on the page.
+    pub fn web_links(&self) -> Result<PdfPageWebLinks<'_>, PdfiumError> {
+        let handle = self
+            .bindings()
+            .FPDFLink_LoadWebLinks(self.text_page_handle());
+
+        if handle.is_null() {
+            Err(PdfiumError::PdfiumLibraryInternalError(
+                PdfiumInternalError::Unknown,
+            ))
+        } else {
+            Ok(PdfPageWebLinks::from_pdfium(handle, self, self.bindings()))
+        }
+    }
 }
 
 impl<'a> Display for PdfPageText<'a> {
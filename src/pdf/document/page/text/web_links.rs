@@ -0,0 +1,359 @@
+//! Defines the [PdfPageWebLinks] struct, exposing functionality related to the web links
+//! detected in the text of a single `PdfPage`.
+
+use crate::bindgen::{FPDF_PAGELINK, FS_RECTF};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::page::text::chars::{PdfPageTextCharIndex, PdfPageTextChars};
+use crate::pdf::document::page::text::PdfPageText;
+use crate::pdf::rect::PdfRect;
+use crate::utils::mem::create_sized_buffer;
+use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
+use bytemuck::cast_slice;
+use std::os::raw::{c_double, c_int};
+use std::ptr::null_mut;
+
+#[cfg(doc)]
+use crate::pdf::document::page::{annotation::link::PdfPageLinkAnnotation, PdfPage};
+
+/// The zero-based index of a single [PdfPageWebLink] inside its containing [PdfPageWebLinks] collection.
+pub type PdfPageWebLinkIndex = usize;
+
+/// The collection of web links detected in the text of a single [PdfPage].
+///
+/// Pdfium detects web links by scanning the page's text for character sequences that look
+/// like URLs or email addresses. Unlike the [PdfPageLinkAnnotation] objects returned by
+/// the `PdfPage::links()` function, web links are not part of the document's structure;
+/// they exist only as plain text, and clicking them in a viewer typically has no effect.
+pub struct PdfPageWebLinks<'a> {
+    handle: FPDF_PAGELINK,
+    text: &'a PdfPageText<'a>,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfPageWebLinks<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        handle: FPDF_PAGELINK,
+        text: &'a PdfPageText<'a>,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfPageWebLinks {
+            handle,
+            text,
+            bindings,
+        }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfPageWebLinks] collection.
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the number of web links in this [PdfPageWebLinks] collection.
+    #[inline]
+    pub fn len(&self) -> PdfPageWebLinkIndex {
+        self.bindings.FPDFLink_CountWebLinks(self.handle).max(0) as PdfPageWebLinkIndex
+    }
+
+    /// Returns `true` if this [PdfPageWebLinks] collection is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a single [PdfPageWebLink] from this [PdfPageWebLinks] collection.
+    pub fn get(&self, index: PdfPageWebLinkIndex) -> Result<PdfPageWebLink<'_>, PdfiumError> {
+        if index >= self.len() {
+            return Err(PdfiumError::PageWebLinkIndexOutOfBounds);
+        }
+
+        Ok(PdfPageWebLink::from_pdfium(
+            self.handle,
+            index,
+            self.text,
+            self.bindings,
+        ))
+    }
+
+    /// Returns an iterator over all the web links in this [PdfPageWebLinks] collection.
+    #[inline]
+    pub fn iter(&self) -> PdfPageWebLinksIterator<'_> {
+        PdfPageWebLinksIterator::new(self)
+    }
+}
+
+impl<'a> Drop for PdfPageWebLinks<'a> {
+    /// Closes this [PdfPageWebLinks] collection, releasing held memory.
+    #[inline]
+    fn drop(&mut self) {
+        self.bindings.FPDFLink_CloseWebLinks(self.handle);
+    }
+}
+
+/// A single web link detected in the text of a [PdfPage].
+pub struct PdfPageWebLink<'a> {
+    handle: FPDF_PAGELINK,
+    index: PdfPageWebLinkIndex,
+    text: &'a PdfPageText<'a>,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfPageWebLink<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        handle: FPDF_PAGELINK,
+        index: PdfPageWebLinkIndex,
+        text: &'a PdfPageText<'a>,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfPageWebLink {
+            handle,
+            index,
+            text,
+            bindings,
+        }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfPageWebLink].
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns the index of this [PdfPageWebLink] in its containing [PdfPageWebLinks] collection.
+    #[inline]
+    pub fn index(&self) -> PdfPageWebLinkIndex {
+        self.index
+    }
+
+    /// Returns the URL of this [PdfPageWebLink]. Pdfium normalizes detected links, so the
+    /// returned URL may differ slightly from the text on the page; for instance, a scheme
+    /// may be added to a link beginning with `www.`, and a detected email address is returned
+    /// with a leading `mailto:`.
+    pub fn url(&self) -> String {
+        // Retrieving the URL from Pdfium is a two-step operation. First, we call
+        // FPDFLink_GetURL() with a null buffer; this will retrieve the length of
+        // the URL in UTF-16 code units, including a trailing terminator. If the length is zero,
+        // then there is no URL.
+
+        // If the length is non-zero, then we reserve a buffer of the given length and call
+        // FPDFLink_GetURL() again with a pointer to the buffer; this will write the URL
+        // into the buffer.
+
+        let buffer_length =
+            self.bindings
+                .FPDFLink_GetURL(self.handle, self.index as c_int, null_mut(), 0);
+
+        if buffer_length <= 0 {
+            // No URL is available.
+
+            return String::new();
+        }
+
+        let mut buffer = create_sized_buffer(buffer_length as usize);
+
+        let result = self.bindings.FPDFLink_GetURL(
+            self.handle,
+            self.index as c_int,
+            buffer.as_mut_ptr(),
+            buffer_length,
+        );
+
+        assert_eq!(result, buffer_length);
+
+        get_string_from_pdfium_utf16le_bytes(cast_slice(buffer.as_slice()).to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Returns the index of the first character of this [PdfPageWebLink] in the
+    /// containing [PdfPageText].
+    #[inline]
+    pub fn start_char_index(&self) -> Result<PdfPageTextCharIndex, PdfiumError> {
+        self.text_range().map(|(start, _)| start)
+    }
+
+    /// Returns the number of characters occupied by this [PdfPageWebLink] in the
+    /// containing [PdfPageText].
+    #[inline]
+    pub fn char_count(&self) -> Result<PdfPageTextCharIndex, PdfiumError> {
+        self.text_range().map(|(_, count)| count)
+    }
+
+    /// Returns the `PdfPageTextChar` characters occupied by this [PdfPageWebLink].
+    pub fn chars(&self) -> Result<PdfPageTextChars<'_>, PdfiumError> {
+        let (start, count) = self.text_range()?;
+
+        Ok(PdfPageTextChars::new(
+            self.text.page.document_handle(),
+            self.text.page.page_handle(),
+            self.text.text_page_handle(),
+            (start as i32..(start + count) as i32).collect(),
+            self.bindings,
+        ))
+    }
+
+    /// Returns the text occupied by this [PdfPageWebLink], exactly as it appears in the
+    /// containing [PdfPageText].
+    pub fn text(&self) -> Result<String, PdfiumError> {
+        let (start, count) = self.text_range()?;
+
+        if count == 0 {
+            return Ok(String::new());
+        }
+
+        // The buffer must be large enough to hold the text and a trailing terminator.
+
+        let mut buffer = create_sized_buffer(count + 1);
+
+        let result = self.bindings.FPDFText_GetText(
+            self.text.text_page_handle(),
+            start as c_int,
+            count as c_int,
+            buffer.as_mut_ptr(),
+        );
+
+        if result <= 0 {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        buffer.truncate(result as usize);
+
+        Ok(
+            get_string_from_pdfium_utf16le_bytes(cast_slice(buffer.as_slice()).to_vec())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Returns the bounding rectangles of the text occupied by this [PdfPageWebLink].
+    /// A link that wraps across several lines of text has one rectangle per line.
+    pub fn rects(&self) -> Vec<PdfRect> {
+        let count = self
+            .bindings
+            .FPDFLink_CountRects(self.handle, self.index as c_int);
+
+        (0..count)
+            .filter_map(|rect_index| {
+                let mut left: c_double = 0.0;
+                let mut top: c_double = 0.0;
+                let mut right: c_double = 0.0;
+                let mut bottom: c_double = 0.0;
+
+                let result = self.bindings.FPDFLink_GetRect(
+                    self.handle,
+                    self.index as c_int,
+                    rect_index,
+                    &mut left,
+                    &mut top,
+                    &mut right,
+                    &mut bottom,
+                );
+
+                PdfRect::from_pdfium_as_result(
+                    result,
+                    FS_RECTF {
+                        left: left as f32,
+                        top: top as f32,
+                        right: right as f32,
+                        bottom: bottom as f32,
+                    },
+                    self.bindings,
+                )
+                .ok()
+            })
+            .collect()
+    }
+
+    /// Returns the start character index and character count of this [PdfPageWebLink].
+    fn text_range(&self) -> Result<(PdfPageTextCharIndex, PdfPageTextCharIndex), PdfiumError> {
+        let mut start: c_int = 0;
+        let mut count: c_int = 0;
+
+        if self.bindings.is_true(self.bindings.FPDFLink_GetTextRange(
+            self.handle,
+            self.index as c_int,
+            &mut start,
+            &mut count,
+        )) {
+            Ok((
+                start.max(0) as PdfPageTextCharIndex,
+                count.max(0) as PdfPageTextCharIndex,
+            ))
+        } else {
+            Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure)
+        }
+    }
+}
+
+/// An iterator over all the [PdfPageWebLink] objects in a [PdfPageWebLinks] collection.
+pub struct PdfPageWebLinksIterator<'a> {
+    links: &'a PdfPageWebLinks<'a>,
+    next_index: PdfPageWebLinkIndex,
+}
+
+impl<'a> PdfPageWebLinksIterator<'a> {
+    #[inline]
+    pub(crate) fn new(links: &'a PdfPageWebLinks<'a>) -> Self {
+        PdfPageWebLinksIterator {
+            links,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfPageWebLinksIterator<'a> {
+    type Item = PdfPageWebLink<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.links.get(self.next_index);
+
+        self.next_index += 1;
+
+        next.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_web_links() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        let mut page = document
+            .pages_mut()
+            .create_page_at_start(PdfPagePaperSize::a4())?;
+
+        let font = document.fonts_mut().helvetica();
+
+        page.objects_mut().create_text_object(
+            PdfPoints::new(100.0),
+            PdfPoints::new(500.0),
+            "Terms are published at https://www.example.com/terms today.",
+            font,
+            PdfPoints::new(12.0),
+        )?;
+
+        let text = page.text()?;
+
+        let links = text.web_links()?;
+
+        assert_eq!(links.len(), 1);
+
+        let link = links.get(0)?;
+
+        assert_eq!(link.url(), "https://www.example.com/terms");
+        assert_eq!(link.text()?, "https://www.example.com/terms");
+        assert_eq!(link.char_count()?, link.chars()?.len());
+        assert!(!link.rects().is_empty());
+
+        assert!(links.get(1).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,451 @@
+//! Defines the [PdfPageLabelRange] struct, describing how the labels of a range of pages
+//! in a `PdfDocument` are generated.
+
+use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::{encode_text_string, PdfIncrementalUpdate};
+use crate::pdf::document::pages::PdfPageIndex;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+#[cfg(doc)]
+use crate::pdf::document::pages::PdfPages;
+
+/// The numbering style used for the numeric portion of each page label in a
+/// [PdfPageLabelRange].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PdfPageLabelStyle {
+    /// Page labels have no numeric portion; each label consists of the range's prefix alone.
+    None,
+
+    /// Decimal arabic numerals: 1, 2, 3, ...
+    Decimal,
+
+    /// Uppercase roman numerals: I, II, III, ...
+    RomanUpper,
+
+    /// Lowercase roman numerals: i, ii, iii, ...
+    RomanLower,
+
+    /// Uppercase letters: A to Z for the first 26 pages, AA to ZZ for the next 26, and so on.
+    LettersUpper,
+
+    /// Lowercase letters: a to z for the first 26 pages, aa to zz for the next 26, and so on.
+    LettersLower,
+}
+
+impl PdfPageLabelStyle {
+    /// Returns the value of the `/S` entry in a page label dictionary for this style, if any.
+    #[inline]
+    fn as_pdf_name(&self) -> Option<&'static str> {
+        match self {
+            PdfPageLabelStyle::None => None,
+            PdfPageLabelStyle::Decimal => Some("/D"),
+            PdfPageLabelStyle::RomanUpper => Some("/R"),
+            PdfPageLabelStyle::RomanLower => Some("/r"),
+            PdfPageLabelStyle::LettersUpper => Some("/A"),
+            PdfPageLabelStyle::LettersLower => Some("/a"),
+        }
+    }
+
+    /// Formats the given page number in this style.
+    fn format(&self, number: u32) -> String {
+        match self {
+            PdfPageLabelStyle::None => String::new(),
+            PdfPageLabelStyle::Decimal => number.to_string(),
+            PdfPageLabelStyle::RomanUpper => to_roman(number),
+            PdfPageLabelStyle::RomanLower => to_roman(number).to_lowercase(),
+            PdfPageLabelStyle::LettersUpper => to_letters(number),
+            PdfPageLabelStyle::LettersLower => to_letters(number).to_lowercase(),
+        }
+    }
+}
+
+/// A range of consecutive pages in a `PdfDocument` whose labels share the same numbering style
+/// and prefix. A range begins at its first page and continues up to the first page of the
+/// next range, or to the end of the document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PdfPageLabelRange {
+    first_page: PdfPageIndex,
+    style: PdfPageLabelStyle,
+    prefix: Option<String>,
+    start: u32,
+}
+
+impl PdfPageLabelRange {
+    /// Creates a new [PdfPageLabelRange] beginning at the given page index, using the given
+    /// numbering style. Numbering starts at 1 and no prefix is applied.
+    #[inline]
+    pub fn new(first_page: PdfPageIndex, style: PdfPageLabelStyle) -> Self {
+        PdfPageLabelRange {
+            first_page,
+            style,
+            prefix: None,
+            start: 1,
+        }
+    }
+
+    /// Sets the prefix prepended to the numeric portion of each page label in this range.
+    #[inline]
+    pub fn set_prefix(mut self, prefix: &str) -> Self {
+        self.prefix = Some(prefix.to_string());
+
+        self
+    }
+
+    /// Sets the number used for the first page in this range. The number must be at least 1.
+    #[inline]
+    pub fn set_start(mut self, start: u32) -> Self {
+        self.start = start;
+
+        self
+    }
+
+    /// Returns the index of the first page in this range.
+    #[inline]
+    pub fn first_page(&self) -> PdfPageIndex {
+        self.first_page
+    }
+
+    /// Returns the numbering style used for page labels in this range.
+    #[inline]
+    pub fn style(&self) -> PdfPageLabelStyle {
+        self.style
+    }
+
+    /// Returns the prefix prepended to each page label in this range, if any.
+    #[inline]
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Returns the number used for the first page in this range.
+    #[inline]
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the label of the page at the given index, which must be within this range.
+    fn label(&self, index: PdfPageIndex) -> String {
+        let number = self.start + (index - self.first_page) as u32;
+
+        format!(
+            "{}{}",
+            self.prefix.as_deref().unwrap_or(""),
+            self.style.format(number)
+        )
+    }
+
+    /// Returns the page label dictionary for this range.
+    fn to_dictionary(&self) -> Vec<u8> {
+        let mut result = b"<<".to_vec();
+
+        if let Some(style) = self.style.as_pdf_name() {
+            result.extend_from_slice(b"/S ");
+            result.extend_from_slice(style.as_bytes());
+        }
+
+        if let Some(prefix) = self.prefix.as_ref() {
+            result.extend_from_slice(b"/P ");
+            result.extend_from_slice(&encode_text_string(prefix));
+        }
+
+        if self.start != 1 {
+            result.extend_from_slice(format!("/St {}", self.start).as_bytes());
+        }
+
+        result.extend_from_slice(b">>");
+
+        result
+    }
+}
+
+/// A handle to the page label ranges of a single document that are waiting to be written
+/// when the document is saved. Cloning the handle shares the same ranges, so the pages
+/// retrieved from the document see any ranges set after they were retrieved.
+#[derive(Clone, Default)]
+pub(crate) struct PdfPendingPageLabels {
+    ranges: Arc<Mutex<Option<Vec<PdfPageLabelRange>>>>,
+}
+
+impl PdfPendingPageLabels {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, Option<Vec<PdfPageLabelRange>>> {
+        // The ranges are never left partially updated, so they remain usable even if
+        // another thread panicked while holding the lock.
+
+        self.ranges.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Replaces the pending page label ranges with the given ranges.
+    #[inline]
+    pub(crate) fn set(&self, ranges: &[PdfPageLabelRange]) {
+        *self.lock() = Some(ranges.to_vec());
+    }
+
+    /// Returns the pending page label ranges, if any have been set.
+    #[inline]
+    pub(crate) fn get(&self) -> Option<Vec<PdfPageLabelRange>> {
+        self.lock().clone()
+    }
+
+    /// Returns the label of the page at the given index according to the pending page label
+    /// ranges. The outer `Option` is `None` if no ranges have been set, in which case the
+    /// label should be taken from the document itself.
+    pub(crate) fn label(&self, index: PdfPageIndex) -> Option<Option<String>> {
+        self.lock()
+            .as_ref()
+            .map(|ranges| get_page_label(ranges, index))
+    }
+
+    /// Updates the pending page label ranges after the given number of pages have been
+    /// inserted at the given index. Ranges beginning at or after the index move down past
+    /// the new pages, so the new pages join the range of the page before them. Pages
+    /// inserted at the start of the document join the first range.
+    pub(crate) fn insert_pages(&self, index: PdfPageIndex, count: PdfPageIndex) {
+        if let Some(ranges) = self.lock().as_mut() {
+            for range in ranges.iter_mut() {
+                if range.first_page >= index && range.first_page > 0 {
+                    range.first_page += count;
+                }
+            }
+        }
+    }
+
+    /// Updates the pending page label ranges after the given number of pages have been
+    /// deleted from the given index, leaving the document with the given number of pages.
+    /// Each remaining page keeps its label: a range beginning on a deleted page moves to the
+    /// first page after the deleted pages, and its numbering is advanced to match.
+    pub(crate) fn delete_pages(
+        &self,
+        index: PdfPageIndex,
+        count: PdfPageIndex,
+        page_count: PdfPageIndex,
+    ) {
+        if let Some(ranges) = self.lock().as_mut() {
+            let end = index + count;
+
+            for range in ranges.iter_mut() {
+                if range.first_page >= end {
+                    range.first_page -= count;
+                } else if range.first_page >= index {
+                    range.start += (end - range.first_page) as u32;
+                    range.first_page = index;
+                }
+            }
+
+            // Where several ranges now begin on the same page, the last of them covers it.
+
+            let mut position = 0;
+
+            while position + 1 < ranges.len() {
+                if ranges[position].first_page == ranges[position + 1].first_page {
+                    ranges.remove(position);
+                } else {
+                    position += 1;
+                }
+            }
+
+            ranges.retain(|range| range.first_page < page_count);
+        }
+    }
+}
+
+/// Checks that the given page label ranges can be applied to a document with the given
+/// number of pages.
+pub(crate) fn validate_page_label_ranges(
+    ranges: &[PdfPageLabelRange],
+    page_count: PdfPageIndex,
+) -> Result<(), PdfiumError> {
+    let is_valid = ranges.first().map_or(true, |range| range.first_page == 0)
+        && ranges
+            .windows(2)
+            .all(|pair| pair[0].first_page < pair[1].first_page)
+        && ranges
+            .iter()
+            .all(|range| range.first_page < page_count && range.start >= 1);
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(PdfiumError::InvalidPageLabelRanges)
+    }
+}
+
+/// Returns the label of the page at the given index according to the given page label ranges,
+/// if any.
+pub(crate) fn get_page_label(ranges: &[PdfPageLabelRange], index: PdfPageIndex) -> Option<String> {
+    let label = ranges
+        .iter()
+        .rev()
+        .find(|range| range.first_page <= index)?
+        .label(index);
+
+    if label.is_empty() {
+        None
+    } else {
+        Some(label)
+    }
+}
+
+/// Writes the given page label ranges to the `/PageLabels` number tree of the catalog
+/// of the given saved document, replacing any existing page labels. If no ranges are given,
+/// the `/PageLabels` entry is removed.
+pub(crate) fn write_page_labels(
+    update: &mut PdfIncrementalUpdate,
+    ranges: &[PdfPageLabelRange],
+) -> Result<(), PdfiumError> {
+    let root = update.root()?;
+
+    let mut catalog = update.get_dictionary(root)?;
+
+    if ranges.is_empty() {
+        catalog.remove("PageLabels");
+    } else {
+        // A number tree small enough to hold in a single node can be written as a root node
+        // containing a /Nums array, with no intermediate /Kids.
+
+        let mut tree = b"<</Nums [".to_vec();
+
+        for (index, range) in ranges.iter().enumerate() {
+            if index > 0 {
+                tree.push(b' ');
+            }
+
+            tree.extend_from_slice(format!("{} ", range.first_page).as_bytes());
+            tree.extend_from_slice(&range.to_dictionary());
+        }
+
+        tree.extend_from_slice(b"]>>");
+
+        catalog.set("PageLabels", tree);
+    }
+
+    update.set_object(root, catalog.to_bytes());
+
+    Ok(())
+}
+
+/// Formats the given number as uppercase roman numerals.
+fn to_roman(mut number: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+
+    let mut result = String::new();
+
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            result.push_str(numeral);
+
+            number -= value;
+        }
+    }
+
+    result
+}
+
+/// Formats the given number as uppercase letters: A to Z, then AA to ZZ, and so on.
+fn to_letters(number: u32) -> String {
+    if number == 0 {
+        return String::new();
+    }
+
+    let letter = (b'A' + ((number - 1) % 26) as u8) as char;
+
+    std::iter::repeat(letter)
+        .take(((number - 1) / 26 + 1) as usize)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_labels() -> Result<(), PdfiumError> {
+        let ranges = [
+            PdfPageLabelRange::new(0, PdfPageLabelStyle::RomanLower),
+            PdfPageLabelRange::new(4, PdfPageLabelStyle::Decimal)
+                .set_prefix("A-")
+                .set_start(9),
+            PdfPageLabelRange::new(6, PdfPageLabelStyle::LettersUpper).set_start(27),
+            PdfPageLabelRange::new(7, PdfPageLabelStyle::None).set_prefix("Cover"),
+        ];
+
+        validate_page_label_ranges(&ranges, 8)?;
+
+        assert_eq!(get_page_label(&ranges, 3), Some("iv".to_string()));
+        assert_eq!(get_page_label(&ranges, 5), Some("A-10".to_string()));
+        assert_eq!(get_page_label(&ranges, 6), Some("AA".to_string()));
+        assert_eq!(get_page_label(&ranges, 7), Some("Cover".to_string()));
+
+        assert_eq!(to_roman(1994), "MCMXCIV");
+        assert_eq!(ranges[1].to_dictionary(), b"<</S /D/P (A-)/St 9>>".to_vec());
+
+        assert!(validate_page_label_ranges(&ranges, 7).is_err());
+        assert!(validate_page_label_ranges(&ranges[1..], 8).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pending_page_labels_follow_inserted_and_deleted_pages() {
+        let labels = PdfPendingPageLabels::new();
+
+        assert_eq!(labels.label(0), None);
+
+        labels.set(&[
+            PdfPageLabelRange::new(0, PdfPageLabelStyle::RomanLower),
+            PdfPageLabelRange::new(3, PdfPageLabelStyle::Decimal),
+            PdfPageLabelRange::new(5, PdfPageLabelStyle::LettersUpper),
+        ]);
+
+        // Pages i, ii, iii, 1, 2, A, B. Inserting two pages at the start and one before
+        // page 1 extends the roman numeral range.
+
+        labels.insert_pages(0, 2);
+        labels.insert_pages(5, 1);
+
+        assert_eq!(labels.label(5), Some(Some("vi".to_string())));
+        assert_eq!(labels.label(6), Some(Some("1".to_string())));
+        assert_eq!(labels.label(8), Some(Some("A".to_string())));
+
+        // Deleting pages vi, 1, and 2 removes the whole decimal range; deleting page A
+        // moves the letter range to page B.
+
+        labels.delete_pages(5, 3, 7);
+
+        assert_eq!(labels.label(5), Some(Some("A".to_string())));
+
+        labels.delete_pages(5, 1, 6);
+
+        assert_eq!(labels.label(5), Some(Some("B".to_string())));
+        assert_eq!(labels.get().unwrap().len(), 2);
+
+        labels.delete_pages(5, 1, 5);
+
+        assert_eq!(
+            labels.get(),
+            Some(vec![PdfPageLabelRange::new(
+                0,
+                PdfPageLabelStyle::RomanLower
+            )])
+        );
+    }
+}
@@ -8,17 +8,21 @@ use crate::bindgen::{
 };
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::{PdfiumError, PdfiumInternalError};
+use crate::pdf::document::page::field::pending_changes::PdfPendingFieldChanges;
 use crate::pdf::document::page::index_cache::PdfPageIndexCache;
 use crate::pdf::document::page::object::group::PdfPageGroupObject;
 use crate::pdf::document::page::size::PdfPagePaperSize;
 use crate::pdf::document::page::PdfPage;
+use crate::pdf::document::page_labels::{
+    validate_page_label_ranges, PdfPageLabelRange, PdfPendingPageLabels,
+};
 use crate::pdf::document::PdfDocument;
 use crate::pdf::points::PdfPoints;
 use crate::pdf::rect::PdfRect;
 use crate::utils::mem::create_byte_buffer;
 use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
 use std::ops::{Range, RangeInclusive};
-use std::os::raw::{c_double, c_int, c_void};
+use std::os::raw::{c_double, c_int, c_ulong, c_void};
 
 /// The zero-based index of a single [PdfPage] inside its containing [PdfPages] collection.
 pub type PdfPageIndex = u16;
@@ -75,6 +79,8 @@ impl PdfPageMode {
 pub struct PdfPages<'a> {
     document_handle: FPDF_DOCUMENT,
     form_handle: Option<FPDF_FORMHANDLE>,
+    page_labels: PdfPendingPageLabels,
+    pending_field_changes: PdfPendingFieldChanges,
     bindings: &'a dyn PdfiumLibraryBindings,
 }
 
@@ -83,15 +89,26 @@ impl<'a> PdfPages<'a> {
     pub(crate) fn from_pdfium(
         document_handle: FPDF_DOCUMENT,
         form_handle: Option<FPDF_FORMHANDLE>,
+        pending_field_changes: PdfPendingFieldChanges,
         bindings: &'a dyn PdfiumLibraryBindings,
     ) -> Self {
         PdfPages {
             document_handle,
             form_handle,
+            page_labels: PdfPendingPageLabels::new(),
+            pending_field_changes,
             bindings,
         }
     }
 
+    /// Sets the form handle passed to the pages retrieved from this [PdfPages] collection,
+    /// as when a form fill environment is initialized for the containing [PdfDocument].
+    #[cfg(feature = "pdfium_future")]
+    #[inline]
+    pub(crate) fn set_form_handle(&mut self, form_handle: FPDF_FORMHANDLE) {
+        self.form_handle = Some(form_handle);
+    }
+
     /// Returns the [PdfiumLibraryBindings] used by this [PdfPages] collection.
     #[inline]
     pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
@@ -254,6 +271,7 @@ impl<'a> PdfPages<'a> {
 
         if let Ok(page) = result.as_ref() {
             PdfPageIndexCache::insert_pages_at_index(self.document_handle, index, 1);
+            self.page_labels.insert_pages(index, 1);
             PdfPageIndexCache::cache_props_for_page(
                 self.document_handle,
                 page.page_handle(),
@@ -283,10 +301,15 @@ impl<'a> PdfPages<'a> {
             return Err(PdfiumError::PageIndexOutOfBounds);
         }
 
+        self.get(index)?
+            .annotations()
+            .discard_pending_field_changes();
+
         self.bindings
             .FPDFPage_Delete(self.document_handle, index as c_int);
 
         PdfPageIndexCache::delete_pages_at_index(self.document_handle, index, 1);
+        self.page_labels.delete_pages(index, 1, self.len());
 
         Ok(())
     }
@@ -342,13 +365,22 @@ impl<'a> PdfPages<'a> {
         pages: &str,
         destination_page_index: PdfPageIndex,
     ) -> Result<(), PdfiumError> {
+        let page_count_before_import = self.len();
+
         Self::copy_pages_between_documents(
             source.handle(),
             pages,
             self.document_handle,
             destination_page_index,
             self.bindings(),
-        )
+        )?;
+
+        self.page_labels.insert_pages(
+            destination_page_index,
+            self.len() - page_count_before_import,
+        );
+
+        Ok(())
     }
 
     /// Copies one or more pages, specified using a user-friendly page range string,
@@ -396,13 +428,20 @@ impl<'a> PdfPages<'a> {
         source_page_range: RangeInclusive<PdfPageIndex>,
         destination_page_index: PdfPageIndex,
     ) -> Result<(), PdfiumError> {
+        let no_of_pages_to_import = source_page_range.len() as PdfPageIndex;
+
         Self::copy_page_range_between_documents(
             source.handle(),
             source_page_range,
             self.document_handle,
             destination_page_index,
             self.bindings(),
-        )
+        )?;
+
+        self.page_labels
+            .insert_pages(destination_page_index, no_of_pages_to_import);
+
+        Ok(())
     }
 
     /// Copies one or more pages with the given range of indices from one raw document handle
@@ -455,6 +494,219 @@ impl<'a> PdfPages<'a> {
         )
     }
 
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+        feature = "pdfium_6406",
+        feature = "pdfium_6337",
+        feature = "pdfium_6295",
+        feature = "pdfium_6259",
+        feature = "pdfium_6164",
+        feature = "pdfium_6124",
+        feature = "pdfium_6110",
+        feature = "pdfium_6084",
+        feature = "pdfium_6043",
+    ))]
+    /// Moves the pages at the given indices so that they appear, in the given order,
+    /// starting at the given destination index. The destination index is a position in the
+    /// page sequence that remains once the moved pages have been taken out, so it can be
+    /// at most `self.len() - indices.len()`.
+    ///
+    /// For example, in a document with pages `[A, B, C, D]`, calling `move_pages(&[3, 2], 1)`
+    /// reorders the pages to `[A, D, C, B]`.
+    ///
+    /// Unlike copying pages into a new document, moving pages preserves all document-level
+    /// state. Any [PdfPage] objects already retrieved from this [PdfPages] collection remain
+    /// valid, and continue to refer to the same pages at their new index positions.
+    ///
+    /// An error will be returned if any index is out of range or appears more than once.
+    pub fn move_pages(
+        &mut self,
+        indices: &[PdfPageIndex],
+        dest_index: PdfPageIndex,
+    ) -> Result<(), PdfiumError> {
+        if indices.is_empty() {
+            return Ok(());
+        }
+
+        let len = self.len();
+
+        let mut is_moved = vec![false; len as usize];
+
+        for index in indices {
+            match is_moved.get_mut(*index as usize) {
+                Some(true) => return Err(PdfiumError::InvalidPageOrder),
+                Some(is_moved) => *is_moved = true,
+                None => return Err(PdfiumError::PageIndexOutOfBounds),
+            }
+        }
+
+        if dest_index as usize > len as usize - indices.len() {
+            return Err(PdfiumError::PageIndexOutOfBounds);
+        }
+
+        // Work out the resulting page order, so we can update the page index cache.
+
+        let remaining = self
+            .as_range()
+            .filter(|index| !is_moved[*index as usize])
+            .collect::<Vec<_>>();
+
+        let (before, after) = remaining.split_at(dest_index as usize);
+
+        let order = [before, indices, after].concat();
+
+        let page_indices = indices
+            .iter()
+            .map(|index| *index as c_int)
+            .collect::<Vec<_>>();
+
+        if !self.bindings.is_true(self.bindings.FPDF_MovePages(
+            self.document_handle,
+            page_indices.as_ptr(),
+            page_indices.len() as c_ulong,
+            dest_index as c_int,
+        )) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        let mut new_indices = vec![0; order.len()];
+
+        for (new_index, index) in order.iter().enumerate() {
+            new_indices[*index as usize] = new_index as PdfPageIndex;
+        }
+
+        PdfPageIndexCache::reorder_pages(self.document_handle, &new_indices);
+
+        Ok(())
+    }
+
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+        feature = "pdfium_6406",
+        feature = "pdfium_6337",
+        feature = "pdfium_6295",
+        feature = "pdfium_6259",
+        feature = "pdfium_6164",
+        feature = "pdfium_6124",
+        feature = "pdfium_6110",
+        feature = "pdfium_6084",
+        feature = "pdfium_6043",
+    ))]
+    /// Swaps the positions of the pages at the two given indices.
+    ///
+    /// Any [PdfPage] objects already retrieved from this [PdfPages] collection remain valid.
+    pub fn swap(&mut self, a: PdfPageIndex, b: PdfPageIndex) -> Result<(), PdfiumError> {
+        if a >= self.len() || b >= self.len() {
+            return Err(PdfiumError::PageIndexOutOfBounds);
+        }
+
+        if a == b {
+            return Ok(());
+        }
+
+        let mut permutation = self.as_range().collect::<Vec<_>>();
+
+        permutation.swap(a as usize, b as usize);
+
+        self.reorder(&permutation)
+    }
+
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+        feature = "pdfium_6406",
+        feature = "pdfium_6337",
+        feature = "pdfium_6295",
+        feature = "pdfium_6259",
+        feature = "pdfium_6164",
+        feature = "pdfium_6124",
+        feature = "pdfium_6110",
+        feature = "pdfium_6084",
+        feature = "pdfium_6043",
+    ))]
+    /// Reverses the order of all the pages in this [PdfPages] collection.
+    ///
+    /// Any [PdfPage] objects already retrieved from this [PdfPages] collection remain valid.
+    pub fn reverse(&mut self) -> Result<(), PdfiumError> {
+        let permutation = self.as_range().rev().collect::<Vec<_>>();
+
+        self.reorder(&permutation)
+    }
+
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+        feature = "pdfium_6406",
+        feature = "pdfium_6337",
+        feature = "pdfium_6295",
+        feature = "pdfium_6259",
+        feature = "pdfium_6164",
+        feature = "pdfium_6124",
+        feature = "pdfium_6110",
+        feature = "pdfium_6084",
+        feature = "pdfium_6043",
+    ))]
+    /// Reorders all the pages in this [PdfPages] collection. The page currently at index
+    /// `permutation[i]` moves to index `i`; for example, in a document with pages
+    /// `[A, B, C]`, calling `reorder(&[2, 0, 1])` reorders the pages to `[C, A, B]`.
+    ///
+    /// Any [PdfPage] objects already retrieved from this [PdfPages] collection remain valid.
+    ///
+    /// An error will be returned if the given permutation does not contain every page index
+    /// in this [PdfPages] collection exactly once.
+    pub fn reorder(&mut self, permutation: &[PdfPageIndex]) -> Result<(), PdfiumError> {
+        if permutation.len() != self.len() as usize {
+            return Err(PdfiumError::InvalidPageOrder);
+        }
+
+        // Moving every page to the start of the document, in order, is equivalent
+        // to applying the permutation.
+
+        self.move_pages(permutation, 0)
+    }
+
     /// Creates a new [PdfDocument] by copying the pages in this [PdfPages] collection
     /// into tiled grids, the size of each tile shrinking or expanding as necessary to fit
     /// the given [PdfPagePaperSize].
@@ -505,8 +757,10 @@ impl<'a> PdfPages<'a> {
             // (if other pages are inserted or removed), it's better if we don't treat the
             // page index as an immutable property of the PdfPage; instead, we look up the label now.
 
-            // (Pdfium does not currently include an FPDF_SetPageLabel() function, so the label
-            // _will_ be an immutable property of the PdfPage for its entire lifetime.)
+            // (Pdfium does not currently include an FPDF_SetPageLabel() function. Page labels
+            // set using PdfPages::set_page_labels() are only written to the document when it
+            // is saved, so until then PdfPage::current_label() generates labels from those
+            // ranges.)
 
             let label = {
                 // Retrieving the label text from Pdfium is a two-step operation. First, we call
@@ -549,11 +803,55 @@ impl<'a> PdfPages<'a> {
                 page_handle,
                 self.form_handle,
                 label,
+                self.page_labels.clone(),
+                self.pending_field_changes.clone(),
                 self.bindings,
             ))
         }
     }
 
+    /// Replaces the page labels of the containing [PdfDocument] with the given ranges.
+    /// Each range specifies the numbering style, prefix, and starting number of the labels
+    /// of the pages from its first page up to the first page of the next range. Passing
+    /// an empty slice removes all page labels.
+    ///
+    /// The first range must begin at the first page in the document, and each subsequent
+    /// range must begin after the one before it; otherwise,
+    /// [PdfiumError::InvalidPageLabelRanges] is returned.
+    ///
+    /// Pdfium cannot write page labels, so the `/PageLabels` number tree in the document's
+    /// catalog is written as an incremental update when the document is saved. Until then,
+    /// [PdfPage::current_label()] returns the label given by these ranges, including for
+    /// any [PdfPage] retrieved before calling this function. [PdfPage::label()] continues
+    /// to return the label stored in the document when the page was retrieved.
+    ///
+    /// The ranges follow their pages as pages are inserted into or deleted from this collection
+    /// before the document is saved. Pages inserted into the document join the range of the
+    /// page before them, or the first range if inserted at the start of the document. When the
+    /// first page of a range is deleted, the range begins at the next page instead, keeping
+    /// that page's label. Moving or reordering pages does not change the ranges, so the
+    /// labels stay with their page indices rather than with the moved pages.
+    pub fn set_page_labels(&mut self, ranges: &[PdfPageLabelRange]) -> Result<(), PdfiumError> {
+        validate_page_label_ranges(ranges, self.len())?;
+
+        self.page_labels.set(ranges);
+
+        Ok(())
+    }
+
+    /// Returns the page label ranges set using [PdfPages::set_page_labels()], if any.
+    #[inline]
+    pub(crate) fn pending_page_labels(&self) -> Option<Vec<PdfPageLabelRange>> {
+        self.page_labels.get()
+    }
+
+    /// Returns the handle to the page label ranges set using [PdfPages::set_page_labels()],
+    /// shared with the pages retrieved from this collection.
+    #[inline]
+    pub(crate) fn page_labels(&self) -> &PdfPendingPageLabels {
+        &self.page_labels
+    }
+
     /// Returns the [PdfPageMode] setting embedded in the containing [PdfDocument].
     pub fn page_mode(&self) -> PdfPageMode {
         PdfPageMode::from_pdfium(self.bindings.FPDFDoc_GetPageMode(self.document_handle))
@@ -706,6 +1004,170 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(any(
+        feature = "pdfium_future",
+        feature = "pdfium_7543",
+        feature = "pdfium_7350",
+        feature = "pdfium_7215",
+        feature = "pdfium_7123",
+        feature = "pdfium_6996",
+        feature = "pdfium_6721",
+        feature = "pdfium_6666",
+        feature = "pdfium_6611",
+        feature = "pdfium_6569",
+        feature = "pdfium_6555",
+        feature = "pdfium_6490",
+        feature = "pdfium_6406",
+        feature = "pdfium_6337",
+        feature = "pdfium_6295",
+        feature = "pdfium_6259",
+        feature = "pdfium_6164",
+        feature = "pdfium_6124",
+        feature = "pdfium_6110",
+        feature = "pdfium_6084",
+        feature = "pdfium_6043",
+    ))]
+    #[test]
+    fn test_reorder_pages() -> Result<(), PdfiumError> {
+        // Creates four pages of increasing widths, so each page can be identified by its width.
+
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        for width in [100.0, 200.0, 300.0, 400.0] {
+            document
+                .pages_mut()
+                .create_page_at_end(PdfPagePaperSize::new_custom(
+                    PdfPoints::new(width),
+                    PdfPoints::new(100.0),
+                ))?;
+        }
+
+        let widths = |document: &PdfDocument| -> Result<Vec<f32>, PdfiumError> {
+            Ok(document
+                .pages()
+                .page_sizes()?
+                .iter()
+                .map(|size| size.width().value)
+                .collect())
+        };
+
+        // Hold a page across each reordering, so we can check its cached index is updated.
+
+        use crate::pdf::document::page::index_cache::PdfPageIndexCache;
+
+        let page = document.pages().get(3)?;
+
+        document.pages_mut().move_pages(&[3, 2], 1)?;
+
+        assert_eq!(widths(&document)?, vec![100.0, 400.0, 300.0, 200.0]);
+        assert_eq!(
+            PdfPageIndexCache::get_index_for_page(document.handle(), page.page_handle()),
+            Some(1)
+        );
+
+        document.pages_mut().swap(0, 3)?;
+
+        assert_eq!(widths(&document)?, vec![200.0, 400.0, 300.0, 100.0]);
+
+        document.pages_mut().reverse()?;
+
+        assert_eq!(widths(&document)?, vec![100.0, 300.0, 400.0, 200.0]);
+        assert_eq!(
+            PdfPageIndexCache::get_index_for_page(document.handle(), page.page_handle()),
+            Some(2)
+        );
+
+        document.pages_mut().reorder(&[3, 2, 1, 0])?;
+
+        assert_eq!(widths(&document)?, vec![200.0, 400.0, 300.0, 100.0]);
+
+        assert!(document.pages_mut().move_pages(&[0, 0], 0).is_err());
+        assert!(document.pages_mut().move_pages(&[4], 0).is_err());
+        assert!(document.pages_mut().move_pages(&[0, 1], 3).is_err());
+        assert!(document.pages_mut().reorder(&[0, 1, 2]).is_err());
+        assert!(document.pages_mut().swap(0, 4).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_page_labels() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let mut document = pdfium.create_new_pdf()?;
+
+        for _ in 0..6 {
+            document
+                .pages_mut()
+                .create_page_at_end(PdfPagePaperSize::a4())?;
+        }
+
+        let page = document.pages().get(4)?;
+
+        document.pages_mut().set_page_labels(&[
+            PdfPageLabelRange::new(0, PdfPageLabelStyle::RomanLower),
+            PdfPageLabelRange::new(3, PdfPageLabelStyle::Decimal).set_prefix("A-"),
+        ])?;
+
+        // A page retrieved before the labels were set sees its new label, while
+        // its stored label is unchanged until the document is saved and reloaded.
+
+        assert_eq!(page.current_label(), Some("A-2".to_string()));
+        assert_eq!(page.label(), None);
+
+        let labels = |document: &PdfDocument| -> Vec<Option<String>> {
+            document
+                .pages()
+                .iter()
+                .map(|page| page.current_label())
+                .collect()
+        };
+
+        let expected = |labels: &[&str]| -> Vec<Option<String>> {
+            labels.iter().map(|label| Some(label.to_string())).collect()
+        };
+
+        assert_eq!(
+            labels(&document),
+            expected(&["i", "ii", "iii", "A-1", "A-2", "A-3"])
+        );
+
+        // Inserting and deleting pages before saving moves the ranges with their pages.
+
+        document
+            .pages_mut()
+            .create_page_at_index(PdfPagePaperSize::a4(), 1)?;
+
+        assert_eq!(page.current_label(), Some("A-2".to_string()));
+
+        document.pages().get(4)?.delete()?;
+
+        assert_eq!(
+            labels(&document),
+            expected(&["i", "ii", "iii", "iv", "A-2", "A-3"])
+        );
+
+        let document = pdfium.load_pdf_from_byte_vec(document.save_to_bytes()?, None)?;
+
+        assert_eq!(
+            labels(&document),
+            expected(&["i", "ii", "iii", "iv", "A-2", "A-3"])
+        );
+
+        assert_eq!(
+            document
+                .pages()
+                .iter()
+                .map(|page| page.label().map(|label| label.to_string()))
+                .collect::<Vec<_>>(),
+            expected(&["i", "ii", "iii", "iv", "A-2", "A-3"])
+        );
+
+        Ok(())
+    }
+
     const fn expected_page_0_size() -> PdfRect {
         PdfRect::new_from_values(0.0, 0.0, 841.8898, 595.30396)
     }
@@ -0,0 +1,125 @@
+//! Defines the [PdfSaveOptions] struct, controlling how a `PdfDocument` is written
+//! when it is saved.
+
+use crate::bindgen::{FPDF_INCREMENTAL, FPDF_NO_INCREMENTAL, FPDF_REMOVE_SECURITY};
+use crate::pdf::document::PdfDocumentVersion;
+use std::os::raw::c_ulong;
+
+#[cfg(doc)]
+use crate::pdf::document::PdfDocument;
+
+/// The way in which Pdfium writes a [PdfDocument] when it is saved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PdfSaveMode {
+    /// The original file is written unchanged, followed by an incremental update containing
+    /// only the objects that were changed since the document was loaded.
+    ///
+    /// Because the bytes of the original file are preserved, an incremental save does not
+    /// invalidate any digital signatures in the document, so long as the changes themselves
+    /// are permitted by the signatures. The file version cannot be changed by an
+    /// incremental save.
+    Incremental,
+
+    /// The entire document is rewritten. Any security handler is kept, so an encrypted
+    /// document remains encrypted. This is the default.
+    NoIncremental,
+
+    /// The entire document is rewritten without its security handler, so an encrypted
+    /// document is saved unencrypted.
+    RemoveSecurity,
+}
+
+impl PdfSaveMode {
+    #[inline]
+    pub(crate) fn as_pdfium(&self) -> c_ulong {
+        (match self {
+            PdfSaveMode::Incremental => FPDF_INCREMENTAL,
+            PdfSaveMode::NoIncremental => FPDF_NO_INCREMENTAL,
+            PdfSaveMode::RemoveSecurity => FPDF_REMOVE_SECURITY,
+        }) as c_ulong
+    }
+}
+
+/// Configures how a [PdfDocument] is written by functions such as
+/// [PdfDocument::save_to_writer_with_options()] and [PdfDocument::save_to_bytes_with_options()].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PdfSaveOptions {
+    mode: PdfSaveMode,
+    version: Option<PdfDocumentVersion>,
+}
+
+impl PdfSaveOptions {
+    /// Creates a new [PdfSaveOptions] object with the default settings: the entire document
+    /// is rewritten using [PdfSaveMode::NoIncremental], with the file version set by
+    /// [PdfDocument::set_version()], if any.
+    ///
+    /// A full rewrite is the default because some changes, such as changes to the
+    /// appearance streams of annotations, are not reliably persisted by an incremental save.
+    #[inline]
+    pub fn new() -> Self {
+        PdfSaveOptions {
+            mode: PdfSaveMode::NoIncremental,
+            version: None,
+        }
+    }
+
+    /// Sets the [PdfSaveMode] used to write the document.
+    #[inline]
+    pub fn set_mode(mut self, mode: PdfSaveMode) -> Self {
+        self.mode = mode;
+
+        self
+    }
+
+    /// Sets the file version written to the document, overriding any version set by
+    /// [PdfDocument::set_version()]. The file version is ignored when saving
+    /// using [PdfSaveMode::Incremental].
+    #[inline]
+    pub fn set_version(mut self, version: PdfDocumentVersion) -> Self {
+        self.version = Some(version);
+
+        self
+    }
+
+    /// Returns the [PdfSaveMode] used to write the document.
+    #[inline]
+    pub fn mode(&self) -> PdfSaveMode {
+        self.mode
+    }
+
+    /// Returns the file version written to the document, if set.
+    #[inline]
+    pub fn version(&self) -> Option<PdfDocumentVersion> {
+        self.version
+    }
+}
+
+impl Default for PdfSaveOptions {
+    #[inline]
+    fn default() -> Self {
+        PdfSaveOptions::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_incremental_save_preserves_original_bytes() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let original = std::fs::read("./test/form-test.pdf").unwrap();
+
+        let document = pdfium.load_pdf_from_byte_slice(&original, None)?;
+
+        let saved = document.save_to_bytes_with_options(
+            &PdfSaveOptions::new().set_mode(PdfSaveMode::Incremental),
+        )?;
+
+        assert!(saved.starts_with(&original));
+
+        Ok(())
+    }
+}
@@ -7,7 +7,8 @@ use crate::error::{PdfiumError, PdfiumInternalError};
 use crate::utils::mem::create_byte_buffer;
 use crate::utils::utf16le::get_string_from_pdfium_utf16le_bytes;
 use std::ffi::{c_uint, CString};
-use std::os::raw::{c_char, c_void};
+use std::ops::Range;
+use std::os::raw::{c_char, c_int, c_void};
 
 /// The modification detection permission (MDP) applicable to a single digital signature
 /// in a `PdfDocument`.
@@ -182,6 +183,192 @@ impl<'a> PdfSignature<'a> {
         }
     }
 
+    /// Returns the name of the encoding of the byte data in this [PdfSignature], if any,
+    /// taken from the `/SubFilter` entry of the signature dictionary. Common values are
+    /// `adbe.pkcs7.detached`, `adbe.pkcs7.sha1`, `adbe.x509.rsa_sha1`, and `ETSI.CAdES.detached`.
+    pub fn sub_filter(&self) -> Option<String> {
+        // Retrieving the sub-filter from Pdfium is a two-step operation. First, we call
+        // FPDFSignatureObj_GetSubFilter() with a null buffer; this will retrieve the length of
+        // the encoding name in bytes. If the length is zero, then there is no sub-filter
+        // associated with this signature.
+
+        // If the length is non-zero, then we reserve a byte buffer of the given
+        // length and call FPDFSignatureObj_GetSubFilter() again with a pointer to the buffer;
+        // this will write the encoding name to the buffer as an array of 7-bit ASCII characters.
+
+        let buffer_length =
+            self.bindings
+                .FPDFSignatureObj_GetSubFilter(self.handle, std::ptr::null_mut(), 0);
+
+        if buffer_length == 0 {
+            // There is no sub-filter given for this signature.
+
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        let result = self.bindings.FPDFSignatureObj_GetSubFilter(
+            self.handle,
+            buffer.as_mut_ptr() as *mut c_char,
+            buffer_length,
+        );
+
+        assert_eq!(result, buffer_length);
+
+        if let Ok(result) = CString::from_vec_with_nul(buffer) {
+            result.into_string().ok()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the byte ranges of the document file that are covered by this [PdfSignature],
+    /// taken from the `/ByteRange` entry of the signature dictionary. The ranges are returned
+    /// in the order in which they are listed in the signature dictionary. An empty result
+    /// means the signature does not specify a byte range.
+    ///
+    /// Returns [PdfiumError::InvalidSignatureByteRange] if the byte range does not consist
+    /// of pairs of non-negative offsets and lengths.
+    pub fn byte_range(&self) -> Result<Vec<Range<usize>>, PdfiumError> {
+        // Retrieving the byte range from Pdfium is a two-step operation. First, we call
+        // FPDFSignatureObj_GetByteRange() with a null buffer; this will retrieve the number
+        // of integers in the byte range. If the number is zero, then there is no byte range
+        // associated with this signature.
+
+        // If the number is non-zero, then we reserve an integer buffer of the given
+        // length and call FPDFSignatureObj_GetByteRange() again with a pointer to the buffer;
+        // this will write pairs of (offset, length) integers to the buffer.
+
+        let buffer_length =
+            self.bindings
+                .FPDFSignatureObj_GetByteRange(self.handle, std::ptr::null_mut(), 0);
+
+        if buffer_length == 0 {
+            // There is no byte range given for this signature.
+
+            return Ok(Vec::new());
+        }
+
+        let mut buffer: Vec<c_int> = vec![0; buffer_length as usize];
+
+        let result = self.bindings.FPDFSignatureObj_GetByteRange(
+            self.handle,
+            buffer.as_mut_ptr(),
+            buffer_length,
+        );
+
+        assert_eq!(result, buffer_length);
+
+        if buffer.len() % 2 != 0 {
+            return Err(PdfiumError::InvalidSignatureByteRange);
+        }
+
+        buffer
+            .chunks_exact(2)
+            .map(|pair| {
+                let offset =
+                    usize::try_from(pair[0]).map_err(|_| PdfiumError::InvalidSignatureByteRange)?;
+
+                let length =
+                    usize::try_from(pair[1]).map_err(|_| PdfiumError::InvalidSignatureByteRange)?;
+
+                Ok(offset..offset + length)
+            })
+            .collect()
+    }
+
+    /// Returns the bytes of the given document file that are covered by this [PdfSignature],
+    /// concatenated in the order given by [PdfSignature::byte_range()]. These are the exact
+    /// bytes over which the message digest in the signature's byte data was calculated.
+    ///
+    /// The given bytes must be the complete, unmodified contents of the file from which
+    /// the document containing this [PdfSignature] was loaded.
+    ///
+    /// Returns [PdfiumError::InvalidSignatureByteRange] if the signature does not specify
+    /// a byte range, or if any part of the byte range lies outside the given bytes.
+    pub fn signed_content(&self, document_bytes: &[u8]) -> Result<Vec<u8>, PdfiumError> {
+        let ranges = self.byte_range()?;
+
+        if ranges.is_empty() {
+            return Err(PdfiumError::InvalidSignatureByteRange);
+        }
+
+        let mut result = Vec::with_capacity(ranges.iter().map(|range| range.len()).sum());
+
+        for range in ranges {
+            result.extend_from_slice(
+                document_bytes
+                    .get(range)
+                    .ok_or(PdfiumError::InvalidSignatureByteRange)?,
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// Returns `true` if the byte range of this [PdfSignature] covers every byte of the given
+    /// document file except for the hexadecimal string holding the signature's byte data,
+    /// i.e. the value of the `/Contents` entry of the signature dictionary.
+    ///
+    /// This is the case for a well-formed signature covering the latest revision of the file.
+    /// A signature covering an earlier revision, which has since been followed by an
+    /// incremental update, covers only the part of the file that existed when it was signed
+    /// and so returns `false`; the bytes appended after it are not protected by the signature.
+    ///
+    /// The given bytes must be the complete, unmodified contents of the file from which
+    /// the document containing this [PdfSignature] was loaded.
+    pub fn is_byte_range_complete(&self, document_bytes: &[u8]) -> Result<bool, PdfiumError> {
+        let ranges = self.byte_range()?;
+
+        // A complete byte range consists of exactly two ranges: one from the start of the file
+        // up to the /Contents hole, and one from the end of the hole up to the end of the file.
+
+        let (before, after) = match ranges.as_slice() {
+            [before, after] => (before, after),
+            _ => return Ok(false),
+        };
+
+        if before.start != 0 || after.end != document_bytes.len() || before.end >= after.start {
+            return Ok(false);
+        }
+
+        Ok(self.is_contents_hole(&document_bytes[before.end..after.start]))
+    }
+
+    /// Returns `true` if the given bytes are a hexadecimal string, including its delimiters,
+    /// that encodes the byte data of this [PdfSignature].
+    fn is_contents_hole(&self, hole: &[u8]) -> bool {
+        let digits = match hole {
+            [b'<', digits @ .., b'>'] => digits,
+            _ => return false,
+        };
+
+        // White space within a hexadecimal string is ignored. If the final digit is missing,
+        // it is assumed to be zero.
+
+        let mut digits = match digits
+            .iter()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .map(|byte| (*byte as char).to_digit(16))
+            .collect::<Option<Vec<_>>>()
+        {
+            Some(digits) => digits,
+            None => return false,
+        };
+
+        if digits.len() % 2 != 0 {
+            digits.push(0);
+        }
+
+        let decoded = digits
+            .chunks_exact(2)
+            .map(|pair| (pair[0] * 16 + pair[1]) as u8)
+            .collect::<Vec<_>>();
+
+        decoded == self.bytes()
+    }
+
     /// Returns the modification detection permission (MDP) applicable to this [PdfSignature],
     /// if available.
     ///
@@ -196,3 +383,58 @@ impl<'a> PdfSignature<'a> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_signature_byte_range() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document_bytes = std::fs::read("./test/signatures-test.pdf").unwrap();
+
+        let document = pdfium.load_pdf_from_byte_slice(&document_bytes, None)?;
+
+        // The test file has been signed three times, each signature appended to the file
+        // as an incremental update. Only the latest signature covers the whole file.
+
+        assert_eq!(document.signatures().len(), 3);
+
+        let mut completeness = Vec::new();
+
+        for signature in document.signatures().iter() {
+            assert!(signature.sub_filter().is_some());
+
+            let ranges = signature.byte_range()?;
+
+            assert_eq!(ranges.len(), 2);
+
+            let content = signature.signed_content(&document_bytes)?;
+
+            assert_eq!(
+                content.len(),
+                ranges.iter().map(|range| range.len()).sum::<usize>()
+            );
+
+            completeness.push((
+                ranges[1].end,
+                signature.is_byte_range_complete(&document_bytes)?,
+            ));
+        }
+
+        completeness.sort();
+
+        assert_eq!(
+            completeness,
+            vec![
+                (218067, false),
+                (255473, false),
+                (document_bytes.len(), true)
+            ]
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,563 @@
+//! Defines the [PdfSignaturePlaceholder] struct, the saved bytes of a `PdfDocument` containing
+//! a signature dictionary whose signature data has yet to be filled in, along with the
+//! [PdfSignatureOptions] used to create it and the [PdfSigner] trait used to complete it.
+
+use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::{
+    encode_name, encode_text_string, find, next_marker_id, PdfIncrementalUpdate, PdfRawDictionary,
+};
+use crate::pdf::document::page::field::private::internal::PdfFormFieldPrivate;
+use crate::pdf::document::page::field::signature::PdfFormSignatureField;
+use crate::utils::dates::date_time_to_pdf_string;
+use chrono::{DateTime, Utc};
+use std::error::Error;
+use std::ops::Range;
+use std::os::raw::c_int;
+
+#[cfg(doc)]
+use crate::pdf::document::{signature::PdfSignature, PdfDocument};
+
+/// Computes the signature data for a [PdfSignaturePlaceholder].
+///
+/// Implement this trait to connect a [PdfSignaturePlaceholder] to your own signing
+/// infrastructure, such as a cryptography library, a hardware security module, or a remote
+/// signing service. It is also implemented for any closure with a matching signature.
+pub trait PdfSigner {
+    /// Returns the signature data for the given content, the exact bytes of the saved file
+    /// covered by the signature's byte range. For the default `adbe.pkcs7.detached`
+    /// sub-filter, this is a DER-encoded detached CMS (PKCS#7) signature over the content.
+    fn sign(&mut self, content: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>;
+}
+
+impl<F> PdfSigner for F
+where
+    F: FnMut(&[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>>,
+{
+    #[inline]
+    fn sign(&mut self, content: &[u8]) -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+        self(content)
+    }
+}
+
+/// Configures the signature dictionary written by [PdfDocument::save_with_signature_placeholder()].
+#[derive(Debug, Clone)]
+pub struct PdfSignatureOptions {
+    capacity: usize,
+    filter: String,
+    sub_filter: String,
+    name: Option<String>,
+    reason: Option<String>,
+    location: Option<String>,
+    contact_info: Option<String>,
+    signing_time: Option<DateTime<Utc>>,
+}
+
+impl PdfSignatureOptions {
+    /// The default number of bytes reserved for signature data. This is enough for
+    /// a typical CMS signature including a certificate chain of moderate length, but not
+    /// necessarily for one that also embeds a timestamp token or revocation information.
+    pub const DEFAULT_CAPACITY: usize = 8192;
+
+    /// Creates a new [PdfSignatureOptions] object with the default settings: a placeholder
+    /// of [PdfSignatureOptions::DEFAULT_CAPACITY] bytes, the `Adobe.PPKLite` filter,
+    /// the `adbe.pkcs7.detached` sub-filter, and the current time as the signing time.
+    pub fn new() -> Self {
+        PdfSignatureOptions {
+            capacity: Self::DEFAULT_CAPACITY,
+            filter: "Adobe.PPKLite".to_string(),
+            sub_filter: "adbe.pkcs7.detached".to_string(),
+            name: None,
+            reason: None,
+            location: None,
+            contact_info: None,
+            signing_time: None,
+        }
+    }
+
+    /// Sets the number of bytes reserved in the saved file for signature data. The signature
+    /// data later passed to [PdfSignaturePlaceholder::set_contents()] must not exceed this size.
+    pub fn set_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+
+        self
+    }
+
+    /// Sets the name of the preferred signature handler, written to the `/Filter` entry
+    /// of the signature dictionary.
+    pub fn set_filter(mut self, filter: &str) -> Self {
+        self.filter = filter.to_string();
+
+        self
+    }
+
+    /// Sets the encoding of the signature data, written to the `/SubFilter` entry of the
+    /// signature dictionary; for instance, `adbe.pkcs7.detached` or `ETSI.CAdES.detached`.
+    pub fn set_sub_filter(mut self, sub_filter: &str) -> Self {
+        self.sub_filter = sub_filter.to_string();
+
+        self
+    }
+
+    /// Sets the name of the person or authority signing the document.
+    pub fn set_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+
+        self
+    }
+
+    /// Sets the reason for the signing, such as "I agree".
+    pub fn set_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_string());
+
+        self
+    }
+
+    /// Sets the physical location of the signing, such as a city name.
+    pub fn set_location(mut self, location: &str) -> Self {
+        self.location = Some(location.to_string());
+
+        self
+    }
+
+    /// Sets information provided by the signer to enable a recipient to contact the signer
+    /// to verify the signature, such as a phone number.
+    pub fn set_contact_info(mut self, contact_info: &str) -> Self {
+        self.contact_info = Some(contact_info.to_string());
+
+        self
+    }
+
+    /// Sets the time of signing written to the `/M` entry of the signature dictionary.
+    /// If not set, the time at which the placeholder is saved is used.
+    pub fn set_signing_time(mut self, signing_time: DateTime<Utc>) -> Self {
+        self.signing_time = Some(signing_time);
+
+        self
+    }
+
+    /// Returns the signature dictionary for these options. The `/ByteRange` and `/Contents`
+    /// entries contain fixed-width placeholders to be filled in once their offsets are known.
+    fn to_dictionary(&self) -> PdfRawDictionary {
+        let mut dictionary = PdfRawDictionary::new();
+
+        dictionary.set("Type", "/Sig");
+        dictionary.set("Filter", encode_name(&self.filter));
+        dictionary.set("SubFilter", encode_name(&self.sub_filter));
+        dictionary.set("ByteRange", PdfSignaturePlaceholder::BYTE_RANGE_PLACEHOLDER);
+
+        let mut contents = vec![b'0'; self.capacity * 2 + 2];
+
+        contents[0] = b'<';
+        contents[self.capacity * 2 + 1] = b'>';
+
+        dictionary.set("Contents", contents);
+
+        let signing_time = self.signing_time.unwrap_or_else(Utc::now);
+
+        dictionary.set(
+            "M",
+            encode_text_string(&date_time_to_pdf_string(signing_time)),
+        );
+
+        for (key, value) in [
+            ("Name", &self.name),
+            ("Reason", &self.reason),
+            ("Location", &self.location),
+            ("ContactInfo", &self.contact_info),
+        ] {
+            if let Some(value) = value {
+                dictionary.set(key, encode_text_string(value));
+            }
+        }
+
+        dictionary
+    }
+}
+
+impl Default for PdfSignatureOptions {
+    #[inline]
+    fn default() -> Self {
+        PdfSignatureOptions::new()
+    }
+}
+
+/// The saved bytes of a [PdfDocument] whose signature field holds a signature dictionary with
+/// a zero-filled `/Contents` placeholder, ready to receive signature data.
+///
+/// The signature dictionary's `/ByteRange` covers the entire file except for the `/Contents`
+/// placeholder. To complete the signature, compute the signature data over the bytes returned
+/// by [PdfSignaturePlaceholder::signed_content()] and write it into the placeholder using
+/// [PdfSignaturePlaceholder::set_contents()], or pass a [PdfSigner] to
+/// [PdfSignaturePlaceholder::sign()] to do both in one step. When the signed file is loaded,
+/// the new signature is available as a [PdfSignature] in the document's signatures.
+///
+/// Create a [PdfSignaturePlaceholder] by calling [PdfDocument::save_with_signature_placeholder()].
+pub struct PdfSignaturePlaceholder {
+    bytes: Vec<u8>,
+    contents: Range<usize>,
+}
+
+impl PdfSignaturePlaceholder {
+    /// A fixed-width `/ByteRange` value, wide enough to hold offsets into files of up to 10 GB.
+    const BYTE_RANGE_PLACEHOLDER: &'static str = "[0 0000000000 0000000000 0000000000]";
+
+    /// The bit set in every temporary value of the `/F` entry of the signature field's widget
+    /// annotation, used to locate the widget in the saved file. The bits below it that hold
+    /// defined annotation flags are left clear.
+    const MARKER_FLAGS: c_int = 0x4000_0000;
+
+    /// Saves the given document bytes with a signature placeholder in the given field.
+    /// The given save function should append the document's changes to the bytes of the
+    /// file it was loaded from as an incremental update, so that existing signatures
+    /// remain valid.
+    pub(crate) fn new(
+        save: impl FnOnce() -> Result<Vec<u8>, PdfiumError>,
+        field: &PdfFormSignatureField,
+        options: &PdfSignatureOptions,
+    ) -> Result<Self, PdfiumError> {
+        let bindings = field.bindings();
+
+        let annotation_handle = field.annotation_handle();
+
+        // Pdfium provides no way to find the object number of an annotation, so we
+        // temporarily set the flags of the signature field's widget to a unique value, save
+        // the document, and then look for that value in the saved bytes. Pdfium cannot remove
+        // a key from an annotation dictionary, so the flags are used rather than a new key:
+        // they can be restored to their exact original value, both in the document and in
+        // the signature field written to the incremental update.
+
+        let has_flags = bindings.is_true(bindings.FPDFAnnot_HasKey(annotation_handle, "F"));
+
+        let original_flags = bindings.FPDFAnnot_GetFlags(annotation_handle);
+
+        let marker_flags = Self::MARKER_FLAGS | (((next_marker_id() & 0x3_ffff) as c_int) << 12);
+
+        if !bindings.is_true(bindings.FPDFAnnot_SetFlags(annotation_handle, marker_flags)) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        let saved = save();
+
+        if !bindings.is_true(bindings.FPDFAnnot_SetFlags(annotation_handle, original_flags)) {
+            return Err(PdfiumError::PdfiumFunctionReturnValueIndicatedFailure);
+        }
+
+        let mut update = PdfIncrementalUpdate::new(saved?)?;
+
+        let widget_id = update.find_object_containing(format!("/F {marker_flags}").as_bytes())?;
+
+        let mut widget = update.get_dictionary(widget_id)?;
+
+        if has_flags {
+            widget.set("F", original_flags.to_string());
+        } else {
+            widget.remove("F");
+        }
+
+        let signature = options.to_dictionary().to_bytes();
+
+        // Note the positions of the placeholders within the signature dictionary,
+        // so they can be located once the dictionary is written.
+
+        let byte_range_offset = find(&signature, Self::BYTE_RANGE_PLACEHOLDER.as_bytes())
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let contents_offset = find(&signature, b"/Contents <")
+            .map(|offset| offset + b"/Contents ".len())
+            .ok_or(PdfiumError::UnsupportedPdfFileStructure)?;
+
+        let signature_id = update.add_object(signature);
+
+        // The signature value belongs in the field dictionary. For a field with a single
+        // widget, the field and widget dictionaries are usually merged; otherwise, the widget
+        // is a child of the field, and the field is the widget's parent.
+
+        match widget
+            .get_reference("Parent")
+            .filter(|_| !widget.contains_key("FT"))
+        {
+            Some(field_id) => {
+                let mut field = update.get_dictionary(field_id)?;
+
+                field.set("V", signature_id.to_reference());
+
+                update.set_object(field_id, field.to_bytes());
+            }
+            None => widget.set("V", signature_id.to_reference()),
+        }
+
+        update.set_object(widget_id, widget.to_bytes());
+
+        Self::set_signature_flags(&mut update)?;
+
+        let (mut bytes, offsets) = update.write()?;
+
+        let signature_offset = offsets[&signature_id.number];
+
+        let byte_range_start = signature_offset + byte_range_offset;
+
+        let contents = signature_offset + contents_offset + 1
+            ..signature_offset + contents_offset + 1 + options.capacity * 2;
+
+        // Fill in the byte range, padding with spaces to keep the same width.
+
+        let byte_range = format!(
+            "[0 {} {} {}",
+            contents.start - 1,
+            contents.end + 1,
+            bytes.len() - (contents.end + 1)
+        );
+
+        let width = Self::BYTE_RANGE_PLACEHOLDER.len();
+
+        if byte_range.len() >= width {
+            return Err(PdfiumError::UnsupportedPdfFileStructure);
+        }
+
+        let byte_range = format!("{byte_range:width$}]", width = width - 1);
+
+        bytes[byte_range_start..byte_range_start + width].copy_from_slice(byte_range.as_bytes());
+
+        Ok(PdfSignaturePlaceholder { bytes, contents })
+    }
+
+    /// Sets the `/SigFlags` entry of the document's interactive form dictionary to indicate
+    /// that the document contains signatures and must be saved using incremental updates.
+    fn set_signature_flags(update: &mut PdfIncrementalUpdate) -> Result<(), PdfiumError> {
+        const SIGNATURES_EXIST_APPEND_ONLY: &str = "3";
+
+        let root_id = update.root()?;
+
+        let mut root = update.get_dictionary(root_id)?;
+
+        match root.get_reference("AcroForm") {
+            Some(form_id) => {
+                let mut form = update.get_dictionary(form_id)?;
+
+                form.set("SigFlags", SIGNATURES_EXIST_APPEND_ONLY);
+
+                update.set_object(form_id, form.to_bytes());
+            }
+            None => {
+                let mut form = match root.get("AcroForm") {
+                    Some(form) => PdfRawDictionary::parse(form)?,
+                    None => PdfRawDictionary::new(),
+                };
+
+                form.set("SigFlags", SIGNATURES_EXIST_APPEND_ONLY);
+
+                root.set("AcroForm", form.to_bytes());
+
+                update.set_object(root_id, root.to_bytes());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the complete bytes of the saved file, including the signature data
+    /// if it has been set.
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        self.bytes.as_slice()
+    }
+
+    /// Consumes this [PdfSignaturePlaceholder], returning the complete bytes of the saved file.
+    #[inline]
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Returns the two byte ranges of the saved file covered by the signature, as written to the
+    /// `/ByteRange` entry of the signature dictionary. Together they span the entire file
+    /// except for the hexadecimal string returned by [PdfSignaturePlaceholder::contents_range()].
+    pub fn byte_range(&self) -> [Range<usize>; 2] {
+        [
+            0..self.contents.start - 1,
+            self.contents.end + 1..self.bytes.len(),
+        ]
+    }
+
+    /// Returns the byte range of the saved file holding the hexadecimal string value of the
+    /// `/Contents` entry of the signature dictionary, including its `<` and `>` delimiters.
+    #[inline]
+    pub fn contents_range(&self) -> Range<usize> {
+        self.contents.start - 1..self.contents.end + 1
+    }
+
+    /// Returns the maximum size, in bytes, of the signature data that can be written
+    /// into the placeholder.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.contents.len() / 2
+    }
+
+    /// Returns the bytes covered by the signature: the concatenation of the two ranges
+    /// returned by [PdfSignaturePlaceholder::byte_range()]. The signature data must be
+    /// computed over exactly these bytes.
+    pub fn signed_content(&self) -> Vec<u8> {
+        self.byte_range()
+            .into_iter()
+            .flat_map(|range| self.bytes[range].iter().copied())
+            .collect()
+    }
+
+    /// Writes the given signature data into the placeholder, hex-encoded and padded with zeros
+    /// to fill the placeholder. Any previously written signature data is replaced.
+    ///
+    /// Returns [PdfiumError::SignatureTooLarge] if the signature data is larger than
+    /// [PdfSignaturePlaceholder::capacity()].
+    pub fn set_contents(&mut self, signature: &[u8]) -> Result<(), PdfiumError> {
+        if signature.len() > self.capacity() {
+            return Err(PdfiumError::SignatureTooLarge);
+        }
+
+        let mut hex = signature
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<String>()
+            .into_bytes();
+
+        hex.resize(self.contents.len(), b'0');
+
+        self.bytes[self.contents.clone()].copy_from_slice(&hex);
+
+        Ok(())
+    }
+
+    /// Computes the signature data for this placeholder using the given [PdfSigner], writes it
+    /// into the placeholder, and returns the complete bytes of the signed file.
+    ///
+    /// Returns [PdfiumError::SignerError] if the [PdfSigner] fails, or
+    /// [PdfiumError::SignatureTooLarge] if it returns more signature data than
+    /// [PdfSignaturePlaceholder::capacity()].
+    pub fn sign(mut self, signer: &mut impl PdfSigner) -> Result<Vec<u8>, PdfiumError> {
+        let signature = signer
+            .sign(&self.signed_content())
+            .map_err(PdfiumError::SignerError)?;
+
+        self.set_contents(&signature)?;
+
+        Ok(self.bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "pdfium_future")]
+    use crate::prelude::*;
+
+    #[cfg(feature = "pdfium_future")]
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[cfg(feature = "pdfium_future")]
+    use std::error::Error;
+
+    #[test]
+    #[cfg(feature = "pdfium_future")]
+    fn test_signature_placeholder() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let original = std::fs::read("./test/signatures-test.pdf").unwrap();
+
+        let mut document = pdfium.load_pdf_from_byte_slice(&original, None)?;
+
+        let existing_signatures = document.signatures().len();
+
+        assert!(existing_signatures > 0);
+
+        let form_handle = document.init_form_fill_environment()?;
+
+        let mut page = document.pages().first()?;
+
+        let widget = page.annotations_mut().create_widget_annotation(
+            form_handle,
+            "Approval",
+            PdfFormFieldType::Signature,
+            PdfRect::new_from_values(50.0, 50.0, 100.0, 250.0),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )?;
+
+        let is_printed = widget.is_printed();
+
+        let field = widget
+            .form_field()
+            .and_then(|field| field.as_signature_field())
+            .unwrap();
+
+        let options = PdfSignatureOptions::new()
+            .set_capacity(256)
+            .set_reason("Testing");
+
+        let placeholder = document.save_with_signature_placeholder(field, &options)?;
+
+        // The temporary marker used to locate the widget is removed from the document.
+
+        assert_eq!(widget.is_printed(), is_printed);
+
+        // The placeholder is appended to the original file, rather than rewriting it.
+
+        assert!(placeholder.bytes().starts_with(&original));
+        assert_eq!(placeholder.capacity(), 256);
+
+        let [before, after] = placeholder.byte_range();
+
+        assert_eq!(before.start, 0);
+        assert_eq!(before.end, placeholder.contents_range().start);
+        assert_eq!(after.start, placeholder.contents_range().end);
+        assert_eq!(after.end, placeholder.bytes().len());
+
+        let update = String::from_utf8_lossy(&placeholder.bytes()[original.len()..]).into_owned();
+
+        assert!(update.contains(&format!(
+            "/ByteRange [0 {} {} {}",
+            before.end,
+            after.start,
+            after.len()
+        )));
+        assert!(update.contains(&format!("/Contents <{}>", "0".repeat(512))));
+
+        let mut signer = |content: &[u8]| -> Result<Vec<u8>, Box<dyn Error + Send + Sync>> {
+            Ok(content.iter().take(16).copied().collect())
+        };
+
+        let signed = placeholder.sign(&mut signer)?;
+
+        let signed_document = pdfium.load_pdf_from_byte_slice(&signed, None)?;
+
+        assert_eq!(signed_document.signatures().len(), existing_signatures + 1);
+
+        let signature = signed_document
+            .signatures()
+            .iter()
+            .find(|signature| signature.reason().as_deref() == Some("Testing"))
+            .expect("signature");
+
+        assert_eq!(
+            signature.sub_filter().as_deref(),
+            Some("adbe.pkcs7.detached")
+        );
+        assert!(signature.bytes().starts_with(&signed[..16]));
+        assert_eq!(signature.byte_range()?, vec![before.clone(), after.clone()]);
+
+        assert!(signature.is_byte_range_complete(&signed)?);
+
+        // The existing signatures still cover only the unchanged bytes of the original file.
+
+        for signature in signed_document.signatures().iter() {
+            if signature.reason().as_deref() != Some("Testing") {
+                assert!(signature
+                    .byte_range()?
+                    .iter()
+                    .all(|range| range.end <= original.len()));
+            }
+        }
+
+        Ok(())
+    }
+}
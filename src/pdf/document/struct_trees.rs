@@ -0,0 +1,115 @@
+//! Defines the [PdfStructTrees] struct, exposing functionality related to the tagged
+//! logical structure of a `PdfDocument`.
+
+use crate::bindgen::FPDF_DOCUMENT;
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::page::struct_tree::PdfStructTree;
+use crate::pdf::document::pages::{PdfPageIndex, PdfPages};
+
+#[cfg(doc)]
+use crate::pdf::document::{page::PdfPage, PdfDocument};
+
+/// The logical structure of a tagged [PdfDocument], exposed as one [PdfStructTree]
+/// for each [PdfPage] in the document.
+///
+/// Pdfium only supports loading the structure tree one page at a time. Each [PdfStructTree]
+/// contains just the structure elements that refer to content on its page.
+pub struct PdfStructTrees<'a> {
+    document_handle: FPDF_DOCUMENT,
+    pages: &'a PdfPages<'a>,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfStructTrees<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        document_handle: FPDF_DOCUMENT,
+        pages: &'a PdfPages<'a>,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfStructTrees {
+            document_handle,
+            pages,
+            bindings,
+        }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfStructTrees] object.
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns `true` if the containing [PdfDocument] is a tagged PDF, i.e. if it
+    /// contains a logical structure tree.
+    #[inline]
+    pub fn is_tagged(&self) -> bool {
+        self.bindings
+            .is_true(self.bindings.FPDFCatalog_IsTagged(self.document_handle))
+    }
+
+    /// Returns the number of pages, and therefore the number of [PdfStructTree] objects,
+    /// in the containing [PdfDocument].
+    #[inline]
+    pub fn len(&self) -> PdfPageIndex {
+        self.pages.len()
+    }
+
+    /// Returns `true` if the containing [PdfDocument] has no pages.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the [PdfStructTree] for the page at the given index in the containing
+    /// [PdfDocument]. The page is loaded and kept open for as long as the returned
+    /// [PdfStructTree] is in scope.
+    ///
+    /// An error will be returned if the document is not a tagged PDF.
+    pub fn get(&self, index: PdfPageIndex) -> Result<PdfStructTree<'a>, PdfiumError> {
+        let page = self.pages.get(index)?;
+
+        PdfStructTree::from_pdfium(page.page_handle(), Some(page), self.bindings)
+    }
+
+    /// Returns an iterator over the [PdfStructTree] for each page in the containing [PdfDocument].
+    ///
+    /// The iterator will be empty if the document is not a tagged PDF.
+    #[inline]
+    pub fn iter(&self) -> PdfStructTreesIterator<'_> {
+        PdfStructTreesIterator::new(self)
+    }
+}
+
+/// An iterator over the [PdfStructTree] for each page in a [PdfDocument].
+pub struct PdfStructTreesIterator<'a> {
+    trees: &'a PdfStructTrees<'a>,
+    next_index: PdfPageIndex,
+}
+
+impl<'a> PdfStructTreesIterator<'a> {
+    #[inline]
+    pub(crate) fn new(trees: &'a PdfStructTrees<'a>) -> Self {
+        PdfStructTreesIterator {
+            trees,
+            next_index: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for PdfStructTreesIterator<'a> {
+    type Item = PdfStructTree<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_index >= self.trees.len() {
+            return None;
+        }
+
+        let next = self.trees.get(self.next_index);
+
+        self.next_index += 1;
+
+        next.ok()
+    }
+}
@@ -0,0 +1,269 @@
+//! Defines the [PdfViewerPreferences] struct, exposing the viewer preferences that control
+//! how a `PdfDocument` should be presented and printed.
+
+use crate::bindgen::{
+    _FPDF_DUPLEXTYPE__DuplexFlipLongEdge, _FPDF_DUPLEXTYPE__DuplexFlipShortEdge,
+    _FPDF_DUPLEXTYPE__DuplexUndefined, _FPDF_DUPLEXTYPE__Simplex, FPDF_DOCUMENT, FPDF_DUPLEXTYPE,
+};
+use crate::bindings::PdfiumLibraryBindings;
+use crate::error::PdfiumError;
+use crate::pdf::document::pages::PdfPageIndex;
+use crate::utils::mem::create_byte_buffer;
+use std::ffi::CString;
+use std::ops::Range;
+use std::os::raw::c_char;
+use std::ptr::null_mut;
+
+#[cfg(doc)]
+use crate::pdf::document::PdfDocument;
+
+/// The paper handling option a [PdfDocument] requests when it is printed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PdfDuplexType {
+    /// The document does not specify a paper handling option.
+    Undefined,
+
+    /// The document should be printed single-sided.
+    Simplex,
+
+    /// The document should be printed double-sided, flipping on the short edge of the sheet.
+    DuplexFlipShortEdge,
+
+    /// The document should be printed double-sided, flipping on the long edge of the sheet.
+    DuplexFlipLongEdge,
+}
+
+impl PdfDuplexType {
+    #[inline]
+    #[allow(non_upper_case_globals)]
+    pub(crate) fn from_pdfium(value: FPDF_DUPLEXTYPE) -> Result<Self, PdfiumError> {
+        match value {
+            _FPDF_DUPLEXTYPE__DuplexUndefined => Ok(PdfDuplexType::Undefined),
+            _FPDF_DUPLEXTYPE__Simplex => Ok(PdfDuplexType::Simplex),
+            _FPDF_DUPLEXTYPE__DuplexFlipShortEdge => Ok(PdfDuplexType::DuplexFlipShortEdge),
+            _FPDF_DUPLEXTYPE__DuplexFlipLongEdge => Ok(PdfDuplexType::DuplexFlipLongEdge),
+            _ => Err(PdfiumError::UnknownPdfDuplexType),
+        }
+    }
+}
+
+/// The viewer preferences of a [PdfDocument], taken from the `/ViewerPreferences` dictionary
+/// in the document catalog.
+///
+/// Viewer preferences are requests from the document's author about how the document
+/// should be displayed on screen and printed. A PDF viewer is free to ignore them.
+///
+/// For more information on viewer preferences, refer to Section 8.1 of
+/// The PDF Reference (Sixth Edition, PDF Format 1.7), starting on page 577.
+pub struct PdfViewerPreferences<'a> {
+    document_handle: FPDF_DOCUMENT,
+    bindings: &'a dyn PdfiumLibraryBindings,
+}
+
+impl<'a> PdfViewerPreferences<'a> {
+    #[inline]
+    pub(crate) fn from_pdfium(
+        document_handle: FPDF_DOCUMENT,
+        bindings: &'a dyn PdfiumLibraryBindings,
+    ) -> Self {
+        PdfViewerPreferences {
+            document_handle,
+            bindings,
+        }
+    }
+
+    /// Returns the [PdfiumLibraryBindings] used by this [PdfViewerPreferences] object.
+    #[inline]
+    pub fn bindings(&self) -> &'a dyn PdfiumLibraryBindings {
+        self.bindings
+    }
+
+    /// Returns `true` if the print dialog should default to the print application's own
+    /// page scaling setting, or `false` if the document asks that no page scaling be applied.
+    ///
+    /// Documents that do not specify a print scaling preference return `true`.
+    #[inline]
+    pub fn is_print_scaling_preferred(&self) -> bool {
+        self.bindings.is_true(
+            self.bindings
+                .FPDF_VIEWERREF_GetPrintScaling(self.document_handle),
+        )
+    }
+
+    /// Returns the number of copies the print dialog should default to when the
+    /// document is printed.
+    ///
+    /// Documents that do not specify a number of copies return `1`.
+    #[inline]
+    pub fn copies(&self) -> u32 {
+        self.bindings
+            .FPDF_VIEWERREF_GetNumCopies(self.document_handle)
+            .max(1) as u32
+    }
+
+    /// Returns the paper handling option the print dialog should default to when the
+    /// document is printed.
+    #[inline]
+    pub fn duplex(&self) -> Result<PdfDuplexType, PdfiumError> {
+        PdfDuplexType::from_pdfium(self.bindings.FPDF_VIEWERREF_GetDuplex(self.document_handle))
+    }
+
+    /// Returns the ranges of pages the print dialog should default to when the document
+    /// is printed. An empty result means the document does not restrict printing to any
+    /// particular pages.
+    ///
+    /// The document stores each range as a pair of one-based, inclusive page numbers;
+    /// these are converted to half-open ranges of zero-based page indices, so a stored
+    /// range of pages 1 to 3 is returned as `0..3`. Malformed ranges are skipped.
+    pub fn print_page_ranges(&self) -> Vec<Range<PdfPageIndex>> {
+        let handle = self
+            .bindings
+            .FPDF_VIEWERREF_GetPrintPageRange(self.document_handle);
+
+        if handle.is_null() {
+            return Vec::new();
+        }
+
+        let count = self.bindings.FPDF_VIEWERREF_GetPrintPageRangeCount(handle);
+
+        let elements = (0..count)
+            .map(|index| {
+                self.bindings
+                    .FPDF_VIEWERREF_GetPrintPageRangeElement(handle, index)
+            })
+            .collect::<Vec<_>>();
+
+        // Any trailing unpaired element is ignored.
+
+        elements
+            .chunks_exact(2)
+            .filter_map(|pair| {
+                let first = pair[0];
+                let last = pair[1];
+
+                if first < 1 || last < first || last > PdfPageIndex::MAX as i32 {
+                    None
+                } else {
+                    Some((first - 1) as PdfPageIndex..last as PdfPageIndex)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the value of the name-valued viewer preference with the given key,
+    /// if the document sets one; for example, `get_name("Direction")` returns the
+    /// predominant reading order of the document's text, either `L2R` or `R2L`.
+    ///
+    /// Only preferences whose values are PDF names can be retrieved. The value is
+    /// returned without its leading `/`.
+    pub fn get_name(&self, key: &str) -> Option<String> {
+        // Retrieving the value from Pdfium is a two-step operation. First, we call
+        // FPDF_VIEWERREF_GetName() with a null buffer; this will retrieve the length of
+        // the value in bytes, including a trailing null terminator. If the length is zero,
+        // then an error occurred; if the length is one, then the value is not set.
+
+        // If the length is greater than one, then we reserve a byte buffer of the given
+        // length and call FPDF_VIEWERREF_GetName() again with a pointer to the buffer;
+        // this will write the value to the buffer as a null-terminated byte string.
+
+        let buffer_length =
+            self.bindings
+                .FPDF_VIEWERREF_GetName(self.document_handle, key, null_mut(), 0);
+
+        if buffer_length <= 1 {
+            return None;
+        }
+
+        let mut buffer = create_byte_buffer(buffer_length as usize);
+
+        let result = self.bindings.FPDF_VIEWERREF_GetName(
+            self.document_handle,
+            key,
+            buffer.as_mut_ptr() as *mut c_char,
+            buffer_length,
+        );
+
+        debug_assert_eq!(result, buffer_length);
+
+        CString::from_vec_with_nul(buffer)
+            .ok()
+            .and_then(|value| value.into_string().ok())
+            .filter(|value| !value.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::utils::test::test_bind_to_pdfium;
+
+    #[test]
+    fn test_viewer_preferences_defaults() -> Result<(), PdfiumError> {
+        let pdfium = test_bind_to_pdfium();
+
+        let document = pdfium.create_new_pdf()?;
+
+        let preferences = document.viewer_preferences();
+
+        assert!(preferences.is_print_scaling_preferred());
+        assert_eq!(preferences.copies(), 1);
+        assert_eq!(preferences.duplex()?, PdfDuplexType::Undefined);
+        assert!(preferences.print_page_ranges().is_empty());
+        assert_eq!(preferences.get_name("Direction"), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_viewer_preferences_from_file() -> Result<(), PdfiumError> {
+        use std::io::Write;
+
+        let pdfium = test_bind_to_pdfium();
+
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+
+        let catalog_offset = bytes.len();
+
+        bytes.extend_from_slice(
+            b"1 0 obj\n<< /Type /Catalog /Pages 2 0 R /ViewerPreferences << \
+            /PrintScaling /None /Duplex /DuplexFlipLongEdge /NumCopies 3 \
+            /PrintPageRange [1 2 4 4 3 2] /Direction /R2L >> >>\nendobj\n",
+        );
+
+        let pages_offset = bytes.len();
+
+        bytes.extend_from_slice(b"2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n");
+
+        let page_offset = bytes.len();
+
+        bytes.extend_from_slice(
+            b"3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 612 792] >>\nendobj\n",
+        );
+
+        let xref_offset = bytes.len();
+
+        write!(
+            bytes,
+            "xref\n0 4\n0000000000 65535 f\r\n{catalog_offset:010} 00000 n\r\n\
+            {pages_offset:010} 00000 n\r\n{page_offset:010} 00000 n\r\n\
+            trailer\n<< /Size 4 /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n"
+        )
+        .unwrap();
+
+        let document = pdfium.load_pdf_from_byte_vec(bytes, None)?;
+
+        let preferences = document.viewer_preferences();
+
+        assert!(!preferences.is_print_scaling_preferred());
+        assert_eq!(preferences.copies(), 3);
+        assert_eq!(preferences.duplex()?, PdfDuplexType::DuplexFlipLongEdge);
+
+        // The stored one-based, inclusive ranges are returned as zero-based, half-open
+        // ranges; the reversed range 3-2 is malformed and skipped.
+
+        assert_eq!(preferences.print_page_ranges(), vec![0..2, 3..4]);
+        assert_eq!(preferences.get_name("Direction"), Some("R2L".to_string()));
+
+        Ok(())
+    }
+}
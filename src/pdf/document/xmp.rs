@@ -0,0 +1,754 @@
+//! Helpers for reading, writing, and generating the XMP metadata stream referenced by the
+//! `/Metadata` entry of a document's catalog, and for keeping the basic properties in that
+//! stream consistent with the tags in the document information dictionary.
+//!
+//! XMP packets are handled as text rather than parsed as XML. Properties are located using
+//! their conventional namespace prefixes (`dc:`, `pdf:`, and `xmp:`), in either element
+//! or attribute form.
+
+use crate::error::PdfiumError;
+use crate::pdf::document::incremental_update::PdfIncrementalUpdate;
+use crate::pdf::document::metadata::PdfDocumentMetadataTagType;
+use std::ops::Range;
+
+/// Returns the XMP property that corresponds to the given document information dictionary tag,
+/// along with the RDF container type used to hold its value, if any.
+pub(crate) fn xmp_property_for_tag(
+    tag: PdfDocumentMetadataTagType,
+) -> (&'static str, Option<&'static str>) {
+    match tag {
+        PdfDocumentMetadataTagType::Title => ("dc:title", Some("rdf:Alt")),
+        PdfDocumentMetadataTagType::Author => ("dc:creator", Some("rdf:Seq")),
+        PdfDocumentMetadataTagType::Subject => ("dc:description", Some("rdf:Alt")),
+        PdfDocumentMetadataTagType::Keywords => ("pdf:Keywords", None),
+        PdfDocumentMetadataTagType::Creator => ("xmp:CreatorTool", None),
+        PdfDocumentMetadataTagType::Producer => ("pdf:Producer", None),
+        PdfDocumentMetadataTagType::CreationDate => ("xmp:CreateDate", None),
+        PdfDocumentMetadataTagType::ModificationDate => ("xmp:ModifyDate", None),
+    }
+}
+
+/// Reads the XMP metadata stream referenced by the catalog of the given saved document,
+/// if any.
+pub(crate) fn read_xmp_metadata(
+    update: &PdfIncrementalUpdate,
+) -> Result<Option<String>, PdfiumError> {
+    let catalog = update.get_dictionary(update.root()?)?;
+
+    match catalog.get_reference("Metadata") {
+        Some(id) => {
+            let data = update.get_stream_data(id)?;
+
+            Ok(Some(String::from_utf8_lossy(&data).into_owned()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Writes the given XMP packet as the metadata stream of the given saved document,
+/// replacing any existing metadata stream.
+pub(crate) fn write_xmp_metadata(
+    update: &mut PdfIncrementalUpdate,
+    xmp: &str,
+) -> Result<(), PdfiumError> {
+    let mut body = format!(
+        "<</Type /Metadata\n/Subtype /XML\n/Length {}>>\nstream\n",
+        xmp.len()
+    )
+    .into_bytes();
+
+    body.extend_from_slice(xmp.as_bytes());
+    body.extend_from_slice(b"\nendstream");
+
+    let root = update.root()?;
+
+    let mut catalog = update.get_dictionary(root)?;
+
+    match catalog.get_reference("Metadata") {
+        Some(id) => update.set_object(id, body),
+        None => {
+            let id = update.add_object(body);
+
+            catalog.set("Metadata", id.to_reference());
+
+            update.set_object(root, catalog.to_bytes());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the value of the given document information dictionary tag as recorded in the
+/// given XMP packet, if the packet defines the corresponding property. Dates are converted
+/// to the PDF date format.
+pub(crate) fn get_tag_from_xmp(xmp: &str, tag: PdfDocumentMetadataTagType) -> Option<String> {
+    let (property, _) = xmp_property_for_tag(tag);
+
+    let values = get_property(xmp, property)?;
+
+    match tag {
+        PdfDocumentMetadataTagType::CreationDate | PdfDocumentMetadataTagType::ModificationDate => {
+            values.first().and_then(|value| xmp_date_to_pdf_date(value))
+        }
+        _ => Some(values.join(", ")),
+    }
+}
+
+/// Generates a new XMP packet containing the properties that correspond to the given
+/// document information dictionary tags.
+pub(crate) fn generate_xmp<'a>(
+    tags: impl Iterator<Item = (PdfDocumentMetadataTagType, &'a str)>,
+) -> String {
+    let mut properties = String::from("   <dc:format>application/pdf</dc:format>\n");
+
+    for (tag, value) in tags {
+        if let Some(element) = property_element(tag, value, false) {
+            properties.push_str(&format!("   {element}\n"));
+        }
+    }
+
+    format!(
+        "<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n\
+        <x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n \
+        <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n  \
+        <rdf:Description rdf:about=\"\"\n    \
+        xmlns:dc=\"{}\"\n    \
+        xmlns:pdf=\"{}\"\n    \
+        xmlns:xmp=\"{}\">\n\
+        {properties}  \
+        </rdf:Description>\n \
+        </rdf:RDF>\n\
+        </x:xmpmeta>\n\
+        <?xpacket end=\"w\"?>",
+        namespace_for_prefix("dc"),
+        namespace_for_prefix("pdf"),
+        namespace_for_prefix("xmp"),
+    )
+}
+
+/// Returns a copy of the given XMP packet in which the property that corresponds to the
+/// given document information dictionary tag holds the given value, or is removed if the
+/// value is `None`. The packet is returned unchanged if it already records the given value.
+pub(crate) fn set_tag_in_xmp(
+    xmp: &str,
+    tag: PdfDocumentMetadataTagType,
+    value: Option<&str>,
+) -> String {
+    if get_tag_from_xmp(xmp, tag).as_deref() == value {
+        return xmp.to_string();
+    }
+
+    let (property, _) = xmp_property_for_tag(tag);
+
+    let mut result = xmp.to_string();
+
+    while let Some(range) = element_property_range(&result, property)
+        .or_else(|| attribute_property_range(&result, property))
+    {
+        result.replace_range(range, "");
+    }
+
+    let prefix = property.split(':').next().unwrap_or_default();
+
+    // The new property declares its own namespace if the packet does not already do so.
+
+    let declares_namespace = result.contains(&format!("xmlns:{prefix}="));
+
+    let element = match value.and_then(|value| property_element(tag, value, !declares_namespace)) {
+        Some(element) => element,
+        None => return result,
+    };
+
+    if let Some(position) = result.find("</rdf:Description>") {
+        result.insert_str(position, &format!(" {element}\n  "));
+    } else if let Some(position) = result.find("</rdf:RDF>") {
+        result.insert_str(
+            position,
+            &format!(" <rdf:Description rdf:about=\"\">\n   {element}\n  </rdf:Description>\n "),
+        );
+    }
+
+    result
+}
+
+/// Returns the XML element holding the XMP property that corresponds to the given document
+/// information dictionary tag, set to the given value, optionally declaring the namespace
+/// of the property's prefix. Returns `None` if the value is a date that cannot be converted.
+fn property_element(
+    tag: PdfDocumentMetadataTagType,
+    value: &str,
+    declare_namespace: bool,
+) -> Option<String> {
+    let (property, container) = xmp_property_for_tag(tag);
+
+    let value = match tag {
+        PdfDocumentMetadataTagType::CreationDate | PdfDocumentMetadataTagType::ModificationDate => {
+            pdf_date_to_xmp_date(value)?
+        }
+        _ => escape(value),
+    };
+
+    let namespace = if declare_namespace {
+        let prefix = property.split(':').next().unwrap_or_default();
+
+        format!(" xmlns:{prefix}=\"{}\"", namespace_for_prefix(prefix))
+    } else {
+        String::new()
+    };
+
+    Some(match container {
+        Some("rdf:Alt") => format!(
+            "<{property}{namespace}><rdf:Alt><rdf:li xml:lang=\"x-default\">{value}</rdf:li></rdf:Alt></{property}>"
+        ),
+        Some(container) => format!(
+            "<{property}{namespace}><{container}><rdf:li>{value}</rdf:li></{container}></{property}>"
+        ),
+        None => format!("<{property}{namespace}>{value}</{property}>"),
+    })
+}
+
+/// Returns the namespace URI conventionally bound to the given XMP prefix.
+fn namespace_for_prefix(prefix: &str) -> &'static str {
+    match prefix {
+        "dc" => "http://purl.org/dc/elements/1.1/",
+        "pdf" => "http://ns.adobe.com/pdf/1.3/",
+        _ => "http://ns.adobe.com/xap/1.0/",
+    }
+}
+
+/// Returns the byte range of the first element holding the given property, including any
+/// white space preceding it on the same line.
+fn element_property_range(xmp: &str, property: &str) -> Option<Range<usize>> {
+    let open = format!("<{property}");
+    let close = format!("</{property}>");
+
+    let mut search_from = 0;
+
+    while let Some(start) = xmp[search_from..]
+        .find(&open)
+        .map(|start| search_from + start)
+    {
+        let after_name = start + open.len();
+
+        search_from = after_name;
+
+        match xmp[after_name..].chars().next() {
+            Some(c) if c == '>' || c == '/' || c.is_whitespace() => {}
+            _ => continue,
+        }
+
+        let tag_end = after_name + xmp[after_name..].find('>')? + 1;
+
+        let end = if xmp[..tag_end].ends_with("/>") {
+            tag_end
+        } else {
+            tag_end + xmp[tag_end..].find(&close)? + close.len()
+        };
+
+        let start = xmp[..start].trim_end_matches([' ', '\t']).len();
+
+        return Some(start..end);
+    }
+
+    None
+}
+
+/// Returns the byte range of the first attribute holding the given property, including
+/// the white space preceding it.
+fn attribute_property_range(xmp: &str, property: &str) -> Option<Range<usize>> {
+    let mut search_from = 0;
+
+    while let Some(start) = xmp[search_from..]
+        .find(property)
+        .map(|start| search_from + start)
+    {
+        search_from = start + property.len();
+
+        if !xmp[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+
+        let after_name = xmp[search_from..].trim_start();
+
+        let value = match after_name.strip_prefix('=') {
+            Some(value) => value.trim_start(),
+            None => continue,
+        };
+
+        let quote = match value.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => quote,
+            _ => continue,
+        };
+
+        let value_start = xmp.len() - value.len() + 1;
+
+        let end = value_start + xmp[value_start..].find(quote)? + 1;
+
+        let start = xmp[..start].trim_end().len();
+
+        return Some(start..end);
+    }
+
+    None
+}
+
+/// Returns the values of the given property in the given XMP packet. A simple property has
+/// a single value; an array property has one value for each of its `rdf:li` items.
+fn get_property(xmp: &str, property: &str) -> Option<Vec<String>> {
+    get_element_property(xmp, property).or_else(|| get_attribute_property(xmp, property))
+}
+
+/// Returns the values of the given property when written as an element,
+/// e.g. `<pdf:Producer>value</pdf:Producer>`.
+fn get_element_property(xmp: &str, property: &str) -> Option<Vec<String>> {
+    let open = format!("<{property}");
+    let close = format!("</{property}>");
+
+    let mut search_from = 0;
+
+    while let Some(start) = xmp[search_from..]
+        .find(&open)
+        .map(|start| search_from + start)
+    {
+        let after_name = start + open.len();
+
+        search_from = after_name;
+
+        // Ensure we have matched the whole element name rather than a prefix of a longer one.
+
+        match xmp[after_name..].chars().next() {
+            Some(c) if c == '>' || c == '/' || c.is_whitespace() => {}
+            _ => continue,
+        }
+
+        let content_start = after_name + xmp[after_name..].find('>')? + 1;
+
+        if xmp[..content_start].ends_with("/>") {
+            // An empty element, e.g. one holding an rdf:resource attribute.
+
+            continue;
+        }
+
+        let content_end = content_start + xmp[content_start..].find(&close)?;
+
+        let content = &xmp[content_start..content_end];
+
+        return Some(if content.contains("<rdf:li") {
+            get_list_items(content)
+        } else {
+            vec![unescape(content.trim())]
+        });
+    }
+
+    None
+}
+
+/// Returns the values of the given property when written as an attribute of an
+/// `rdf:Description` element, e.g. `pdf:Producer="value"`.
+fn get_attribute_property(xmp: &str, property: &str) -> Option<Vec<String>> {
+    let mut search_from = 0;
+
+    while let Some(start) = xmp[search_from..]
+        .find(property)
+        .map(|start| search_from + start)
+    {
+        search_from = start + property.len();
+
+        if !xmp[..start].ends_with(char::is_whitespace) {
+            continue;
+        }
+
+        let rest = xmp[search_from..].trim_start();
+
+        let rest = match rest.strip_prefix('=') {
+            Some(rest) => rest.trim_start(),
+            None => continue,
+        };
+
+        let quote = match rest.chars().next() {
+            Some(quote) if quote == '"' || quote == '\'' => quote,
+            _ => continue,
+        };
+
+        let value = &rest[1..];
+
+        return value.find(quote).map(|end| vec![unescape(&value[..end])]);
+    }
+
+    None
+}
+
+/// Returns the text of each `rdf:li` item in the given array content.
+fn get_list_items(content: &str) -> Vec<String> {
+    let mut result = Vec::new();
+
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<rdf:li") {
+        rest = &rest[start + "<rdf:li".len()..];
+
+        let tag_end = match rest.find('>') {
+            Some(tag_end) => tag_end,
+            None => break,
+        };
+
+        if rest[..tag_end].ends_with('/') {
+            result.push(String::new());
+
+            rest = &rest[tag_end + 1..];
+
+            continue;
+        }
+
+        rest = &rest[tag_end + 1..];
+
+        match rest.find("</rdf:li>") {
+            Some(end) => {
+                result.push(unescape(rest[..end].trim()));
+
+                rest = &rest[end..];
+            }
+            None => break,
+        }
+    }
+
+    result
+}
+
+/// Escapes the given text for use as XML character data or as an attribute value.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Replaces predefined and numeric XML character references in the given text.
+pub(crate) fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+
+        rest = &rest[start..];
+
+        let end = match rest.find(';') {
+            Some(end) => end,
+            None => break,
+        };
+
+        let reference = &rest[1..end];
+
+        let character = match reference {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                if let Some(hex) = reference.strip_prefix("#x") {
+                    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                } else if let Some(decimal) = reference.strip_prefix('#') {
+                    decimal.parse().ok().and_then(char::from_u32)
+                } else {
+                    None
+                }
+            }
+        };
+
+        match character {
+            Some(character) => {
+                result.push(character);
+
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+
+    result
+}
+
+/// Converts an XMP date, as defined in ISO 8601, e.g. `2024-03-01T09:30:00+01:00`,
+/// to a PDF date, e.g. `D:20240301093000+01'00'`.
+fn xmp_date_to_pdf_date(date: &str) -> Option<String> {
+    let date = date.trim();
+
+    let (date_time, timezone) = match date.find(['Z', '+']) {
+        Some(position) => date.split_at(position),
+        None => match date.get(10..).and_then(|time| time.rfind('-')) {
+            // A minus sign after the date part introduces a negative timezone offset.
+            Some(position) => date.split_at(10 + position),
+            None => (date, ""),
+        },
+    };
+
+    // Discard any fractional seconds.
+
+    let date_time = date_time.split('.').next()?;
+
+    let digits = date_time
+        .chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>();
+
+    if !(4..=14).contains(&digits.len())
+        || !date_time
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '-' || c == 'T' || c == ':')
+    {
+        return None;
+    }
+
+    let timezone = match timezone {
+        "" => String::new(),
+        "Z" => "Z00'00'".to_string(),
+        offset => {
+            let (hours, minutes) = offset[1..].split_once(':').unwrap_or((&offset[1..], "00"));
+
+            format!("{}{hours}'{minutes}'", &offset[..1])
+        }
+    };
+
+    Some(format!("D:{digits}{timezone}"))
+}
+
+/// Converts a PDF date, e.g. `D:20240301093000+01'00'`, to an XMP date as defined in
+/// ISO 8601, e.g. `2024-03-01T09:30:00+01:00`.
+fn pdf_date_to_xmp_date(date: &str) -> Option<String> {
+    let date = date.trim();
+
+    let date = date.strip_prefix("D:").unwrap_or(date);
+
+    let digits_length = date.chars().take_while(char::is_ascii_digit).count();
+
+    if !(4..=14).contains(&digits_length) || digits_length % 2 != 0 {
+        return None;
+    }
+
+    let (digits, timezone) = date.split_at(digits_length);
+
+    let mut result = digits[..4].to_string();
+
+    for (index, separator) in ["-", "-", "T", ":", ":"].iter().enumerate() {
+        match digits.get(4 + index * 2..6 + index * 2) {
+            Some(part) => {
+                result.push_str(separator);
+                result.push_str(part);
+            }
+            None => break,
+        }
+    }
+
+    if digits_length == 10 {
+        // ISO 8601 does not allow hours without minutes.
+
+        result.push_str(":00");
+    }
+
+    if digits_length >= 10 {
+        let timezone = timezone.replace('\'', "");
+
+        match timezone.chars().next() {
+            Some('Z') => result.push('Z'),
+            Some(sign) if sign == '+' || sign == '-' => {
+                let offset = &timezone[1..];
+
+                match (offset.get(..2), offset.get(2..4)) {
+                    (Some(hours), Some(minutes)) => {
+                        result.push_str(&format!("{sign}{hours}:{minutes}"))
+                    }
+                    (Some(hours), None) => result.push_str(&format!("{sign}{hours}:00")),
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_properties() {
+        let xmp = "<rdf:Description rdf:about=\"\" pdf:Producer='Acme &amp; Co'>\
+            <dc:title><rdf:Alt><rdf:li xml:lang=\"x-default\">Invoice &lt;1234&gt;</rdf:li>\
+            </rdf:Alt></dc:title>\
+            <dc:creator><rdf:Seq><rdf:li>Alice</rdf:li><rdf:li>Bob</rdf:li></rdf:Seq></dc:creator>\
+            <xmp:CreateDate>2024-03-01T09:30:00.25-05:00</xmp:CreateDate>\
+            <xmp:ModifyDate>2024-03-02T10:00:00Z</xmp:ModifyDate>\
+            </rdf:Description>";
+
+        assert_eq!(
+            get_tag_from_xmp(xmp, PdfDocumentMetadataTagType::Title),
+            Some("Invoice <1234>".to_string())
+        );
+        assert_eq!(
+            get_tag_from_xmp(xmp, PdfDocumentMetadataTagType::Author),
+            Some("Alice, Bob".to_string())
+        );
+        assert_eq!(
+            get_tag_from_xmp(xmp, PdfDocumentMetadataTagType::Producer),
+            Some("Acme & Co".to_string())
+        );
+        assert_eq!(
+            get_tag_from_xmp(xmp, PdfDocumentMetadataTagType::CreationDate),
+            Some("D:20240301093000-05'00'".to_string())
+        );
+        assert_eq!(
+            get_tag_from_xmp(xmp, PdfDocumentMetadataTagType::ModificationDate),
+            Some("D:20240302100000Z00'00'".to_string())
+        );
+        assert_eq!(
+            get_tag_from_xmp(xmp, PdfDocumentMetadataTagType::Subject),
+            None
+        );
+    }
+
+    #[test]
+    fn test_generated_xmp_round_trips() {
+        let tags = [
+            (PdfDocumentMetadataTagType::Title, "Q&A"),
+            (PdfDocumentMetadataTagType::Author, "Accounts"),
+            (PdfDocumentMetadataTagType::Creator, "Ledger 2.0"),
+            (
+                PdfDocumentMetadataTagType::CreationDate,
+                "D:20240301093000+01'00'",
+            ),
+        ];
+
+        let xmp = generate_xmp(tags.iter().copied());
+
+        assert!(xmp.contains("<xmp:CreateDate>2024-03-01T09:30:00+01:00</xmp:CreateDate>"));
+
+        for (tag, value) in tags {
+            assert_eq!(get_tag_from_xmp(&xmp, tag), Some(value.to_string()));
+        }
+
+        assert_eq!(
+            pdf_date_to_xmp_date("D:2024030109"),
+            Some("2024-03-01T09:00".to_string())
+        );
+        assert_eq!(
+            pdf_date_to_xmp_date("D:202403"),
+            Some("2024-03".to_string())
+        );
+    }
+
+    #[test]
+    fn test_set_tags_in_xmp() {
+        let xmp = generate_xmp(
+            [
+                (PdfDocumentMetadataTagType::Title, "Draft"),
+                (PdfDocumentMetadataTagType::Producer, "Acme"),
+            ]
+            .iter()
+            .copied(),
+        );
+
+        let updated = set_tag_in_xmp(&xmp, PdfDocumentMetadataTagType::Title, Some("Final"));
+
+        assert_eq!(
+            get_tag_from_xmp(&updated, PdfDocumentMetadataTagType::Title),
+            Some("Final".to_string())
+        );
+        assert_eq!(updated.matches("<dc:title>").count(), 1);
+
+        let updated = set_tag_in_xmp(&updated, PdfDocumentMetadataTagType::Producer, None);
+
+        assert_eq!(
+            get_tag_from_xmp(&updated, PdfDocumentMetadataTagType::Producer),
+            None
+        );
+        assert_eq!(
+            set_tag_in_xmp(&updated, PdfDocumentMetadataTagType::Title, Some("Final")),
+            updated
+        );
+
+        // Properties written as attributes are replaced by elements, declaring the
+        // namespace if the packet does not already do so.
+
+        let xmp = "<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF>\
+            <rdf:Description rdf:about=\"\" xmlns:pdf=\"http://ns.adobe.com/pdf/1.3/\" \
+            pdf:Producer=\"Acme\">\n  </rdf:Description></rdf:RDF></x:xmpmeta>";
+
+        let updated = set_tag_in_xmp(xmp, PdfDocumentMetadataTagType::Producer, Some("Acme 2"));
+
+        assert!(!updated.contains("pdf:Producer=\"Acme\""));
+        assert!(updated.contains("<pdf:Producer>Acme 2</pdf:Producer>"));
+
+        let updated = set_tag_in_xmp(
+            &updated,
+            PdfDocumentMetadataTagType::ModificationDate,
+            Some("D:20240302100000Z00'00'"),
+        );
+
+        assert!(updated.contains(
+            "<xmp:ModifyDate xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\">2024-03-02T10:00:00Z</xmp:ModifyDate>"
+        ));
+        assert_eq!(
+            get_tag_from_xmp(&updated, PdfDocumentMetadataTagType::ModificationDate),
+            Some("D:20240302100000Z00'00'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_read_compressed_xmp_metadata() -> Result<(), PdfiumError> {
+        use std::io::Write;
+
+        let xmp = generate_xmp(
+            [(PdfDocumentMetadataTagType::Title, "Packed")]
+                .iter()
+                .copied(),
+        );
+
+        let data = miniz_oxide::deflate::compress_to_vec_zlib(xmp.as_bytes(), 6);
+
+        let mut bytes = b"%PDF-1.7\n".to_vec();
+
+        let catalog_offset = bytes.len();
+
+        bytes.extend_from_slice(b"1 0 obj\n<< /Type /Catalog /Metadata 2 0 R >>\nendobj\n");
+
+        let metadata_offset = bytes.len();
+
+        write!(
+            bytes,
+            "2 0 obj\n<< /Type /Metadata /Subtype /XML /Filter /FlateDecode /Length {} >>\nstream\n",
+            data.len()
+        )
+        .unwrap();
+
+        bytes.extend_from_slice(&data);
+        bytes.extend_from_slice(b"\nendstream\nendobj\n");
+
+        let xref_offset = bytes.len();
+
+        write!(
+            bytes,
+            "xref\n0 3\n0000000000 65535 f\r\n{catalog_offset:010} 00000 n\r\n\
+            {metadata_offset:010} 00000 n\r\ntrailer\n<< /Size 3 /Root 1 0 R >>\n\
+            startxref\n{xref_offset}\n%%EOF\n"
+        )
+        .unwrap();
+
+        assert_eq!(
+            read_xmp_metadata(&PdfIncrementalUpdate::new(bytes)?)?,
+            Some(xmp)
+        );
+
+        Ok(())
+    }
+}
@@ -4,7 +4,7 @@
 use crate::bindgen::{FPDF_DOCUMENT, FPDF_LINK, FS_RECTF};
 use crate::bindings::PdfiumLibraryBindings;
 use crate::error::PdfiumError;
-use crate::pdf::action::PdfAction;
+use crate::pdf::action::{PdfAction, PdfActionOwner};
 use crate::pdf::destination::PdfDestination;
 use crate::pdf::rect::PdfRect;
 
@@ -66,6 +66,7 @@ impl<'a> PdfLink<'a> {
             Some(PdfAction::from_pdfium(
                 handle,
                 self.document,
+                PdfActionOwner::Link(self.handle()),
                 self.bindings(),
             ))
         }
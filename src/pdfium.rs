@@ -16,6 +16,7 @@ use crate::bindings::static_bindings::StaticPdfiumBindings;
 
 #[cfg(not(target_arch = "wasm32"))]
 use {
+    crate::pdf::document::availability::PdfDocumentAvailability,
     crate::utils::files::get_pdfium_file_accessor_from_reader,
     std::fs::File,
     std::io::{Read, Seek},
@@ -307,6 +308,36 @@ impl Pdfium {
         })
     }
 
+    /// Creates a new [PdfDocumentAvailability] that opens a [PdfDocument] from the given reader
+    /// incrementally, as the document's data arrives.
+    ///
+    /// The `is_data_available` callback will be called with an offset and a length in bytes;
+    /// it should return `true` if that range of the file has arrived and can be read from the
+    /// given reader. The `add_download_hint` callback will be called with an offset and a length
+    /// in bytes that Pdfium needs to continue loading the document; the caller should
+    /// prioritise downloading that range of the file.
+    ///
+    /// Because Pdfium must know the total content length in advance, the given reader must
+    /// implement the [Seek] trait and report the full length of the file, even though
+    /// most of the file may not yet have arrived.
+    ///
+    /// This function is not available when compiling to WASM.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn load_pdf_availability_from_reader<'a, R: Read + Seek + 'a>(
+        &'a self,
+        reader: R,
+        is_data_available: impl FnMut(u64, u64) -> bool + 'a,
+        add_download_hint: impl FnMut(u64, u64) + 'a,
+    ) -> Result<PdfDocumentAvailability<'a>, PdfiumError> {
+        PdfDocumentAvailability::from_reader(
+            reader,
+            is_data_available,
+            add_download_hint,
+            self.bindings(),
+        )
+    }
+
     /// Attempts to open a [PdfDocument] by loading document data from the given URL.
     /// The Javascript `fetch` API is used to download data over the network.
     ///
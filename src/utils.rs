@@ -256,7 +256,9 @@ pub(crate) mod utf16le {
 }
 
 pub(crate) mod files {
-    use crate::bindgen::{FPDF_FILEACCESS, FPDF_FILEWRITE};
+    use crate::bindgen::{
+        FPDF_BOOL, FPDF_FILEACCESS, FPDF_FILEWRITE, FX_DOWNLOADHINTS, FX_FILEAVAIL,
+    };
     use std::io::{Read, Seek, SeekFrom, Write};
     use std::ops::Deref;
     use std::os::raw::{c_int, c_uchar, c_ulong, c_void};
@@ -379,6 +381,107 @@ pub(crate) mod files {
         }
     }
 
+    /// Returns a wrapped Pdfium `FX_FILEAVAIL` struct that uses the given callback to answer
+    /// Pdfium's queries as to whether a range of bytes in the underlying file has arrived.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    // This function is never used when compiling to WASM.
+    pub(crate) fn get_pdfium_file_avail_from_callback<'a>(
+        callback: impl FnMut(u64, u64) -> bool + 'a,
+    ) -> Box<FxFileAvailExt<'a>> {
+        Box::new(FxFileAvailExt {
+            version: 1,
+            is_data_avail: Some(is_data_avail_from_callback),
+            callback: Box::new(callback),
+        })
+    }
+
+    #[repr(C)]
+    pub(crate) struct FxFileAvailExt<'a> {
+        // An extension of Pdfium's FX_FILEAVAIL struct that adds an extra field to carry the
+        // user-provided Rust callback. Pdfium passes a pointer to this struct back to our
+        // callback function, so - like FpdfFileAccessExt - it must be boxed to keep its
+        // memory location stable.
+        version: c_int,
+        is_data_avail: Option<
+            unsafe extern "C" fn(
+                file_avail_ptr: *mut FxFileAvailExt,
+                offset: usize,
+                size: usize,
+            ) -> FPDF_BOOL,
+        >,
+        callback: Box<dyn FnMut(u64, u64) -> bool + 'a>,
+    }
+
+    impl<'a> FxFileAvailExt<'a> {
+        /// Returns an `FX_FILEAVAIL` pointer suitable for passing to `FPDFAvail_Create()`.
+        #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+        // This function is never used when compiling to WASM.
+        #[inline]
+        pub(crate) fn as_fx_file_avail_mut_ptr(&mut self) -> &mut FX_FILEAVAIL {
+            unsafe { &mut *(self as *mut FxFileAvailExt as *mut FX_FILEAVAIL) }
+        }
+    }
+
+    // The callback function invoked by Pdfium.
+    pub(crate) extern "C" fn is_data_avail_from_callback(
+        file_avail_ptr: *mut FxFileAvailExt,
+        offset: usize,
+        size: usize,
+    ) -> FPDF_BOOL {
+        let is_available = unsafe { ((*file_avail_ptr).callback)(offset as u64, size as u64) };
+
+        if is_available {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Returns a wrapped Pdfium `FX_DOWNLOADHINTS` struct that passes each byte range Pdfium
+    /// asks to be downloaded to the given callback.
+    #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+    // This function is never used when compiling to WASM.
+    pub(crate) fn get_pdfium_download_hints_from_callback<'a>(
+        callback: impl FnMut(u64, u64) + 'a,
+    ) -> Box<FxDownloadHintsExt<'a>> {
+        Box::new(FxDownloadHintsExt {
+            version: 1,
+            add_segment: Some(add_segment_from_callback),
+            callback: Box::new(callback),
+        })
+    }
+
+    #[repr(C)]
+    pub(crate) struct FxDownloadHintsExt<'a> {
+        // An extension of Pdfium's FX_DOWNLOADHINTS struct that adds an extra field to carry
+        // the user-provided Rust callback.
+        version: c_int,
+        add_segment: Option<
+            unsafe extern "C" fn(hints_ptr: *mut FxDownloadHintsExt, offset: usize, size: usize),
+        >,
+        callback: Box<dyn FnMut(u64, u64) + 'a>,
+    }
+
+    impl<'a> FxDownloadHintsExt<'a> {
+        /// Returns an `FX_DOWNLOADHINTS` pointer suitable for passing to the
+        /// `FPDFAvail_Is*Avail()` functions.
+        #[cfg_attr(target_arch = "wasm32", allow(dead_code))]
+        // This function is never used when compiling to WASM.
+        #[inline]
+        pub(crate) fn as_fx_download_hints_mut_ptr(&mut self) -> *mut FX_DOWNLOADHINTS {
+            self as *mut FxDownloadHintsExt as *mut FX_DOWNLOADHINTS
+        }
+    }
+
+    // The callback function invoked by Pdfium.
+    pub(crate) extern "C" fn add_segment_from_callback(
+        hints_ptr: *mut FxDownloadHintsExt,
+        offset: usize,
+        size: usize,
+    ) {
+        unsafe { ((*hints_ptr).callback)(offset as u64, size as u64) }
+    }
+
     /// Returns a wrapped Pdfium `FPDF_FILEWRITE` struct that uses the given writer as an
     /// output source for Pdfium's file writing callback function.
     pub(crate) fn get_pdfium_file_writer_from_writer<W: Write + 'static>(